@@ -3,7 +3,6 @@
 use std::path::PathBuf;
 use std::sync::Arc;
 
-use tokio::sync::broadcast;
 use tokio_util::sync::CancellationToken;
 use uuid::Uuid;
 
@@ -27,7 +26,7 @@ pub struct AgentContext {
     pub max_iterations: usize,
 
     /// Optional event sink for streaming agent events (e.g. control session SSE).
-    pub control_events: Option<broadcast::Sender<crate::api::control::AgentEvent>>,
+    pub control_events: Option<crate::api::control::EventSender>,
 
     /// Optional hub for awaiting frontend (interactive) tool results.
     pub frontend_tool_hub: Option<Arc<crate::api::control::FrontendToolHub>>,
@@ -642,6 +642,7 @@ impl Agent for OpenCodeAgent {
                 "session_id": session.id,
             })),
             terminal_reason: Some(TerminalReason::Completed),
+            usage: None,
         }
     }
 }
@@ -726,6 +727,7 @@ impl OpenCodeAgent {
                 "session_id": session_id,
             })),
             terminal_reason: Some(TerminalReason::Completed),
+            usage: None,
         }
     }
 }
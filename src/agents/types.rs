@@ -3,6 +3,8 @@
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::cost::TokenUsage;
+
 /// Unique identifier for an agent.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct AgentId(Uuid);
@@ -71,6 +73,10 @@ pub struct AgentResult {
 
     /// Reason why execution terminated (if not successful completion)
     pub terminal_reason: Option<TerminalReason>,
+
+    /// Tokens consumed (if the backend reports them)
+    #[serde(default)]
+    pub usage: Option<TokenUsage>,
 }
 
 impl AgentResult {
@@ -83,6 +89,7 @@ impl AgentResult {
             model_used: None,
             data: None,
             terminal_reason: None,
+            usage: None,
         }
     }
 
@@ -95,6 +102,7 @@ impl AgentResult {
             model_used: None,
             data: None,
             terminal_reason: None,
+            usage: None,
         }
     }
 
@@ -110,6 +118,12 @@ impl AgentResult {
         self
     }
 
+    /// Add token usage to the result.
+    pub fn with_usage(mut self, usage: TokenUsage) -> Self {
+        self.usage = Some(usage);
+        self
+    }
+
     /// Add terminal reason to the result.
     pub fn with_terminal_reason(mut self, reason: TerminalReason) -> Self {
         self.terminal_reason = Some(reason);
@@ -132,6 +146,12 @@ pub enum TerminalReason {
     InfiniteLoop,
     /// Hit maximum iterations limit
     MaxIterations,
+    /// Backend CLI is not installed and could not be installed
+    CliMissing,
+    /// Backend has no usable credentials
+    AuthMissing,
+    /// Backend's circuit breaker is open after repeated failures
+    BackendUnavailable,
 }
 
 /// Errors that can occur in agent operations.
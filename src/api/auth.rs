@@ -21,7 +21,7 @@ use jsonwebtoken::{DecodingKey, EncodingKey, Header, Validation};
 
 use super::routes::AppState;
 use super::types::{LoginRequest, LoginResponse};
-use crate::config::{AuthMode, Config, UserAccount};
+use crate::config::{AuthConfig, AuthMode, Config, UserAccount};
 
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
 struct Claims {
@@ -184,6 +184,26 @@ pub async fn login(
     Ok(Json(LoginResponse { token, exp }))
 }
 
+/// Token for processes the server spawns on behalf of `user_id` (the workspace
+/// MCP host) to call back into the API. `None` when the API needs no auth.
+pub fn service_token(config: &Config, user_id: &str) -> Option<String> {
+    if !config.auth.auth_required(config.dev_mode) {
+        return None;
+    }
+    let secret = config.auth.jwt_secret.as_deref()?;
+    let user = AuthUser {
+        id: user_id.to_string(),
+        username: user_id.to_string(),
+    };
+    match issue_jwt(secret, config.auth.jwt_ttl_days, &user) {
+        Ok((token, _)) => Some(token),
+        Err(e) => {
+            tracing::warn!("Failed to issue service token: {}", e);
+            None
+        }
+    }
+}
+
 pub async fn require_auth(
     State(state): State<std::sync::Arc<AppState>>,
     mut req: Request<Body>,
@@ -268,3 +288,133 @@ fn user_for_claims(claims: &Claims, users: &[UserAccount]) -> Option<AuthUser> {
             username: u.username.clone(),
         })
 }
+
+/// Models the user may run. Empty means unrestricted.
+pub fn allowed_models<'a>(auth: &'a AuthConfig, user: &AuthUser) -> &'a [String] {
+    auth.users
+        .iter()
+        .find(|u| effective_user_id(u) == user.id)
+        .map(|u| u.allowed_models.as_slice())
+        .unwrap_or(&[])
+}
+
+fn model_matches(pattern: &str, model: &str) -> bool {
+    match pattern.strip_suffix('*') {
+        Some(prefix) => model.starts_with(prefix),
+        None => pattern == model,
+    }
+}
+
+/// Apply the user's model allow-list before a mission starts.
+///
+/// An explicitly requested model must be allowed or the request is rejected.
+/// Without one, `fallback` (the backend default) is kept if allowed, otherwise
+/// the first concrete allowed model is substituted. When the allow-list holds
+/// only wildcards there is nothing to substitute, so the request is rejected
+/// rather than letting the backend pick an unchecked default.
+pub fn resolve_allowed_model(
+    auth: &AuthConfig,
+    user: &AuthUser,
+    requested: Option<&str>,
+    fallback: Option<String>,
+) -> Result<Option<String>, String> {
+    let allowed = allowed_models(auth, user);
+    if allowed.is_empty() {
+        return Ok(requested.map(str::to_string).or(fallback));
+    }
+    let is_allowed = |model: &str| allowed.iter().any(|p| model_matches(p, model));
+
+    if let Some(model) = requested {
+        return if is_allowed(model) {
+            Ok(Some(model.to_string()))
+        } else {
+            Err(format!(
+                "Model '{}' is not allowed for user '{}'",
+                model, user.username
+            ))
+        };
+    }
+    if let Some(model) = fallback.filter(|m| is_allowed(m)) {
+        return Ok(Some(model));
+    }
+    match allowed.iter().find(|p| !p.ends_with('*')) {
+        Some(model) => Ok(Some(model.clone())),
+        None => Err(format!(
+            "User '{}' may only use models matching {}; choose one explicitly",
+            user.username,
+            allowed.join(", ")
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn auth_with_allow_list() -> (AuthConfig, AuthUser) {
+        let auth = AuthConfig {
+            users: vec![UserAccount {
+                id: "tenant-a".to_string(),
+                username: "alice".to_string(),
+                password: "secret".to_string(),
+                allowed_models: vec![
+                    "anthropic/claude-sonnet-4".to_string(),
+                    "openai/*".to_string(),
+                ],
+            }],
+            ..AuthConfig::default()
+        };
+        let user = AuthUser {
+            id: "tenant-a".to_string(),
+            username: "alice".to_string(),
+        };
+        (auth, user)
+    }
+
+    #[test]
+    fn test_disallowed_model_is_rejected() {
+        let (auth, user) = auth_with_allow_list();
+        let err =
+            resolve_allowed_model(&auth, &user, Some("anthropic/claude-opus-4"), None).unwrap_err();
+        assert!(err.contains("not allowed"));
+
+        assert_eq!(
+            resolve_allowed_model(&auth, &user, Some("openai/gpt-5"), None).unwrap(),
+            Some("openai/gpt-5".to_string())
+        );
+    }
+
+    #[test]
+    fn test_disallowed_default_is_substituted() {
+        let (auth, user) = auth_with_allow_list();
+        assert_eq!(
+            resolve_allowed_model(&auth, &user, None, Some("claude-opus-4".to_string())).unwrap(),
+            Some("anthropic/claude-sonnet-4".to_string())
+        );
+
+        let other = AuthUser {
+            id: "someone-else".to_string(),
+            username: "bob".to_string(),
+        };
+        assert_eq!(
+            resolve_allowed_model(&auth, &other, Some("any/model"), None).unwrap(),
+            Some("any/model".to_string())
+        );
+    }
+
+    #[test]
+    fn test_wildcard_only_allow_list_needs_a_concrete_model() {
+        let (mut auth, user) = auth_with_allow_list();
+        auth.users[0].allowed_models = vec!["openai/*".to_string()];
+
+        let err = resolve_allowed_model(&auth, &user, None, None).unwrap_err();
+        assert!(err.contains("openai/*"));
+        assert!(
+            resolve_allowed_model(&auth, &user, None, Some("claude-opus-4".to_string())).is_err()
+        );
+        assert_eq!(
+            resolve_allowed_model(&auth, &user, None, Some("openai/gpt-5".to_string())).unwrap(),
+            Some("openai/gpt-5".to_string())
+        );
+    }
+}
@@ -216,11 +216,18 @@ pub async fn update_backend_config(
                 .get("permissive")
                 .and_then(|v| v.as_bool())
                 .unwrap_or(false);
-            serde_json::json!({
+            let mut updated = serde_json::json!({
                 "base_url": base_url,
                 "default_agent": default_agent,
                 "permissive": permissive,
-            })
+            });
+            // CLI overrides (see `backend_config::CliProfile`)
+            for key in ["cli_path", "workspaces", "profiles"] {
+                if let Some(value) = settings.get(key) {
+                    updated[key] = value.clone();
+                }
+            }
+            updated
         }
         "claudecode" => {
             let mut settings = req.settings.clone();
@@ -296,12 +303,16 @@ pub async fn update_backend_config(
                 .get("permissive")
                 .and_then(|v| v.as_bool())
                 .unwrap_or(true);
+            let options = crate::backend::amp::AmpConfig::from_settings(&req.settings);
 
             serde_json::json!({
                 "api_key": api_key,
                 "cli_path": cli_path,
                 "default_mode": default_mode,
                 "permissive": permissive,
+                "temperature": options.temperature,
+                "max_tokens": options.max_tokens,
+                "extra_args": options.extra_args,
             })
         }
         _ => req.settings.clone(),
@@ -13,7 +13,7 @@ use std::sync::Arc;
 
 use axum::{
     extract::{Extension, Path, State},
-    http::StatusCode,
+    http::{HeaderMap, StatusCode},
     response::sse::{Event, Sse},
     Json,
 };
@@ -21,6 +21,7 @@ use futures::stream::Stream;
 use serde::{Deserialize, Serialize};
 use tokio::sync::{broadcast, mpsc, oneshot, Mutex, RwLock};
 use tokio_util::sync::CancellationToken;
+use tracing::Instrument;
 use uuid::Uuid;
 
 use crate::agents::{AgentContext, AgentRef, TerminalReason};
@@ -33,8 +34,8 @@ use super::auth::AuthUser;
 use super::desktop;
 use super::library::SharedLibrary;
 use super::mission_store::{
-    self, create_mission_store, now_string, Mission, MissionHistoryEntry, MissionStore,
-    MissionStoreType, StoredEvent,
+    self, create_mission_store, now_string, Mission, MissionHistoryEntry, MissionSearchHit,
+    MissionStore, MissionStoreType, StoredEvent,
 };
 use super::routes::AppState;
 
@@ -52,7 +53,7 @@ pub(super) fn safe_truncate_index(s: &str, max: usize) -> usize {
 }
 
 /// Derive a human-readable activity label from a tool call.
-fn activity_label_from_tool_call(tool_name: &str, args: &serde_json::Value) -> String {
+pub(super) fn activity_label_from_tool_call(tool_name: &str, args: &serde_json::Value) -> String {
     fn extract_str<'a>(args: &'a serde_json::Value, keys: &[&str]) -> Option<&'a str> {
         for key in keys {
             if let Some(v) = args.get(*key).and_then(|v| v.as_str()) {
@@ -88,6 +89,13 @@ fn activity_label_from_tool_call(tool_name: &str, args: &serde_json::Value) -> S
             let path = extract_str(args, &["file_path", "path"]).unwrap_or("…");
             format!("Reading: {}", basename(path))
         }
+        "read_many_files" => {
+            let count = args
+                .get("paths")
+                .and_then(|p| p.as_array())
+                .map_or(0, |p| p.len());
+            format!("Reading {} files", count)
+        }
         "Edit" | "edit_file" => {
             let path = extract_str(args, &["file_path", "path"]).unwrap_or("…");
             format!("Editing: {}", basename(path))
@@ -405,6 +413,11 @@ pub enum AgentEvent {
         content: String,
         success: bool,
         cost_cents: u64,
+        /// Input tokens of the turn, cache reads and writes included
+        #[serde(default)]
+        prompt_tokens: u64,
+        #[serde(default)]
+        completion_tokens: u64,
         model: Option<String>,
         /// Mission this message belongs to (for parallel execution)
         #[serde(skip_serializing_if = "Option::is_none")]
@@ -516,6 +529,39 @@ pub enum AgentEvent {
         #[serde(skip_serializing_if = "Option::is_none")]
         mission_id: Option<Uuid>,
     },
+    /// A mission turn concluded
+    MissionComplete {
+        mission_id: Uuid,
+        success: bool,
+        cost_cents: u64,
+        /// Expected deliverables that exist
+        deliverables_present: Vec<String>,
+        /// Expected deliverables that are still missing
+        deliverables_missing: Vec<String>,
+    },
+    /// The agent's todo list changed (todo_add / todo_complete)
+    TodosUpdated {
+        todos: Vec<crate::tools::todo::TodoItem>,
+        /// Mission this todo list belongs to
+        #[serde(skip_serializing_if = "Option::is_none")]
+        mission_id: Option<Uuid>,
+    },
+    /// Running cost and token totals of the turn in progress, sent as the
+    /// backend reports usage so the ledger doesn't wait for the turn to end
+    UsageUpdate {
+        cost_cents: u64,
+        prompt_tokens: u64,
+        completion_tokens: u64,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        mission_id: Option<Uuid>,
+    },
+    /// Periodic one-line summary of a mission's recent progress
+    ProgressSummary {
+        text: String,
+        /// Mission this summary belongs to
+        #[serde(skip_serializing_if = "Option::is_none")]
+        mission_id: Option<Uuid>,
+    },
 }
 
 /// A node in the agent tree (for visualization)
@@ -596,6 +642,10 @@ impl AgentEvent {
             AgentEvent::Progress { .. } => "progress",
             AgentEvent::SessionIdUpdate { .. } => "session_id_update",
             AgentEvent::MissionActivity { .. } => "mission_activity",
+            AgentEvent::MissionComplete { .. } => "mission_complete",
+            AgentEvent::TodosUpdated { .. } => "todos_updated",
+            AgentEvent::ProgressSummary { .. } => "progress_summary",
+            AgentEvent::UsageUpdate { .. } => "usage_update",
         }
     }
 
@@ -615,7 +665,177 @@ impl AgentEvent {
             AgentEvent::Progress { mission_id, .. } => *mission_id,
             AgentEvent::SessionIdUpdate { mission_id, .. } => Some(*mission_id),
             AgentEvent::MissionActivity { mission_id, .. } => *mission_id,
+            AgentEvent::MissionComplete { mission_id, .. } => Some(*mission_id),
+            AgentEvent::TodosUpdated { mission_id, .. } => *mission_id,
+            AgentEvent::ProgressSummary { mission_id, .. } => *mission_id,
+            AgentEvent::UsageUpdate { mission_id, .. } => *mission_id,
+        }
+    }
+}
+
+/// Convert a backend [`ExecutionEvent`] into the event broadcast for `mission_id`.
+///
+/// Text deltas are appended to `text`, the answer being streamed, and broadcast
+/// as the text so far. `MessageComplete` has no counterpart and yields `None`.
+///
+/// [`ExecutionEvent`]: crate::backend::events::ExecutionEvent
+pub fn agent_event_from_execution(
+    event: crate::backend::events::ExecutionEvent,
+    mission_id: Option<Uuid>,
+    text: &mut String,
+) -> Option<AgentEvent> {
+    use crate::backend::events::ExecutionEvent;
+
+    Some(match event {
+        ExecutionEvent::Thinking { content } => AgentEvent::Thinking {
+            content,
+            done: false,
+            mission_id,
+        },
+        ExecutionEvent::ToolCall { id, name, args } => AgentEvent::ToolCall {
+            tool_call_id: id,
+            name,
+            args,
+            mission_id,
+        },
+        ExecutionEvent::ToolResult { id, name, result } => AgentEvent::ToolResult {
+            tool_call_id: id,
+            name,
+            result,
+            mission_id,
+        },
+        ExecutionEvent::TextDelta { content } => {
+            text.push_str(&content);
+            AgentEvent::TextDelta {
+                content: text.clone(),
+                mission_id,
+            }
+        }
+        ExecutionEvent::Error { message } => AgentEvent::Error {
+            message,
+            mission_id,
+            resumable: mission_id.is_some(),
+        },
+        ExecutionEvent::MessageComplete { .. } => return None,
+    })
+}
+
+/// An [`AgentEvent`] as broadcast to subscribers, stamped at emission time.
+///
+/// `seq` is monotonic per mission (events without a mission share one
+/// sequence), so clients can detect gaps and order events after reconnecting.
+#[derive(Debug, Clone, Serialize)]
+pub struct StampedEvent {
+    pub seq: u64,
+    /// Emission time in milliseconds since the Unix epoch (never decreases within a mission).
+    pub ts_ms: i64,
+    /// Position in the sender's stream across all missions; sent as the SSE event id.
+    #[serde(skip)]
+    pub stream_pos: u64,
+    #[serde(flatten)]
+    pub event: AgentEvent,
+}
+
+/// Recent events kept per mission for subscribers that (re)connect late.
+const REPLAY_EVENTS_PER_MISSION: usize = 256;
+/// Missions with a replay buffer; the one idle the longest is dropped first.
+const REPLAY_MAX_MISSIONS: usize = 32;
+
+/// Stamping state and replay buffers, shared by clones of an [`EventSender`].
+#[derive(Debug, Default)]
+struct EventLog {
+    /// Last `(seq, ts_ms)` issued per mission.
+    clocks: HashMap<Option<Uuid>, (u64, i64)>,
+    last_pos: u64,
+    recent: HashMap<Option<Uuid>, VecDeque<StampedEvent>>,
+}
+
+impl EventLog {
+    fn record(&mut self, event: StampedEvent) {
+        let mission_id = event.event.mission_id();
+        if !self.recent.contains_key(&mission_id) && self.recent.len() >= REPLAY_MAX_MISSIONS {
+            let idle = self
+                .recent
+                .iter()
+                .min_by_key(|(_, events)| events.back().map_or(0, |e| e.stream_pos))
+                .map(|(id, _)| *id);
+            if let Some(idle) = idle {
+                self.recent.remove(&idle);
+            }
+        }
+        let events = self.recent.entry(mission_id).or_default();
+        if events.len() >= REPLAY_EVENTS_PER_MISSION {
+            events.pop_front();
         }
+        events.push_back(event);
+    }
+}
+
+/// Broadcast sender for agent events that assigns `seq` and `ts_ms` on send
+/// and keeps the latest events of each mission for [`EventSender::replay`].
+#[derive(Debug, Clone)]
+pub struct EventSender {
+    tx: broadcast::Sender<StampedEvent>,
+    log: Arc<std::sync::Mutex<EventLog>>,
+}
+
+impl EventSender {
+    pub fn channel(capacity: usize) -> (Self, broadcast::Receiver<StampedEvent>) {
+        let (tx, rx) = broadcast::channel(capacity);
+        let sender = Self {
+            tx,
+            log: Arc::new(std::sync::Mutex::new(EventLog::default())),
+        };
+        (sender, rx)
+    }
+
+    /// Stamp and broadcast an event. Returns the number of receivers, or an
+    /// error when nobody is subscribed (the event is then only kept for replay).
+    pub fn send(&self, event: AgentEvent) -> Result<usize, broadcast::error::SendError<()>> {
+        let mut log = self.log.lock().unwrap_or_else(|e| e.into_inner());
+        let (last_seq, last_ts) = log
+            .clocks
+            .get(&event.mission_id())
+            .copied()
+            .unwrap_or((0, 0));
+        let seq = last_seq + 1;
+        let ts_ms = chrono::Utc::now().timestamp_millis().max(last_ts);
+        log.clocks.insert(event.mission_id(), (seq, ts_ms));
+        log.last_pos += 1;
+        let stamped = StampedEvent {
+            seq,
+            ts_ms,
+            stream_pos: log.last_pos,
+            event,
+        };
+        log.record(stamped.clone());
+        // Send while holding the lock so channel order matches sequence order.
+        self.tx
+            .send(stamped)
+            .map_err(|_| broadcast::error::SendError(()))
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<StampedEvent> {
+        self.tx.subscribe()
+    }
+
+    /// Buffered events after stream position `after` (all of them if `None`),
+    /// in emission order.
+    ///
+    /// Positions restart with the process, so an `after` beyond the last
+    /// position issued comes from an earlier run and everything is replayed.
+    pub fn replay(&self, after: Option<u64>) -> Vec<StampedEvent> {
+        let log = self.log.lock().unwrap_or_else(|e| e.into_inner());
+        let after = after.filter(|after| *after <= log.last_pos).unwrap_or(0);
+        let mut events: Vec<StampedEvent> = log
+            .recent
+            .values()
+            .flatten()
+            .filter(|e| e.stream_pos > after)
+            .cloned()
+            .collect();
+        events.sort_by_key(|e| e.stream_pos);
+        events
     }
 }
 
@@ -653,6 +873,14 @@ pub enum ControlCommand {
         model_override: Option<String>,
         /// Backend to use for this mission ("opencode" or "claudecode")
         backend: Option<String>,
+        /// Snapshot the workspace before the first turn
+        snapshot_before_run: bool,
+        /// Named backend profile from the backend config
+        backend_profile: Option<String>,
+        /// Destructive tool calls wait for user approval
+        safe_mode: bool,
+        /// Scheduling weight when execution slots are contended
+        priority: u32,
         respond: oneshot::Sender<Result<Mission, String>>,
     },
     /// Update mission status
@@ -767,6 +995,27 @@ pub struct SetMissionStatusRequest {
     pub status: MissionStatus,
 }
 
+/// Request to override a mission's log verbosity.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SetMissionLogLevelRequest {
+    /// `error`, `warn`, `info`, `debug` or `trace`; `null` clears the override.
+    pub level: Option<String>,
+}
+
+/// Output lines of a tool running in the workspace MCP host.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ToolOutputRequest {
+    pub lines: Vec<String>,
+}
+
+/// A destructive tool call awaiting safe-mode approval.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ConfirmActionRequest {
+    pub tool: String,
+    #[serde(default)]
+    pub args: serde_json::Value,
+}
+
 // MissionStore trait and implementations are in mission_store module
 
 /// Shared tool hub used to await frontend tool results.
@@ -837,7 +1086,7 @@ impl FrontendToolHub {
 #[derive(Clone)]
 pub struct ControlState {
     pub cmd_tx: mpsc::Sender<ControlCommand>,
-    pub events_tx: broadcast::Sender<AgentEvent>,
+    pub events_tx: EventSender,
     pub tool_hub: Arc<FrontendToolHub>,
     pub status: Arc<RwLock<ControlStatus>>,
     /// Current mission ID (if any) - primary mission in the old sequential model
@@ -848,8 +1097,8 @@ pub struct ControlState {
     pub progress: Arc<RwLock<ExecutionProgress>>,
     /// Running missions (for parallel execution)
     pub running_missions: Arc<RwLock<Vec<super::mission_runner::RunningMissionInfo>>>,
-    /// Max parallel missions allowed
-    pub max_parallel: usize,
+    /// Server-wide parallel mission slots (shared by every control session)
+    pub slots: Arc<super::mission_slots::MissionSlots>,
     /// Mission persistence (SQLite-backed)
     pub mission_store: Arc<dyn MissionStore>,
 }
@@ -864,6 +1113,7 @@ pub struct ControlHub {
     workspaces: workspace::SharedWorkspaceStore,
     library: SharedLibrary,
     secrets: Option<Arc<SecretsStore>>,
+    slots: Arc<super::mission_slots::MissionSlots>,
 }
 
 impl ControlHub {
@@ -875,6 +1125,9 @@ impl ControlHub {
         library: SharedLibrary,
         secrets: Option<Arc<SecretsStore>>,
     ) -> Self {
+        let slots = Arc::new(super::mission_slots::MissionSlots::new(
+            config.max_parallel_missions,
+        ));
         Self {
             sessions: Arc::new(RwLock::new(HashMap::new())),
             config,
@@ -883,6 +1136,7 @@ impl ControlHub {
             workspaces,
             library,
             secrets,
+            slots,
         }
     }
 
@@ -922,6 +1176,8 @@ impl ControlHub {
             Arc::clone(&self.library),
             mission_store,
             self.secrets.clone(),
+            Arc::clone(&self.slots),
+            user.id.clone(),
         );
         sessions.insert(user.id.clone(), state.clone());
         state
@@ -981,7 +1237,7 @@ pub struct ControlStatus {
 
 async fn set_and_emit_status(
     status: &Arc<RwLock<ControlStatus>>,
-    events: &broadcast::Sender<AgentEvent>,
+    events: &EventSender,
     state: ControlRunState,
     queue_len: usize,
     mission_id: Option<Uuid>,
@@ -1259,6 +1515,17 @@ pub struct CreateMissionRequest {
     pub model_override: Option<String>,
     /// Backend to use for this mission ("opencode" or "claudecode")
     pub backend: Option<String>,
+    /// Snapshot the workspace before the first turn (for destructive missions)
+    #[serde(default)]
+    pub snapshot_before_run: bool,
+    /// Named backend profile (CLI path, model, extra args) from the backend config
+    pub backend_profile: Option<String>,
+    /// Ask the user before running destructive tool calls (deletes, `rm`, `git reset --hard`, ...)
+    #[serde(default)]
+    pub safe_mode: bool,
+    /// Scheduling weight when execution slots are contended: a priority-2
+    /// mission gets twice the turns of a priority-1 one (default 1)
+    pub priority: Option<u32>,
 }
 
 pub async fn create_mission(
@@ -1268,7 +1535,17 @@ pub async fn create_mission(
 ) -> Result<Json<Mission>, (StatusCode, String)> {
     let (tx, rx) = oneshot::channel();
 
-    let (title, workspace_id, agent, model_override, mut backend) = body
+    let (
+        title,
+        workspace_id,
+        agent,
+        model_override,
+        mut backend,
+        snapshot_before_run,
+        backend_profile,
+        safe_mode,
+        priority,
+    ) = body
         .map(|b| {
             (
                 b.title.clone(),
@@ -1276,9 +1553,19 @@ pub async fn create_mission(
                 b.agent.clone(),
                 b.model_override.clone(),
                 b.backend.clone(),
+                b.snapshot_before_run,
+                b.backend_profile.clone(),
+                b.safe_mode,
+                b.priority,
             )
         })
-        .unwrap_or((None, None, None, None, None));
+        .unwrap_or((None, None, None, None, None, false, None, false, None));
+    if priority == Some(0) {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "priority must be at least 1".to_string(),
+        ));
+    }
 
     let mut model_override = model_override;
     if let Some(value) = backend.as_ref() {
@@ -1314,12 +1601,45 @@ pub async fn create_mission(
         }
     }
 
-    if backend.as_deref() == Some("claudecode") && model_override.is_none() {
-        if let Some(default_model) = resolve_claudecode_default_model(&state.library).await {
-            model_override = Some(default_model);
-        }
+    // Per-workspace and profile overrides from the backend config; the
+    // resolved model becomes the mission's default model.
+    let backend_profile = backend_profile.filter(|p| !p.trim().is_empty());
+    let profile_backend = backend.clone().unwrap_or_else(|| "opencode".to_string());
+    if backend_profile.is_some() && !matches!(profile_backend.as_str(), "claudecode" | "opencode") {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            format!("Backend profiles are not supported for {}", profile_backend),
+        ));
+    }
+    let settings = state
+        .backend_configs
+        .get(&profile_backend)
+        .await
+        .map(|entry| entry.settings)
+        .unwrap_or(serde_json::Value::Null);
+    let cli_profile = crate::backend_config::CliProfile::resolve(
+        &settings,
+        Some(workspace_id.unwrap_or(workspace::DEFAULT_WORKSPACE_ID)),
+        backend_profile.as_deref(),
+    )
+    .map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+    if model_override.is_none() {
+        model_override = cli_profile.model;
     }
 
+    let default_model = if backend.as_deref() == Some("claudecode") && model_override.is_none() {
+        resolve_claudecode_default_model(&state.library).await
+    } else {
+        None
+    };
+    let model_override = super::auth::resolve_allowed_model(
+        &state.config.auth,
+        &user,
+        model_override.as_deref(),
+        default_model,
+    )
+    .map_err(|e| (StatusCode::FORBIDDEN, e))?;
+
     let control = control_for_user(&state, &user).await;
     control
         .cmd_tx
@@ -1329,6 +1649,10 @@ pub async fn create_mission(
             agent,
             model_override,
             backend,
+            snapshot_before_run,
+            backend_profile,
+            safe_mode,
+            priority: priority.unwrap_or(1),
             respond: tx,
         })
         .await
@@ -1486,6 +1810,45 @@ pub async fn get_mission_tree(
     }
 }
 
+/// Raise (or reset) log verbosity for a single mission.
+///
+/// Only log lines emitted inside the mission's turns are affected; the rest of
+/// the process keeps the global `RUST_LOG` level.
+pub async fn set_mission_log_level(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<AuthUser>,
+    Path(mission_id): Path<Uuid>,
+    Json(req): Json<SetMissionLogLevelRequest>,
+) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
+    let level = match req.level.as_deref() {
+        Some(name) => Some(crate::mission_log::parse_level(name).ok_or_else(|| {
+            (
+                StatusCode::BAD_REQUEST,
+                format!("Invalid log level '{}'", name),
+            )
+        })?),
+        None => None,
+    };
+
+    let control = control_for_user(&state, &user).await;
+    let mission = control
+        .mission_store
+        .get_mission(mission_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+    if mission.is_none() {
+        return Err((StatusCode::NOT_FOUND, "Mission not found".to_string()));
+    }
+
+    crate::mission_log::set_level(mission_id, level);
+    tracing::info!(mission_id = %mission_id, level = ?level, "Mission log level override updated");
+    Ok(Json(serde_json::json!({
+        "ok": true,
+        "mission_id": mission_id,
+        "level": level.map(|l| l.to_string().to_lowercase()),
+    })))
+}
+
 /// Get current execution progress (for progress indicator).
 pub async fn get_progress(
     State(state): State<Arc<AppState>>,
@@ -1545,6 +1908,37 @@ pub async fn get_mission_events(
     Ok(Json(events))
 }
 
+/// Query params for mission search endpoint.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SearchMissionsQuery {
+    /// Keywords that must all appear in one message
+    pub q: String,
+    /// Maximum number of missions to return (default: 10)
+    #[serde(default)]
+    pub limit: Option<usize>,
+    /// Mission to leave out (typically the caller's own)
+    #[serde(default)]
+    pub exclude: Option<Uuid>,
+}
+
+/// Full-text search past missions' conversations.
+pub async fn search_missions(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<AuthUser>,
+    axum::extract::Query(query): axum::extract::Query<SearchMissionsQuery>,
+) -> Result<Json<Vec<MissionSearchHit>>, (StatusCode, String)> {
+    let control = control_for_user(&state, &user).await;
+    let limit = query.limit.unwrap_or(10).min(100);
+    let mut hits = control
+        .mission_store
+        .search_missions(&query.q, limit + 1)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+    hits.retain(|hit| Some(hit.mission_id) != query.exclude);
+    hits.truncate(limit);
+    Ok(Json(hits))
+}
+
 // ==================== Diagnostic Endpoints ====================
 
 /// Response for OpenCode diagnostic endpoint.
@@ -1610,6 +2004,236 @@ pub async fn list_running_missions(
     Ok(Json(running))
 }
 
+/// Relay output of a tool still running in the workspace MCP host (e.g. a long
+/// `run_command` build) to the mission's stream as `thinking` events.
+pub async fn post_tool_output(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<AuthUser>,
+    Path(mission_id): Path<Uuid>,
+    Json(req): Json<ToolOutputRequest>,
+) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
+    let control = control_for_user(&state, &user).await;
+    let mission = control
+        .mission_store
+        .get_mission(mission_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+    if mission.is_none() {
+        return Err((StatusCode::NOT_FOUND, "Mission not found".to_string()));
+    }
+
+    if !req.lines.is_empty() {
+        let _ = control.events_tx.send(AgentEvent::Thinking {
+            content: req.lines.join("\n"),
+            done: false,
+            mission_id: Some(mission_id),
+        });
+    }
+    Ok(Json(serde_json::json!({ "ok": true })))
+}
+
+/// Ask the user to approve a destructive tool call of a safe-mode mission.
+///
+/// Called by the workspace MCP host before running the tool; answers at once
+/// when the mission is not in safe mode or the call isn't destructive,
+/// otherwise waits for the user's decision.
+pub async fn confirm_action(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<AuthUser>,
+    Path(mission_id): Path<Uuid>,
+    Json(req): Json<ConfirmActionRequest>,
+) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
+    use crate::tools::safe_mode::{destructive_reason, ConfirmAction, SafeModeGate};
+
+    let control = control_for_user(&state, &user).await;
+    let mission = control
+        .mission_store
+        .get_mission(mission_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?
+        .ok_or_else(|| (StatusCode::NOT_FOUND, "Mission not found".to_string()))?;
+
+    let reason = match destructive_reason(&req.tool, &req.args) {
+        Some(reason) if mission.safe_mode => reason,
+        _ => return Ok(Json(serde_json::json!({ "approved": true }))),
+    };
+    let mut gate = SafeModeGate::new(
+        Arc::clone(&control.tool_hub),
+        control.events_tx.clone(),
+        Some(mission_id),
+    );
+    if *control.current_mission.read().await == Some(mission_id) {
+        gate = gate.with_status(Arc::clone(&control.status));
+    }
+    let approved = gate
+        .confirm(&req.tool, &req.args, &reason)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    Ok(Json(serde_json::json!({ "approved": approved })))
+}
+
+/// Show a `ui_form` in the mission's stream and wait for the submitted values.
+/// The body is the form (the `ui_form` tool arguments); used by the workspace
+/// MCP host, whose tools can't reach the frontend tool hub directly.
+pub async fn post_ui_form(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<AuthUser>,
+    Path(mission_id): Path<Uuid>,
+    Json(form): Json<serde_json::Value>,
+) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
+    let control = control_for_user(&state, &user).await;
+    if control
+        .mission_store
+        .get_mission(mission_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?
+        .is_none()
+    {
+        return Err((StatusCode::NOT_FOUND, "Mission not found".to_string()));
+    }
+
+    let tool_call_id = format!("ui_form_{}", Uuid::new_v4());
+    let rx = control.tool_hub.register(tool_call_id.clone()).await;
+    let _ = control.events_tx.send(AgentEvent::ToolCall {
+        tool_call_id: tool_call_id.clone(),
+        name: "ui_form".to_string(),
+        args: form,
+        mission_id: Some(mission_id),
+    });
+    let result = rx.await.unwrap_or(serde_json::Value::Null);
+    let _ = control.events_tx.send(AgentEvent::ToolResult {
+        tool_call_id,
+        name: "ui_form".to_string(),
+        result: result.clone(),
+        mission_id: Some(mission_id),
+    });
+    Ok(Json(result))
+}
+
+/// Cost and token totals of one mission.
+#[derive(Debug, Serialize)]
+pub struct MissionCostResponse {
+    pub mission_id: Uuid,
+    #[serde(flatten)]
+    pub cost: super::mission_runner::CostLedger,
+    /// Whether the totals come from a live runner. Otherwise they are rebuilt
+    /// from the stored assistant messages.
+    pub live: bool,
+}
+
+/// Get the cost ledger of a mission (cost in cents, prompt/completion tokens).
+pub async fn get_mission_cost(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<AuthUser>,
+    Path(mission_id): Path<Uuid>,
+) -> Result<Json<MissionCostResponse>, (StatusCode, String)> {
+    let control = control_for_user(&state, &user).await;
+
+    let (tx, rx) = oneshot::channel();
+    control
+        .cmd_tx
+        .send(ControlCommand::ListRunning { respond: tx })
+        .await
+        .map_err(|_| {
+            (
+                StatusCode::SERVICE_UNAVAILABLE,
+                "control session unavailable".to_string(),
+            )
+        })?;
+    let running = rx.await.map_err(|_| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "Failed to receive response".to_string(),
+        )
+    })?;
+    if let Some(info) = running.into_iter().find(|r| r.mission_id == mission_id) {
+        return Ok(Json(MissionCostResponse {
+            mission_id,
+            cost: info.cost,
+            live: true,
+        }));
+    }
+
+    if control
+        .mission_store
+        .get_mission(mission_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?
+        .is_none()
+    {
+        return Err((StatusCode::NOT_FOUND, "Mission not found".to_string()));
+    }
+    let messages = control
+        .mission_store
+        .get_events(mission_id, Some(&["assistant_message"]), None, None)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+    let total = |key: &str| -> u64 {
+        messages
+            .iter()
+            .filter_map(|m| m.metadata.get(key).and_then(|c| c.as_u64()))
+            .sum()
+    };
+    let cost = super::mission_runner::CostLedger {
+        cost_cents: total("cost_cents"),
+        prompt_tokens: total("prompt_tokens"),
+        completion_tokens: total("completion_tokens"),
+        turns: messages.len() as u32,
+    };
+    Ok(Json(MissionCostResponse {
+        mission_id,
+        cost,
+        live: false,
+    }))
+}
+
+/// Report which of a mission's expected deliverables exist yet (the same report
+/// as the `deliverable_status` tool). Missions without a live runner have no
+/// tracked deliverables.
+pub async fn get_mission_deliverables(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<AuthUser>,
+    Path(mission_id): Path<Uuid>,
+) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
+    let control = control_for_user(&state, &user).await;
+
+    let (tx, rx) = oneshot::channel();
+    control
+        .cmd_tx
+        .send(ControlCommand::ListRunning { respond: tx })
+        .await
+        .map_err(|_| {
+            (
+                StatusCode::SERVICE_UNAVAILABLE,
+                "control session unavailable".to_string(),
+            )
+        })?;
+    let running = rx.await.map_err(|_| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "Failed to receive response".to_string(),
+        )
+    })?;
+    let deliverables = match running.into_iter().find(|r| r.mission_id == mission_id) {
+        Some(info) => info.deliverables,
+        None => {
+            if control
+                .mission_store
+                .get_mission(mission_id)
+                .await
+                .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?
+                .is_none()
+            {
+                return Err((StatusCode::NOT_FOUND, "Mission not found".to_string()));
+            }
+            Default::default()
+        }
+    };
+    Ok(Json(
+        crate::tools::mission::deliverable_status_report(&deliverables).await,
+    ))
+}
+
 /// Request body for starting a mission in parallel.
 #[derive(Debug, Deserialize)]
 pub struct StartParallelRequest {
@@ -1737,10 +2361,48 @@ pub async fn get_parallel_config(
     State(state): State<Arc<AppState>>,
     Extension(user): Extension<AuthUser>,
 ) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
-    // Query actual running count from the control actor
+    let control = control_for_user(&state, &user).await;
+    parallel_config(&control).await
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateParallelConfigRequest {
+    pub max_parallel_missions: usize,
+}
+
+/// Change the server-wide limit on concurrently running missions.
+/// Missions already running keep running; queued ones start as slots free up.
+/// The limit is shared by every user, so multi-user servers refuse the change.
+pub async fn update_parallel_config(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<AuthUser>,
+    Json(req): Json<UpdateParallelConfigRequest>,
+) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
+    if state.config.auth.auth_mode(state.config.dev_mode) == crate::config::AuthMode::MultiUser {
+        return Err((
+            StatusCode::FORBIDDEN,
+            "The parallel mission limit is server-wide and can only be changed in single-tenant mode"
+                .to_string(),
+        ));
+    }
+    if req.max_parallel_missions == 0 {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "max_parallel_missions must be at least 1".to_string(),
+        ));
+    }
+    let control = control_for_user(&state, &user).await;
+    control.slots.set_limit(req.max_parallel_missions);
+    tracing::info!("Max parallel missions set to {}", req.max_parallel_missions);
+    parallel_config(&control).await
+}
+
+async fn parallel_config(
+    control: &ControlState,
+) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
+    // Query this session's runners from the control actor
     // (the running state is tracked in the actor loop, not in shared state)
     let (tx, rx) = oneshot::channel();
-    let control = control_for_user(&state, &user).await;
     control
         .cmd_tx
         .send(ControlCommand::ListRunning { respond: tx })
@@ -1759,9 +2421,22 @@ pub async fn get_parallel_config(
         )
     })?;
 
+    let running_count = running
+        .iter()
+        .filter(|m| matches!(m.state.as_str(), "running" | "waiting_for_tool"))
+        .count();
+    let queued_count = running
+        .iter()
+        .filter(|m| !matches!(m.state.as_str(), "running" | "waiting_for_tool") && m.queue_len > 0)
+        .count();
+    let global = control.slots.total();
+
     Ok(Json(serde_json::json!({
-        "max_parallel_missions": control.max_parallel,
-        "running_count": running.len(),
+        "max_parallel_missions": control.slots.limit(),
+        "running_count": running_count,
+        "queued_count": queued_count,
+        "global_running_count": global.running,
+        "global_queued_count": global.queued,
     })))
 }
 
@@ -1864,9 +2539,16 @@ pub async fn cleanup_empty_missions(
 pub async fn stream(
     State(state): State<Arc<AppState>>,
     Extension(user): Extension<AuthUser>,
+    headers: HeaderMap,
 ) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, (StatusCode, String)> {
     let control = control_for_user(&state, &user).await;
+    let last_event_id = headers
+        .get("last-event-id")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.trim().parse::<u64>().ok());
+    // Subscribe before taking the replay so no event falls in between; duplicates are skipped below.
     let mut rx = control.events_tx.subscribe();
+    let replay = control.events_tx.replay(last_event_id);
     let stream_id = Uuid::new_v4();
     tracing::info!(
         stream_id = %stream_id,
@@ -1909,6 +2591,19 @@ pub async fn stream(
             .unwrap();
         yield Ok(init_ev);
 
+        // Flush recent history, so a reconnecting client catches up before going live.
+        // Live events already sent by the replay are skipped.
+        let mut replayed_to = 0;
+        for stamped in replay {
+            replayed_to = stamped.stream_pos;
+            let sse = Event::default()
+                .event(stamped.event.event_name())
+                .id(stamped.stream_pos.to_string())
+                .json_data(&stamped)
+                .unwrap();
+            yield Ok(sse);
+        }
+
         // Keepalive interval to prevent connection timeouts during long LLM calls
         let mut keepalive_interval = tokio::time::interval(std::time::Duration::from_secs(15));
         keepalive_interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
@@ -1917,9 +2612,11 @@ pub async fn stream(
             tokio::select! {
                 result = rx.recv() => {
                     match result {
-                        Ok(ev) => {
+                        Ok(stamped) if stamped.stream_pos <= replayed_to => {}
+                        Ok(stamped) => {
+                            let ev = &stamped.event;
                             let mission_id = ev.mission_id();
-                            match &ev {
+                            match ev {
                                 AgentEvent::Thinking { .. } => {
                                     tracing::trace!(
                                         stream_id = %stream_id,
@@ -1937,7 +2634,11 @@ pub async fn stream(
                                     );
                                 }
                             }
-                            let sse = Event::default().event(ev.event_name()).json_data(&ev).unwrap();
+                            let sse = Event::default()
+                                .event(ev.event_name())
+                                .id(stamped.stream_pos.to_string())
+                                .json_data(&stamped)
+                                .unwrap();
                             yield Ok(sse);
                         }
                         Err(broadcast::error::RecvError::Lagged(_)) => {
@@ -1971,6 +2672,7 @@ pub async fn stream(
 }
 
 /// Spawn the global control session actor.
+#[allow(clippy::too_many_arguments)]
 fn spawn_control_session(
     config: Config,
     root_agent: AgentRef,
@@ -1979,9 +2681,11 @@ fn spawn_control_session(
     library: SharedLibrary,
     mission_store: Arc<dyn MissionStore>,
     secrets: Option<Arc<SecretsStore>>,
+    slots: Arc<super::mission_slots::MissionSlots>,
+    session_key: String,
 ) -> ControlState {
     let (cmd_tx, cmd_rx) = mpsc::channel::<ControlCommand>(256);
-    let (events_tx, events_rx) = broadcast::channel::<AgentEvent>(1024);
+    let (events_tx, events_rx) = EventSender::channel(1024);
     let tool_hub = Arc::new(FrontendToolHub::new());
     let status = Arc::new(RwLock::new(ControlStatus {
         state: ControlRunState::Idle,
@@ -1997,8 +2701,6 @@ fn spawn_control_session(
     let current_tree = Arc::new(RwLock::new(None));
     let progress = Arc::new(RwLock::new(ExecutionProgress::default()));
     let running_missions = Arc::new(RwLock::new(Vec::new()));
-    let max_parallel = config.max_parallel_missions;
-
     let state = ControlState {
         cmd_tx,
         events_tx: events_tx.clone(),
@@ -2008,7 +2710,7 @@ fn spawn_control_session(
         current_tree: Arc::clone(&current_tree),
         progress: Arc::clone(&progress),
         running_missions: Arc::clone(&running_missions),
-        max_parallel,
+        slots: Arc::clone(&slots),
         mission_store: Arc::clone(&mission_store),
     };
 
@@ -2031,6 +2733,8 @@ fn spawn_control_session(
         progress,
         mission_store,
         secrets,
+        slots,
+        session_key,
     ));
 
     // Recover orphaned missions from previous run.
@@ -2097,6 +2801,15 @@ fn spawn_control_session(
         ));
     }
 
+    // Spawn progress summary task (if enabled)
+    if !config.mission.progress_summary_interval.is_zero() {
+        tokio::spawn(super::progress_summary::progress_summary_loop(
+            events_tx.clone(),
+            events_tx.subscribe(),
+            config.mission.progress_summary_interval,
+        ));
+    }
+
     // Spawn event logger task (logs all events to SQLite for debugging/replay)
     if state.mission_store.is_persistent() {
         let store = Arc::clone(&state.mission_store);
@@ -2104,7 +2817,7 @@ fn spawn_control_session(
         tokio::spawn(async move {
             loop {
                 match event_rx.recv().await {
-                    Ok(event) => {
+                    Ok(StampedEvent { event, .. }) => {
                         // Extract mission_id from event
                         if let Some(mid) = event.mission_id() {
                             if let Err(e) = store.log_event(mid, &event).await {
@@ -2137,7 +2850,7 @@ async fn stale_mission_cleanup_loop(
     mission_store: Arc<dyn MissionStore>,
     stale_hours: u64,
     running_missions: Arc<RwLock<Vec<super::mission_runner::RunningMissionInfo>>>,
-    events_tx: broadcast::Sender<AgentEvent>,
+    events_tx: EventSender,
 ) {
     // Check every 5 minutes (fast enough to catch orphans promptly).
     let check_interval = std::time::Duration::from_secs(300);
@@ -2237,8 +2950,8 @@ async fn control_actor_loop(
     mut cmd_rx: mpsc::Receiver<ControlCommand>,
     mut mission_cmd_rx: mpsc::Receiver<crate::tools::mission::MissionControlCommand>,
     mission_cmd_tx: mpsc::Sender<crate::tools::mission::MissionControlCommand>,
-    events_tx: broadcast::Sender<AgentEvent>,
-    mut events_rx: broadcast::Receiver<AgentEvent>,
+    events_tx: EventSender,
+    mut events_rx: broadcast::Receiver<StampedEvent>,
     tool_hub: Arc<FrontendToolHub>,
     status: Arc<RwLock<ControlStatus>>,
     current_mission: Arc<RwLock<Option<Uuid>>>,
@@ -2246,6 +2959,8 @@ async fn control_actor_loop(
     progress: Arc<RwLock<ExecutionProgress>>,
     mission_store: Arc<dyn MissionStore>,
     secrets: Option<Arc<SecretsStore>>,
+    slots: Arc<super::mission_slots::MissionSlots>,
+    session_key: String,
 ) {
     // Queue stores (id, content, agent) for the current/primary mission
     let mut queue: VecDeque<(Uuid, String, Option<String>)> = VecDeque::new();
@@ -2256,12 +2971,20 @@ async fn control_actor_loop(
     // Track which mission the main `running` task is actually working on.
     // This is different from `current_mission` which can change when user creates a new mission.
     let mut running_mission_id: Option<Uuid> = None;
+    // Slot held (dropped to release) by the main session's turn; it always runs,
+    // but counts against parallel starts
+    let mut _running_slot: Option<super::mission_slots::SlotPermit> = None;
     // Track last activity for the main runner (for stall detection)
     let mut main_runner_last_activity: std::time::Instant = std::time::Instant::now();
     // Track current activity label for the main runner
     let mut main_runner_activity: Option<String> = None;
     // Track subtasks for the main runner
     let mut main_runner_subtasks: Vec<super::mission_runner::SubtaskInfo> = Vec::new();
+    // Cost ledger of the last mission the main runner worked on
+    let mut main_runner_cost: (Option<Uuid>, super::mission_runner::CostLedger) =
+        (None, Default::default());
+    // Usage streamed so far by the main runner's turn in progress
+    let mut main_runner_turn_cost = super::mission_runner::CostLedger::default();
 
     // Parallel mission runners - each runs independently
     let mut parallel_runners: std::collections::HashMap<
@@ -2269,6 +2992,34 @@ async fn control_actor_loop(
         super::mission_runner::MissionRunner,
     > = std::collections::HashMap::new();
 
+    // Rehydrate runners persisted before the last shutdown (only this user's missions)
+    let runner_state_dir = super::mission_runner::runner_state_dir(&config.working_dir);
+    for mut runner in super::mission_runner::load_runner_states(&runner_state_dir, &config.mission)
+    {
+        let Ok(Some(mission)) = mission_store.get_mission(runner.mission_id).await else {
+            continue;
+        };
+        tracing::info!(
+            "Restored mission runner {} (state: {:?}, queued: {}, interrupted: {})",
+            runner.mission_id,
+            runner.state,
+            runner.queue.len(),
+            runner.interrupted
+        );
+        runner.mission_store = Some(Arc::clone(&mission_store));
+        runner.mcp_env = workspace_mcp_env(&config, &session_key, mission.safe_mode);
+        parallel_runners.insert(runner.mission_id, runner);
+    }
+    let persist_runner = |runner: &super::mission_runner::MissionRunner| {
+        if let Err(e) = runner.save_state(&runner_state_dir) {
+            tracing::warn!(
+                "Failed to persist runner for mission {}: {}",
+                runner.mission_id,
+                e
+            );
+        }
+    };
+
     // Helper to extract file paths from text (for mission summaries)
     fn extract_file_paths(text: &str) -> Vec<String> {
         let mut paths = Vec::new();
@@ -2592,17 +3343,22 @@ async fn control_actor_loop(
                         if let Some(tid) = effective_target {
                             if target_in_parallel {
                                 if let Some(runner) = parallel_runners.get_mut(&tid) {
-                                    let was_running = runner.is_running();
+                                    // Without a free slot the message waits; the poll loop starts it later.
+                                    let permit = if runner.is_running() {
+                                        None
+                                    } else {
+                                        slots.try_reserve(&session_key)
+                                    };
+                                    let start_now = permit.is_some();
                                     runner.queue_message(id, content.clone(), msg_agent);
                                     let _ = events_tx.send(AgentEvent::UserMessage {
                                         id,
                                         content: content.clone(),
-                                        queued: was_running,
+                                        queued: !start_now,
                                         mission_id: Some(tid),
                                     });
-                                    // Try to start if not already running
-                                    if !runner.is_running() {
-                                        runner.start_next(
+                                    if let Some(permit) = permit {
+                                        if runner.start_next(
                                             config.clone(),
                                             Arc::clone(&root_agent),
                                             Arc::clone(&mcp),
@@ -2614,9 +3370,14 @@ async fn control_actor_loop(
                                             mission_cmd_tx.clone(),
                                             Arc::new(RwLock::new(Some(tid))),
                                             secrets.clone(),
-                                        );
+                                        ) {
+                                            runner.slot = Some(permit);
+                                        }
                                     }
-                                    let _ = respond.send(was_running);
+                                    // Saved after starting, so a restart sees the turn as running
+                                    // (and interrupted) rather than still queued.
+                                    persist_runner(runner);
+                                    let _ = respond.send(!start_now);
                                     continue;
                                 }
                             }
@@ -2625,77 +3386,81 @@ async fn control_actor_loop(
                         // Case 2: Target differs from main AND main is running → start parallel
                         if let Some(tid) = effective_target {
                             if !target_is_main && main_is_running {
-                                // Check capacity
-                                let parallel_running = parallel_runners.values().filter(|r| r.is_running()).count();
-                                let total_running = parallel_running + 1; // +1 for main
-                                let max_parallel = config.max_parallel_missions;
-
-                                if total_running >= max_parallel {
-                                    tracing::warn!(
-                                        "Cannot start parallel mission {}: max {} reached",
-                                        tid, max_parallel
-                                    );
-                                    // Fall through to queue on main as fallback
-                                } else {
-                                    // Load mission and start in parallel
-                                    match load_mission_record(&mission_store, tid).await {
-                                        Ok(mission) => {
-                                            // Activate mission: if pending, interrupted, or blocked, update status to active
-                                            if matches!(mission.status, MissionStatus::Pending | MissionStatus::Interrupted | MissionStatus::Blocked) {
-                                                tracing::info!("Activating parallel mission {} (was {})", tid, mission.status);
-                                                if let Err(e) = mission_store.update_mission_status(tid, MissionStatus::Active).await {
-                                                    tracing::warn!("Failed to activate parallel mission {}: {}", tid, e);
-                                                } else {
-                                                    let _ = events_tx.send(AgentEvent::MissionStatusChanged {
-                                                        mission_id: tid,
-                                                        status: MissionStatus::Active,
-                                                        summary: None,
-                                                    });
-                                                }
-                                            }
-                                            let mut runner = super::mission_runner::MissionRunner::new(
-                                                tid,
-                                                mission.workspace_id,
-                                                mission.agent.clone(),
-                                                Some(mission.backend.clone()),
-                                                mission.session_id.clone(),
-                                            );
-                                            // Load existing history
-                                            for entry in &mission.history {
-                                                runner.history.push((entry.role.clone(), entry.content.clone()));
+                                // Load mission and start in parallel, or leave it queued until a slot frees
+                                match load_mission_record(&mission_store, tid).await {
+                                    Ok(mission) => {
+                                        // Activate mission: if pending, interrupted, or blocked, update status to active
+                                        if matches!(mission.status, MissionStatus::Pending | MissionStatus::Interrupted | MissionStatus::Blocked) {
+                                            tracing::info!("Activating parallel mission {} (was {})", tid, mission.status);
+                                            if let Err(e) = mission_store.update_mission_status(tid, MissionStatus::Active).await {
+                                                tracing::warn!("Failed to activate parallel mission {}: {}", tid, e);
+                                            } else {
+                                                let _ = events_tx.send(AgentEvent::MissionStatusChanged {
+                                                    mission_id: tid,
+                                                    status: MissionStatus::Active,
+                                                    summary: None,
+                                                });
                                             }
-                                            // Queue the message
-                                            runner.queue_message(id, content.clone(), msg_agent);
-                                            // Emit user message event
-                                            let _ = events_tx.send(AgentEvent::UserMessage {
-                                                id,
-                                                content: content.clone(),
-                                                queued: false,
-                                                mission_id: Some(tid),
-                                            });
-                                            // Start execution
-                                            runner.start_next(
-                                                config.clone(),
-                                                Arc::clone(&root_agent),
-                                                Arc::clone(&mcp),
-                                                Arc::clone(&workspaces),
-                                                library.clone(),
-                                                events_tx.clone(),
-                                                Arc::clone(&tool_hub),
-                                                Arc::clone(&status),
-                                                mission_cmd_tx.clone(),
-                                                Arc::new(RwLock::new(Some(tid))),
-                                                secrets.clone(),
-                                            );
-                                            tracing::info!("Auto-started mission {} in parallel", tid);
+                                        }
+                                        let mut runner = super::mission_runner::MissionRunner::new(
+                                            tid,
+                                            mission.workspace_id,
+                                            mission.agent.clone(),
+                                            Some(mission.backend.clone()),
+                                            mission.session_id.clone(),
+                                            &config.mission,
+                                        );
+                                        runner.mission_store = Some(Arc::clone(&mission_store));
+                                        runner.backend_profile = mission.backend_profile.clone();
+                                        runner.mcp_env = workspace_mcp_env(&config, &session_key, mission.safe_mode);
+                                        runner.priority = mission.priority;
+                                        runner.fair_pass = super::mission_runner::starting_fair_pass(&parallel_runners);
+                                        // Load existing history
+                                        for entry in &mission.history {
+                                            runner.history.push((entry.role.clone(), entry.content.clone()));
+                                        }
+                                        // Queue the message
+                                        runner.queue_message(id, content.clone(), msg_agent);
+                                        let permit = slots.try_reserve(&session_key);
+                                        // Emit user message event
+                                        let _ = events_tx.send(AgentEvent::UserMessage {
+                                            id,
+                                            content: content.clone(),
+                                            queued: permit.is_none(),
+                                            mission_id: Some(tid),
+                                        });
+                                        let Some(permit) = permit else {
+                                            tracing::info!("Mission {} queued: {} missions already running", tid, slots.limit());
+                                            persist_runner(&runner);
                                             parallel_runners.insert(tid, runner);
-                                            let _ = respond.send(false);
+                                            let _ = respond.send(true);
                                             continue;
+                                        };
+                                        // Start execution
+                                        if runner.start_next(
+                                            config.clone(),
+                                            Arc::clone(&root_agent),
+                                            Arc::clone(&mcp),
+                                            Arc::clone(&workspaces),
+                                            library.clone(),
+                                            events_tx.clone(),
+                                            Arc::clone(&tool_hub),
+                                            Arc::clone(&status),
+                                            mission_cmd_tx.clone(),
+                                            Arc::new(RwLock::new(Some(tid))),
+                                            secrets.clone(),
+                                        ) {
+                                            runner.slot = Some(permit);
                                         }
-                                        Err(e) => {
-                                            tracing::error!("Failed to load mission {} for parallel: {}", tid, e);
-                                            // Fall through to queue on main as fallback
-                                        }
+                                        tracing::info!("Auto-started mission {} in parallel", tid);
+                                        persist_runner(&runner);
+                                        parallel_runners.insert(tid, runner);
+                                        let _ = respond.send(false);
+                                        continue;
+                                    }
+                                    Err(e) => {
+                                        tracing::error!("Failed to load mission {} for parallel: {}", tid, e);
+                                        // Fall through to queue on main as fallback
                                     }
                                 }
                             }
@@ -2856,12 +3621,13 @@ async fn control_actor_loop(
                                 let mission_ctrl = crate::tools::mission::MissionControl {
                                     current_mission_id: Arc::clone(&current_mission),
                                     cmd_tx: mission_cmd_tx.clone(),
+                                    deliverables: Default::default(),
                                 };
                                 let tree_ref = Arc::clone(&current_tree);
                                 let progress_ref = Arc::clone(&progress);
                                 // Capture which mission this task is working on
                                 let mission_id = current_mission.read().await.clone();
-                                let (workspace_id, model_override, mission_agent, backend_id, session_id) = if let Some(mid) = mission_id {
+                                let (workspace_id, model_override, mission_agent, backend_id, session_id, backend_profile, safe_mode) = if let Some(mid) = mission_id {
                                     match mission_store.get_mission(mid).await {
                                         Ok(Some(mission)) => {
                                             // Activate mission: if pending, interrupted, or blocked, update status to active
@@ -2884,6 +3650,8 @@ async fn control_actor_loop(
                                                 mission.agent.clone(),
                                                 Some(mission.backend.clone()),
                                                 mission.session_id.clone(),
+                                                mission.backend_profile.clone(),
+                                                mission.safe_mode,
                                             )
                                         }
                                         Ok(None) => {
@@ -2891,7 +3659,7 @@ async fn control_actor_loop(
                                                 "Mission {} not found while resolving workspace",
                                                 mid
                                             );
-                                            (None, None, None, None, None)
+                                            (None, None, None, None, None, None, false)
                                         }
                                         Err(e) => {
                                             tracing::warn!(
@@ -2899,21 +3667,27 @@ async fn control_actor_loop(
                                                 mid,
                                                 e
                                             );
-                                            (None, None, None, None, None)
+                                            (None, None, None, None, None, None, false)
                                         }
                                     }
                                 } else {
-                                    (None, None, None, None, None)
+                                    (None, None, None, None, None, None, false)
                                 };
                                 // Per-message agent overrides mission agent
                                 let agent_override = per_msg_agent.or(mission_agent);
+                                let mcp_env = workspace_mcp_env(&config, &session_key, safe_mode);
                                 running_cancel = Some(cancel.clone());
                                 running_mission_id = mission_id;
                                 // Reset activity tracking when new task starts
                                 main_runner_last_activity = std::time::Instant::now();
                                 main_runner_activity = None;
                                 main_runner_subtasks.clear();
+                                let store_ref = Arc::clone(&mission_store);
+                                _running_slot = Some(slots.reserve_unchecked(&session_key));
                                 running = Some(tokio::spawn(async move {
+                                    if let Some(id) = mission_id {
+                                        super::snapshot::snapshot_before_first_turn(&store_ref, &workspaces_ref, &cfg, id).await;
+                                    }
                                     let result = run_single_control_turn(
                                         cfg,
                                         agent,
@@ -2935,11 +3709,13 @@ async fn control_actor_loop(
                                         model_override,
                                         agent_override,
                                         session_id,
+                                        backend_profile,
+                                        mcp_env,
                                         false, // force_session_resume: regular message, not a resume
                                     )
                                     .await;
                                     (mid, msg, result)
-                                }));
+                                }.instrument(crate::mission_log::mission_span(running_mission_id))));
                             } else {
                                 set_and_emit_status(&status, &events_tx, ControlRunState::Idle, 0, None).await;
                             }
@@ -3008,7 +3784,7 @@ async fn control_actor_loop(
                             }
                         }
                     }
-                    ControlCommand::CreateMission { title, workspace_id, agent, model_override, backend, respond } => {
+                    ControlCommand::CreateMission { title, workspace_id, agent, model_override, backend, snapshot_before_run, backend_profile, safe_mode, priority, respond } => {
                         // First persist current mission history
                         persist_mission_history(
                             &mission_store,
@@ -3027,7 +3803,43 @@ async fn control_actor_loop(
                             backend.as_deref(),
                         )
                         .await {
-                            Ok(mission) => {
+                            Ok(mut mission) => {
+                                if snapshot_before_run {
+                                    match mission_store.set_mission_snapshot_before_run(mission.id, true).await {
+                                        Ok(()) => mission.snapshot_before_run = true,
+                                        Err(e) => {
+                                            let _ = respond.send(Err(e));
+                                            continue;
+                                        }
+                                    }
+                                }
+                                if safe_mode {
+                                    match mission_store.set_mission_safe_mode(mission.id, true).await {
+                                        Ok(()) => mission.safe_mode = true,
+                                        Err(e) => {
+                                            let _ = respond.send(Err(e));
+                                            continue;
+                                        }
+                                    }
+                                }
+                                if priority != 1 {
+                                    match mission_store.set_mission_priority(mission.id, priority).await {
+                                        Ok(()) => mission.priority = priority,
+                                        Err(e) => {
+                                            let _ = respond.send(Err(e));
+                                            continue;
+                                        }
+                                    }
+                                }
+                                if let Some(profile) = backend_profile {
+                                    match mission_store.set_mission_backend_profile(mission.id, Some(&profile)).await {
+                                        Ok(()) => mission.backend_profile = Some(profile),
+                                        Err(e) => {
+                                            let _ = respond.send(Err(e));
+                                            continue;
+                                        }
+                                    }
+                                }
                                 history.clear();
                                 *current_mission.write().await = Some(mission.id);
 
@@ -3080,18 +3892,7 @@ async fn control_actor_loop(
                     ControlCommand::StartParallel { mission_id, content, respond } => {
                         tracing::info!("StartParallel requested for mission {}", mission_id);
 
-                        // Count currently running parallel missions
-                        let parallel_running = parallel_runners.values().filter(|r| r.is_running()).count();
-                        let main_running = if running.is_some() { 1 } else { 0 };
-                        let total_running = parallel_running + main_running;
-                        let max_parallel = config.max_parallel_missions;
-
-                        if total_running >= max_parallel {
-                            let _ = respond.send(Err(format!(
-                                "Maximum parallel missions ({}) reached. {} running.",
-                                max_parallel, total_running
-                            )));
-                        } else if parallel_runners.contains_key(&mission_id) {
+                        if parallel_runners.contains_key(&mission_id) {
                             let _ = respond.send(Err(format!(
                                 "Mission {} is already running in parallel",
                                 mission_id
@@ -3117,7 +3918,13 @@ async fn control_actor_loop(
                                 mission.agent.clone(),
                                 Some(mission.backend.clone()),
                                 mission.session_id.clone(),
+                                &config.mission,
                             );
+                            runner.mission_store = Some(Arc::clone(&mission_store));
+                            runner.backend_profile = mission.backend_profile.clone();
+                            runner.mcp_env = workspace_mcp_env(&config, &session_key, mission.safe_mode);
+                            runner.priority = mission.priority;
+                            runner.fair_pass = super::mission_runner::starting_fair_pass(&parallel_runners);
 
                             // Load existing history into runner to preserve conversation context
                             for entry in &mission.history {
@@ -3127,6 +3934,18 @@ async fn control_actor_loop(
                             // Queue the initial message (no per-message agent override for parallel start)
                             runner.queue_message(Uuid::new_v4(), content, None);
 
+                            // At the global limit the mission stays queued; the poll loop starts it later
+                            let Some(permit) = slots.try_reserve(&session_key) else {
+                                tracing::info!(
+                                    "Mission {} queued: all {} slots in use",
+                                    mission_id, slots.limit()
+                                );
+                                persist_runner(&runner);
+                                parallel_runners.insert(mission_id, runner);
+                                let _ = respond.send(Ok(()));
+                                continue;
+                            };
+
                             // Start execution
                             let started = runner.start_next(
                                 config.clone(),
@@ -3144,6 +3963,8 @@ async fn control_actor_loop(
 
                             if started {
                                 tracing::info!("Mission {} started in parallel", mission_id);
+                                runner.slot = Some(permit);
+                                persist_runner(&runner);
                                 parallel_runners.insert(mission_id, runner);
                                 let _ = respond.send(Ok(()));
                             } else {
@@ -3161,6 +3982,7 @@ async fn control_actor_loop(
                                 resumable: true, // Cancelled missions can be resumed
                             });
                             parallel_runners.remove(&mission_id);
+                            super::mission_runner::remove_runner_state(&runner_state_dir, mission_id);
                             close_mission_desktop_sessions(
                                 &mission_store,
                                 mission_id,
@@ -3212,6 +4034,14 @@ async fn control_actor_loop(
                                     current_activity: main_runner_activity.clone(),
                                     subtask_total: main_runner_subtasks.len(),
                                     subtask_completed: main_runner_subtasks.iter().filter(|s| s.completed).count(),
+                                    interrupted: false,
+                                    cost: if main_runner_cost.0 == Some(mission_id) {
+                                        main_runner_cost.1
+                                    } else {
+                                        super::mission_runner::CostLedger::default()
+                                    }
+                                    .with_turn(&main_runner_turn_cost),
+                                    deliverables: Default::default(),
                                 });
                             }
                         }
@@ -3291,6 +4121,7 @@ async fn control_actor_loop(
                                         let mission_ctrl = crate::tools::mission::MissionControl {
                                             current_mission_id: Arc::clone(&current_mission),
                                             cmd_tx: mission_cmd_tx.clone(),
+                                            deliverables: Default::default(),
                                         };
                                         let tree_ref = Arc::clone(&current_tree);
                                         let progress_ref = Arc::clone(&progress);
@@ -3300,12 +4131,17 @@ async fn control_actor_loop(
                                         // Resume uses mission agent (no per-message override for resumes)
                                         let agent_override = mission.agent.clone();
                                         let session_id = mission.session_id.clone();
+                                        let backend_profile = mission.backend_profile.clone();
+                                        let mcp_env = workspace_mcp_env(&config, &session_key, mission.safe_mode);
                                         running_cancel = Some(cancel.clone());
                                         // Capture which mission this task is working on (the resumed mission)
                                         running_mission_id = Some(mission_id);
                                         main_runner_activity = None;
                                         main_runner_subtasks.clear();
+                                        let store_ref = Arc::clone(&mission_store);
+                                        _running_slot = Some(slots.reserve_unchecked(&session_key));
                                         running = Some(tokio::spawn(async move {
+                                            super::snapshot::snapshot_before_first_turn(&store_ref, &workspaces_ref, &cfg, mission_id).await;
                                             let result = run_single_control_turn(
                                                 cfg,
                                                 agent,
@@ -3327,11 +4163,13 @@ async fn control_actor_loop(
                                                 model_override,
                                                 agent_override,
                                                 session_id,
+                                                backend_profile,
+                                                mcp_env,
                                                 true, // force_session_resume: this is a resume operation
                                             )
                                             .await;
                                             (mid, msg, result)
-                                        }));
+                                        }.instrument(crate::mission_log::mission_span(running_mission_id))));
                                     }
                                 }
 
@@ -3555,6 +4393,7 @@ async fn control_actor_loop(
                     // (current_mission can change if user clicks "New Mission" while task was running)
                     let completed_mission_id = running_mission_id;
                     running = None;
+                    _running_slot = None;
                     running_cancel = None;
                     running_mission_id = None;
                     main_runner_activity = None;
@@ -3663,6 +4502,9 @@ async fn control_actor_loop(
                                                     TerminalReason::Stalled => "stalled",
                                                     TerminalReason::InfiniteLoop => "infinite_loop",
                                                     TerminalReason::MaxIterations => "max_iterations",
+                                                    TerminalReason::CliMissing => "cli_missing",
+                                                    TerminalReason::AuthMissing => "auth_missing",
+                                                    TerminalReason::BackendUnavailable => "backend_unavailable",
                                                 });
                                                 tracing::info!(
                                                     "Auto-completing mission {} with status '{:?}' (terminal_reason: {:?})",
@@ -3684,6 +4526,9 @@ async fn control_actor_loop(
                                                         Some(TerminalReason::Stalled) => Some("No progress detected".to_string()),
                                                         Some(TerminalReason::InfiniteLoop) => Some("Detected repetitive behavior".to_string()),
                                                         Some(TerminalReason::LlmError) => Some("Model error".to_string()),
+                                                        Some(TerminalReason::CliMissing) => Some("Backend CLI not installed".to_string()),
+                                                        Some(TerminalReason::AuthMissing) => Some("Backend credentials missing".to_string()),
+                                                        Some(TerminalReason::BackendUnavailable) => Some("Backend unavailable (circuit open)".to_string()),
                                                         None if agent_result.success => None,
                                                         None => Some("Unexpected termination".to_string()),
                                                     };
@@ -3721,12 +4566,27 @@ async fn control_actor_loop(
                                 content: agent_result.output.clone(),
                                 success: agent_result.success,
                                 cost_cents: agent_result.cost_cents,
-                                model: agent_result.model_used,
+                                prompt_tokens: agent_result.usage.as_ref().map_or(0, |u| u.prompt_tokens()),
+                                completion_tokens: agent_result.usage.as_ref().map_or(0, |u| u.output_tokens),
+                                model: agent_result.model_used.clone(),
                                 mission_id: completed_mission_id,
                                 shared_files: None,
                                 resumable,
                             });
+                            main_runner_turn_cost = Default::default();
                             if let Some(mission_id) = completed_mission_id {
+                                if main_runner_cost.0 != Some(mission_id) {
+                                    main_runner_cost = (Some(mission_id), Default::default());
+                                }
+                                main_runner_cost.1.record(&agent_result);
+                                let _ = events_tx.send(
+                                    super::mission_runner::mission_complete_event(
+                                        mission_id,
+                                        &agent_result,
+                                        &Default::default(),
+                                    )
+                                    .await,
+                                );
                                 close_mission_desktop_sessions(
                                     &mission_store,
                                     mission_id,
@@ -3783,13 +4643,14 @@ async fn control_actor_loop(
                     let mission_ctrl = crate::tools::mission::MissionControl {
                         current_mission_id: Arc::clone(&current_mission),
                         cmd_tx: mission_cmd_tx.clone(),
+                        deliverables: Default::default(),
                     };
                     let tree_ref = Arc::clone(&current_tree);
                     let progress_ref = Arc::clone(&progress);
                     running_cancel = Some(cancel.clone());
                     // Capture which mission this task is working on
                     let mission_id = current_mission.read().await.clone();
-                    let (workspace_id, model_override, mission_agent, backend_id, session_id) = if let Some(mid) = mission_id {
+                    let (workspace_id, model_override, mission_agent, backend_id, session_id, backend_profile, safe_mode) = if let Some(mid) = mission_id {
                         match mission_store.get_mission(mid).await {
                             Ok(Some(mission)) => (
                                 Some(mission.workspace_id),
@@ -3797,13 +4658,15 @@ async fn control_actor_loop(
                                 mission.agent.clone(),
                                 Some(mission.backend.clone()),
                                 mission.session_id.clone(),
+                                mission.backend_profile.clone(),
+                                mission.safe_mode,
                             ),
                             Ok(None) => {
                                 tracing::warn!(
                                     "Mission {} not found while resolving workspace",
                                     mid
                                 );
-                                (None, None, None, None, None)
+                                (None, None, None, None, None, None, false)
                             }
                             Err(e) => {
                                 tracing::warn!(
@@ -3811,20 +4674,26 @@ async fn control_actor_loop(
                                     mid,
                                     e
                                 );
-                                (None, None, None, None, None)
+                                (None, None, None, None, None, None, false)
                             }
                         }
                     } else {
-                        (None, None, None, None, None)
+                        (None, None, None, None, None, None, false)
                     };
                     // Per-message agent overrides mission agent
                     let agent_override = per_msg_agent.or(mission_agent);
+                    let mcp_env = workspace_mcp_env(&config, &session_key, safe_mode);
                     running_mission_id = mission_id;
                     // Reset activity tracking when new task starts
                     main_runner_last_activity = std::time::Instant::now();
                     main_runner_activity = None;
                     main_runner_subtasks.clear();
+                    let store_ref = Arc::clone(&mission_store);
+                    _running_slot = Some(slots.reserve_unchecked(&session_key));
                     running = Some(tokio::spawn(async move {
+                        if let Some(id) = mission_id {
+                            super::snapshot::snapshot_before_first_turn(&store_ref, &workspaces_ref, &cfg, id).await;
+                        }
                         let result = run_single_control_turn(
                             cfg,
                             agent,
@@ -3846,11 +4715,13 @@ async fn control_actor_loop(
                             model_override,
                             agent_override,
                             session_id,
+                            backend_profile,
+                            mcp_env,
                             false, // force_session_resume: continuation turn, not a resume
                         )
                         .await;
                         (mid, msg, result)
-                    }));
+                    }.instrument(crate::mission_log::mission_span(running_mission_id))));
                 } else {
                     set_and_emit_status(&status, &events_tx, ControlRunState::Idle, 0, None).await;
                 }
@@ -3875,11 +4746,21 @@ async fn control_actor_loop(
                                 content: result.output.clone(),
                                 success: result.success,
                                 cost_cents: result.cost_cents,
+                                prompt_tokens: result.usage.as_ref().map_or(0, |u| u.prompt_tokens()),
+                                completion_tokens: result.usage.as_ref().map_or(0, |u| u.output_tokens),
                                 model: result.model_used.clone(),
                                 mission_id: Some(*mission_id),
                                 shared_files: None,
                                 resumable,
                             });
+                            let _ = events_tx.send(
+                                super::mission_runner::mission_complete_event(
+                                    *mission_id,
+                                    &result,
+                                    &runner.deliverables,
+                                )
+                                .await,
+                            );
 
                             // Persist history for this mission
                             let entries: Vec<MissionHistoryEntry> = runner
@@ -3903,6 +4784,8 @@ async fn control_actor_loop(
                             // If runner has no more queued messages, mark for cleanup
                             if runner.queue.is_empty() && !runner.is_running() {
                                 completed_missions.push(*mission_id);
+                            } else {
+                                persist_runner(runner);
                             }
                         }
                     }
@@ -3911,12 +4794,45 @@ async fn control_actor_loop(
                 // Remove completed runners
                 for mid in completed_missions {
                     parallel_runners.remove(&mid);
+                    super::mission_runner::remove_runner_state(&runner_state_dir, mid);
                     tracing::info!("Parallel mission {} removed from runners", mid);
                 }
+
+                // Start queued turns for idle runners while execution slots are free,
+                // weighted-fair so one busy mission can't keep reclaiming every slot.
+                for mid in super::mission_runner::next_fair_missions(&parallel_runners, slots.available()) {
+                    // Another session may have taken the slot since `available()`.
+                    let Some(permit) = slots.try_reserve(&session_key) else {
+                        break;
+                    };
+                    if let Some(runner) = parallel_runners.get_mut(&mid) {
+                        if runner.start_next(
+                            config.clone(),
+                            Arc::clone(&root_agent),
+                            Arc::clone(&mcp),
+                            Arc::clone(&workspaces),
+                            library.clone(),
+                            events_tx.clone(),
+                            Arc::clone(&tool_hub),
+                            Arc::clone(&status),
+                            mission_cmd_tx.clone(),
+                            Arc::new(RwLock::new(Some(mid))),
+                            secrets.clone(),
+                        ) {
+                            runner.slot = Some(permit);
+                        }
+                        persist_runner(runner);
+                    }
+                }
+                let queued = parallel_runners
+                    .values()
+                    .filter(|r| !r.is_running() && !r.queue.is_empty())
+                    .count();
+                slots.report_queued(&session_key, queued);
             }
             // Update last_activity for runners when we receive events for them
             event = events_rx.recv() => {
-                if let Ok(event) = event {
+                if let Ok(StampedEvent { event, .. }) = event {
                     // Extract mission_id from event if present
                     let mission_id = match &event {
                         AgentEvent::ToolCall { mission_id, .. } => *mission_id,
@@ -3925,6 +4841,7 @@ async fn control_actor_loop(
                         AgentEvent::AgentPhase { mission_id, .. } => *mission_id,
                         AgentEvent::AgentTree { mission_id, .. } => *mission_id,
                         AgentEvent::Progress { mission_id, .. } => *mission_id,
+                        AgentEvent::UsageUpdate { mission_id, .. } => *mission_id,
                         _ => None,
                     };
                     // Update last_activity for matching runner (main or parallel)
@@ -3932,9 +4849,19 @@ async fn control_actor_loop(
                         if running_mission_id == Some(mid) {
                             // Update main runner activity
                             main_runner_last_activity = std::time::Instant::now();
+                            if let AgentEvent::UsageUpdate { cost_cents, prompt_tokens, completion_tokens, .. } = &event {
+                                if running.is_some() {
+                                    main_runner_turn_cost = super::mission_runner::CostLedger {
+                                        cost_cents: *cost_cents,
+                                        prompt_tokens: *prompt_tokens,
+                                        completion_tokens: *completion_tokens,
+                                        turns: 0,
+                                    };
+                                }
+                            }
                         } else if let Some(runner) = parallel_runners.get_mut(&mid) {
                             // Update parallel runner activity
-                            runner.touch();
+                            runner.record_event(&event);
                         }
                     }
 
@@ -3951,6 +4878,21 @@ async fn control_actor_loop(
                                     runner.current_activity = Some(label.clone());
                                 }
 
+                                // Interactive UI tools block until the user answers.
+                                if crate::tools::awaits_user_input(name) {
+                                    if running_mission_id == Some(*mid) {
+                                        set_and_emit_status(
+                                            &status,
+                                            &events_tx,
+                                            ControlRunState::WaitingForTool,
+                                            queue.len(),
+                                            Some(*mid),
+                                        ).await;
+                                    } else if let Some(runner) = parallel_runners.get_mut(mid) {
+                                        runner.state = super::mission_runner::MissionRunState::WaitingForTool;
+                                    }
+                                }
+
                                 // Emit activity event for real-time SSE
                                 let _ = events_tx.send(AgentEvent::MissionActivity {
                                     label,
@@ -4069,8 +5011,27 @@ async fn control_actor_loop(
                                 }
                             }
                         }
-                        AgentEvent::ToolResult { tool_call_id, mission_id, .. } => {
+                        AgentEvent::ToolResult { tool_call_id, name, mission_id, .. } => {
                             if let Some(mid) = mission_id {
+                                // The user answered: the mission is running again.
+                                if crate::tools::awaits_user_input(name) {
+                                    if running_mission_id == Some(*mid) {
+                                        if status.read().await.state == ControlRunState::WaitingForTool {
+                                            set_and_emit_status(
+                                                &status,
+                                                &events_tx,
+                                                ControlRunState::Running,
+                                                queue.len(),
+                                                Some(*mid),
+                                            ).await;
+                                        }
+                                    } else if let Some(runner) = parallel_runners.get_mut(mid) {
+                                        if runner.state == super::mission_runner::MissionRunState::WaitingForTool {
+                                            runner.state = super::mission_runner::MissionRunState::Running;
+                                        }
+                                    }
+                                }
+
                                 // Clear activity label (tool finished)
                                 if running_mission_id == Some(*mid) {
                                     main_runner_activity = None;
@@ -4121,6 +5082,16 @@ async fn control_actor_loop(
                         _ => {}
                     }
 
+                    // Surface todo list changes as a checklist for the UI.
+                    if let AgentEvent::ToolResult { name, result, mission_id, .. } = &event {
+                        if let Some(todos) = crate::tools::todo::todos_from_tool_result(name, result) {
+                            let _ = events_tx.send(AgentEvent::TodosUpdated {
+                                todos,
+                                mission_id: *mission_id,
+                            });
+                        }
+                    }
+
                     // Track desktop sessions for mission reconnect/resume.
                     if let AgentEvent::ToolResult { name, result, mission_id, .. } = &event {
                         let Some(mid) = mission_id else {
@@ -4255,13 +5226,27 @@ async fn control_actor_loop(
     }
 }
 
+/// Environment for a mission's workspace MCP: the safe-mode flag and, when the
+/// API requires auth, a token for the mission's user so the MCP can call back
+/// to confirm destructive actions.
+fn workspace_mcp_env(config: &Config, user_id: &str, safe_mode: bool) -> HashMap<String, String> {
+    let mut env = HashMap::new();
+    if safe_mode {
+        env.insert("OPEN_AGENT_SAFE_MODE".to_string(), "1".to_string());
+        if let Some(token) = super::auth::service_token(config, user_id) {
+            env.insert("OPEN_AGENT_API_TOKEN".to_string(), token);
+        }
+    }
+    env
+}
+
 async fn run_single_control_turn(
     mut config: Config,
     root_agent: AgentRef,
     mcp: Arc<McpRegistry>,
     workspaces: workspace::SharedWorkspaceStore,
     library: SharedLibrary,
-    events_tx: broadcast::Sender<AgentEvent>,
+    events_tx: EventSender,
     tool_hub: Arc<FrontendToolHub>,
     status: Arc<RwLock<ControlStatus>>,
     cancel: CancellationToken,
@@ -4276,6 +5261,8 @@ async fn run_single_control_turn(
     model_override: Option<String>,
     agent_override: Option<String>,
     session_id: Option<String>,
+    backend_profile: Option<String>,
+    mcp_env: HashMap<String, String>,
     force_session_resume: bool,
 ) -> crate::agents::AgentResult {
     let is_claudecode = backend_id.as_deref() == Some("claudecode");
@@ -4289,6 +5276,20 @@ async fn run_single_control_turn(
     if let Some(agent) = agent_override {
         config.opencode_agent = Some(agent);
     }
+    // Fail fast while the backend's circuit breaker is open.
+    let breaker_guard = match crate::backend::circuit_breaker::global()
+        .admit(backend_id.as_deref().unwrap_or("opencode"))
+    {
+        Ok(guard) => guard,
+        Err(rejected) => {
+            let _ = events_tx.send(AgentEvent::Error {
+                message: rejected.output.clone(),
+                mission_id,
+                resumable: mission_id.is_some(),
+            });
+            return *rejected;
+        }
+    };
     // Ensure a workspace directory for this mission (if applicable).
     let (working_dir_path, runtime_workspace) = if let Some(mid) = mission_id {
         let ws = workspace::resolve_workspace(&workspaces, &config, workspace_id).await;
@@ -4302,6 +5303,7 @@ async fn run_single_control_turn(
             mid,
             backend_id.as_deref().unwrap_or("opencode"),
             None, // custom_providers: TODO integrate with provider store
+            &mcp_env,
         )
         .await
         {
@@ -4411,6 +5413,8 @@ async fn run_single_control_turn(
                 session_id.as_deref(),
                 is_continuation,
                 Some(tool_hub.clone()),
+                config.max_tool_calls_per_turn(),
+                backend_profile.as_deref(),
             )
             .await
         }
@@ -4445,17 +5449,39 @@ async fn run_single_control_turn(
                 session_id.as_deref(),
                 is_continuation,
                 api_key.as_deref(),
+                config.max_tool_calls_per_turn(),
             )
             .await
         }
         Some(backend) if backend != "opencode" => {
-            let _ = events_tx.send(AgentEvent::Error {
-                message: format!("Unsupported backend: {}", backend),
-                mission_id,
-                resumable: mission_id.is_some(),
-            });
-            crate::agents::AgentResult::failure(format!("Unsupported backend: {}", backend), 0)
-                .with_terminal_reason(TerminalReason::LlmError)
+            match crate::backend::registry::lookup(backend).await {
+                Some(registered) => {
+                    super::mission_runner::run_backend_turn(
+                        registered,
+                        &ctx.working_dir,
+                        &user_message,
+                        config.default_model.as_deref(),
+                        config.opencode_agent.as_deref(),
+                        session_id.as_deref(),
+                        mission_id.unwrap_or_else(Uuid::nil),
+                        events_tx.clone(),
+                        cancel,
+                    )
+                    .await
+                }
+                None => {
+                    let _ = events_tx.send(AgentEvent::Error {
+                        message: format!("Unsupported backend: {}", backend),
+                        mission_id,
+                        resumable: mission_id.is_some(),
+                    });
+                    crate::agents::AgentResult::failure(
+                        format!("Unsupported backend: {}", backend),
+                        0,
+                    )
+                    .with_terminal_reason(TerminalReason::LlmError)
+                }
+            }
         }
         _ => {
             // Default to opencode using per-workspace CLI execution
@@ -4470,9 +5496,135 @@ async fn run_single_control_turn(
                 events_tx.clone(),
                 cancel,
                 &config.working_dir,
+                backend_profile.as_deref(),
             )
             .await
         }
     };
+    breaker_guard.finish(&result);
     result
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_amp_stream_converts_to_agent_events() {
+        use crate::backend::shared::{convert_cli_event, CliEvent};
+
+        let lines = [
+            r#"{"type":"assistant","message":{"content":[{"type":"thinking","thinking":"Look first"},{"type":"tool_use","id":"toolu_1","name":"Bash","input":{"command":"ls"}}]},"session_id":"T-1"}"#,
+            r#"{"type":"user","message":{"content":[{"type":"tool_result","tool_use_id":"toolu_1","content":"Cargo.toml"}]},"session_id":"T-1"}"#,
+            r#"{"type":"stream_event","event":{"type":"content_block_delta","index":0,"delta":{"type":"text_delta","text":"One file."}},"session_id":"T-1"}"#,
+            r#"{"type":"result","subtype":"error","session_id":"T-1","is_error":true,"error":"quota exceeded"}"#,
+        ];
+        let mission_id = Some(Uuid::new_v4());
+        let mut pending = HashMap::new();
+        let mut text = String::new();
+        let events: Vec<AgentEvent> = lines
+            .iter()
+            .flat_map(|line| {
+                let event: CliEvent = serde_json::from_str(line).unwrap();
+                convert_cli_event(event, &mut pending)
+            })
+            .filter_map(|event| agent_event_from_execution(event, mission_id, &mut text))
+            .collect();
+
+        let names: Vec<&str> = events.iter().map(|e| e.event_name()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "thinking",
+                "tool_call",
+                "tool_result",
+                "text_delta",
+                "error"
+            ]
+        );
+        assert!(events.iter().all(|e| e.mission_id() == mission_id));
+        match &events[2] {
+            AgentEvent::ToolResult { name, .. } => assert_eq!(name, "Bash"),
+            other => panic!("expected a tool result, got {:?}", other),
+        }
+        assert_eq!(text, "One file.");
+    }
+
+    #[test]
+    fn test_event_sender_stamps_monotonic_per_mission() {
+        let (tx, mut rx) = EventSender::channel(16);
+        let (a, b) = (Uuid::new_v4(), Uuid::new_v4());
+        let thinking = |mission_id| AgentEvent::Thinking {
+            content: "...".to_string(),
+            done: false,
+            mission_id: Some(mission_id),
+        };
+        for mission_id in [a, b, a, a, b] {
+            tx.send(thinking(mission_id)).unwrap();
+        }
+
+        let mut last: HashMap<Uuid, (u64, i64)> = HashMap::new();
+        let mut seqs = Vec::new();
+        while let Ok(stamped) = rx.try_recv() {
+            let mission_id = stamped.event.mission_id().unwrap();
+            if let Some((seq, ts)) = last.get(&mission_id) {
+                assert!(stamped.seq > *seq);
+                assert!(stamped.ts_ms >= *ts);
+            }
+            last.insert(mission_id, (stamped.seq, stamped.ts_ms));
+            seqs.push(stamped.seq);
+        }
+        assert_eq!(seqs, vec![1, 1, 2, 3, 2]);
+
+        let json = serde_json::to_value(StampedEvent {
+            seq: 7,
+            ts_ms: 1_700_000_000_000,
+            stream_pos: 12,
+            event: thinking(a),
+        })
+        .unwrap();
+        assert_eq!(json["type"], "thinking");
+        assert_eq!(json["seq"], 7);
+        assert_eq!(json["ts_ms"], 1_700_000_000_000i64);
+    }
+
+    #[test]
+    fn test_event_sender_replays_recent_events() {
+        let (tx, rx) = EventSender::channel(16);
+        drop(rx);
+        let (a, b) = (Uuid::new_v4(), Uuid::new_v4());
+        let thinking = |mission_id| AgentEvent::Thinking {
+            content: "...".to_string(),
+            done: false,
+            mission_id: Some(mission_id),
+        };
+        // Events sent while nobody listens are still kept for replay.
+        for mission_id in [a, b, a] {
+            assert!(tx.send(thinking(mission_id)).is_err());
+        }
+        let all = tx.replay(None);
+        assert_eq!(
+            all.iter().map(|e| e.stream_pos).collect::<Vec<_>>(),
+            vec![1, 2, 3]
+        );
+        assert_eq!(all[2].seq, 2);
+        let after = tx.replay(Some(2));
+        assert_eq!(after.len(), 1);
+        assert_eq!(after[0].event.mission_id(), Some(a));
+        assert_eq!(tx.replay(Some(3)).len(), 0);
+        assert_eq!(tx.replay(Some(1_000)).len(), 3, "id from a previous run");
+
+        // Each mission keeps only its latest events.
+        for _ in 0..REPLAY_EVENTS_PER_MISSION {
+            let _ = tx.send(thinking(b));
+        }
+        let all = tx.replay(None);
+        assert_eq!(all.len(), REPLAY_EVENTS_PER_MISSION + 2);
+        assert_eq!(
+            all.iter()
+                .filter(|e| e.event.mission_id() == Some(b))
+                .count(),
+            REPLAY_EVENTS_PER_MISSION
+        );
+    }
+}
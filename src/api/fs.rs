@@ -1,6 +1,6 @@
 //! Local file explorer endpoints (list/upload/download) via server filesystem access.
 
-use std::net::IpAddr;
+use std::net::{IpAddr, SocketAddr};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
@@ -337,13 +337,35 @@ fn sanitize_path_component(s: &str) -> String {
         .to_string()
 }
 
+/// A URL that passed [`validate_url_for_ssrf`], with the addresses it was checked against.
+#[derive(Debug)]
+pub struct CheckedUrl {
+    /// Host name, or `None` for IP literals (nothing to resolve)
+    domain: Option<String>,
+    addrs: Vec<SocketAddr>,
+}
+
+impl CheckedUrl {
+    /// Make `builder` connect only to the checked addresses, so a second DNS
+    /// answer (DNS rebinding) can't send the request somewhere internal.
+    pub fn pin(&self, builder: reqwest::ClientBuilder) -> reqwest::ClientBuilder {
+        match &self.domain {
+            Some(domain) => builder.resolve_to_addrs(domain, &self.addrs),
+            None => builder,
+        }
+    }
+}
+
 /// Validate a URL to prevent SSRF attacks.
 /// Blocks requests to:
 /// - localhost and loopback addresses (127.0.0.0/8, ::1)
 /// - Private network ranges (10.0.0.0/8, 172.16.0.0/12, 192.168.0.0/16)
 /// - Link-local addresses (169.254.0.0/16, fe80::/10)
 /// - Cloud metadata endpoints (169.254.169.254)
-fn validate_url_for_ssrf(url: &str) -> Result<(), String> {
+///
+/// IPv4-mapped IPv6 addresses are judged by their IPv4 part. Host names are
+/// resolved and every address must be public; pin them with [`CheckedUrl::pin`].
+pub async fn validate_url_for_ssrf(url: &str) -> Result<CheckedUrl, String> {
     let parsed = url::Url::parse(url).map_err(|e| format!("Invalid URL: {}", e))?;
 
     // Only allow http and https schemes
@@ -352,41 +374,124 @@ fn validate_url_for_ssrf(url: &str) -> Result<(), String> {
         other => return Err(format!("Disallowed URL scheme: {}", other)),
     }
 
-    // Get the host
-    let host = parsed
-        .host_str()
-        .ok_or_else(|| "URL has no host".to_string())?;
-
-    // Check for localhost variants
-    let host_lower = host.to_lowercase();
-    if host_lower == "localhost" || host_lower.ends_with(".localhost") || host_lower == "0.0.0.0" {
-        return Err("Requests to localhost are not allowed".to_string());
-    }
-
-    // Try to parse as IP address
-    if let Ok(ip) = host.parse::<IpAddr>() {
-        if is_internal_ip(&ip) {
-            return Err(format!(
-                "Requests to internal IP addresses are not allowed: {}",
-                ip
-            ));
-        }
-    }
+    let ip = match parsed.host().ok_or_else(|| "URL has no host".to_string())? {
+        url::Host::Ipv4(ip) => IpAddr::V4(ip),
+        url::Host::Ipv6(ip) => IpAddr::V6(ip),
+        url::Host::Domain(domain) => {
+            let domain = domain.to_lowercase();
+            if domain == "localhost" || domain.ends_with(".localhost") {
+                return Err("Requests to localhost are not allowed".to_string());
+            }
 
-    // Try DNS resolution to catch DNS rebinding attacks
-    // (hostname that resolves to internal IP)
-    if let Ok(addrs) = std::net::ToSocketAddrs::to_socket_addrs(&(host, 80u16)) {
-        for addr in addrs {
-            if is_internal_ip(&addr.ip()) {
+            // Resolve now to catch DNS rebinding attacks (hostname that resolves to internal IP)
+            let port = parsed.port_or_known_default().unwrap_or(80);
+            let addrs: Vec<SocketAddr> = tokio::net::lookup_host((domain.as_str(), port))
+                .await
+                .map_err(|e| format!("Failed to resolve {}: {}", domain, e))?
+                .collect();
+            if addrs.is_empty() {
+                return Err(format!("{} did not resolve to any address", domain));
+            }
+            if let Some(addr) = addrs.iter().find(|addr| is_internal_ip(&addr.ip())) {
                 return Err(format!(
                     "URL resolves to internal IP address: {}",
                     addr.ip()
                 ));
             }
+            return Ok(CheckedUrl {
+                domain: Some(domain),
+                addrs,
+            });
         }
+    };
+    if is_internal_ip(&ip) {
+        return Err(format!(
+            "Requests to internal IP addresses are not allowed: {}",
+            ip
+        ));
     }
+    Ok(CheckedUrl {
+        domain: None,
+        addrs: Vec::new(),
+    })
+}
 
-    Ok(())
+/// Why [`send_with_ssrf_guard`] failed.
+#[derive(Debug)]
+pub enum GuardedRequestError {
+    /// The URL or a redirect target points at an internal address.
+    Blocked(String),
+    /// The request itself failed.
+    Failed(reqwest::Error),
+}
+
+impl std::fmt::Display for GuardedRequestError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Blocked(reason) => f.write_str(reason),
+            Self::Failed(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+/// Send `request` on a client from `client`, following up to `max_redirects`
+/// redirects by hand so every hop is validated and pinned like the first.
+pub async fn send_with_ssrf_guard(
+    client: impl Fn() -> reqwest::ClientBuilder,
+    mut request: reqwest::Request,
+    max_redirects: usize,
+) -> Result<reqwest::Response, GuardedRequestError> {
+    use reqwest::header::{AUTHORIZATION, CONTENT_LENGTH, CONTENT_TYPE, COOKIE, LOCATION};
+
+    for _ in 0..=max_redirects {
+        let checked = validate_url_for_ssrf(request.url().as_str())
+            .await
+            .map_err(GuardedRequestError::Blocked)?;
+        let http = checked
+            .pin(client())
+            .redirect(reqwest::redirect::Policy::none())
+            .build()
+            .map_err(GuardedRequestError::Failed)?;
+        let next = request.try_clone();
+        let response = http
+            .execute(request)
+            .await
+            .map_err(GuardedRequestError::Failed)?;
+
+        let location = response
+            .headers()
+            .get(LOCATION)
+            .and_then(|l| l.to_str().ok())
+            .and_then(|l| response.url().join(l).ok());
+        let (Some(location), Some(mut next)) = (
+            location.filter(|_| response.status().is_redirection()),
+            next,
+        ) else {
+            return Ok(response);
+        };
+
+        // Same rules as reqwest's own redirect handling.
+        let status = response.status().as_u16();
+        if matches!(status, 301..=303)
+            && !matches!(*next.method(), reqwest::Method::GET | reqwest::Method::HEAD)
+        {
+            *next.method_mut() = reqwest::Method::GET;
+            *next.body_mut() = None;
+            next.headers_mut().remove(CONTENT_TYPE);
+            next.headers_mut().remove(CONTENT_LENGTH);
+        }
+        if location.host_str() != next.url().host_str()
+            || location.port_or_known_default() != next.url().port_or_known_default()
+        {
+            next.headers_mut().remove(AUTHORIZATION);
+            next.headers_mut().remove(COOKIE);
+        }
+        *next.url_mut() = location;
+        request = next;
+    }
+    Err(GuardedRequestError::Blocked(
+        "Too many redirects".to_string(),
+    ))
 }
 
 /// Check if an IP address is internal/private
@@ -565,9 +670,105 @@ pub async fn download(
     Ok((headers, body).into_response())
 }
 
+/// Reject an upload up front if it exceeds the configured limit or won't fit on disk.
+fn check_upload_capacity(
+    len: u64,
+    max_bytes: u64,
+    targets: &[&Path],
+) -> Result<(), (StatusCode, String)> {
+    if len > max_bytes {
+        return Err((
+            StatusCode::PAYLOAD_TOO_LARGE,
+            format!(
+                "Upload of {} bytes exceeds limit of {} bytes",
+                len, max_bytes
+            ),
+        ));
+    }
+    for target in targets {
+        if let Some(available) = available_disk_space(target) {
+            if len > available {
+                return Err((
+                    StatusCode::INSUFFICIENT_STORAGE,
+                    format!(
+                        "Insufficient disk space at {}: {} bytes needed, {} available",
+                        target.display(),
+                        len,
+                        available
+                    ),
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Free space available to unprivileged users on the filesystem holding `path`.
+///
+/// Walks up to the nearest existing ancestor so not-yet-created targets still resolve.
+fn available_disk_space(path: &Path) -> Option<u64> {
+    use std::os::unix::ffi::OsStrExt;
+
+    let existing = path.ancestors().find(|p| p.exists())?;
+    let c_path = std::ffi::CString::new(existing.as_os_str().as_bytes()).ok()?;
+    let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+    // SAFETY: c_path is a valid NUL-terminated string and stat is a valid out-pointer.
+    if unsafe { libc::statvfs(c_path.as_ptr(), &mut stat) } != 0 {
+        return None;
+    }
+    Some(stat.f_bavail as u64 * stat.f_frsize as u64)
+}
+
+/// Stream upload chunks into `tmp`, aborting once more than `max_bytes` have been received.
+///
+/// The temp file is removed on any failure so oversized or broken uploads don't leak disk.
+async fn stream_to_temp_file<S, E>(
+    chunks: S,
+    tmp: &Path,
+    max_bytes: u64,
+) -> Result<u64, (StatusCode, String)>
+where
+    S: futures::Stream<Item = Result<bytes::Bytes, E>>,
+    E: std::fmt::Display,
+{
+    use futures::StreamExt;
+
+    let result = async {
+        let mut f = tokio::fs::File::create(tmp)
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+        tokio::pin!(chunks);
+        let mut written: u64 = 0;
+        while let Some(chunk) = chunks.next().await {
+            let chunk = chunk.map_err(|e| (StatusCode::BAD_REQUEST, e.to_string()))?;
+            written += chunk.len() as u64;
+            if written > max_bytes {
+                return Err((
+                    StatusCode::PAYLOAD_TOO_LARGE,
+                    format!("Upload exceeds limit of {} bytes", max_bytes),
+                ));
+            }
+            f.write_all(&chunk)
+                .await
+                .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+        }
+        f.flush()
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+        Ok(written)
+    }
+    .await;
+
+    if result.is_err() {
+        let _ = tokio::fs::remove_file(tmp).await;
+    }
+    result
+}
+
 pub async fn upload(
     State(state): State<Arc<AppState>>,
     Query(q): Query<PathQuery>,
+    headers: HeaderMap,
     mut multipart: Multipart,
 ) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
     // If workspace_id is provided, resolve path relative to that workspace
@@ -578,6 +779,15 @@ pub async fn upload(
         resolve_upload_base(&q.path)?
     };
 
+    let max_bytes = state.config.max_upload_bytes;
+    let content_length = headers
+        .get(header::CONTENT_LENGTH)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse::<u64>().ok());
+    if let Some(len) = content_length {
+        check_upload_capacity(len, max_bytes, &[&std::env::temp_dir(), &base])?;
+    }
+
     // Expect one file field.
     if let Some(field) = multipart
         .next_field()
@@ -590,23 +800,7 @@ pub async fn upload(
             .unwrap_or_else(|| "upload.bin".to_string());
         // Stream to temp file first (avoid buffering large uploads in memory).
         let tmp = std::env::temp_dir().join(format!("open_agent_ul_{}", uuid::Uuid::new_v4()));
-        let mut f = tokio::fs::File::create(&tmp)
-            .await
-            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
-
-        let mut field = field;
-        while let Some(chunk) = field
-            .chunk()
-            .await
-            .map_err(|e| (StatusCode::BAD_REQUEST, e.to_string()))?
-        {
-            f.write_all(&chunk)
-                .await
-                .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
-        }
-        f.flush()
-            .await
-            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+        stream_to_temp_file(field, &tmp, max_bytes).await?;
 
         let remote_path = if q.path.ends_with('/') {
             base.join(&file_name)
@@ -840,36 +1034,22 @@ pub async fn download_from_url(
     State(state): State<Arc<AppState>>,
     Json(req): Json<DownloadUrlRequest>,
 ) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
-    // Validate URL to prevent SSRF attacks
-    validate_url_for_ssrf(&req.url).map_err(|e| (StatusCode::BAD_REQUEST, e))?;
-
-    // Download to temp file
-    let client = reqwest::Client::builder()
-        .timeout(std::time::Duration::from_secs(300)) // 5 min timeout
-        // Don't follow redirects automatically to prevent redirect-based SSRF
-        .redirect(reqwest::redirect::Policy::limited(5))
-        .build()
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                format!("Failed to create HTTP client: {}", e),
-            )
-        })?;
-
-    let response = client.get(&req.url).send().await.map_err(|e| {
-        (
+    let url = reqwest::Url::parse(&req.url)
+        .map_err(|e| (StatusCode::BAD_REQUEST, format!("Invalid URL: {}", e)))?;
+
+    // Validate every hop (and connect only to the checked addresses) to prevent SSRF attacks
+    let response = send_with_ssrf_guard(
+        || reqwest::Client::builder().timeout(std::time::Duration::from_secs(300)), // 5 min timeout
+        reqwest::Request::new(reqwest::Method::GET, url),
+        5,
+    )
+    .await
+    .map_err(|e| match e {
+        GuardedRequestError::Blocked(reason) => (StatusCode::BAD_REQUEST, reason),
+        GuardedRequestError::Failed(e) => (
             StatusCode::BAD_REQUEST,
             format!("Failed to fetch URL: {}", e),
-        )
-    })?;
-
-    // Validate the final URL after redirects to prevent redirect-based SSRF
-    let final_url = response.url().to_string();
-    validate_url_for_ssrf(&final_url).map_err(|e| {
-        (
-            StatusCode::BAD_REQUEST,
-            format!("Redirect target blocked: {}", e),
-        )
+        ),
     })?;
 
     if !response.status().is_success() {
@@ -985,3 +1165,57 @@ pub async fn download_from_url(
         serde_json::json!({ "ok": true, "path": remote_path, "name": file_name }),
     ))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_ssrf_blocks_ipv6_and_mapped_addresses() {
+        for url in [
+            "http://127.0.0.1/",
+            "http://localhost:8080/",
+            "http://[::1]/",
+            "http://[::ffff:127.0.0.1]/",
+            "http://[::ffff:169.254.169.254]/latest/meta-data",
+            "http://[fd00::1]/",
+            "http://0.0.0.0/",
+            "file:///etc/passwd",
+        ] {
+            assert!(validate_url_for_ssrf(url).await.is_err(), "{}", url);
+        }
+        assert!(validate_url_for_ssrf("https://8.8.8.8/").await.is_ok());
+        assert!(validate_url_for_ssrf("http://[2001:4860:4860::8888]/")
+            .await
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_upload_over_limit_is_rejected_and_cleaned_up() {
+        let dir = tempfile::tempdir().unwrap();
+        let tmp = dir.path().join("open_agent_ul_test");
+        let chunks = futures::stream::iter(vec![
+            Ok::<_, std::io::Error>(bytes::Bytes::from(vec![0u8; 600])),
+            Ok(bytes::Bytes::from(vec![0u8; 600])),
+        ]);
+
+        let err = stream_to_temp_file(chunks, &tmp, 1000).await.unwrap_err();
+        assert_eq!(err.0, StatusCode::PAYLOAD_TOO_LARGE);
+        assert!(!tmp.exists());
+
+        let err = check_upload_capacity(2000, 1000, &[dir.path()]).unwrap_err();
+        assert_eq!(err.0, StatusCode::PAYLOAD_TOO_LARGE);
+    }
+
+    #[tokio::test]
+    async fn test_upload_within_limit_is_written() {
+        let dir = tempfile::tempdir().unwrap();
+        let tmp = dir.path().join("open_agent_ul_test");
+        let chunks =
+            futures::stream::iter(vec![Ok::<_, std::io::Error>(bytes::Bytes::from("hello"))]);
+
+        assert_eq!(stream_to_temp_file(chunks, &tmp, 1000).await.unwrap(), 5);
+        assert_eq!(std::fs::read(&tmp).unwrap(), b"hello");
+        assert!(check_upload_capacity(5, 1000, &[dir.path()]).is_ok());
+    }
+}
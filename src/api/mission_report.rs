@@ -0,0 +1,442 @@
+//! Mission report generation from the stored event log.
+//!
+//! Compiles a mission's events into a shareable markdown report (task, key
+//! decisions, tools used, files changed, outcome) and writes it to the mission's
+//! `output/` directory. The report also records the execution environment
+//! (workspace type, environment variable names visible to the CLI, active MCP
+//! servers) so users can audit what host state a mission could see; pass
+//! `?environment=false` to leave it out.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Extension, Json,
+};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::auth::AuthUser;
+use super::mission_store::{Mission, StoredEvent};
+use super::routes::AppState;
+use crate::workspace::{self, Workspace, WorkspaceType};
+
+/// File name of the generated report inside the mission output directory.
+pub const REPORT_FILE_NAME: &str = "mission-report.md";
+
+/// Maximum number of key decisions listed in the report.
+const MAX_DECISIONS: usize = 10;
+
+/// Maximum characters kept from the final assistant response.
+const MAX_OUTCOME_CHARS: usize = 4000;
+
+/// Tool names (across backends) whose calls modify files.
+const FILE_WRITE_TOOLS: &[&str] = &[
+    "write_file",
+    "edit_file",
+    "delete_file",
+    "move_file",
+    "copy_file",
+    "normalize_file",
+    "write",
+    "edit",
+    "multiedit",
+    "notebookedit",
+];
+
+fn truncate_chars(text: &str, max: usize) -> String {
+    if text.chars().count() <= max {
+        return text.to_string();
+    }
+    let truncated: String = text.chars().take(max).collect();
+    format!("{}…", truncated)
+}
+
+fn first_line(text: &str) -> &str {
+    text.lines()
+        .map(str::trim)
+        .find(|l| !l.is_empty())
+        .unwrap_or("")
+}
+
+/// Extract the file path a tool call operated on, if any.
+fn changed_path(event: &StoredEvent) -> Option<String> {
+    let name = event.tool_name.as_deref()?.to_lowercase();
+    let short = name.rsplit("__").next().unwrap_or(&name);
+    if !FILE_WRITE_TOOLS.contains(&short) {
+        return None;
+    }
+    let args: serde_json::Value = serde_json::from_str(&event.content).ok()?;
+    ["path", "file_path", "filePath", "to", "notebook_path"]
+        .iter()
+        .find_map(|key| args.get(*key).and_then(|v| v.as_str()))
+        .map(|s| s.to_string())
+}
+
+/// Build a markdown report for a mission from its event log.
+pub fn build_mission_report(mission: &Mission, events: &[StoredEvent]) -> String {
+    let title = mission.title.as_deref().unwrap_or("Untitled mission");
+
+    let task = events
+        .iter()
+        .find(|e| e.event_type == "user_message")
+        .map(|e| e.content.trim().to_string())
+        .unwrap_or_else(|| "_No user message recorded._".to_string());
+
+    let decisions: Vec<String> = events
+        .iter()
+        .filter(|e| e.event_type == "thinking")
+        .filter(|e| e.metadata.get("done").and_then(|v| v.as_bool()) != Some(false))
+        .map(|e| truncate_chars(first_line(&e.content), 200))
+        .filter(|l| !l.is_empty())
+        .take(MAX_DECISIONS)
+        .collect();
+
+    let mut tools: BTreeMap<String, usize> = BTreeMap::new();
+    let mut files: Vec<String> = Vec::new();
+    for event in events.iter().filter(|e| e.event_type == "tool_call") {
+        if let Some(name) = &event.tool_name {
+            *tools.entry(name.clone()).or_default() += 1;
+        }
+        if let Some(path) = changed_path(event) {
+            if !files.contains(&path) {
+                files.push(path);
+            }
+        }
+    }
+
+    let errors: Vec<&StoredEvent> = events.iter().filter(|e| e.event_type == "error").collect();
+    let final_message = events
+        .iter()
+        .rev()
+        .find(|e| e.event_type == "assistant_message");
+    let total_cost_cents: u64 = events
+        .iter()
+        .filter(|e| e.event_type == "assistant_message")
+        .filter_map(|e| e.metadata.get("cost_cents").and_then(|v| v.as_u64()))
+        .sum();
+
+    let mut report = String::new();
+    report.push_str(&format!("# Mission Report: {}\n\n", title));
+    report.push_str(&format!("- **Mission ID:** {}\n", mission.id));
+    report.push_str(&format!("- **Status:** {}\n", mission.status));
+    report.push_str(&format!("- **Backend:** {}\n", mission.backend));
+    report.push_str(&format!("- **Created:** {}\n", mission.created_at));
+    report.push_str(&format!("- **Updated:** {}\n", mission.updated_at));
+    if total_cost_cents > 0 {
+        report.push_str(&format!(
+            "- **Cost:** ${:.2}\n",
+            total_cost_cents as f64 / 100.0
+        ));
+    }
+
+    report.push_str("\n## Task\n\n");
+    report.push_str(&task);
+    report.push_str("\n\n## Key Decisions\n\n");
+    if decisions.is_empty() {
+        report.push_str("_No reasoning steps recorded._\n");
+    } else {
+        for decision in &decisions {
+            report.push_str(&format!("- {}\n", decision));
+        }
+    }
+
+    report.push_str("\n## Tools Used\n\n");
+    if tools.is_empty() {
+        report.push_str("_No tool calls recorded._\n");
+    } else {
+        report.push_str("| Tool | Calls |\n|------|-------|\n");
+        for (name, count) in &tools {
+            report.push_str(&format!("| `{}` | {} |\n", name, count));
+        }
+    }
+
+    report.push_str("\n## Files Changed\n\n");
+    if files.is_empty() {
+        report.push_str("_No file changes recorded._\n");
+    } else {
+        for file in &files {
+            report.push_str(&format!("- `{}`\n", file));
+        }
+    }
+
+    if !errors.is_empty() {
+        report.push_str("\n## Errors\n\n");
+        for error in &errors {
+            report.push_str(&format!(
+                "- {}\n",
+                truncate_chars(first_line(&error.content), 300)
+            ));
+        }
+    }
+
+    report.push_str("\n## Outcome\n\n");
+    match final_message {
+        Some(event) => {
+            let success = event
+                .metadata
+                .get("success")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(true);
+            report.push_str(&format!(
+                "**{}**\n\n{}\n",
+                if success { "Succeeded" } else { "Failed" },
+                truncate_chars(event.content.trim(), MAX_OUTCOME_CHARS)
+            ));
+        }
+        None => report.push_str("_No final response recorded._\n"),
+    }
+
+    report
+}
+
+/// Execution environment of a mission's CLI process.
+#[derive(Debug, Clone, Serialize)]
+pub struct EnvironmentReport {
+    pub workspace_name: String,
+    pub workspace_type: &'static str,
+    /// Whether the CLI inherits the Open Agent process environment (host
+    /// workspaces and containers running without systemd-nspawn).
+    pub inherits_host_env: bool,
+    /// Names (never values) of the environment variables visible to the CLI.
+    pub visible_env: Vec<String>,
+    /// Variables passed through from the workspace configuration.
+    pub workspace_env: Vec<String>,
+    /// MCP servers enabled for the workspace.
+    pub mcp_servers: Vec<String>,
+}
+
+/// Describe the environment a mission's CLI runs in.
+pub fn environment_report(
+    workspace: &Workspace,
+    host_env_names: &[String],
+    mcp_servers: Vec<String>,
+) -> EnvironmentReport {
+    let inherits_host_env = match workspace.workspace_type {
+        WorkspaceType::Host => true,
+        WorkspaceType::Container => !workspace::use_nspawn_for_workspace(workspace),
+    };
+    let mut workspace_env: Vec<String> = workspace.env_vars.keys().cloned().collect();
+    workspace_env.sort();
+
+    let mut visible_env = workspace_env.clone();
+    if inherits_host_env {
+        visible_env.extend(host_env_names.iter().cloned());
+    } else {
+        // nspawn starts from a clean environment; only HOME is added.
+        visible_env.push("HOME".to_string());
+    }
+    visible_env.sort();
+    visible_env.dedup();
+
+    EnvironmentReport {
+        workspace_name: workspace.name.clone(),
+        workspace_type: workspace.workspace_type.as_str(),
+        inherits_host_env,
+        visible_env,
+        workspace_env,
+        mcp_servers,
+    }
+}
+
+/// Append the environment section to a markdown report.
+pub fn push_environment_section(report: &mut String, env: &EnvironmentReport) {
+    report.push_str("\n## Environment\n\n");
+    report.push_str(&format!(
+        "- **Workspace:** {} ({})\n",
+        env.workspace_name, env.workspace_type
+    ));
+    report.push_str(&format!(
+        "- **Host environment inherited:** {}\n",
+        if env.inherits_host_env { "yes" } else { "no" }
+    ));
+    let list = |names: &[String]| {
+        if names.is_empty() {
+            "_none_".to_string()
+        } else {
+            names
+                .iter()
+                .map(|n| format!("`{}`", n))
+                .collect::<Vec<_>>()
+                .join(", ")
+        }
+    };
+    report.push_str(&format!(
+        "- **Workspace env passthrough:** {}\n",
+        list(&env.workspace_env)
+    ));
+    report.push_str(&format!("- **MCP servers:** {}\n", list(&env.mcp_servers)));
+    report.push_str(&format!(
+        "- **Visible env vars ({}):** {}\n",
+        env.visible_env.len(),
+        list(&env.visible_env)
+    ));
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct ReportQuery {
+    /// Include the environment section (default: true).
+    pub environment: Option<bool>,
+}
+
+/// POST /api/control/missions/:id/report
+///
+/// Generate the mission report and write it to the mission's output directory.
+pub async fn generate_mission_report(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<AuthUser>,
+    Path(mission_id): Path<Uuid>,
+    Query(query): Query<ReportQuery>,
+) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
+    let control = state.control.get_or_spawn(&user).await;
+    let mission = control
+        .mission_store
+        .get_mission(mission_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?
+        .ok_or_else(|| (StatusCode::NOT_FOUND, "Mission not found".to_string()))?;
+
+    let events = control
+        .mission_store
+        .get_events(mission_id, None, None, None)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+
+    let mut report = build_mission_report(&mission, &events);
+
+    let workspace = state.workspaces.get(mission.workspace_id).await;
+    let mut environment = None;
+    if query.environment.unwrap_or(true) {
+        if let Some(ws) = &workspace {
+            let host_env: Vec<String> = std::env::vars().map(|(name, _)| name).collect();
+            let mcp_servers = workspace::filter_mcp_configs_for_workspace(
+                state.mcp.list_configs().await,
+                &ws.mcps,
+            )
+            .into_iter()
+            .map(|c| c.name)
+            .collect();
+            let env = environment_report(ws, &host_env, mcp_servers);
+            push_environment_section(&mut report, &env);
+            environment = Some(env);
+        }
+    }
+
+    let workspace_root = match workspace {
+        Some(ws) => ws.path,
+        None => state.config.working_dir.clone(),
+    };
+    let output_dir =
+        workspace::mission_workspace_dir_for_root(&workspace_root, mission_id).join("output");
+    tokio::fs::create_dir_all(&output_dir).await.map_err(|e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("Failed to create output directory: {}", e),
+        )
+    })?;
+    let report_path = output_dir.join(REPORT_FILE_NAME);
+    tokio::fs::write(&report_path, &report).await.map_err(|e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("Failed to write report: {}", e),
+        )
+    })?;
+
+    Ok(Json(serde_json::json!({
+        "path": report_path.display().to_string(),
+        "report": report,
+        "environment": environment,
+    })))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::control::MissionStatus;
+
+    fn event(seq: i64, event_type: &str, tool: Option<&str>, content: &str) -> StoredEvent {
+        StoredEvent {
+            id: seq,
+            mission_id: Uuid::nil(),
+            sequence: seq,
+            event_type: event_type.to_string(),
+            timestamp: "2026-01-01T00:00:00Z".to_string(),
+            event_id: None,
+            tool_call_id: tool.map(|_| format!("call_{}", seq)),
+            tool_name: tool.map(str::to_string),
+            content: content.to_string(),
+            metadata: serde_json::json!({}),
+        }
+    }
+
+    #[test]
+    fn test_report_from_synthetic_event_log() {
+        let mission: Mission = serde_json::from_value(serde_json::json!({
+            "id": Uuid::nil(),
+            "status": "completed",
+            "title": "Summarize logs",
+            "history": [],
+            "created_at": "2026-01-01T00:00:00Z",
+            "updated_at": "2026-01-01T00:05:00Z",
+        }))
+        .unwrap();
+        assert_eq!(mission.status, MissionStatus::Completed);
+
+        let mut done = event(5, "assistant_message", None, "Wrote output/summary.md");
+        done.metadata = serde_json::json!({ "success": true, "cost_cents": 12 });
+        let events = vec![
+            event(1, "user_message", None, "Summarize /var/log/app.log"),
+            event(
+                2,
+                "thinking",
+                None,
+                "Read the log first, then group errors.",
+            ),
+            event(
+                3,
+                "tool_call",
+                Some("read_file"),
+                r#"{"path":"/var/log/app.log"}"#,
+            ),
+            event(
+                4,
+                "tool_call",
+                Some("write_file"),
+                r#"{"path":"output/summary.md","content":"..."}"#,
+            ),
+            done,
+        ];
+
+        let report = build_mission_report(&mission, &events);
+        assert!(report.starts_with("# Mission Report: Summarize logs"));
+        assert!(report.contains("Summarize /var/log/app.log"));
+        assert!(report.contains("- Read the log first, then group errors."));
+        assert!(report.contains("| `read_file` | 1 |"));
+        assert!(report.contains("- `output/summary.md`"));
+        assert!(report.contains("**Succeeded**"));
+        assert!(report.contains("$0.12"));
+    }
+
+    #[test]
+    fn test_environment_report_lists_passthrough_and_workspace_type() {
+        let mut ws = Workspace::default_host(std::path::PathBuf::from("/tmp/ws"));
+        ws.env_vars
+            .insert("DATABASE_URL".to_string(), "postgres://secret".to_string());
+        let host_env = vec!["PATH".to_string(), "DATABASE_URL".to_string()];
+
+        let env = environment_report(&ws, &host_env, vec!["playwright".to_string()]);
+        assert_eq!(env.workspace_type, "host");
+        assert!(env.inherits_host_env);
+        assert_eq!(env.workspace_env, vec!["DATABASE_URL"]);
+        assert_eq!(env.visible_env, vec!["DATABASE_URL", "PATH"]);
+
+        let mut report = String::new();
+        push_environment_section(&mut report, &env);
+        assert!(report.contains("- **Workspace:** host (host)"));
+        assert!(report.contains("- **Workspace env passthrough:** `DATABASE_URL`"));
+        assert!(report.contains("- **MCP servers:** `playwright`"));
+        assert!(!report.contains("postgres://secret"));
+    }
+}
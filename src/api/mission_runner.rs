@@ -12,27 +12,31 @@
 
 use std::collections::{HashMap, HashSet, VecDeque};
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
-use tokio::sync::{broadcast, mpsc, RwLock};
+use tokio::sync::{mpsc, RwLock};
 use tokio_util::sync::CancellationToken;
+use tracing::Instrument;
 use uuid::Uuid;
 
 use crate::agents::{AgentRef, AgentResult, TerminalReason};
+use crate::backend::circuit_breaker::{self, CircuitBreakers};
 use crate::backend::claudecode::client::{ClaudeEvent, ContentBlock, StreamEvent};
-use crate::config::Config;
+use crate::config::{Config, MissionConfig};
 use crate::mcp::McpRegistry;
 use crate::opencode::{extract_reasoning, extract_text};
 use crate::secrets::SecretsStore;
 use crate::task::{extract_deliverables, DeliverableSet};
+use crate::tools::mission::{MissionControlCommand, MissionStatusValue};
 use crate::workspace::{self, Workspace, WorkspaceType};
 use crate::workspace_exec::WorkspaceExec;
 
 use super::control::{
-    safe_truncate_index, AgentEvent, AgentTreeNode, ControlStatus, ExecutionProgress,
-    FrontendToolHub,
+    agent_event_from_execution, safe_truncate_index, AgentEvent, AgentTreeNode, ControlStatus,
+    EventSender, ExecutionProgress, FrontendToolHub,
 };
 use super::library::SharedLibrary;
+use super::mission_store::MissionStore;
 
 #[derive(Debug, Default)]
 struct OpencodeSseState {
@@ -43,6 +47,8 @@ struct OpencodeSseState {
     response_tool_args: HashMap<String, String>,
     response_tool_names: HashMap<String, String>,
     last_emitted_thinking: Option<String>,
+    /// `tool.execute` calls still waiting for their `tool.result`: (id, name)
+    pending_tool_calls: Vec<(String, String)>,
 }
 
 struct OpencodeSseParseResult {
@@ -95,7 +101,10 @@ fn is_opencode_status_line(line: &str) -> bool {
     if lower.starts_with("[session.error]") {
         return true;
     }
-    if lower.starts_with("session:") || lower.contains("session: ses_") {
+    if lower.starts_with("session:")
+        || lower.starts_with("session id:")
+        || lower.contains("session: ses_")
+    {
         return true;
     }
     if lower.contains("starting opencode server") {
@@ -191,6 +200,115 @@ fn handle_tool_part_update(
     }
 }
 
+/// Turn a `tool.execute` / `tool.result` payload into a tool call or result.
+///
+/// Calls and results are paired by the invocation id. Results without one go
+/// to the oldest pending call of the same tool.
+fn handle_tool_event(
+    is_result: bool,
+    props: &serde_json::Value,
+    state: &mut OpencodeSseState,
+    mission_id: Uuid,
+) -> Option<AgentEvent> {
+    const ID_KEYS: &[&str] = &[
+        "callID",
+        "callId",
+        "call_id",
+        "toolCallId",
+        "tool_call_id",
+        "id",
+    ];
+    let id = extract_str(props, ID_KEYS).map(|id| id.to_string());
+    let name = extract_str(props, &["name", "tool"]).map(|name| name.to_string());
+
+    if !is_result {
+        let tool_call_id = id.unwrap_or_else(|| format!("opencode-{}", Uuid::new_v4()));
+        if state.emitted_tool_calls.contains_key(&tool_call_id) {
+            return None;
+        }
+        let name = name.unwrap_or_else(|| "unknown".to_string());
+        let args = match props
+            .get("input")
+            .or_else(|| props.get("args"))
+            .or_else(|| props.get("arguments"))
+        {
+            Some(serde_json::Value::String(raw)) => serde_json::from_str(raw)
+                .unwrap_or_else(|_| serde_json::json!({ "arguments": raw })),
+            Some(args) => args.clone(),
+            None => serde_json::json!({}),
+        };
+        state.emitted_tool_calls.insert(tool_call_id.clone(), ());
+        state
+            .pending_tool_calls
+            .push((tool_call_id.clone(), name.clone()));
+        return Some(AgentEvent::ToolCall {
+            tool_call_id,
+            name,
+            args,
+            mission_id: Some(mission_id),
+        });
+    }
+
+    let pending = match &id {
+        Some(id) => state.pending_tool_calls.iter().position(|(p, _)| p == id),
+        None => state
+            .pending_tool_calls
+            .iter()
+            .position(|(_, p)| Some(p) == name.as_ref())
+            .or((!state.pending_tool_calls.is_empty()).then_some(0)),
+    }
+    .map(|index| state.pending_tool_calls.remove(index));
+    let tool_call_id = id
+        .or_else(|| pending.as_ref().map(|(id, _)| id.clone()))
+        .unwrap_or_else(|| format!("opencode-{}", Uuid::new_v4()));
+    if state.emitted_tool_results.contains_key(&tool_call_id) {
+        return None;
+    }
+    state.emitted_tool_results.insert(tool_call_id.clone(), ());
+    let name = name
+        .or_else(|| pending.map(|(_, name)| name))
+        .unwrap_or_else(|| "unknown".to_string());
+
+    let mut result = match props.get("error").filter(|e| !e.is_null()) {
+        Some(error) => serde_json::json!({ "error": error }),
+        None => serde_json::json!({
+            "output": props.get("output").cloned().unwrap_or(serde_json::Value::Null),
+        }),
+    };
+    for key in ["title", "metadata"] {
+        if let Some(value) = props.get(key) {
+            result[key] = value.clone();
+        }
+    }
+    Some(AgentEvent::ToolResult {
+        tool_call_id,
+        name,
+        result,
+        mission_id: Some(mission_id),
+    })
+}
+
+/// Parse an oh-my-opencode stderr event line (`TOOL.EXECUTE: {json}` or
+/// `TOOL.RESULT: {json}`) into a tool call or result.
+fn parse_opencode_stderr_line(
+    line: &str,
+    state: &mut OpencodeSseState,
+    mission_id: Uuid,
+) -> Option<AgentEvent> {
+    let line = strip_ansi_codes(line);
+    let upper = line.to_ascii_uppercase();
+    let (is_result, marker) = if let Some(pos) = upper.find("TOOL.EXECUTE:") {
+        (false, pos + "TOOL.EXECUTE:".len())
+    } else if let Some(pos) = upper.find("TOOL.RESULT:") {
+        (true, pos + "TOOL.RESULT:".len())
+    } else {
+        return None;
+    };
+    let payload: serde_json::Value = serde_json::from_str(line[marker..].trim()).ok()?;
+    let props = payload.get("properties").cloned().unwrap_or(payload);
+    handle_tool_event(is_result, &props, state, mission_id)
+}
+
 fn handle_part_update(
     props: &serde_json::Value,
     state: &mut OpencodeSseState,
@@ -414,45 +532,8 @@ fn parse_opencode_sse_event(
             }
         }
         "message.part.updated" => handle_part_update(&props, state, mission_id),
-        "tool.execute" => {
-            let tool_name = props
-                .get("name")
-                .and_then(|v| v.as_str())
-                .unwrap_or("unknown")
-                .to_string();
-            let tool_id = format!("opencode-{}", uuid::Uuid::new_v4());
-            let args = props
-                .get("input")
-                .cloned()
-                .unwrap_or_else(|| serde_json::json!({}));
-            state.emitted_tool_calls.insert(tool_id.clone(), ());
-            Some(AgentEvent::ToolCall {
-                tool_call_id: tool_id,
-                name: tool_name,
-                args,
-                mission_id: Some(mission_id),
-            })
-        }
-        "tool.result" => {
-            let tool_name = props
-                .get("name")
-                .and_then(|v| v.as_str())
-                .unwrap_or("unknown")
-                .to_string();
-            let output = props
-                .get("output")
-                .and_then(|v| v.as_str())
-                .unwrap_or("")
-                .to_string();
-            // Use the most recent tool call id if tracking
-            let tool_id = format!("opencode-{}", uuid::Uuid::new_v4());
-            Some(AgentEvent::ToolResult {
-                tool_call_id: tool_id,
-                name: tool_name,
-                result: serde_json::json!({ "output": output }),
-                mission_id: Some(mission_id),
-            })
-        }
+        "tool.execute" => handle_tool_event(false, &props, state, mission_id),
+        "tool.result" => handle_tool_event(true, &props, state, mission_id),
         "message.completed" | "assistant.message.completed" => {
             message_complete = true;
             None
@@ -496,7 +577,8 @@ fn parse_opencode_sse_event(
 }
 
 /// State of a running mission.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum MissionRunState {
     /// Waiting in queue
     Queued,
@@ -508,6 +590,18 @@ pub enum MissionRunState {
     Finished,
 }
 
+/// Kind of the last event a running mission emitted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LastEvent {
+    /// Nothing since the turn started
+    None,
+    Thinking,
+    ToolCall,
+    ToolResult,
+    Other,
+}
+
 /// Health status of a mission.
 #[derive(Debug, Clone, serde::Serialize)]
 pub enum MissionHealth {
@@ -517,6 +611,9 @@ pub enum MissionHealth {
     Stalled {
         seconds_since_activity: u64,
         last_state: String,
+        last_event: LastEvent,
+        /// Quiet time after which the mission counted as stalled
+        threshold_secs: u64,
     },
     /// Mission completed without deliverables
     MissingDeliverables { missing: Vec<String> },
@@ -525,7 +622,7 @@ pub enum MissionHealth {
 }
 
 /// A message queued for this mission.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct QueuedMessage {
     pub id: Uuid,
     pub content: String,
@@ -535,7 +632,7 @@ pub struct QueuedMessage {
 
 /// Isolated runner for a single mission.
 /// Info about a tracked subtask (from delegate_task/Task tool calls).
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct SubtaskInfo {
     pub tool_call_id: String,
     pub description: String,
@@ -585,24 +682,244 @@ pub struct MissionRunner {
     /// Last activity timestamp for health monitoring
     pub last_activity: Instant,
 
-    /// Whether complete_mission was explicitly called
+    /// Kind of the last event seen during the current turn
+    pub last_event: LastEvent,
+
+    /// Tool calls of the current turn still waiting for their result
+    tools_in_flight: HashSet<String>,
+
+    /// Quiet time before a turn waiting on the model is considered stalled
+    pub stall_timeout: Duration,
+
+    /// Quiet time before a turn waiting on a tool call is considered stalled
+    pub tool_stall_timeout: Duration,
+
+    /// Whether complete_mission was explicitly called (seen on the turn's command channel)
     pub explicitly_completed: bool,
 
+    /// Status passed to complete_mission during the running turn, if it was called
+    agent_completion: Arc<std::sync::Mutex<Option<MissionStatusValue>>>,
+
     /// Current activity label (derived from latest tool call)
     pub current_activity: Option<String>,
 
     /// Tracked subtasks (from delegate_task/Task tool calls)
     pub subtasks: Vec<SubtaskInfo>,
+
+    /// Scheduling weight when execution slots are contended (higher = more turns)
+    pub priority: u32,
+
+    /// Stride-scheduling pass: advances by `FAIR_STRIDE / priority` per started turn
+    pub fair_pass: u64,
+
+    /// Global execution slot held while a turn runs (see `api::mission_slots`)
+    pub slot: Option<super::mission_slots::SlotPermit>,
+
+    /// Mission store, used to take and record the pre-run workspace snapshot
+    pub mission_store: Option<Arc<dyn MissionStore>>,
+
+    /// Restored after a server restart cut its last turn short
+    pub interrupted: bool,
+
+    /// Cost and tokens of the finished turns
+    pub cost: CostLedger,
+
+    /// Usage streamed so far by the running turn, replaced by the turn's
+    /// reported result once it finishes
+    pub turn_cost: CostLedger,
+
+    /// Named backend profile from the backend config (CLI path, extra args)
+    pub backend_profile: Option<String>,
+
+    /// Extra environment for the mission's workspace MCP (API token, safe-mode
+    /// flag). Not persisted: the token is reissued when the runner is restored.
+    pub mcp_env: HashMap<String, String>,
+}
+
+/// Running cost and token totals of a mission, summed over its turns.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct CostLedger {
+    pub cost_cents: u64,
+    /// Input tokens, cache reads and writes included
+    pub prompt_tokens: u64,
+    pub completion_tokens: u64,
+    pub turns: u32,
+}
+
+impl CostLedger {
+    /// Add a finished turn. Backends that don't report tokens only add cost.
+    pub fn record(&mut self, result: &AgentResult) {
+        self.cost_cents += result.cost_cents;
+        if let Some(usage) = &result.usage {
+            self.prompt_tokens += usage.prompt_tokens();
+            self.completion_tokens += usage.output_tokens;
+        }
+        self.turns += 1;
+    }
+
+    /// Totals with the usage streamed so far by the turn in progress.
+    pub fn with_turn(self, turn: &CostLedger) -> Self {
+        Self {
+            cost_cents: self.cost_cents + turn.cost_cents,
+            prompt_tokens: self.prompt_tokens + turn.prompt_tokens,
+            completion_tokens: self.completion_tokens + turn.completion_tokens,
+            turns: self.turns,
+        }
+    }
+}
+
+/// Usage a CLI reports on its assistant messages, summed over the turn.
+///
+/// Claude Code repeats a message's usage on every content block it streams,
+/// so each message id is counted once.
+#[derive(Debug, Default)]
+struct StreamedUsage {
+    usage: crate::cost::TokenUsage,
+    seen: HashSet<String>,
+}
+
+impl StreamedUsage {
+    /// Add one assistant message's usage and return the turn's running totals
+    /// to broadcast, or `None` when nothing new was reported.
+    fn add(
+        &mut self,
+        message: &crate::backend::shared::AssistantMessage,
+        model: Option<&str>,
+        mission_id: Uuid,
+    ) -> Option<AgentEvent> {
+        let usage = message.usage.as_ref()?;
+        if let Some(id) = &message.id {
+            if !self.seen.insert(id.clone()) {
+                return None;
+            }
+        }
+        self.usage.add(&usage.token_usage());
+        let model = message.model.as_deref().or(model);
+        Some(AgentEvent::UsageUpdate {
+            cost_cents: model.map_or(0, |m| crate::cost::cost_cents_from_usage(m, &self.usage)),
+            prompt_tokens: self.usage.prompt_tokens(),
+            completion_tokens: self.usage.output_tokens,
+            mission_id: Some(mission_id),
+        })
+    }
+}
+
+/// Note shown for runners whose turn was cut short by a server restart.
+const INTERRUPTED_NOTE: &str = "Interrupted by a server restart; the last turn did not finish";
+
+/// Directory holding persisted runner state (`<mission-id>.json`).
+pub fn runner_state_dir(working_dir: &std::path::Path) -> std::path::PathBuf {
+    working_dir.join(".openagent").join("missions")
+}
+
+/// On-disk form of a [`MissionRunner`], written after every completed turn so
+/// queued and finished runners survive a server restart.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct PersistedRunner {
+    pub mission_id: Uuid,
+    pub workspace_id: Uuid,
+    pub backend_id: String,
+    pub session_id: Option<String>,
+    pub state: MissionRunState,
+    pub agent_override: Option<String>,
+    pub queue: Vec<QueuedMessage>,
+    pub history: Vec<(String, String)>,
+    pub deliverables: DeliverableSet,
+    pub explicitly_completed: bool,
+    pub subtasks: Vec<SubtaskInfo>,
+    pub priority: u32,
+    #[serde(default)]
+    pub interrupted: bool,
+    #[serde(default)]
+    pub cost: CostLedger,
+    #[serde(default)]
+    pub backend_profile: Option<String>,
+}
+
+/// Pass increment for a priority-1 mission turn.
+const FAIR_STRIDE: u64 = 1 << 16;
+
+/// Delete the persisted state of a runner that is done.
+pub fn remove_runner_state(dir: &std::path::Path, mission_id: Uuid) {
+    let path = dir.join(format!("{}.json", mission_id));
+    if let Err(e) = std::fs::remove_file(&path) {
+        if e.kind() != std::io::ErrorKind::NotFound {
+            tracing::warn!("Failed to remove runner state {}: {}", path.display(), e);
+        }
+    }
+}
+
+/// Load every runner persisted under `dir`, skipping unreadable files.
+pub fn load_runner_states(dir: &std::path::Path, health: &MissionConfig) -> Vec<MissionRunner> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut runners = Vec::new();
+    for path in entries.flatten().map(|e| e.path()) {
+        // Only `<uuid>.json`; the mission store keeps its own files here too.
+        let is_runner_file = path.extension().is_some_and(|ext| ext == "json")
+            && path
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .is_some_and(|stem| Uuid::parse_str(stem).is_ok());
+        if !is_runner_file {
+            continue;
+        }
+        let saved = std::fs::read(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|bytes| {
+                serde_json::from_slice::<PersistedRunner>(&bytes).map_err(|e| e.to_string())
+            });
+        match saved {
+            Ok(saved) => runners.push(MissionRunner::from_persisted(saved, health)),
+            Err(e) => tracing::warn!("Skipping runner state {}: {}", path.display(), e),
+        }
+    }
+    runners
+}
+
+/// Pass for a runner joining the schedule: the lowest pass among the active
+/// runners, so a newcomer doesn't get a run of turns to catch up with
+/// missions that have been busy for hours.
+pub fn starting_fair_pass(runners: &HashMap<Uuid, MissionRunner>) -> u64 {
+    runners
+        .values()
+        .filter(|r| !r.is_finished())
+        .map(|r| r.fair_pass)
+        .min()
+        .unwrap_or(0)
+}
+
+/// Pick up to `slots` idle runners with queued turns, lowest pass first.
+///
+/// A mission that just ran has advanced its pass, so a waiting mission gets the
+/// next free slot instead of the busy one taking it again (weighted round-robin).
+/// Finished runners (e.g. interrupted by a restart) are only resumed explicitly.
+pub fn next_fair_missions(runners: &HashMap<Uuid, MissionRunner>, slots: usize) -> Vec<Uuid> {
+    let mut ready: Vec<&MissionRunner> = runners
+        .values()
+        .filter(|r| !r.is_running() && !r.is_finished() && !r.queue.is_empty())
+        .collect();
+    ready.sort_by_key(|r| (r.fair_pass, r.mission_id));
+    ready
+        .into_iter()
+        .take(slots)
+        .map(|r| r.mission_id)
+        .collect()
 }
 
 impl MissionRunner {
     /// Create a new mission runner.
+    ///
+    /// `health` sets the stall thresholds; pass the server's `Config::mission`
+    /// or a per-mission override.
     pub fn new(
         mission_id: Uuid,
         workspace_id: Uuid,
         agent_override: Option<String>,
         backend_id: Option<String>,
         session_id: Option<String>,
+        health: &MissionConfig,
     ) -> Self {
         Self {
             mission_id,
@@ -619,10 +936,91 @@ impl MissionRunner {
             progress_snapshot: Arc::new(RwLock::new(ExecutionProgress::default())),
             deliverables: DeliverableSet::default(),
             last_activity: Instant::now(),
+            last_event: LastEvent::None,
+            tools_in_flight: HashSet::new(),
+            stall_timeout: health.stall_timeout,
+            tool_stall_timeout: health.tool_stall_timeout,
             explicitly_completed: false,
+            agent_completion: Arc::new(std::sync::Mutex::new(None)),
             current_activity: None,
             subtasks: Vec::new(),
+            priority: 1,
+            fair_pass: 0,
+            slot: None,
+            mission_store: None,
+            interrupted: false,
+            cost: CostLedger::default(),
+            turn_cost: CostLedger::default(),
+            backend_profile: None,
+            mcp_env: HashMap::new(),
+        }
+    }
+
+    /// Snapshot the parts of this runner that can outlive the process.
+    pub fn to_persisted(&self) -> PersistedRunner {
+        PersistedRunner {
+            mission_id: self.mission_id,
+            workspace_id: self.workspace_id,
+            backend_id: self.backend_id.clone(),
+            session_id: self.session_id.clone(),
+            state: self.state,
+            agent_override: self.agent_override.clone(),
+            queue: self.queue.iter().cloned().collect(),
+            history: self.history.clone(),
+            deliverables: self.deliverables.clone(),
+            explicitly_completed: self.explicitly_completed,
+            subtasks: self.subtasks.clone(),
+            priority: self.priority,
+            interrupted: self.interrupted,
+            cost: self.cost,
+            backend_profile: self.backend_profile.clone(),
+        }
+    }
+
+    /// Rebuild a runner from saved state.
+    ///
+    /// A running task can't be restored: runners saved mid-turn come back as
+    /// `Finished` and flagged as interrupted.
+    pub fn from_persisted(saved: PersistedRunner, health: &MissionConfig) -> Self {
+        let mut runner = Self::new(
+            saved.mission_id,
+            saved.workspace_id,
+            saved.agent_override,
+            Some(saved.backend_id),
+            saved.session_id,
+            health,
+        );
+        runner.queue = saved.queue.into();
+        runner.history = saved.history;
+        runner.deliverables = saved.deliverables;
+        runner.explicitly_completed = saved.explicitly_completed;
+        runner.subtasks = saved.subtasks;
+        runner.priority = saved.priority;
+        runner.state = saved.state;
+        runner.interrupted = saved.interrupted;
+        runner.cost = saved.cost;
+        runner.backend_profile = saved.backend_profile;
+        if matches!(
+            saved.state,
+            MissionRunState::Running | MissionRunState::WaitingForTool
+        ) {
+            runner.state = MissionRunState::Finished;
+            runner.interrupted = true;
+        }
+        if runner.interrupted {
+            runner.current_activity = Some(INTERRUPTED_NOTE.to_string());
         }
+        runner
+    }
+
+    /// Write this runner's state to `<dir>/<mission-id>.json`.
+    pub fn save_state(&self, dir: &std::path::Path) -> std::io::Result<()> {
+        std::fs::create_dir_all(dir)?;
+        let path = dir.join(format!("{}.json", self.mission_id));
+        let tmp = path.with_extension("json.tmp");
+        let json = serde_json::to_vec_pretty(&self.to_persisted())?;
+        std::fs::write(&tmp, json)?;
+        std::fs::rename(&tmp, &path)
     }
 
     /// Check if this runner is currently executing.
@@ -643,15 +1041,63 @@ impl MissionRunner {
         self.last_activity = Instant::now();
     }
 
+    /// Record an event emitted by this mission's current turn.
+    pub fn record_event(&mut self, event: &AgentEvent) {
+        self.last_event = match event {
+            AgentEvent::Thinking { .. } => LastEvent::Thinking,
+            AgentEvent::ToolCall { tool_call_id, .. } => {
+                self.tools_in_flight.insert(tool_call_id.clone());
+                LastEvent::ToolCall
+            }
+            AgentEvent::ToolResult { tool_call_id, .. } => {
+                self.tools_in_flight.remove(tool_call_id);
+                LastEvent::ToolResult
+            }
+            AgentEvent::UsageUpdate {
+                cost_cents,
+                prompt_tokens,
+                completion_tokens,
+                ..
+            } => {
+                // A late update from a finished turn must not count twice.
+                if self.is_running() {
+                    self.turn_cost = CostLedger {
+                        cost_cents: *cost_cents,
+                        prompt_tokens: *prompt_tokens,
+                        completion_tokens: *completion_tokens,
+                        turns: 0,
+                    };
+                }
+                LastEvent::Other
+            }
+            _ => LastEvent::Other,
+        };
+        self.touch();
+    }
+
+    /// Quiet time after which the current turn counts as stalled.
+    ///
+    /// A tool running quietly (a long build, a large download) gets the longer
+    /// tool threshold; a turn waiting on the model gets the regular one.
+    pub fn stall_threshold(&self) -> Duration {
+        if self.tools_in_flight.is_empty() {
+            self.stall_timeout
+        } else {
+            self.tool_stall_timeout
+        }
+    }
+
     /// Check the health of this mission.
     pub async fn check_health(&self) -> MissionHealth {
         let seconds_since = self.last_activity.elapsed().as_secs();
+        let threshold = self.stall_threshold().as_secs();
 
-        // If running and no activity for 60+ seconds, consider stalled
-        if self.is_running() && seconds_since > 60 {
+        if self.is_running() && seconds_since > threshold {
             return MissionHealth::Stalled {
                 seconds_since_activity: seconds_since,
                 last_state: format!("{:?}", self.state),
+                last_event: self.last_event,
+                threshold_secs: threshold,
             };
         }
 
@@ -698,6 +1144,14 @@ impl MissionRunner {
         }
     }
 
+    /// Dequeue the next message, mark the runner running and charge its fair-share pass.
+    fn begin_turn(&mut self) -> Option<QueuedMessage> {
+        let msg = self.queue.pop_front()?;
+        self.state = MissionRunState::Running;
+        self.fair_pass += FAIR_STRIDE / u64::from(self.priority.max(1));
+        Some(msg)
+    }
+
     /// Start executing the next queued message (if any and not already running).
     /// Returns true if execution was started.
     pub fn start_next(
@@ -707,7 +1161,7 @@ impl MissionRunner {
         mcp: Arc<McpRegistry>,
         workspaces: workspace::SharedWorkspaceStore,
         library: SharedLibrary,
-        events_tx: broadcast::Sender<AgentEvent>,
+        events_tx: EventSender,
         tool_hub: Arc<FrontendToolHub>,
         status: Arc<RwLock<ControlStatus>>,
         mission_cmd_tx: mpsc::Sender<crate::tools::mission::MissionControlCommand>,
@@ -719,13 +1173,14 @@ impl MissionRunner {
             return false;
         }
 
-        // Get next message from queue
-        let msg = match self.queue.pop_front() {
+        let msg = match self.begin_turn() {
             Some(m) => m,
             None => return false,
         };
-
-        self.state = MissionRunState::Running;
+        self.interrupted = false;
+        self.current_activity = None;
+        self.last_event = LastEvent::None;
+        self.tools_in_flight.clear();
 
         let cancel = CancellationToken::new();
         self.cancel_token = Some(cancel.clone());
@@ -738,6 +1193,8 @@ impl MissionRunner {
         let agent_override = self.agent_override.clone();
         let backend_id = self.backend_id.clone();
         let session_id = self.session_id.clone();
+        let backend_profile = self.backend_profile.clone();
+        let mcp_env = self.mcp_env.clone();
         let user_message = msg.content.clone();
         let msg_id = msg.id;
         tracing::info!(
@@ -749,11 +1206,16 @@ impl MissionRunner {
             "Mission runner starting"
         );
 
-        // Create mission control for complete_mission tool
+        // Create mission control for complete_mission tool. Its commands go
+        // through a per-turn channel so the runner sees the call itself.
+        let (turn_cmd_tx, mut turn_cmd_rx) = mpsc::channel::<MissionControlCommand>(16);
         let mission_ctrl = crate::tools::mission::MissionControl {
             current_mission_id: current_mission,
-            cmd_tx: mission_cmd_tx,
+            cmd_tx: turn_cmd_tx,
+            deliverables: Default::default(),
         };
+        let agent_completion = Arc::clone(&self.agent_completion);
+        *agent_completion.lock().unwrap() = None;
 
         // Emit user message event with mission context
         let _ = events_tx.send(AgentEvent::UserMessage {
@@ -763,32 +1225,53 @@ impl MissionRunner {
             mission_id: Some(mission_id),
         });
 
-        let handle = tokio::spawn(async move {
-            let result = run_mission_turn(
-                config,
-                root_agent,
-                mcp,
-                workspaces,
-                library,
-                events_tx,
-                tool_hub,
-                status,
-                cancel,
-                hist_snapshot,
-                user_message.clone(),
-                Some(mission_ctrl),
-                tree_ref,
-                progress_ref,
-                mission_id,
-                Some(workspace_id),
-                backend_id,
-                agent_override,
-                secrets,
-                session_id,
-            )
-            .await;
-            (msg_id, user_message, result)
-        });
+        let mission_store = self.mission_store.clone();
+        let handle = tokio::spawn(
+            async move {
+                if let Some(store) = mission_store {
+                    super::snapshot::snapshot_before_first_turn(
+                        &store,
+                        &workspaces,
+                        &config,
+                        mission_id,
+                    )
+                    .await;
+                }
+                let turn = run_mission_turn(
+                    config,
+                    root_agent,
+                    mcp,
+                    workspaces,
+                    library,
+                    events_tx,
+                    tool_hub,
+                    status,
+                    cancel,
+                    hist_snapshot,
+                    user_message.clone(),
+                    Some(mission_ctrl),
+                    tree_ref,
+                    progress_ref,
+                    mission_id,
+                    Some(workspace_id),
+                    backend_id,
+                    agent_override,
+                    secrets,
+                    session_id,
+                    backend_profile,
+                    mcp_env,
+                );
+                let result = relay_mission_commands(
+                    turn,
+                    &mut turn_cmd_rx,
+                    &agent_completion,
+                    &mission_cmd_tx,
+                )
+                .await;
+                (msg_id, user_message, result)
+            }
+            .instrument(crate::mission_log::mission_span(Some(mission_id))),
+        );
 
         self.running_handle = Some(handle);
         true
@@ -800,17 +1283,30 @@ impl MissionRunner {
 
         // Check if handle is finished
         if handle.is_finished() {
+            // Give the execution slot back for the next queued turn
+            self.slot = None;
             match handle.await {
                 Ok(result) => {
                     self.touch(); // Update last activity
                     self.state = MissionRunState::Queued; // Ready for next message
 
-                    // Check if complete_mission was called
-                    if result.2.output.contains("Mission marked as")
-                        || result.2.output.contains("complete_mission")
-                    {
+                    // complete_mission was called during this turn
+                    if self.agent_completion.lock().unwrap().take().is_some() {
                         self.explicitly_completed = true;
                     }
+                    self.cost.record(&result.2);
+                    self.turn_cost = CostLedger::default();
+
+                    // Keep the backend session (Claude Code may have started a new one)
+                    if let Some(session_id) = result
+                        .2
+                        .data
+                        .as_ref()
+                        .and_then(|d| d.get("session_id"))
+                        .and_then(|v| v.as_str())
+                    {
+                        self.session_id = Some(session_id.to_string());
+                    }
 
                     // Add to history
                     self.history.push(("user".to_string(), result.1.clone()));
@@ -853,6 +1349,65 @@ impl MissionRunner {
     }
 }
 
+/// Drive a mission turn while relaying its complete_mission commands to the control actor.
+///
+/// Each `SetStatus` is recorded in `completion` before being forwarded, so the
+/// runner knows the agent completed the mission without parsing its output.
+async fn relay_mission_commands<Fut>(
+    turn: Fut,
+    turn_cmd_rx: &mut mpsc::Receiver<MissionControlCommand>,
+    completion: &std::sync::Mutex<Option<MissionStatusValue>>,
+    mission_cmd_tx: &mpsc::Sender<MissionControlCommand>,
+) -> AgentResult
+where
+    Fut: std::future::Future<Output = AgentResult>,
+{
+    async fn relay(
+        cmd: MissionControlCommand,
+        completion: &std::sync::Mutex<Option<MissionStatusValue>>,
+        mission_cmd_tx: &mpsc::Sender<MissionControlCommand>,
+    ) {
+        match &cmd {
+            MissionControlCommand::SetStatus { status, .. } => {
+                *completion.lock().unwrap() = Some(*status);
+            }
+        }
+        if mission_cmd_tx.send(cmd).await.is_err() {
+            tracing::warn!("Control actor gone; mission status command dropped");
+        }
+    }
+
+    tokio::pin!(turn);
+    let result = loop {
+        tokio::select! {
+            result = &mut turn => break result,
+            Some(cmd) = turn_cmd_rx.recv() => relay(cmd, completion, mission_cmd_tx).await,
+        }
+    };
+    // Commands sent just before the turn returned
+    turn_cmd_rx.close();
+    while let Ok(cmd) = turn_cmd_rx.try_recv() {
+        relay(cmd, completion, mission_cmd_tx).await;
+    }
+    result
+}
+
+/// `MissionComplete` event for a concluded turn, checking `deliverables` on disk.
+pub async fn mission_complete_event(
+    mission_id: Uuid,
+    result: &AgentResult,
+    deliverables: &DeliverableSet,
+) -> AgentEvent {
+    let (deliverables_present, deliverables_missing) = deliverables.partition_paths().await;
+    AgentEvent::MissionComplete {
+        mission_id,
+        success: result.success,
+        cost_cents: result.cost_cents,
+        deliverables_present,
+        deliverables_missing,
+    }
+}
+
 /// Build a history context string from conversation history.
 fn build_history_context(history: &[(String, String)], max_chars: usize) -> String {
     let mut result = String::new();
@@ -946,13 +1501,13 @@ async fn run_mission_turn(
     mcp: Arc<McpRegistry>,
     workspaces: workspace::SharedWorkspaceStore,
     library: SharedLibrary,
-    events_tx: broadcast::Sender<AgentEvent>,
+    events_tx: EventSender,
     tool_hub: Arc<FrontendToolHub>,
     _status: Arc<RwLock<ControlStatus>>,
     cancel: CancellationToken,
     history: Vec<(String, String)>,
     user_message: String,
-    _mission_control: Option<crate::tools::mission::MissionControl>,
+    mission_control: Option<crate::tools::mission::MissionControl>,
     _tree_snapshot: Arc<RwLock<Option<AgentTreeNode>>>,
     _progress_snapshot: Arc<RwLock<ExecutionProgress>>,
     mission_id: Uuid,
@@ -961,6 +1516,8 @@ async fn run_mission_turn(
     agent_override: Option<String>,
     secrets: Option<Arc<SecretsStore>>,
     session_id: Option<String>,
+    backend_profile: Option<String>,
+    mcp_env: HashMap<String, String>,
 ) -> AgentResult {
     let mut config = config;
     let effective_agent = agent_override.clone();
@@ -990,6 +1547,9 @@ async fn run_mission_turn(
 
     // Extract deliverables to include in instructions
     let deliverable_set = extract_deliverables(&user_message);
+    if let Some(ref ctrl) = mission_control {
+        *ctrl.deliverables.write().await = deliverable_set.clone();
+    }
     let deliverable_reminder = if !deliverable_set.deliverables.is_empty() {
         let paths: Vec<String> = deliverable_set
             .deliverables
@@ -1039,98 +1599,155 @@ async fn run_mission_turn(
 
     // Ensure mission workspace exists and is configured for OpenCode.
     let workspace = workspace::resolve_workspace(&workspaces, &config, workspace_id).await;
-    let workspace_root = workspace.path.clone();
-    let mission_work_dir = match {
-        let lib_guard = library.read().await;
-        let lib_ref = lib_guard.as_ref().map(|l| l.as_ref());
-        workspace::prepare_mission_workspace_with_skills_backend(
-            &workspace,
-            &mcp,
-            lib_ref,
-            mission_id,
-            &backend_id,
-            None, // custom_providers: TODO integrate with provider store
-        )
-        .await
-    } {
-        Ok(dir) => {
-            tracing::info!(
-                "Mission {} workspace directory: {}",
-                mission_id,
-                dir.display()
-            );
-            dir
-        }
-        Err(e) => {
-            tracing::warn!("Failed to prepare mission workspace, using default: {}", e);
-            workspace_root
-        }
-    };
 
     // Execute based on backend
     // For Claude Code, check if this is a continuation turn (has prior assistant response).
     // Note: history may include the current user message before the turn runs,
     // so we check for assistant messages to determine if this is truly a continuation.
     let is_continuation = history.iter().any(|(role, _)| role == "assistant");
-    let result = match backend_id.as_str() {
-        "claudecode" => {
-            run_claudecode_turn(
-                &workspace,
-                &mission_work_dir,
-                &user_message,
-                config.default_model.as_deref(),
-                effective_agent.as_deref(),
-                mission_id,
-                events_tx.clone(),
-                cancel,
-                secrets,
-                &config.working_dir,
-                session_id.as_deref(),
-                is_continuation,
-                Some(Arc::clone(&tool_hub)),
-            )
-            .await
-        }
-        "opencode" => {
-            // Use per-workspace CLI execution for all workspace types to ensure
-            // native bash + correct filesystem scope.
-            run_opencode_turn(
-                &workspace,
-                &mission_work_dir,
-                &convo,
-                config.default_model.as_deref(),
-                effective_agent.as_deref(),
-                mission_id,
-                events_tx.clone(),
-                cancel,
-                &config.working_dir,
-            )
-            .await
-        }
-        "amp" => {
-            let api_key = get_amp_api_key_from_config();
-            run_amp_turn(
-                &workspace,
-                &mission_work_dir,
-                &user_message,
-                effective_agent.as_deref(), // Used as mode (smart/rush)
-                mission_id,
-                events_tx.clone(),
-                cancel,
-                &config.working_dir,
-                session_id.as_deref(),
-                is_continuation,
-                api_key.as_deref(),
-            )
-            .await
-        }
-        _ => {
-            // Don't send Error event - the failure will be emitted as an AssistantMessage
-            // with success=false by the caller (control.rs), avoiding duplicate messages.
-            AgentResult::failure(format!("Unsupported backend: {}", backend_id), 0)
-                .with_terminal_reason(TerminalReason::LlmError)
-        }
-    };
+    let chain =
+        backend_fallback_chain(&backend_id, &get_backend_fallbacks_from_config(&backend_id));
+    let workspace = &workspace;
+    let (config, mcp, library, convo, user_message, mcp_env) =
+        (&config, &mcp, &library, &convo, &user_message, &mcp_env);
+    let result = run_with_backend_fallback(
+        &chain,
+        mission_id,
+        &events_tx,
+        circuit_breaker::global(),
+        |backend, is_primary| {
+            let events_tx = events_tx.clone();
+            let cancel = cancel.clone();
+            let secrets = secrets.clone();
+            let tool_hub = Arc::clone(&tool_hub);
+            let effective_agent = effective_agent.clone();
+            // Fallback backends start a fresh session with their own default model.
+            let model = if is_primary {
+                config.default_model.clone()
+            } else {
+                None
+            };
+            let session_id = if is_primary { session_id.clone() } else { None };
+            let profile = if is_primary {
+                backend_profile.clone()
+            } else {
+                None
+            };
+            let is_continuation = is_primary && is_continuation;
+            async move {
+                let workspace_root = workspace.path.clone();
+                let mission_work_dir = match {
+                    let lib_guard = library.read().await;
+                    let lib_ref = lib_guard.as_ref().map(|l| l.as_ref());
+                    workspace::prepare_mission_workspace_with_skills_backend(
+                        workspace, mcp, lib_ref, mission_id, &backend,
+                        None, // custom_providers: TODO integrate with provider store
+                        mcp_env,
+                    )
+                    .await
+                } {
+                    Ok(dir) => {
+                        tracing::info!(
+                            "Mission {} workspace directory: {}",
+                            mission_id,
+                            dir.display()
+                        );
+                        dir
+                    }
+                    Err(e) => {
+                        tracing::warn!("Failed to prepare mission workspace, using default: {}", e);
+                        workspace_root
+                    }
+                };
+
+                match backend.as_str() {
+                    "claudecode" => {
+                        run_claudecode_turn(
+                            workspace,
+                            &mission_work_dir,
+                            user_message,
+                            model.as_deref(),
+                            effective_agent.as_deref(),
+                            mission_id,
+                            events_tx,
+                            cancel,
+                            secrets,
+                            &config.working_dir,
+                            session_id.as_deref(),
+                            is_continuation,
+                            Some(tool_hub),
+                            config.max_tool_calls_per_turn(),
+                            profile.as_deref(),
+                        )
+                        .await
+                    }
+                    "opencode" => {
+                        // Use per-workspace CLI execution for all workspace types to ensure
+                        // native bash + correct filesystem scope.
+                        run_opencode_turn(
+                            workspace,
+                            &mission_work_dir,
+                            convo,
+                            model.as_deref(),
+                            effective_agent.as_deref(),
+                            mission_id,
+                            events_tx,
+                            cancel,
+                            &config.working_dir,
+                            profile.as_deref(),
+                        )
+                        .await
+                    }
+                    "amp" => {
+                        let api_key = get_amp_api_key_from_config();
+                        run_amp_turn(
+                            workspace,
+                            &mission_work_dir,
+                            user_message,
+                            effective_agent.as_deref(), // Used as mode (smart/rush)
+                            mission_id,
+                            events_tx,
+                            cancel,
+                            &config.working_dir,
+                            session_id.as_deref(),
+                            is_continuation,
+                            api_key.as_deref(),
+                            config.max_tool_calls_per_turn(),
+                        )
+                        .await
+                    }
+                    other => match crate::backend::registry::lookup(other).await {
+                        Some(registered) => {
+                            let message = if session_id.is_some() && is_continuation {
+                                user_message
+                            } else {
+                                convo
+                            };
+                            run_backend_turn(
+                                registered,
+                                &mission_work_dir,
+                                message,
+                                model.as_deref(),
+                                effective_agent.as_deref(),
+                                session_id.as_deref(),
+                                mission_id,
+                                events_tx,
+                                cancel,
+                            )
+                            .await
+                        }
+                        // Don't send Error event - the failure will be emitted as an AssistantMessage
+                        // with success=false by the caller (control.rs), avoiding duplicate messages.
+                        None => {
+                            AgentResult::failure(format!("Unsupported backend: {}", backend), 0)
+                                .with_terminal_reason(TerminalReason::LlmError)
+                        }
+                    },
+                }
+            }
+        },
+    )
+    .await;
 
     tracing::info!(
         mission_id = %mission_id,
@@ -1143,8 +1760,110 @@ async fn run_mission_turn(
     result
 }
 
-fn read_backend_configs() -> Option<Vec<serde_json::Value>> {
-    let home = std::env::var("HOME").ok()?;
+/// Read the fallback backends configured for `backend_id` (`settings.fallback_backends`).
+///
+/// Accepts either a JSON array (`["opencode"]`) or a comma-separated string.
+fn get_backend_fallbacks_from_config(backend_id: &str) -> Vec<String> {
+    let Some(configs) = read_backend_configs() else {
+        return Vec::new();
+    };
+
+    for config in configs {
+        if config.get("id").and_then(|v| v.as_str()) != Some(backend_id) {
+            continue;
+        }
+        let Some(value) = config
+            .get("settings")
+            .and_then(|s| s.get("fallback_backends"))
+        else {
+            return Vec::new();
+        };
+        return match value {
+            serde_json::Value::Array(items) => items
+                .iter()
+                .filter_map(|v| v.as_str())
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+                .collect(),
+            serde_json::Value::String(list) => list
+                .split(',')
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+                .collect(),
+            _ => Vec::new(),
+        };
+    }
+    Vec::new()
+}
+
+/// Primary backend followed by its fallbacks, without duplicates.
+fn backend_fallback_chain(primary: &str, fallbacks: &[String]) -> Vec<String> {
+    let mut chain = vec![primary.to_string()];
+    for backend in fallbacks {
+        if !chain.contains(backend) {
+            chain.push(backend.clone());
+        }
+    }
+    chain
+}
+
+/// Whether a turn failed because the backend could not run at all (as opposed to a
+/// model-produced error), so another backend may be tried.
+fn is_backend_unavailable(result: &AgentResult) -> bool {
+    !result.success
+        && matches!(
+            result.terminal_reason,
+            Some(
+                TerminalReason::CliMissing
+                    | TerminalReason::AuthMissing
+                    | TerminalReason::BackendUnavailable
+            )
+        )
+}
+
+/// Run a turn on each backend of `chain` in order until one is able to run.
+///
+/// Only environment failures (`CliMissing`/`AuthMissing`) and open circuit
+/// breakers trigger a fallback; any other result, successful or not, is
+/// returned as-is.
+async fn run_with_backend_fallback<F, Fut>(
+    chain: &[String],
+    mission_id: Uuid,
+    events_tx: &EventSender,
+    breakers: &CircuitBreakers,
+    mut run_turn: F,
+) -> AgentResult
+where
+    F: FnMut(String, bool) -> Fut,
+    Fut: std::future::Future<Output = AgentResult>,
+{
+    let mut result = AgentResult::failure("No backend configured".to_string(), 0)
+        .with_terminal_reason(TerminalReason::LlmError);
+    for (idx, backend) in chain.iter().enumerate() {
+        result = breakers
+            .run(backend, run_turn(backend.clone(), idx == 0))
+            .await;
+        if !is_backend_unavailable(&result) {
+            break;
+        }
+        if let Some(next) = chain.get(idx + 1) {
+            let note = format!(
+                "Backend '{}' unavailable ({}); falling back to '{}'",
+                backend, result.output, next
+            );
+            tracing::warn!(mission_id = %mission_id, "{}", note);
+            let _ = events_tx.send(AgentEvent::MissionActivity {
+                label: note,
+                tool_name: "backend_fallback".to_string(),
+                mission_id: Some(mission_id),
+            });
+        }
+    }
+    result
+}
+
+fn read_backend_configs() -> Option<Vec<serde_json::Value>> {
+    let home = std::env::var("HOME").ok()?;
 
     // Check WORKING_DIR first (for custom deployment paths), then HOME
     let working_dir = std::env::var("WORKING_DIR").ok();
@@ -1201,26 +1920,33 @@ fn read_backend_configs() -> Option<Vec<serde_json::Value>> {
     None
 }
 
-/// Read CLI path from backend config file if available.
-fn get_claudecode_cli_path_from_config(_app_working_dir: &std::path::Path) -> Option<String> {
-    let configs = read_backend_configs()?;
-
-    for config in configs {
-        if config.get("id")?.as_str()? == "claudecode" {
-            if let Some(settings) = config.get("settings") {
-                if let Some(cli_path) = settings.get("cli_path").and_then(|v| v.as_str()) {
-                    if !cli_path.is_empty() {
-                        tracing::info!(
-                            "Using Claude Code CLI path from backend config: {}",
-                            cli_path
-                        );
-                        return Some(cli_path.to_string());
-                    }
-                }
-            }
-        }
+/// CLI settings for a backend from the backend config file, with the
+/// workspace override and the mission's profile applied.
+fn get_cli_profile_from_config(
+    backend_id: &str,
+    workspace_id: Uuid,
+    profile: Option<&str>,
+) -> Result<crate::backend_config::CliProfile, String> {
+    let settings = read_backend_configs()
+        .and_then(|configs| {
+            configs
+                .into_iter()
+                .find(|config| config.get("id").and_then(|v| v.as_str()) == Some(backend_id))
+        })
+        .and_then(|config| config.get("settings").cloned())
+        .unwrap_or(serde_json::Value::Null);
+    let resolved =
+        crate::backend_config::CliProfile::resolve(&settings, Some(workspace_id), profile)
+            .map_err(|e| format!("{} backend: {}", backend_id, e))?;
+    if let Some(cli_path) = &resolved.cli_path {
+        tracing::info!(
+            backend = backend_id,
+            profile = ?profile,
+            "Using CLI path from backend config: {}",
+            cli_path
+        );
     }
-    None
+    Ok(resolved)
 }
 
 /// Read API key from Amp backend config file if available.
@@ -1245,6 +1971,19 @@ pub fn get_amp_api_key_from_config() -> Option<String> {
     None
 }
 
+/// Amp sampling options and extra CLI flags from the `amp` backend settings.
+fn get_amp_cli_options_from_config() -> crate::backend::amp::AmpConfig {
+    read_backend_configs()
+        .and_then(|configs| {
+            configs
+                .into_iter()
+                .find(|config| config.get("id").and_then(|v| v.as_str()) == Some("amp"))
+        })
+        .and_then(|config| config.get("settings").cloned())
+        .map(|settings| crate::backend::amp::AmpConfig::from_settings(&settings))
+        .unwrap_or_default()
+}
+
 /// Read amp.url from Amp CLI settings file (~/.config/amp/settings.json)
 fn get_amp_url_from_settings() -> Option<String> {
     let home = std::env::var("HOME").ok()?;
@@ -1263,10 +2002,64 @@ fn get_amp_url_from_settings() -> Option<String> {
         .map(|s| s.to_string())
 }
 
+/// Message sent to the model once a turn reaches its tool-call limit.
+const TOOL_CALL_LIMIT_MESSAGE: &str = "You have reached the tool-call limit for this turn. \
+Do not call any more tools. Reply now with your final answer based on what you have done so far.";
+
+/// Counts tool calls within a single turn against an optional limit.
+#[derive(Debug, Clone, Copy)]
+struct ToolCallBudget {
+    /// Maximum tool calls allowed (`None` = unlimited, `Some(0)` = none at all).
+    max: Option<usize>,
+    count: usize,
+}
+
+impl ToolCallBudget {
+    fn new(max: Option<usize>) -> Self {
+        Self { max, count: 0 }
+    }
+
+    /// Record a tool call. Returns `false` once the call exceeds the limit.
+    fn record(&mut self) -> bool {
+        self.count += 1;
+        match self.max {
+            Some(max) => self.count <= max,
+            None => true,
+        }
+    }
+
+    fn data(&self, limit_reached: bool) -> serde_json::Value {
+        serde_json::json!({
+            "tool_calls": self.count,
+            "tool_call_limit_reached": limit_reached,
+        })
+    }
+}
+
+/// CLI arguments selecting the Claude Code session: `--resume` continues the
+/// stored session, `--session-id` creates it on the first turn.
+fn claudecode_session_args(session_id: &str, is_continuation: bool) -> [String; 2] {
+    let flag = if is_continuation {
+        "--resume"
+    } else {
+        "--session-id"
+    };
+    [flag.to_string(), session_id.to_string()]
+}
+
+/// Whether Claude Code output says the session to resume does not exist.
+fn is_resume_rejected(output: &str) -> bool {
+    output.contains("No conversation found")
+}
+
 /// Execute a turn using Claude Code CLI backend.
 ///
 /// For Host workspaces: spawns the CLI directly on the host.
 /// For Container workspaces: spawns the CLI inside the container using systemd-nspawn.
+///
+/// `max_tool_calls` bounds the tool calls of the turn (`None` = unlimited). Once
+/// exceeded, the CLI is stopped and the session resumed with an instruction to
+/// answer without tools (`Some(0)`).
 pub fn run_claudecode_turn<'a>(
     workspace: &'a Workspace,
     work_dir: &'a std::path::Path,
@@ -1274,13 +2067,15 @@ pub fn run_claudecode_turn<'a>(
     model: Option<&'a str>,
     agent: Option<&'a str>,
     mission_id: Uuid,
-    events_tx: broadcast::Sender<AgentEvent>,
+    events_tx: EventSender,
     cancel: CancellationToken,
     secrets: Option<Arc<SecretsStore>>,
     app_working_dir: &'a std::path::Path,
     session_id: Option<&'a str>,
     is_continuation: bool,
     tool_hub: Option<Arc<FrontendToolHub>>,
+    max_tool_calls: Option<usize>,
+    profile: Option<&'a str>,
 ) -> std::pin::Pin<Box<dyn std::future::Future<Output = AgentResult> + Send + 'a>> {
     Box::pin(async move {
         use super::ai_providers::{
@@ -1289,16 +2084,18 @@ pub fn run_claudecode_turn<'a>(
             get_workspace_auth_path, refresh_workspace_anthropic_auth,
             write_claudecode_credentials_for_workspace, ClaudeCodeAuth,
         };
+        use crate::backend::claudecode::auth::AuthStrategies;
         use std::collections::HashMap;
         use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
 
-        fn classify_claudecode_secret(value: String) -> ClaudeCodeAuth {
-            if value.starts_with("sk-ant-oat") {
+        let auth_strategies = AuthStrategies::from_env();
+        let classify_claudecode_secret = |value: String| -> ClaudeCodeAuth {
+            if auth_strategies.detect(&value).is_oauth {
                 ClaudeCodeAuth::OAuthToken(value)
             } else {
                 ClaudeCodeAuth::ApiKey(value)
             }
-        }
+        };
 
         // Ensure OAuth tokens are fresh before resolving credentials.
         let oauth_refresh_result = ensure_anthropic_oauth_token_valid().await;
@@ -1475,7 +2272,7 @@ pub fn run_claudecode_turn<'a>(
             let err_msg = "No Anthropic credentials detected; please authenticate in Settings → AI Providers or set CLAUDE_CODE_OAUTH_TOKEN/ANTHROPIC_API_KEY.";
             tracing::warn!(mission_id = %mission_id, "{}", err_msg);
             return AgentResult::failure(err_msg.to_string(), 0)
-                .with_terminal_reason(TerminalReason::LlmError);
+                .with_terminal_reason(TerminalReason::AuthMissing);
         }
 
         // Write Claude Code credentials file with refresh token for long-running missions.
@@ -1510,14 +2307,24 @@ pub fn run_claudecode_turn<'a>(
             }
         }
 
-        // Determine CLI path: prefer backend config, then env var, then default
-        let cli_path = get_claudecode_cli_path_from_config(app_working_dir)
+        // Determine CLI path: prefer backend config (with the workspace override
+        // and mission profile), then env var, then default
+        let cli_profile = match get_cli_profile_from_config("claudecode", workspace.id, profile) {
+            Ok(cli_profile) => cli_profile,
+            Err(err_msg) => {
+                return AgentResult::failure(err_msg, 0)
+                    .with_terminal_reason(TerminalReason::BackendUnavailable);
+            }
+        };
+        let cli_path = cli_profile
+            .cli_path
+            .clone()
             .or_else(|| std::env::var("CLAUDE_CLI_PATH").ok())
             .unwrap_or_else(|| "claude".to_string());
 
         // Use stored session_id for conversation persistence.
-        // If session_id is None (legacy mission), generate a new one but warn that continuation
-        // won't work correctly since the generated ID isn't persisted back to the mission store.
+        // If session_id is None (legacy mission), generate a new one and persist it so
+        // later turns can resume this session.
         let session_id = match session_id {
             Some(id) => id.to_string(),
             None => {
@@ -1525,8 +2332,12 @@ pub fn run_claudecode_turn<'a>(
                 tracing::warn!(
                     mission_id = %mission_id,
                     generated_session_id = %generated,
-                    "Mission has no stored session_id (legacy mission). Generated temporary ID, but conversation continuation will not work correctly. Consider recreating the mission."
+                    "Mission has no stored session_id (legacy mission). Generated a new one"
                 );
+                let _ = events_tx.send(AgentEvent::SessionIdUpdate {
+                    session_id: generated.clone(),
+                    mission_id,
+                });
                 generated
             }
         };
@@ -1538,7 +2349,7 @@ pub fn run_claudecode_turn<'a>(
                 Err(err_msg) => {
                     tracing::error!("{}", err_msg);
                     return AgentResult::failure(err_msg, 0)
-                        .with_terminal_reason(TerminalReason::LlmError);
+                        .with_terminal_reason(TerminalReason::CliMissing);
                 }
             };
 
@@ -1602,31 +2413,27 @@ pub fn run_claudecode_turn<'a>(
             args.push(m.to_string());
         }
 
-        // For continuation turns, use --resume to resume existing session
-        // For first turn, use --session-id to create new session with that ID
-        if is_continuation {
-            args.push("--resume".to_string());
-            args.push(session_id.clone());
-            tracing::debug!(
-                mission_id = %mission_id,
-                session_id = %session_id,
-                "Resuming existing Claude Code session"
-            );
-        } else {
-            args.push("--session-id".to_string());
-            args.push(session_id.clone());
-            tracing::debug!(
-                mission_id = %mission_id,
-                session_id = %session_id,
-                "Starting new Claude Code session"
-            );
-        }
+        args.extend(claudecode_session_args(&session_id, is_continuation));
+        tracing::debug!(
+            mission_id = %mission_id,
+            session_id = %session_id,
+            is_continuation,
+            "Claude Code session"
+        );
 
         if let Some(a) = agent {
             args.push("--agent".to_string());
             args.push(a.to_string());
         }
 
+        // Tool-call limit reached: allow a single response without tool use.
+        if max_tool_calls == Some(0) {
+            args.push("--max-turns".to_string());
+            args.push("1".to_string());
+        }
+
+        args.extend(cli_profile.extra_args.iter().cloned());
+
         // Build environment variables
         let mut env: HashMap<String, String> = HashMap::new();
         // Allow --dangerously-skip-permissions when running as root inside containers.
@@ -1648,8 +2455,12 @@ pub fn run_claudecode_turn<'a>(
                     }
                 }
                 ClaudeCodeAuth::ApiKey(key) => {
-                    env.insert("ANTHROPIC_API_KEY".to_string(), key.clone());
-                    tracing::debug!("Using API key for Claude CLI authentication");
+                    let strategy = auth_strategies.detect(key);
+                    env.extend(strategy.env(key));
+                    tracing::debug!(
+                        "Using {} credentials for Claude CLI authentication",
+                        strategy.name
+                    );
                 }
             }
         } else {
@@ -1735,7 +2546,11 @@ pub fn run_claudecode_turn<'a>(
 
         // Track tool calls for result mapping
         let mut pending_tools: HashMap<String, String> = HashMap::new();
+        let mut tool_budget = ToolCallBudget::new(max_tool_calls);
+        let mut tool_limit_stopped = false;
         let mut total_cost_usd = 0.0f64;
+        let mut usage: Option<crate::cost::TokenUsage> = None;
+        let mut streamed_usage = StreamedUsage::default();
         let mut final_result = String::new();
         let mut had_error = false;
 
@@ -1867,6 +2682,9 @@ pub fn run_claudecode_turn<'a>(
                                     }
                                 }
                                 ClaudeEvent::Assistant(evt) => {
+                                    if let Some(update) = streamed_usage.add(&evt.message, model, mission_id) {
+                                        let _ = events_tx.send(update);
+                                    }
                                     for block in evt.message.content {
                                         match block {
                                             ContentBlock::Text { text } => {
@@ -1876,6 +2694,15 @@ pub fn run_claudecode_turn<'a>(
                                                     final_result = text;
                                                 }
                                             }
+                                            ContentBlock::ToolUse { .. } if !tool_budget.record() => {
+                                                // Stop the CLI before it runs the tool.
+                                                let _ = child.kill().await;
+                                                if let Some(handle) = stderr_handle.take() {
+                                                    handle.abort();
+                                                }
+                                                tool_limit_stopped = true;
+                                                break;
+                                            }
                                             ContentBlock::ToolUse { id, name, input } => {
                                                 pending_tools.insert(id.clone(), name.clone());
                                                 let _ = events_tx.send(AgentEvent::ToolCall {
@@ -1945,6 +2772,8 @@ pub fn run_claudecode_turn<'a>(
                                                             Some(&session_id),
                                                             true,
                                                             tool_hub,
+                                                            max_tool_calls.map(|max| max.saturating_sub(tool_budget.count)),
+                                                            profile,
                                                         ).await;
                                                     }
                                                 }
@@ -1964,6 +2793,14 @@ pub fn run_claudecode_turn<'a>(
                                             _ => {}
                                         }
                                     }
+                                    if tool_limit_stopped {
+                                        tracing::warn!(
+                                            mission_id = %mission_id,
+                                            tool_calls = tool_budget.count,
+                                            "Tool-call limit reached, stopping turn"
+                                        );
+                                        break;
+                                    }
                                 }
                                 ClaudeEvent::User(evt) => {
                                     for block in evt.message.content {
@@ -1976,16 +2813,11 @@ pub fn run_claudecode_turn<'a>(
                                             // Convert content to string representation (handles both text and image results)
                                             let content_str = content.to_string_lossy();
 
-                                            let result_value = if let Some(ref extra) = evt.tool_use_result {
-                                                serde_json::json!({
-                                                    "content": content_str,
-                                                    "stdout": extra.stdout,
-                                                    "stderr": extra.stderr,
-                                                    "is_error": is_error,
-                                                })
-                                            } else {
-                                                serde_json::Value::String(content_str)
-                                            };
+                                            let result_value = crate::backend::shared::tool_result_payload(
+                                                content_str,
+                                                is_error,
+                                                evt.tool_use_result.as_ref(),
+                                            );
 
                                             let _ = events_tx.send(AgentEvent::ToolResult {
                                                 tool_call_id: tool_use_id,
@@ -2000,6 +2832,9 @@ pub fn run_claudecode_turn<'a>(
                                     if let Some(cost) = res.total_cost_usd {
                                         total_cost_usd = cost;
                                     }
+                                    if let Some(reported) = &res.usage {
+                                        usage = Some(reported.token_usage());
+                                    }
                                     // Check for errors: explicit error flags OR result text that looks like an API error
                                     let result_text = res.result.clone().unwrap_or_default();
                                     let looks_like_api_error = result_text.starts_with("API Error:")
@@ -2040,6 +2875,72 @@ pub fn run_claudecode_turn<'a>(
             }
         }
 
+        // The stored session is gone (e.g. CLI state was wiped): start a new one.
+        if is_continuation
+            && had_error
+            && (is_resume_rejected(&final_result)
+                || is_resume_rejected(&stderr_capture.lock().await))
+        {
+            let _ = child.wait().await;
+            let new_session_id = Uuid::new_v4().to_string();
+            tracing::warn!(
+                mission_id = %mission_id,
+                old_session_id = %session_id,
+                new_session_id = %new_session_id,
+                "Claude Code rejected session resume, starting a new session"
+            );
+            let _ = events_tx.send(AgentEvent::SessionIdUpdate {
+                session_id: new_session_id.clone(),
+                mission_id,
+            });
+            return run_claudecode_turn(
+                workspace,
+                work_dir,
+                message,
+                model,
+                agent,
+                mission_id,
+                events_tx,
+                cancel,
+                secrets,
+                app_working_dir,
+                Some(&new_session_id),
+                false,
+                tool_hub,
+                max_tool_calls,
+                profile,
+            )
+            .await;
+        }
+
+        // Over the limit: resume once, asking for a final answer without tools.
+        if tool_limit_stopped && max_tool_calls != Some(0) {
+            let _ = child.wait().await;
+            let result = run_claudecode_turn(
+                workspace,
+                work_dir,
+                TOOL_CALL_LIMIT_MESSAGE,
+                model,
+                agent,
+                mission_id,
+                events_tx,
+                cancel,
+                secrets,
+                app_working_dir,
+                Some(&session_id),
+                true,
+                tool_hub,
+                Some(0),
+                profile,
+            )
+            .await;
+            let mut data = tool_budget.data(true);
+            if let Some(resumed) = result.data.as_ref().and_then(|d| d.get("session_id")) {
+                data["session_id"] = resumed.clone();
+            }
+            return result.with_data(data);
+        }
+
         // Wait for child process to finish and clean up
         let exit_status = child.wait().await;
 
@@ -2069,6 +2970,13 @@ pub fn run_claudecode_turn<'a>(
             );
         }
 
+        if tool_limit_stopped && final_result.trim().is_empty() {
+            final_result = format!(
+                "Stopped: the tool-call limit was reached ({} calls) and no final answer was produced.",
+                tool_budget.count
+            );
+        }
+
         if final_result.trim().is_empty() && !had_error {
             had_error = true;
             // Include stderr in error message if available
@@ -2100,35 +3008,23 @@ pub fn run_claudecode_turn<'a>(
             }
         }
 
-        if had_error {
+        let result = if had_error {
             AgentResult::failure(final_result, cost_cents)
                 .with_terminal_reason(TerminalReason::LlmError)
         } else {
             AgentResult::success(final_result, cost_cents)
                 .with_terminal_reason(TerminalReason::Completed)
-        }
+        };
+        let result = match usage {
+            Some(usage) => result.with_usage(usage),
+            None => result,
+        };
+        let mut data = tool_budget.data(tool_limit_stopped);
+        data["session_id"] = serde_json::json!(session_id);
+        result.with_data(data)
     }) // end Box::pin(async move { ... })
 }
 
-/// Read CLI path for opencode from backend config file if available.
-fn get_opencode_cli_path_from_config(_app_working_dir: &std::path::Path) -> Option<String> {
-    let configs = read_backend_configs()?;
-
-    for config in configs {
-        if config.get("id")?.as_str()? == "opencode" {
-            if let Some(settings) = config.get("settings") {
-                if let Some(cli_path) = settings.get("cli_path").and_then(|v| v.as_str()) {
-                    if !cli_path.is_empty() {
-                        tracing::info!("Using OpenCode CLI path from backend config: {}", cli_path);
-                        return Some(cli_path.to_string());
-                    }
-                }
-            }
-        }
-    }
-    None
-}
-
 fn get_opencode_permissive_from_config(_app_working_dir: &std::path::Path) -> Option<bool> {
     let configs = read_backend_configs()?;
 
@@ -2341,36 +3237,6 @@ fn extract_opencode_session_id(output: &str) -> Option<String> {
     None
 }
 
-fn opencode_output_needs_fallback(output: &str) -> bool {
-    let cleaned = strip_ansi_codes(output);
-    let mut lines: Vec<String> = cleaned
-        .lines()
-        .map(|line| line.trim().to_string())
-        .filter(|line| !line.is_empty())
-        .collect();
-
-    if lines.is_empty() {
-        return true;
-    }
-
-    for line in lines.drain(..) {
-        let lower = line.to_lowercase();
-        let is_banner = lower.contains("starting opencode server")
-            || lower.contains("opencode server started")
-            || lower.contains("sending prompt")
-            || lower.contains("waiting for completion")
-            || lower.contains("all tasks completed")
-            || lower.contains("completed")
-            || lower.contains("session id:")
-            || lower.contains("session:");
-        if !is_banner {
-            return false;
-        }
-    }
-
-    true
-}
-
 fn allocate_opencode_server_port() -> Option<u16> {
     std::net::TcpListener::bind("127.0.0.1:0")
         .ok()
@@ -3756,6 +4622,24 @@ fn apply_opencode_auth_env(
 struct StoredOpenCodeMessage {
     parts: Vec<serde_json::Value>,
     model: Option<String>,
+    usage: Option<crate::cost::TokenUsage>,
+}
+
+/// Token usage recorded on a stored OpenCode message
+/// (`tokens: { input, output, cache: { read, write } }`).
+fn extract_usage_from_message(value: &serde_json::Value) -> Option<crate::cost::TokenUsage> {
+    let tokens = value.get("tokens")?;
+    let count = |v: Option<&serde_json::Value>| v.and_then(|v| v.as_u64());
+    let cache = tokens.get("cache");
+    let usage = crate::cost::TokenUsage {
+        input_tokens: count(tokens.get("input")).unwrap_or(0),
+        // Reasoning tokens are billed as output.
+        output_tokens: count(tokens.get("output")).unwrap_or(0)
+            + count(tokens.get("reasoning")).unwrap_or(0),
+        cache_creation_input_tokens: count(cache.and_then(|c| c.get("write"))).filter(|n| *n > 0),
+        cache_read_input_tokens: count(cache.and_then(|c| c.get("read"))).filter(|n| *n > 0),
+    };
+    usage.has_usage().then_some(usage)
 }
 
 fn extract_model_from_message(value: &serde_json::Value) -> Option<String> {
@@ -3821,6 +4705,7 @@ fn load_latest_opencode_assistant_message(
     let mut latest_time = 0i64;
     let mut latest_message_id: Option<String> = None;
     let mut latest_model: Option<String> = None;
+    let mut latest_usage: Option<crate::cost::TokenUsage> = None;
 
     let entries = std::fs::read_dir(&message_dir).ok()?;
     for entry in entries.flatten() {
@@ -3846,6 +4731,7 @@ fn load_latest_opencode_assistant_message(
                 .and_then(|v| v.as_str())
                 .map(|s| s.to_string());
             latest_model = extract_model_from_message(&value);
+            latest_usage = extract_usage_from_message(&value);
         }
     }
 
@@ -3895,6 +4781,7 @@ fn load_latest_opencode_assistant_message(
     Some(StoredOpenCodeMessage {
         parts,
         model: latest_model,
+        usage: latest_usage,
     })
 }
 
@@ -3995,15 +4882,14 @@ async fn command_available(
         if program.contains('/') {
             return std::path::Path::new(program).is_file();
         }
-        if let Ok(path_var) = std::env::var("PATH") {
-            for dir in path_var.split(':') {
-                if dir.is_empty() {
-                    continue;
-                }
-                let candidate = std::path::Path::new(dir).join(program);
-                if candidate.is_file() {
-                    return true;
-                }
+        let path_var = workspace_exec.search_path(cwd, &workspace_exec.workspace.env_vars);
+        for dir in path_var.split(':') {
+            if dir.is_empty() {
+                continue;
+            }
+            let candidate = std::path::Path::new(dir).join(program);
+            if candidate.is_file() {
+                return true;
             }
         }
         return false;
@@ -4342,9 +5228,10 @@ pub async fn run_opencode_turn(
     model: Option<&str>,
     agent: Option<&str>,
     mission_id: Uuid,
-    events_tx: broadcast::Sender<AgentEvent>,
+    events_tx: EventSender,
     cancel: CancellationToken,
     app_working_dir: &std::path::Path,
+    profile: Option<&str>,
 ) -> AgentResult {
     use super::ai_providers::{
         ensure_anthropic_oauth_token_valid, ensure_google_oauth_token_valid,
@@ -4359,7 +5246,7 @@ pub async fn run_opencode_turn(
     let workspace_exec = WorkspaceExec::new(workspace.clone());
     if let Err(err) = ensure_opencode_cli_available(&workspace_exec, work_dir).await {
         tracing::error!("{}", err);
-        return AgentResult::failure(err, 0).with_terminal_reason(TerminalReason::LlmError);
+        return AgentResult::failure(err, 0).with_terminal_reason(TerminalReason::CliMissing);
     }
 
     let opencode_config_dir_host = work_dir.join(".opencode");
@@ -4454,7 +5341,16 @@ pub async fn run_opencode_turn(
         return AgentResult::failure(err_msg, 0).with_terminal_reason(TerminalReason::LlmError);
     }
 
-    let configured_runner = get_opencode_cli_path_from_config(app_working_dir)
+    let cli_profile = match get_cli_profile_from_config("opencode", workspace.id, profile) {
+        Ok(cli_profile) => cli_profile,
+        Err(err_msg) => {
+            return AgentResult::failure(err_msg, 0)
+                .with_terminal_reason(TerminalReason::BackendUnavailable);
+        }
+    };
+    let configured_runner = cli_profile
+        .cli_path
+        .clone()
         .or_else(|| std::env::var("OPENCODE_CLI_PATH").ok());
 
     let mut runner_is_direct = false;
@@ -4468,7 +5364,8 @@ pub async fn run_opencode_turn(
                 path
             );
             tracing::error!("{}", err_msg);
-            return AgentResult::failure(err_msg, 0).with_terminal_reason(TerminalReason::LlmError);
+            return AgentResult::failure(err_msg, 0)
+                .with_terminal_reason(TerminalReason::CliMissing);
         }
     } else {
         // Prefer bunx for oh-my-opencode (avoids version conflicts from npm global installs)
@@ -4480,7 +5377,8 @@ pub async fn run_opencode_turn(
             let err_msg =
                 "No OpenCode CLI runner found in workspace (expected bunx or npx).".to_string();
             tracing::error!("{}", err_msg);
-            return AgentResult::failure(err_msg, 0).with_terminal_reason(TerminalReason::LlmError);
+            return AgentResult::failure(err_msg, 0)
+                .with_terminal_reason(TerminalReason::CliMissing);
         }
     };
 
@@ -4591,6 +5489,8 @@ pub async fn run_opencode_turn(
     args.push("--timeout".to_string());
     args.push("0".to_string());
 
+    args.extend(cli_profile.extra_args.iter().cloned());
+
     // The message is passed as the final argument
     args.push(message.to_string());
 
@@ -4733,9 +5633,12 @@ pub async fn run_opencode_turn(
 
     let mut final_result = String::new();
     let mut had_error = false;
+    // Error banner printed on stdout, used when there is no answer
+    let mut stdout_error: Option<String> = None;
     let session_id_capture: Arc<Mutex<Option<String>>> = Arc::new(Mutex::new(None));
     let sse_emitted_thinking = Arc::new(std::sync::atomic::AtomicBool::new(false));
     let sse_done_sent = Arc::new(std::sync::atomic::AtomicBool::new(false));
+    let stderr_emitted_thinking = Arc::new(std::sync::atomic::AtomicBool::new(false));
     let sse_cancel = CancellationToken::new();
 
     // oh-my-opencode doesn't support --format json, so use SSE curl for events.
@@ -4903,17 +5806,43 @@ pub async fn run_opencode_turn(
             None
         };
 
-    // Spawn a task to read stderr (just log in JSON mode, events come on stdout)
+    // Spawn a task to read stderr: the progress log. Without the SSE stream, its
+    // TOOL.EXECUTE / TOOL.RESULT lines become tool events and the remaining
+    // lines stream as thinking. The answer only ever comes from stdout/storage.
     let mission_id_clone = mission_id;
+    let stderr_events_tx = sse_handle.is_none().then(|| events_tx.clone());
+    let stderr_thinking = Arc::clone(&stderr_emitted_thinking);
     let stderr_handle = if let Some(stderr) = stderr {
         Some(tokio::spawn(async move {
             let stderr_reader = BufReader::new(stderr);
             let mut stderr_lines = stderr_reader.lines();
+            let mut state = OpencodeSseState::default();
+            let mut progress = String::new();
             while let Ok(Some(line)) = stderr_lines.next_line().await {
-                let clean = line.trim().to_string();
-                if !clean.is_empty() {
-                    tracing::debug!(mission_id = %mission_id_clone, line = %clean, "OpenCode CLI stderr");
+                let clean = strip_ansi_codes(line.trim());
+                if clean.is_empty() {
+                    continue;
+                }
+                if let Some(events_tx) = &stderr_events_tx {
+                    if let Some(event) =
+                        parse_opencode_stderr_line(&clean, &mut state, mission_id_clone)
+                    {
+                        let _ = events_tx.send(event);
+                        continue;
+                    }
+                    if !is_opencode_status_line(&clean) {
+                        progress.push_str(&clean);
+                        progress.push('\n');
+                        let _ = events_tx.send(AgentEvent::Thinking {
+                            content: progress.trim_end().to_string(),
+                            done: false,
+                            mission_id: Some(mission_id_clone),
+                        });
+                        stderr_thinking.store(true, std::sync::atomic::Ordering::SeqCst);
+                        continue;
+                    }
                 }
+                tracing::debug!(mission_id = %mission_id_clone, line = %clean, "OpenCode CLI stderr");
             }
         }))
     } else {
@@ -5022,9 +5951,25 @@ pub async fn run_opencode_turn(
                                 }
                             }
                         } else {
-                            // Non-JSON line - this is the expected output format without --format json
-                            tracing::debug!(mission_id = %mission_id, line = %trimmed, "OpenCode stdout");
-                            final_result.push_str(trimmed);
+                            // Non-JSON line - this is the expected output format without --format json.
+                            // Runner banners are progress, not part of the answer.
+                            let clean = strip_ansi_codes(trimmed);
+                            if is_opencode_status_line(&clean) {
+                                tracing::debug!(mission_id = %mission_id, line = %clean, "OpenCode stdout status");
+                                if let Some(session_id) = extract_opencode_session_id(&clean) {
+                                    session_id_capture.lock().unwrap().get_or_insert(session_id);
+                                }
+                                let lower = clean.to_lowercase();
+                                if lower.starts_with("session ended with error")
+                                    || lower.starts_with("[session.error]")
+                                {
+                                    had_error = true;
+                                    stdout_error.get_or_insert(clean);
+                                }
+                                continue;
+                            }
+                            tracing::debug!(mission_id = %mission_id, line = %clean, "OpenCode stdout");
+                            final_result.push_str(&clean);
                             final_result.push('\n');
                         }
                     }
@@ -5055,18 +6000,20 @@ pub async fn run_opencode_turn(
         if !status.success() {
             had_error = true;
             if final_result.is_empty() {
-                final_result = format!("OpenCode CLI exited with status: {}", status);
+                final_result = stdout_error
+                    .take()
+                    .unwrap_or_else(|| format!("OpenCode CLI exited with status: {}", status));
             }
         }
     }
 
     let session_id = session_id_capture.lock().unwrap().clone();
-    let session_id = session_id.or_else(|| extract_opencode_session_id(&final_result));
     let stored_message = session_id
         .as_deref()
         .and_then(|id| load_latest_opencode_assistant_message(workspace, id));
 
-    if opencode_output_needs_fallback(&final_result) {
+    // Banners were filtered out above, so no stdout text means no answer there.
+    if final_result.trim().is_empty() {
         if let Some(session_id) = session_id.as_deref() {
             if let Some(message) = stored_message.as_ref() {
                 let text = extract_text(&message.parts);
@@ -5099,6 +6046,11 @@ pub async fn run_opencode_turn(
             );
         }
     }
+    if final_result.trim().is_empty() {
+        if let Some(error) = stdout_error {
+            final_result = error;
+        }
+    }
 
     let mut emitted_thinking = false;
     let sse_emitted = sse_emitted_thinking.load(std::sync::atomic::Ordering::SeqCst);
@@ -5124,7 +6076,9 @@ pub async fn run_opencode_turn(
             done: true,
             mission_id: Some(mission_id),
         });
-    } else if sse_emitted && !sse_done_sent.load(std::sync::atomic::Ordering::SeqCst) {
+    } else if (sse_emitted || stderr_emitted_thinking.load(std::sync::atomic::Ordering::SeqCst))
+        && !sse_done_sent.load(std::sync::atomic::Ordering::SeqCst)
+    {
         let _ = events_tx.send(AgentEvent::Thinking {
             content: String::new(),
             done: true,
@@ -5145,11 +6099,24 @@ pub async fn run_opencode_turn(
         "OpenCode CLI execution completed"
     );
 
+    // OpenCode reports tokens per message; price them (OpenRouter and other
+    // providers alike) with the model's pricing.
+    let usage = stored_message.as_ref().and_then(|m| m.usage.clone());
+    let cost_cents = match (&usage, model_used.as_deref()) {
+        (Some(usage), Some(model)) => crate::cost::cost_cents_from_usage(model, usage),
+        _ => 0,
+    };
+
     let mut result = if had_error {
-        AgentResult::failure(final_result, 0).with_terminal_reason(TerminalReason::LlmError)
+        AgentResult::failure(final_result, cost_cents)
+            .with_terminal_reason(TerminalReason::LlmError)
     } else {
-        AgentResult::success(final_result, 0).with_terminal_reason(TerminalReason::Completed)
+        AgentResult::success(final_result, cost_cents)
+            .with_terminal_reason(TerminalReason::Completed)
     };
+    if let Some(usage) = usage {
+        result = result.with_usage(usage);
+    }
     if let Some(model) = model_used {
         result = result.with_model(model);
     }
@@ -5160,22 +6127,25 @@ pub async fn run_opencode_turn(
 ///
 /// For Host workspaces: spawns the CLI directly on the host.
 /// For Container workspaces: spawns the CLI inside the container using systemd-nspawn.
+///
+/// `max_tool_calls` bounds the tool calls of the turn (`None` = unlimited), see
+/// [`read_amp_stream`].
+#[allow(clippy::too_many_arguments)]
 pub async fn run_amp_turn(
     workspace: &Workspace,
     work_dir: &std::path::Path,
     message: &str,
     mode: Option<&str>,
     mission_id: Uuid,
-    events_tx: broadcast::Sender<AgentEvent>,
+    events_tx: EventSender,
     cancel: CancellationToken,
     app_working_dir: &std::path::Path,
     session_id: Option<&str>,
     is_continuation: bool,
     api_key: Option<&str>,
+    max_tool_calls: Option<usize>,
 ) -> AgentResult {
-    use crate::backend::amp::client::{AmpEvent, ContentBlock, StreamEvent};
     use std::collections::HashMap;
-    use tokio::io::{AsyncBufReadExt, BufReader};
 
     let workspace_exec = WorkspaceExec::new(workspace.clone());
 
@@ -5306,7 +6276,7 @@ pub async fn run_amp_turn(
         let err_msg = "Amp CLI not found. Install it with: bun install -g @sourcegraph/amp (or npm install -g @sourcegraph/amp)";
         tracing::error!(mission_id = %mission_id, "{}", err_msg);
         return AgentResult::failure(err_msg.to_string(), 0)
-            .with_terminal_reason(TerminalReason::LlmError);
+            .with_terminal_reason(TerminalReason::CliMissing);
     };
 
     tracing::info!(
@@ -5348,6 +6318,9 @@ pub async fn run_amp_turn(
         args.push(m.to_string());
     }
 
+    // Sampling options and extra flags from the backend settings
+    args.extend(get_amp_cli_options_from_config().cli_args());
+
     // Build environment
     let mut env = HashMap::new();
 
@@ -5390,7 +6363,7 @@ pub async fn run_amp_turn(
     );
 
     // Use WorkspaceExec to spawn the CLI
-    let mut child = match workspace_exec
+    let child = match workspace_exec
         .spawn_streaming(work_dir, &amp_binary, &args, env)
         .await
     {
@@ -5402,6 +6375,25 @@ pub async fn run_amp_turn(
         }
     };
 
+    read_amp_stream(child, mission_id, events_tx, cancel, max_tool_calls).await
+}
+
+/// Read the `--stream-json` output of a spawned Amp CLI into the turn result.
+///
+/// Unlike Claude Code, an Amp turn is not resumed once it hits `max_tool_calls`:
+/// the CLI is stopped before it runs the first call over the limit and the turn
+/// ends with the answer produced so far.
+async fn read_amp_stream(
+    mut child: tokio::process::Child,
+    mission_id: Uuid,
+    events_tx: EventSender,
+    cancel: CancellationToken,
+    max_tool_calls: Option<usize>,
+) -> AgentResult {
+    use crate::backend::amp::client::{AmpEvent, ContentBlock, StreamEvent};
+    use std::collections::HashMap;
+    use tokio::io::{AsyncBufReadExt, BufReader};
+
     // Close stdin immediately - Amp uses --execute with args, not stdin
     // Leaving the pipe open can cause issues with Node.js process lifecycle
     drop(child.stdin.take());
@@ -5449,10 +6441,7 @@ pub async fn run_amp_turn(
     let mut model_used: Option<String> = None;
 
     // Track token usage for cost calculation
-    let mut total_input_tokens: u64 = 0;
-    let mut total_output_tokens: u64 = 0;
-    let mut total_cache_creation_tokens: u64 = 0;
-    let mut total_cache_read_tokens: u64 = 0;
+    let mut streamed_usage = StreamedUsage::default();
 
     // Track content blocks for streaming
     let mut block_types: HashMap<u32, String> = HashMap::new();
@@ -5461,6 +6450,8 @@ pub async fn run_amp_turn(
     let mut last_thinking_len: usize = 0;
     let mut last_text_len: usize = 0;
     let mut thinking_streamed = false; // Track if thinking was already streamed
+    let mut tool_budget = ToolCallBudget::new(max_tool_calls);
+    let mut tool_limit_stopped = false;
 
     let reader = BufReader::new(stdout);
     let mut lines = reader.lines();
@@ -5578,11 +6569,8 @@ pub async fn run_amp_turn(
                                 }
 
                                 // Accumulate token usage for cost calculation
-                                if let Some(usage) = &evt.message.usage {
-                                    total_input_tokens += usage.input_tokens.unwrap_or(0);
-                                    total_output_tokens += usage.output_tokens.unwrap_or(0);
-                                    total_cache_creation_tokens += usage.cache_creation_input_tokens.unwrap_or(0);
-                                    total_cache_read_tokens += usage.cache_read_input_tokens.unwrap_or(0);
+                                if let Some(update) = streamed_usage.add(&evt.message, model_used.as_deref(), mission_id) {
+                                    let _ = events_tx.send(update);
                                 }
 
                                 for block in evt.message.content {
@@ -5592,6 +6580,12 @@ pub async fn run_amp_turn(
                                                 final_result = text;
                                             }
                                         }
+                                        ContentBlock::ToolUse { .. } if !tool_budget.record() => {
+                                            // Stop the CLI before it runs the tool.
+                                            let _ = child.kill().await;
+                                            tool_limit_stopped = true;
+                                            break;
+                                        }
                                         ContentBlock::ToolUse { id, name, input } => {
                                             pending_tools.insert(id.clone(), name.clone());
                                             let _ = events_tx.send(AgentEvent::ToolCall {
@@ -5622,6 +6616,14 @@ pub async fn run_amp_turn(
                                         _ => {}
                                     }
                                 }
+                                if tool_limit_stopped {
+                                    tracing::warn!(
+                                        mission_id = %mission_id,
+                                        tool_calls = tool_budget.count,
+                                        "Tool-call limit reached, stopping Amp turn"
+                                    );
+                                    break;
+                                }
                             }
                             AmpEvent::User(evt) => {
                                 for block in evt.message.content {
@@ -5633,17 +6635,11 @@ pub async fn run_amp_turn(
 
                                         let content_str = content.to_string_lossy();
 
-                                        let result_value = if let Some(ref extra) = evt.tool_use_result {
-                                            serde_json::json!({
-                                                "content": content_str,
-                                                "stdout": extra.stdout,
-                                                "stderr": extra.stderr,
-                                                "is_error": is_error,
-                                                "interrupted": extra.interrupted,
-                                            })
-                                        } else {
-                                            serde_json::json!(content_str)
-                                        };
+                                        let result_value = crate::backend::shared::tool_result_payload(
+                                            content_str,
+                                            is_error,
+                                            evt.tool_use_result.as_ref(),
+                                        );
 
                                         let _ = events_tx.send(AgentEvent::ToolResult {
                                             tool_call_id: tool_use_id,
@@ -5713,20 +6709,7 @@ pub async fn run_amp_turn(
     }
 
     // Compute cost from accumulated token usage
-    let usage = crate::cost::TokenUsage {
-        input_tokens: total_input_tokens,
-        output_tokens: total_output_tokens,
-        cache_creation_input_tokens: if total_cache_creation_tokens > 0 {
-            Some(total_cache_creation_tokens)
-        } else {
-            None
-        },
-        cache_read_input_tokens: if total_cache_read_tokens > 0 {
-            Some(total_cache_read_tokens)
-        } else {
-            None
-        },
-    };
+    let usage = streamed_usage.usage;
     let cost_cents = model_used
         .as_deref()
         .map(|m| crate::cost::cost_cents_from_usage(m, &usage))
@@ -5735,10 +6718,10 @@ pub async fn run_amp_turn(
     tracing::debug!(
         mission_id = %mission_id,
         model = ?model_used,
-        input_tokens = total_input_tokens,
-        output_tokens = total_output_tokens,
-        cache_creation_tokens = total_cache_creation_tokens,
-        cache_read_tokens = total_cache_read_tokens,
+        input_tokens = usage.input_tokens,
+        output_tokens = usage.output_tokens,
+        cache_creation_tokens = usage.cache_creation_input_tokens.unwrap_or(0),
+        cache_read_tokens = usage.cache_read_input_tokens.unwrap_or(0),
         cost_cents = cost_cents,
         "Amp cost computed from token usage"
     );
@@ -5759,6 +6742,13 @@ pub async fn run_amp_turn(
         );
     }
 
+    if tool_limit_stopped && final_result.trim().is_empty() {
+        final_result = format!(
+            "Stopped: the tool-call limit was reached ({} calls) and no final answer was produced.",
+            tool_budget.count
+        );
+    }
+
     // If result is still empty/generic, include stderr for a useful error message
     if (final_result.trim().is_empty() || final_result == "Unknown error") && !had_error {
         had_error = true;
@@ -5809,9 +6799,9 @@ pub async fn run_amp_turn(
         }
     }
 
-    // Check exit status
+    // Check exit status (a CLI stopped at the tool-call limit exits killed)
     let success = match exit_status {
-        Ok(status) => status.success() && !had_error,
+        Ok(status) => (status.success() || tool_limit_stopped) && !had_error,
         Err(e) => {
             tracing::error!(mission_id = %mission_id, error = %e, "Failed to wait for Amp process");
             false
@@ -5828,12 +6818,134 @@ pub async fn run_amp_turn(
         AgentResult::failure(final_result, cost_cents)
             .with_terminal_reason(TerminalReason::LlmError)
     };
+    if usage.has_usage() {
+        result = result.with_usage(usage);
+    }
 
     if let Some(model) = model_used {
         result = result.with_model(model);
     }
 
-    result
+    result.with_data(tool_budget.data(tool_limit_stopped))
+}
+
+/// Execute a turn on a registered backend through the [`Backend`] trait.
+///
+/// Used for backends without a dedicated CLI runner: the backend's
+/// `ExecutionEvent` stream is forwarded as `AgentEvent`s (see
+/// [`agent_event_from_execution`]) and the streamed text becomes the answer. Passing `session_id` continues that backend session.
+///
+/// [`Backend`]: crate::backend::Backend
+#[allow(clippy::too_many_arguments)]
+pub async fn run_backend_turn(
+    backend: Arc<dyn crate::backend::Backend>,
+    work_dir: &std::path::Path,
+    message: &str,
+    model: Option<&str>,
+    agent: Option<&str>,
+    session_id: Option<&str>,
+    mission_id: Uuid,
+    events_tx: EventSender,
+    cancel: CancellationToken,
+) -> AgentResult {
+    use crate::backend::events::ExecutionEvent;
+    use crate::backend::{Session, SessionConfig};
+
+    let directory = work_dir.to_string_lossy().to_string();
+    let session = match session_id {
+        Some(id) => Session {
+            id: id.to_string(),
+            directory,
+            model: model.map(str::to_string),
+            agent: agent.map(str::to_string),
+        },
+        None => match backend
+            .create_session(SessionConfig {
+                directory,
+                title: None,
+                model: model.map(str::to_string),
+                agent: agent.map(str::to_string),
+            })
+            .await
+        {
+            Ok(session) => session,
+            Err(e) => {
+                return AgentResult::failure(
+                    format!("{} failed to create a session: {}", backend.name(), e),
+                    0,
+                )
+                .with_terminal_reason(TerminalReason::BackendUnavailable);
+            }
+        },
+    };
+
+    let (mut rx, handle) = match backend.send_message_streaming(&session, message).await {
+        Ok(stream) => stream,
+        Err(e) => {
+            return AgentResult::failure(format!("{} failed to start: {}", backend.name(), e), 0)
+                .with_terminal_reason(TerminalReason::BackendUnavailable);
+        }
+    };
+
+    let mut text = String::new();
+    let mut error: Option<String> = None;
+    let mut last_thinking: Option<String> = None;
+    let mut session_id = session.id.clone();
+    loop {
+        let event = tokio::select! {
+            _ = cancel.cancelled() => {
+                handle.abort();
+                return AgentResult::failure("Cancelled".to_string(), 0)
+                    .with_terminal_reason(TerminalReason::Cancelled);
+            }
+            event = rx.recv() => event,
+        };
+        let Some(event) = event else {
+            break;
+        };
+        match &event {
+            ExecutionEvent::MessageComplete {
+                session_id: completed,
+            } => {
+                session_id = completed.clone();
+                break;
+            }
+            ExecutionEvent::Error { message } => error = Some(message.clone()),
+            ExecutionEvent::Thinking { content } => last_thinking = Some(content.clone()),
+            _ => {}
+        }
+        if let Some(event) = agent_event_from_execution(event, Some(mission_id), &mut text) {
+            let _ = events_tx.send(event);
+        }
+    }
+    if last_thinking.is_some() {
+        let _ = events_tx.send(AgentEvent::Thinking {
+            content: String::new(),
+            done: true,
+            mission_id: Some(mission_id),
+        });
+    }
+    // CLI backends report complete assistant text blocks as thinking; without
+    // streamed text, the last of them is the answer.
+    if text.trim().is_empty() && error.is_none() {
+        text = last_thinking.unwrap_or_default();
+    }
+
+    let result = match error {
+        Some(message) if text.trim().is_empty() => {
+            AgentResult::failure(message, 0).with_terminal_reason(TerminalReason::LlmError)
+        }
+        _ if text.trim().is_empty() => {
+            AgentResult::failure(format!("{} produced no output", backend.name()), 0)
+                .with_terminal_reason(TerminalReason::LlmError)
+        }
+        _ => AgentResult::success(text, 0).with_terminal_reason(TerminalReason::Completed),
+    };
+    let result = match session.model {
+        Some(model) => result.with_model(model),
+        None => result,
+    };
+    result.with_data(serde_json::json!({ "session_id": session_id }))
 }
 
 /// Compact info about a running mission (for API responses).
@@ -5852,6 +6964,14 @@ pub struct RunningMissionInfo {
     pub subtask_total: usize,
     /// Completed subtasks
     pub subtask_completed: usize,
+    /// Restored after a server restart cut its last turn short
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub interrupted: bool,
+    /// Cost and tokens so far
+    pub cost: CostLedger,
+    /// Expected deliverables, for `GET /api/control/missions/:id/deliverables`
+    #[serde(skip)]
+    pub deliverables: DeliverableSet,
 }
 
 impl From<&MissionRunner> for RunningMissionInfo {
@@ -5871,6 +6991,9 @@ impl From<&MissionRunner> for RunningMissionInfo {
             current_activity: runner.current_activity.clone(),
             subtask_total: runner.subtasks.len(),
             subtask_completed: runner.subtasks.iter().filter(|s| s.completed).count(),
+            interrupted: runner.interrupted,
+            cost: runner.cost.with_turn(&runner.turn_cost),
+            deliverables: runner.deliverables.clone(),
         }
     }
 }
@@ -5966,4 +7089,835 @@ mod tests {
         assert_eq!(prometheus_model, "openai/gpt-4o");
         assert_eq!(sisyphus_model, "openai/gpt-4o-mini");
     }
+
+    #[tokio::test]
+    async fn backend_fallback_runs_when_primary_cli_missing() {
+        use super::{backend_fallback_chain, run_with_backend_fallback};
+        use crate::agents::{AgentResult, TerminalReason};
+        use crate::backend::circuit_breaker::CircuitBreakers;
+        use crate::config::CircuitBreakerConfig;
+
+        let (events_tx, mut events_rx) = super::EventSender::channel(8);
+        let chain = backend_fallback_chain("claudecode", &["opencode".to_string()]);
+        let mut attempted = Vec::new();
+
+        let breakers = CircuitBreakers::new(CircuitBreakerConfig::default());
+        let result = run_with_backend_fallback(
+            &chain,
+            uuid::Uuid::new_v4(),
+            &events_tx,
+            &breakers,
+            |backend, is_primary| {
+                attempted.push((backend.clone(), is_primary));
+                async move {
+                    if backend == "claudecode" {
+                        AgentResult::failure("Claude Code CLI 'claude' not found".to_string(), 0)
+                            .with_terminal_reason(TerminalReason::CliMissing)
+                    } else {
+                        AgentResult::success(format!("done by {}", backend), 0)
+                    }
+                }
+            },
+        )
+        .await;
+
+        assert!(result.success);
+        assert_eq!(result.output, "done by opencode");
+        assert_eq!(
+            attempted,
+            vec![
+                ("claudecode".to_string(), true),
+                ("opencode".to_string(), false)
+            ]
+        );
+        assert!(events_rx.try_recv().is_ok());
+    }
+
+    #[tokio::test]
+    async fn backend_fallback_skipped_for_model_errors() {
+        use super::run_with_backend_fallback;
+        use crate::agents::{AgentResult, TerminalReason};
+        use crate::backend::circuit_breaker::CircuitBreakers;
+        use crate::config::CircuitBreakerConfig;
+
+        let (events_tx, _events_rx) = super::EventSender::channel(8);
+        let chain = vec!["claudecode".to_string(), "opencode".to_string()];
+        let mut calls = 0;
+
+        let breakers = CircuitBreakers::new(CircuitBreakerConfig::default());
+        let result = run_with_backend_fallback(
+            &chain,
+            uuid::Uuid::new_v4(),
+            &events_tx,
+            &breakers,
+            |_, _| {
+                calls += 1;
+                async {
+                    AgentResult::failure("rate limited".to_string(), 0)
+                        .with_terminal_reason(TerminalReason::LlmError)
+                }
+            },
+        )
+        .await;
+
+        assert!(!result.success);
+        assert_eq!(calls, 1);
+    }
+
+    #[test]
+    fn busy_missions_interleave_under_fair_scheduling() {
+        use super::{next_fair_missions, MissionRunState, MissionRunner};
+        use std::collections::HashMap;
+        use uuid::Uuid;
+
+        let a = Uuid::from_u128(1);
+        let b = Uuid::from_u128(2);
+        let mut runners = HashMap::new();
+        for id in [a, b] {
+            let mut runner =
+                MissionRunner::new(id, Uuid::nil(), None, None, None, &Default::default());
+            for _ in 0..3 {
+                runner.queue_message(Uuid::new_v4(), "turn".to_string(), None);
+            }
+            runners.insert(id, runner);
+        }
+
+        // One execution slot: run a turn for whichever mission is picked, then finish it.
+        let mut order = Vec::new();
+        while let Some(&next) = next_fair_missions(&runners, 1).first() {
+            let runner = runners.get_mut(&next).unwrap();
+            runner.begin_turn().unwrap();
+            runner.state = MissionRunState::Queued;
+            order.push(next);
+        }
+        assert_eq!(order, vec![a, b, a, b, a, b]);
+
+        // Priority acts as a weight: a priority-2 mission gets twice the turns.
+        for runner in runners.values_mut() {
+            runner.fair_pass = 0;
+            for _ in 0..6 {
+                runner.queue_message(Uuid::new_v4(), "turn".to_string(), None);
+            }
+        }
+        runners.get_mut(&a).unwrap().priority = 2;
+        let mut a_turns = 0;
+        for _ in 0..6 {
+            let next = next_fair_missions(&runners, 1)[0];
+            let runner = runners.get_mut(&next).unwrap();
+            runner.begin_turn().unwrap();
+            runner.state = MissionRunState::Queued;
+            if next == a {
+                a_turns += 1;
+            }
+        }
+        assert_eq!(a_turns, 4);
+
+        // A mission joining now starts level with the others instead of at 0.
+        let c = Uuid::from_u128(3);
+        let mut runner = MissionRunner::new(c, Uuid::nil(), None, None, None, &Default::default());
+        runner.fair_pass = super::starting_fair_pass(&runners);
+        for _ in 0..2 {
+            runner.queue_message(Uuid::new_v4(), "turn".to_string(), None);
+        }
+        runners.insert(c, runner);
+        let mut order = Vec::new();
+        for _ in 0..3 {
+            let next = next_fair_missions(&runners, 1)[0];
+            let runner = runners.get_mut(&next).unwrap();
+            runner.begin_turn().unwrap();
+            runner.state = MissionRunState::Queued;
+            order.push(next);
+        }
+        assert_eq!(order, vec![a, b, c]);
+    }
+
+    #[test]
+    fn test_tool_call_budget_halts_endless_tool_calls() {
+        use super::ToolCallBudget;
+
+        // A model that calls a tool on every step never stops by itself.
+        let mut budget = ToolCallBudget::new(Some(3));
+        let accepted = std::iter::repeat("run_command")
+            .take_while(|_| budget.record())
+            .count();
+        assert_eq!(accepted, 3);
+        assert_eq!(budget.count, 4);
+        assert_eq!(budget.data(true)["tool_calls"], 4);
+
+        // After the limit message, any further tool call is refused.
+        let mut wrap_up = ToolCallBudget::new(Some(0));
+        assert!(!wrap_up.record());
+
+        let mut unlimited = ToolCallBudget::new(None);
+        assert!((0..1000).all(|_| unlimited.record()));
+    }
+
+    #[tokio::test]
+    async fn test_amp_turn_stops_cli_at_tool_call_limit() {
+        use super::{read_amp_stream, AgentEvent, EventSender};
+        use tokio_util::sync::CancellationToken;
+        use uuid::Uuid;
+
+        // A fake CLI that calls a tool on every step and never finishes by itself.
+        let script = r#"while true; do echo '{"type":"assistant","message":{"content":[{"type":"tool_use","id":"toolu_1","name":"Bash","input":{"command":"ls"}}],"stop_reason":"tool_use"},"session_id":"T-1"}'; done"#;
+        let child = tokio::process::Command::new("/bin/sh")
+            .args(["-c", script])
+            .stdin(std::process::Stdio::null())
+            .stdout(std::process::Stdio::piped())
+            .stderr(std::process::Stdio::piped())
+            .spawn()
+            .unwrap();
+
+        let (events_tx, mut events_rx) = EventSender::channel(16);
+        let result = tokio::time::timeout(
+            std::time::Duration::from_secs(10),
+            read_amp_stream(
+                child,
+                Uuid::new_v4(),
+                events_tx,
+                CancellationToken::new(),
+                Some(2),
+            ),
+        )
+        .await
+        .expect("the turn must stop at the tool-call limit");
+
+        assert!(result.success);
+        assert!(result
+            .output
+            .contains("tool-call limit was reached (3 calls)"));
+        let data = result.data.unwrap();
+        assert_eq!(data["tool_calls"], 3);
+        assert_eq!(data["tool_call_limit_reached"], true);
+
+        let mut tool_calls = 0;
+        while let Ok(stamped) = events_rx.try_recv() {
+            if matches!(stamped.event, AgentEvent::ToolCall { .. }) {
+                tool_calls += 1;
+            }
+        }
+        assert_eq!(tool_calls, 2);
+    }
+
+    #[tokio::test]
+    async fn test_second_claudecode_turn_resumes_stored_session() {
+        use super::{claudecode_session_args, is_resume_rejected, AgentResult, MissionRunner};
+        use uuid::Uuid;
+
+        let mut runner = MissionRunner::new(
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            None,
+            Some("claudecode".to_string()),
+            None,
+            &Default::default(),
+        );
+        // First turn: the backend reports the session it created.
+        runner.running_handle = Some(tokio::spawn(async {
+            let result = AgentResult::success("done", 0)
+                .with_data(serde_json::json!({ "session_id": "sess-1" }));
+            (Uuid::new_v4(), "first".to_string(), result)
+        }));
+        while runner.poll_completion().await.is_none() {
+            tokio::task::yield_now().await;
+        }
+        assert_eq!(runner.session_id.as_deref(), Some("sess-1"));
+
+        let is_continuation = runner.history.iter().any(|(role, _)| role == "assistant");
+        assert_eq!(
+            claudecode_session_args(runner.session_id.as_deref().unwrap(), is_continuation),
+            ["--resume".to_string(), "sess-1".to_string()]
+        );
+        assert_eq!(claudecode_session_args("sess-1", false)[0], "--session-id");
+        assert!(is_resume_rejected(
+            "No conversation found with session ID: sess-1"
+        ));
+    }
+
+    #[test]
+    fn test_runner_state_survives_restart() {
+        use super::{load_runner_states, MissionRunState, MissionRunner};
+        use uuid::Uuid;
+
+        let dir = tempfile::tempdir().expect("temp dir");
+        let mut queued = MissionRunner::new(
+            Uuid::new_v4(),
+            Uuid::nil(),
+            None,
+            None,
+            None,
+            &Default::default(),
+        );
+        queued.queue_message(Uuid::new_v4(), "next step".to_string(), None);
+        queued
+            .history
+            .push(("user".to_string(), "first step".to_string()));
+        queued.save_state(dir.path()).expect("save queued");
+
+        let mut running = MissionRunner::new(
+            Uuid::new_v4(),
+            Uuid::nil(),
+            None,
+            Some("claudecode".to_string()),
+            Some("sess-1".to_string()),
+            &Default::default(),
+        );
+        running.state = MissionRunState::Running;
+        running.save_state(dir.path()).expect("save running");
+        fs::write(dir.path().join("notes.json"), "{}").expect("write stray file");
+
+        let restored = load_runner_states(dir.path(), &Default::default());
+        assert_eq!(restored.len(), 2);
+        let queued_back = restored
+            .iter()
+            .find(|r| r.mission_id == queued.mission_id)
+            .expect("queued runner restored");
+        assert_eq!(queued_back.state, MissionRunState::Queued);
+        assert_eq!(queued_back.queue.len(), 1);
+        assert_eq!(queued_back.queue[0].content, "next step");
+        assert_eq!(queued_back.history, queued.history);
+        assert!(!queued_back.interrupted);
+
+        let running_back = restored
+            .iter()
+            .find(|r| r.mission_id == running.mission_id)
+            .expect("running runner restored");
+        assert_eq!(running_back.state, MissionRunState::Finished);
+        assert!(running_back.interrupted);
+        assert!(running_back.current_activity.is_some());
+        assert_eq!(running_back.backend_id, "claudecode");
+        assert_eq!(running_back.session_id.as_deref(), Some("sess-1"));
+    }
+
+    #[test]
+    fn test_started_turn_is_not_replayed_after_restart() {
+        use super::{load_runner_states, MissionRunState, MissionRunner};
+        use uuid::Uuid;
+
+        let dir = tempfile::tempdir().expect("temp dir");
+        let mut runner = MissionRunner::new(
+            Uuid::new_v4(),
+            Uuid::nil(),
+            None,
+            None,
+            None,
+            &Default::default(),
+        );
+        runner.queue_message(Uuid::new_v4(), "build it".to_string(), None);
+        // What start_next does before the control actor persists the runner.
+        runner.begin_turn().expect("queued message");
+        runner.save_state(dir.path()).expect("save state");
+
+        let restored = load_runner_states(dir.path(), &Default::default());
+        assert_eq!(restored[0].state, MissionRunState::Finished);
+        assert!(restored[0].interrupted);
+        assert!(restored[0].queue.is_empty(), "the turn must not run again");
+    }
+
+    #[tokio::test]
+    async fn test_cost_ledger_accumulates_turns() {
+        use super::{extract_usage_from_message, AgentResult, MissionRunner, RunningMissionInfo};
+        use crate::backend::shared::CliEvent;
+        use uuid::Uuid;
+
+        // Claude Code reports the run's usage on its result event.
+        let line = r#"{"type":"result","subtype":"success","session_id":"s","total_cost_usd":0.12,"usage":{"input_tokens":900,"cache_read_input_tokens":100,"output_tokens":250}}"#;
+        let CliEvent::Result(res) = serde_json::from_str(line).expect("parse result") else {
+            panic!("expected a result event");
+        };
+        let claude_turn =
+            AgentResult::success("done", 12).with_usage(res.usage.expect("usage").token_usage());
+
+        // OpenCode stores it on the assistant message.
+        let message = serde_json::json!({
+            "role": "assistant",
+            "tokens": { "input": 400, "output": 50, "reasoning": 10, "cache": { "read": 0, "write": 0 } }
+        });
+        let opencode_turn = AgentResult::success("done", 3)
+            .with_usage(extract_usage_from_message(&message).expect("opencode usage"));
+
+        let dir = tempfile::tempdir().expect("temp dir");
+        let mut runner = MissionRunner::new(
+            Uuid::new_v4(),
+            Uuid::nil(),
+            None,
+            None,
+            None,
+            &Default::default(),
+        );
+        for result in [
+            claude_turn,
+            opencode_turn,
+            AgentResult::failure("no usage", 1),
+        ] {
+            runner.running_handle = Some(tokio::spawn(async move {
+                (Uuid::new_v4(), "step".to_string(), result)
+            }));
+            while runner.poll_completion().await.is_none() {
+                tokio::task::yield_now().await;
+            }
+        }
+
+        let info = RunningMissionInfo::from(&runner);
+        assert_eq!(info.cost.cost_cents, 16);
+        assert_eq!(info.cost.prompt_tokens, 1400);
+        assert_eq!(info.cost.completion_tokens, 310);
+        assert_eq!(info.cost.turns, 3);
+
+        runner.save_state(dir.path()).expect("save state");
+        let restored = super::load_runner_states(dir.path(), &Default::default());
+        assert_eq!(restored[0].cost, runner.cost);
+    }
+
+    #[tokio::test]
+    async fn test_running_info_carries_deliverables_for_the_status_report() {
+        use super::{MissionRunner, RunningMissionInfo};
+        use crate::task::{Deliverable, DeliverableSet};
+        use uuid::Uuid;
+
+        let dir = tempfile::tempdir().unwrap();
+        let mut runner = MissionRunner::new(
+            Uuid::new_v4(),
+            Uuid::nil(),
+            None,
+            None,
+            None,
+            &Default::default(),
+        );
+        runner.deliverables = DeliverableSet {
+            deliverables: vec![Deliverable::File {
+                path: dir.path().join("out.csv"),
+                description: None,
+            }],
+            ..Default::default()
+        };
+
+        let info = RunningMissionInfo::from(&runner);
+        assert!(serde_json::to_value(&info)
+            .unwrap()
+            .get("deliverables")
+            .is_none());
+        let report = crate::tools::mission::deliverable_status_report(&info.deliverables).await;
+        assert_eq!(
+            report["deliverables"][0]["exists"],
+            serde_json::json!(false)
+        );
+        assert_eq!(report["all_complete"], serde_json::json!(false));
+    }
+
+    #[tokio::test]
+    async fn test_streamed_usage_counts_before_the_turn_ends() {
+        use super::{
+            AgentResult, CostLedger, MissionRunState, MissionRunner, RunningMissionInfo,
+            StreamedUsage,
+        };
+        use crate::backend::shared::CliEvent;
+        use uuid::Uuid;
+
+        let mission_id = Uuid::new_v4();
+        let mut runner = MissionRunner::new(
+            mission_id,
+            Uuid::nil(),
+            None,
+            None,
+            None,
+            &Default::default(),
+        );
+        runner.state = MissionRunState::Running;
+
+        // Claude Code repeats the message usage on each content block it streams.
+        let block = |id: &str| {
+            let line = format!(
+                r#"{{"type":"assistant","session_id":"s","message":{{"id":"{id}","model":"claude-sonnet-4","content":[],"usage":{{"input_tokens":1000,"cache_read_input_tokens":500,"output_tokens":200}}}}}}"#
+            );
+            match serde_json::from_str(&line).expect("parse assistant") {
+                CliEvent::Assistant(evt) => evt.message,
+                _ => panic!("expected an assistant event"),
+            }
+        };
+        let mut streamed = StreamedUsage::default();
+        for message in [block("m1"), block("m1"), block("m2")] {
+            if let Some(update) = streamed.add(&message, None, mission_id) {
+                runner.record_event(&update);
+            }
+        }
+        let live = RunningMissionInfo::from(&runner).cost;
+        assert_eq!((live.prompt_tokens, live.completion_tokens), (3000, 400));
+        assert!(live.cost_cents > 0);
+        assert_eq!(live.turns, 0);
+
+        // The reported result replaces the streamed estimate when the turn ends.
+        runner.running_handle = Some(tokio::spawn(async move {
+            (
+                Uuid::new_v4(),
+                "step".to_string(),
+                AgentResult::success("done", 7),
+            )
+        }));
+        while runner.poll_completion().await.is_none() {
+            tokio::task::yield_now().await;
+        }
+        assert_eq!(runner.turn_cost, CostLedger::default());
+        assert_eq!(RunningMissionInfo::from(&runner).cost.cost_cents, 7);
+
+        // A late update from the finished turn is ignored.
+        if let Some(update) = streamed.add(&block("m3"), None, mission_id) {
+            runner.record_event(&update);
+        }
+        assert_eq!(runner.turn_cost, CostLedger::default());
+    }
+
+    #[test]
+    fn test_opencode_stderr_tool_events_are_paired_by_id() {
+        use super::{parse_opencode_stderr_line, AgentEvent, OpencodeSseState};
+        use uuid::Uuid;
+
+        let mission_id = Uuid::new_v4();
+        let mut state = OpencodeSseState::default();
+        let mut parse = |line: &str| parse_opencode_stderr_line(line, &mut state, mission_id);
+
+        let lines = [
+            "\x1b[36mTOOL.EXECUTE:\x1b[0m {\"callID\":\"call_1\",\"name\":\"bash\",\"input\":{\"command\":\"ls\"}}",
+            r#"TOOL.EXECUTE: {"callID":"call_2","name":"read","input":{"filePath":"a.rs"}}"#,
+            r#"TOOL.RESULT: {"callID":"call_1","output":"a.rs"}"#,
+            // No id: paired with the pending call of the same tool.
+            r#"TOOL.RESULT: {"name":"read","output":"fn main() {}"}"#,
+        ];
+        let events: Vec<AgentEvent> = lines.iter().filter_map(|line| parse(line)).collect();
+        match &events[..] {
+            [AgentEvent::ToolCall {
+                tool_call_id: bash_id,
+                args,
+                ..
+            }, AgentEvent::ToolCall { .. }, AgentEvent::ToolResult {
+                tool_call_id: bash_result_id,
+                name: bash_name,
+                result,
+                ..
+            }, AgentEvent::ToolResult {
+                tool_call_id: read_result_id,
+                name: read_name,
+                ..
+            }] => {
+                assert_eq!(bash_id, "call_1");
+                assert_eq!(args["command"], "ls");
+                assert_eq!(
+                    (bash_result_id.as_str(), bash_name.as_str()),
+                    ("call_1", "bash")
+                );
+                assert_eq!(result["output"], "a.rs");
+                assert_eq!(
+                    (read_result_id.as_str(), read_name.as_str()),
+                    ("call_2", "read")
+                );
+            }
+            other => panic!("unexpected events: {:?}", other),
+        }
+
+        // Duplicates and plain log lines are ignored.
+        assert!(parse(r#"TOOL.RESULT: {"callID":"call_1","output":"a.rs"}"#).is_none());
+        assert!(parse("INFO session started").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_registered_backend_runs_a_turn_through_the_trait() {
+        use super::{run_backend_turn, AgentEvent, EventSender};
+        use crate::backend::events::ExecutionEvent;
+        use crate::backend::{AgentInfo, Backend, Session, SessionConfig};
+        use std::sync::Arc;
+        use tokio::sync::mpsc;
+        use tokio_util::sync::CancellationToken;
+        use uuid::Uuid;
+
+        struct ScriptedBackend;
+
+        #[async_trait::async_trait]
+        impl Backend for ScriptedBackend {
+            fn id(&self) -> &str {
+                "scripted"
+            }
+            fn name(&self) -> &str {
+                "Scripted"
+            }
+            async fn list_agents(&self) -> anyhow::Result<Vec<AgentInfo>> {
+                Ok(Vec::new())
+            }
+            async fn create_session(&self, config: SessionConfig) -> anyhow::Result<Session> {
+                Ok(Session {
+                    id: "sess-1".to_string(),
+                    directory: config.directory,
+                    model: config.model,
+                    agent: config.agent,
+                })
+            }
+            async fn send_message_streaming(
+                &self,
+                session: &Session,
+                _message: &str,
+            ) -> anyhow::Result<(mpsc::Receiver<ExecutionEvent>, tokio::task::JoinHandle<()>)>
+            {
+                let (tx, rx) = mpsc::channel(8);
+                let session_id = session.id.clone();
+                let handle = tokio::spawn(async move {
+                    for event in [
+                        ExecutionEvent::ToolCall {
+                            id: "t1".to_string(),
+                            name: "bash".to_string(),
+                            args: serde_json::json!({ "command": "ls" }),
+                        },
+                        ExecutionEvent::TextDelta {
+                            content: "Hello ".to_string(),
+                        },
+                        ExecutionEvent::TextDelta {
+                            content: "world".to_string(),
+                        },
+                        ExecutionEvent::MessageComplete { session_id },
+                    ] {
+                        let _ = tx.send(event).await;
+                    }
+                });
+                Ok((rx, handle))
+            }
+        }
+
+        let (events_tx, mut events_rx) = EventSender::channel(16);
+        let mission_id = Uuid::new_v4();
+        let result = run_backend_turn(
+            Arc::new(ScriptedBackend),
+            std::path::Path::new("/tmp"),
+            "say hello",
+            Some("scripted-model"),
+            None,
+            None,
+            mission_id,
+            events_tx,
+            CancellationToken::new(),
+        )
+        .await;
+        assert!(result.success);
+        assert_eq!(result.output, "Hello world");
+        assert_eq!(result.model_used.as_deref(), Some("scripted-model"));
+        assert_eq!(result.data.unwrap()["session_id"], "sess-1");
+
+        let first = events_rx.recv().await.unwrap().event;
+        assert!(matches!(
+            first,
+            AgentEvent::ToolCall { ref tool_call_id, mission_id: Some(id), .. }
+                if tool_call_id == "t1" && id == mission_id
+        ));
+    }
+
+    #[test]
+    fn test_opencode_stdout_banners_are_not_part_of_the_answer() {
+        use super::{extract_opencode_session_id, is_opencode_status_line, strip_ansi_codes};
+
+        let stdout = "\x1b[2mStarting opencode server...\x1b[0m\nSession ID: ses_abc123\nSending prompt\nThe fix is in src/lib.rs.\nAll tasks completed\n";
+        let mut answer = Vec::new();
+        let mut session_id = None;
+        for line in stdout.lines() {
+            let clean = strip_ansi_codes(line.trim());
+            if is_opencode_status_line(&clean) {
+                session_id = session_id.or_else(|| extract_opencode_session_id(&clean));
+            } else {
+                answer.push(clean);
+            }
+        }
+        assert_eq!(answer, vec!["The fix is in src/lib.rs."]);
+        assert_eq!(session_id.as_deref(), Some("ses_abc123"));
+    }
+
+    #[tokio::test]
+    async fn test_stall_threshold_depends_on_tool_in_flight() {
+        use super::{AgentEvent, LastEvent, MissionHealth, MissionRunState, MissionRunner};
+        use crate::config::MissionConfig;
+        use std::time::{Duration, Instant};
+        use uuid::Uuid;
+
+        let health = MissionConfig {
+            stall_timeout: Duration::from_secs(30),
+            tool_stall_timeout: Duration::from_secs(600),
+            ..Default::default()
+        };
+        let mut runner = MissionRunner::new(Uuid::new_v4(), Uuid::nil(), None, None, None, &health);
+        runner.state = MissionRunState::Running;
+        let quiet_for = |runner: &mut MissionRunner, secs| {
+            runner.last_activity = Instant::now()
+                .checked_sub(Duration::from_secs(secs))
+                .expect("monotonic clock past the test offset");
+        };
+
+        // No output at all since the turn started.
+        quiet_for(&mut runner, 45);
+        match runner.check_health().await {
+            MissionHealth::Stalled {
+                last_event,
+                threshold_secs,
+                ..
+            } => assert_eq!((last_event, threshold_secs), (LastEvent::None, 30)),
+            other => panic!("expected stall, got {:?}", other),
+        }
+
+        // A quiet run_command is not a stall until the tool threshold.
+        runner.record_event(&AgentEvent::ToolCall {
+            tool_call_id: "call-1".to_string(),
+            name: "run_command".to_string(),
+            args: serde_json::json!({ "command": "cargo build" }),
+            mission_id: Some(runner.mission_id),
+        });
+        quiet_for(&mut runner, 45);
+        assert!(matches!(
+            runner.check_health().await,
+            MissionHealth::Healthy
+        ));
+        quiet_for(&mut runner, 700);
+        assert!(matches!(
+            runner.check_health().await,
+            MissionHealth::Stalled {
+                last_event: LastEvent::ToolCall,
+                threshold_secs: 600,
+                ..
+            }
+        ));
+
+        // Once the tool returned, waiting on the model uses the regular threshold again.
+        runner.record_event(&AgentEvent::ToolResult {
+            tool_call_id: "call-1".to_string(),
+            name: "run_command".to_string(),
+            result: serde_json::json!("ok"),
+            mission_id: Some(runner.mission_id),
+        });
+        quiet_for(&mut runner, 45);
+        assert!(matches!(
+            runner.check_health().await,
+            MissionHealth::Stalled {
+                last_event: LastEvent::ToolResult,
+                threshold_secs: 30,
+                ..
+            }
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_mission_complete_event_reports_deliverables() {
+        use super::{mission_complete_event, AgentEvent, AgentResult};
+        use crate::task::{Deliverable, DeliverableSet};
+        use uuid::Uuid;
+
+        let dir = tempfile::tempdir().expect("temp dir");
+        let report = dir.path().join("report.md");
+        let chart = dir.path().join("chart.png");
+        fs::write(&report, "# Report").expect("write report");
+        let deliverables = DeliverableSet {
+            deliverables: vec![
+                Deliverable::File {
+                    path: report.clone(),
+                    description: None,
+                },
+                Deliverable::File {
+                    path: chart.clone(),
+                    description: None,
+                },
+            ],
+            ..Default::default()
+        };
+
+        let mission_id = Uuid::new_v4();
+        let event = mission_complete_event(
+            mission_id,
+            &AgentResult::success("done".to_string(), 42),
+            &deliverables,
+        )
+        .await;
+        match event {
+            AgentEvent::MissionComplete {
+                mission_id: id,
+                success,
+                cost_cents,
+                deliverables_present,
+                deliverables_missing,
+            } => {
+                assert_eq!((id, success, cost_cents), (mission_id, true, 42));
+                assert_eq!(deliverables_present, vec![report.display().to_string()]);
+                assert_eq!(deliverables_missing, vec![chart.display().to_string()]);
+            }
+            other => panic!("unexpected event {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_complete_mission_tool_sets_explicitly_completed() {
+        use super::{relay_mission_commands, AgentResult, MissionRunner};
+        use crate::tools::mission::{
+            CompleteMission, MissionControl, MissionControlCommand, MissionStatusValue,
+        };
+        use crate::tools::Tool;
+        use std::sync::Arc;
+        use tokio::sync::{mpsc, RwLock};
+        use uuid::Uuid;
+
+        async fn finish_turn(runner: &mut MissionRunner) -> AgentResult {
+            loop {
+                if let Some((_, _, result)) = runner.poll_completion().await {
+                    return result;
+                }
+                tokio::task::yield_now().await;
+            }
+        }
+
+        let dir = tempfile::tempdir().expect("temp dir");
+        fs::create_dir(dir.path().join("output")).expect("create output dir");
+        fs::write(dir.path().join("output/result.txt"), "42").expect("write deliverable");
+        let mut runner = MissionRunner::new(
+            Uuid::new_v4(),
+            Uuid::nil(),
+            None,
+            None,
+            None,
+            &Default::default(),
+        );
+        let (control_tx, mut control_rx) = mpsc::channel(4);
+
+        // Output that merely claims completion does not count.
+        runner.running_handle = Some(tokio::spawn(async {
+            let result = AgentResult::success("Mission marked as completed.", 0);
+            (Uuid::new_v4(), "first".to_string(), result)
+        }));
+        finish_turn(&mut runner).await;
+        assert!(!runner.explicitly_completed);
+
+        // The tool call does, even though the final answer never mentions it.
+        let (turn_tx, mut turn_rx) = mpsc::channel(4);
+        let control = MissionControl {
+            current_mission_id: Arc::new(RwLock::new(Some(runner.mission_id))),
+            cmd_tx: turn_tx,
+            deliverables: Default::default(),
+        };
+        let completion = Arc::clone(&runner.agent_completion);
+        let work_dir = dir.path().to_path_buf();
+        runner.running_handle = Some(tokio::spawn(async move {
+            let turn = async move {
+                CompleteMission::with_control(control)
+                    .execute(
+                        serde_json::json!({ "status": "completed", "summary": "answer written" }),
+                        &work_dir,
+                    )
+                    .await
+                    .expect("complete_mission");
+                AgentResult::success("All set.", 0)
+            };
+            let result = relay_mission_commands(turn, &mut turn_rx, &completion, &control_tx).await;
+            (Uuid::new_v4(), "second".to_string(), result)
+        }));
+        let result = finish_turn(&mut runner).await;
+        assert_eq!(result.output, "All set.");
+        assert!(runner.explicitly_completed);
+
+        // The control actor still receives the status change.
+        match control_rx.try_recv().expect("status forwarded") {
+            MissionControlCommand::SetStatus { status, summary } => {
+                assert_eq!(status, MissionStatusValue::Completed);
+                assert_eq!(summary.as_deref(), Some("answer written"));
+            }
+        }
+    }
 }
@@ -0,0 +1,158 @@
+//! Server-wide cap on concurrently running missions.
+//!
+//! Every control session (one per user) runs its own actor, but they all draw
+//! execution slots from one [`MissionSlots`], so twenty submitted missions don't
+//! spawn twenty CLI processes. A turn starts only after reserving a
+//! [`SlotPermit`], which is taken atomically and released when the permit is
+//! dropped (the turn finished, or the runner went away). A mission that gets no
+//! permit stays queued in its runner; the actor's poll loop starts it once
+//! another mission finishes. The limit starts at `MAX_PARALLEL_MISSIONS` and can
+//! be changed at runtime through `PUT /api/control/parallel/config`.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// Running and queued mission counts.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SessionLoad {
+    pub running: usize,
+    pub queued: usize,
+}
+
+/// Shared execution slots for all control sessions.
+#[derive(Debug)]
+pub struct MissionSlots {
+    limit: AtomicUsize,
+    /// Load of each control session, keyed by user id: `running` counts the
+    /// permits it holds, `queued` is the last count it reported
+    sessions: Mutex<HashMap<String, SessionLoad>>,
+}
+
+/// A reserved execution slot, given back when dropped.
+#[derive(Debug)]
+pub struct SlotPermit {
+    slots: Arc<MissionSlots>,
+    session: String,
+}
+
+impl Drop for SlotPermit {
+    fn drop(&mut self) {
+        let mut sessions = self.slots.lock();
+        if let Some(load) = sessions.get_mut(&self.session) {
+            load.running = load.running.saturating_sub(1);
+        }
+    }
+}
+
+impl MissionSlots {
+    pub fn new(limit: usize) -> Self {
+        Self {
+            limit: AtomicUsize::new(limit.max(1)),
+            sessions: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, SessionLoad>> {
+        self.sessions.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Maximum missions allowed to run at once.
+    pub fn limit(&self) -> usize {
+        self.limit.load(Ordering::Relaxed)
+    }
+
+    /// Change the limit (at least 1). Running missions are never stopped;
+    /// a lower limit just holds back new starts.
+    pub fn set_limit(&self, limit: usize) {
+        self.limit.store(limit.max(1), Ordering::Relaxed);
+    }
+
+    /// Reserve a slot for `session`, or `None` when every slot is taken.
+    pub fn try_reserve(self: &Arc<Self>, session: &str) -> Option<SlotPermit> {
+        let mut sessions = self.lock();
+        let running: usize = sessions.values().map(|load| load.running).sum();
+        if running >= self.limit() {
+            return None;
+        }
+        sessions.entry(session.to_string()).or_default().running += 1;
+        Some(SlotPermit {
+            slots: Arc::clone(self),
+            session: session.to_string(),
+        })
+    }
+
+    /// Reserve a slot even past the limit, for a turn that must run now (the
+    /// main control session). It still counts against everyone else's starts.
+    pub fn reserve_unchecked(self: &Arc<Self>, session: &str) -> SlotPermit {
+        self.lock().entry(session.to_string()).or_default().running += 1;
+        SlotPermit {
+            slots: Arc::clone(self),
+            session: session.to_string(),
+        }
+    }
+
+    /// Slots still free across the server.
+    pub fn available(&self) -> usize {
+        let running: usize = self.lock().values().map(|load| load.running).sum();
+        self.limit().saturating_sub(running)
+    }
+
+    /// Record how many missions `session` has waiting for a slot.
+    pub fn report_queued(&self, session: &str, queued: usize) {
+        self.lock().entry(session.to_string()).or_default().queued = queued;
+    }
+
+    /// Server-wide running and queued counts.
+    pub fn total(&self) -> SessionLoad {
+        self.lock()
+            .values()
+            .fold(SessionLoad::default(), |acc, load| SessionLoad {
+                running: acc.running + load.running,
+                queued: acc.queued + load.queued,
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slots_are_shared_across_sessions() {
+        let slots = Arc::new(MissionSlots::new(3));
+        let alice: Vec<_> = (0..2)
+            .map(|_| slots.try_reserve("alice").unwrap())
+            .collect();
+        slots.report_queued("alice", 4);
+        let bob = slots.try_reserve("bob").expect("one slot left");
+        assert!(slots.try_reserve("alice").is_none());
+        assert!(slots.try_reserve("carol").is_none());
+        assert_eq!(
+            slots.total(),
+            SessionLoad {
+                running: 3,
+                queued: 4
+            }
+        );
+
+        // Dropping a permit frees its slot for anyone.
+        drop(bob);
+        assert_eq!(slots.available(), 1);
+        let carol = slots.try_reserve("carol").expect("freed slot");
+
+        // The main session may run past the limit, but then blocks new starts longer.
+        let main = slots.reserve_unchecked("bob");
+        assert_eq!(slots.total().running, 4);
+        drop(alice);
+        assert_eq!(slots.available(), 1);
+        drop((carol, main));
+
+        // Raising the limit at runtime frees slots; it never drops below 1.
+        slots.set_limit(5);
+        assert_eq!(slots.available(), 5);
+        slots.set_limit(0);
+        assert_eq!(slots.limit(), 1);
+        assert!(slots.try_reserve("carol").is_some());
+    }
+}
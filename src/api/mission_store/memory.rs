@@ -73,6 +73,11 @@ impl MissionStore for InMemoryMissionStore {
             desktop_sessions: Vec::new(),
             session_id: Some(Uuid::new_v4().to_string()),
             terminal_reason: None,
+            snapshot_before_run: false,
+            snapshot_id: None,
+            backend_profile: None,
+            safe_mode: false,
+            priority: 1,
         };
         self.missions
             .write()
@@ -162,6 +167,60 @@ impl MissionStore for InMemoryMissionStore {
         Ok(())
     }
 
+    async fn set_mission_snapshot_before_run(&self, id: Uuid, enabled: bool) -> Result<(), String> {
+        let mut missions = self.missions.write().await;
+        let mission = missions
+            .get_mut(&id)
+            .ok_or_else(|| format!("Mission {} not found", id))?;
+        mission.snapshot_before_run = enabled;
+        mission.updated_at = now_string();
+        Ok(())
+    }
+
+    async fn set_mission_safe_mode(&self, id: Uuid, enabled: bool) -> Result<(), String> {
+        let mut missions = self.missions.write().await;
+        let mission = missions
+            .get_mut(&id)
+            .ok_or_else(|| format!("Mission {} not found", id))?;
+        mission.safe_mode = enabled;
+        mission.updated_at = now_string();
+        Ok(())
+    }
+
+    async fn set_mission_priority(&self, id: Uuid, priority: u32) -> Result<(), String> {
+        let mut missions = self.missions.write().await;
+        let mission = missions
+            .get_mut(&id)
+            .ok_or_else(|| format!("Mission {} not found", id))?;
+        mission.priority = priority.max(1);
+        mission.updated_at = now_string();
+        Ok(())
+    }
+
+    async fn set_mission_backend_profile(
+        &self,
+        id: Uuid,
+        profile: Option<&str>,
+    ) -> Result<(), String> {
+        let mut missions = self.missions.write().await;
+        let mission = missions
+            .get_mut(&id)
+            .ok_or_else(|| format!("Mission {} not found", id))?;
+        mission.backend_profile = profile.map(str::to_string);
+        mission.updated_at = now_string();
+        Ok(())
+    }
+
+    async fn update_mission_snapshot_id(&self, id: Uuid, snapshot_id: &str) -> Result<(), String> {
+        let mut missions = self.missions.write().await;
+        let mission = missions
+            .get_mut(&id)
+            .ok_or_else(|| format!("Mission {} not found", id))?;
+        mission.snapshot_id = Some(snapshot_id.to_string());
+        mission.updated_at = now_string();
+        Ok(())
+    }
+
     async fn update_mission_tree(&self, id: Uuid, tree: &AgentTreeNode) -> Result<(), String> {
         self.trees.write().await.insert(id, tree.clone());
         Ok(())
@@ -59,6 +59,25 @@ pub struct Mission {
     /// Why the mission terminated (for failed/completed missions)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub terminal_reason: Option<String>,
+    /// Snapshot the workspace before the first turn (see `api::snapshot`)
+    #[serde(default)]
+    pub snapshot_before_run: bool,
+    /// Snapshot taken before the first turn (`git:<sha>` or `tar:<path>`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub snapshot_id: Option<String>,
+    /// Named backend profile (CLI path, model, extra args) from the backend config
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub backend_profile: Option<String>,
+    /// Destructive tool calls wait for user approval (see `tools::safe_mode`)
+    #[serde(default)]
+    pub safe_mode: bool,
+    /// Scheduling weight when execution slots are contended (higher = more turns)
+    #[serde(default = "default_priority")]
+    pub priority: u32,
+}
+
+fn default_priority() -> u32 {
+    1
 }
 
 fn default_backend() -> String {
@@ -91,6 +110,57 @@ pub struct StoredEvent {
     pub metadata: serde_json::Value,
 }
 
+/// A past mission whose conversation matches a search query.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MissionSearchHit {
+    pub mission_id: Uuid,
+    pub title: Option<String>,
+    pub status: MissionStatus,
+    pub updated_at: String,
+    /// Role of the best-matching message ("user" or "assistant")
+    pub role: String,
+    /// Excerpt of the best-matching message around the keywords
+    pub snippet: String,
+    /// Number of messages in the mission that match
+    pub matches: usize,
+}
+
+/// Lowercased keywords of a search query.
+fn search_terms(query: &str) -> Vec<String> {
+    query.split_whitespace().map(|t| t.to_lowercase()).collect()
+}
+
+/// Excerpt of `content` around the first occurrence of `term`.
+fn snippet_around(content: &str, term: &str) -> String {
+    const CONTEXT: usize = 80;
+    let lower = content.to_lowercase();
+    // Byte offsets only carry over when lowercasing kept the length.
+    let start = if lower.len() == content.len() {
+        lower.find(term).unwrap_or(0)
+    } else {
+        0
+    };
+    let mut from = start.saturating_sub(CONTEXT);
+    while !content.is_char_boundary(from) {
+        from -= 1;
+    }
+    let mut to = (start + term.len() + CONTEXT).min(content.len());
+    while !content.is_char_boundary(to) {
+        to += 1;
+    }
+    let mut snippet = content[from..to]
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    if from > 0 {
+        snippet.insert(0, '…');
+    }
+    if to < content.len() {
+        snippet.push('…');
+    }
+    snippet
+}
+
 /// Get current timestamp as RFC3339 string.
 pub fn now_string() -> String {
     Utc::now().to_rfc3339()
@@ -166,6 +236,25 @@ pub trait MissionStore: Send + Sync {
     /// Update mission session ID (for backends like Amp that generate their own IDs).
     async fn update_mission_session_id(&self, id: Uuid, session_id: &str) -> Result<(), String>;
 
+    /// Enable or disable the workspace snapshot taken before the first turn.
+    async fn set_mission_snapshot_before_run(&self, id: Uuid, enabled: bool) -> Result<(), String>;
+
+    /// Enable or disable safe mode (user approval for destructive tool calls).
+    async fn set_mission_safe_mode(&self, id: Uuid, enabled: bool) -> Result<(), String>;
+
+    /// Set the mission's scheduling weight (at least 1).
+    async fn set_mission_priority(&self, id: Uuid, priority: u32) -> Result<(), String>;
+
+    /// Record the workspace snapshot taken before the first turn.
+    async fn update_mission_snapshot_id(&self, id: Uuid, snapshot_id: &str) -> Result<(), String>;
+
+    /// Select the named backend profile the mission runs with.
+    async fn set_mission_backend_profile(
+        &self,
+        id: Uuid,
+        profile: Option<&str>,
+    ) -> Result<(), String>;
+
     /// Update mission agent tree.
     async fn update_mission_tree(&self, id: Uuid, tree: &AgentTreeNode) -> Result<(), String>;
 
@@ -216,6 +305,48 @@ pub trait MissionStore: Send + Sync {
         Ok(vec![])
     }
 
+    /// Full-text search past missions' user and assistant messages.
+    ///
+    /// Every keyword must appear in the same message (case-insensitive).
+    /// Returns at most `limit` missions, best match first.
+    async fn search_missions(
+        &self,
+        query: &str,
+        limit: usize,
+    ) -> Result<Vec<MissionSearchHit>, String> {
+        let terms = search_terms(query);
+        if terms.is_empty() {
+            return Ok(vec![]);
+        }
+        let mut hits = Vec::new();
+        for mission in self.list_missions(usize::MAX, 0).await? {
+            let matching: Vec<&MissionHistoryEntry> = mission
+                .history
+                .iter()
+                .filter(|entry| {
+                    let content = entry.content.to_lowercase();
+                    terms.iter().all(|term| content.contains(term.as_str()))
+                })
+                .collect();
+            let Some(best) = matching.first() else {
+                continue;
+            };
+            hits.push(MissionSearchHit {
+                mission_id: mission.id,
+                title: mission.title.clone(),
+                status: mission.status,
+                updated_at: mission.updated_at.clone(),
+                role: best.role.clone(),
+                snippet: snippet_around(&best.content, &terms[0]),
+                matches: matching.len(),
+            });
+        }
+        // Stable: ties keep the most recently updated mission first.
+        hits.sort_by_key(|hit| std::cmp::Reverse(hit.matches));
+        hits.truncate(limit);
+        Ok(hits)
+    }
+
     /// Get total cost in cents across all missions.
     /// Aggregates cost_cents from all assistant_message events.
     async fn get_total_cost_cents(&self) -> Result<u64, String> {
@@ -287,6 +418,49 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_safe_mode_is_persisted() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = SqliteMissionStore::new(dir.path().to_path_buf(), "test")
+            .await
+            .unwrap();
+        let mission = store
+            .create_mission(None, None, None, None, None)
+            .await
+            .unwrap();
+        assert!(!mission.safe_mode);
+
+        store.set_mission_safe_mode(mission.id, true).await.unwrap();
+        let reloaded = store.get_mission(mission.id).await.unwrap().unwrap();
+        assert!(reloaded.safe_mode);
+        let listed = store.list_missions(10, 0).await.unwrap();
+        assert!(listed[0].safe_mode);
+    }
+
+    #[tokio::test]
+    async fn test_priority_is_persisted() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = SqliteMissionStore::new(dir.path().to_path_buf(), "test")
+            .await
+            .unwrap();
+        let mission = store
+            .create_mission(None, None, None, None, None)
+            .await
+            .unwrap();
+        assert_eq!(mission.priority, 1);
+
+        store.set_mission_priority(mission.id, 3).await.unwrap();
+        let reloaded = store.get_mission(mission.id).await.unwrap().unwrap();
+        assert_eq!(reloaded.priority, 3);
+        let listed = store.list_missions(10, 0).await.unwrap();
+        assert_eq!(listed[0].priority, 3);
+
+        // A weight of 0 would starve the mission; it is stored as 1.
+        store.set_mission_priority(mission.id, 0).await.unwrap();
+        let reloaded = store.get_mission(mission.id).await.unwrap().unwrap();
+        assert_eq!(reloaded.priority, 1);
+    }
+
     /// Test that Pending missions are NOT returned by get_all_active_missions.
     /// This ensures the orphan detection won't mark Pending missions as interrupted.
     #[tokio::test]
@@ -413,4 +587,103 @@ mod tests {
         assert_eq!(format!("{}", MissionStatus::Completed), "completed");
         assert_eq!(format!("{}", MissionStatus::Interrupted), "interrupted");
     }
+
+    /// Test that past missions are found by keyword in their messages.
+    #[tokio::test]
+    async fn test_search_missions_finds_past_mission_by_keyword() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = SqliteMissionStore::new(dir.path().to_path_buf(), "test")
+            .await
+            .unwrap();
+        let message = |user: bool, content: &str| {
+            if user {
+                AgentEvent::UserMessage {
+                    id: Uuid::new_v4(),
+                    content: content.to_string(),
+                    queued: false,
+                    mission_id: None,
+                }
+            } else {
+                AgentEvent::AssistantMessage {
+                    id: Uuid::new_v4(),
+                    content: content.to_string(),
+                    success: true,
+                    cost_cents: 0,
+                    prompt_tokens: 0,
+                    completion_tokens: 0,
+                    model: None,
+                    mission_id: None,
+                    shared_files: None,
+                    resumable: false,
+                }
+            }
+        };
+
+        let nginx = store
+            .create_mission(Some("Fix proxy"), None, None, None, None)
+            .await
+            .unwrap();
+        for event in [
+            message(true, "The reverse proxy returns 502 errors"),
+            message(
+                false,
+                "Raised proxy_read_timeout in the Nginx config; the upstream was slow.",
+            ),
+        ] {
+            store.log_event(nginx.id, &event).await.unwrap();
+        }
+        let docs = store
+            .create_mission(Some("Write docs"), None, None, None, None)
+            .await
+            .unwrap();
+        store
+            .log_event(docs.id, &message(true, "Document the nginx setup"))
+            .await
+            .unwrap();
+
+        let hits = store.search_missions("NGINX upstream", 10).await.unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].mission_id, nginx.id);
+        assert_eq!(hits[0].title.as_deref(), Some("Fix proxy"));
+        assert_eq!(hits[0].role, "assistant");
+        assert!(hits[0].snippet.contains("Nginx"));
+
+        let hits = store.search_missions("nginx", 10).await.unwrap();
+        assert_eq!(hits.len(), 2);
+        assert!(store
+            .search_missions("kubernetes", 10)
+            .await
+            .unwrap()
+            .is_empty());
+
+        // Deleting a mission drops it from the index.
+        store.delete_mission(docs.id).await.unwrap();
+        let hits = store.search_missions("nginx", 10).await.unwrap();
+        assert_eq!(hits.len(), 1);
+    }
+
+    /// Test keyword search over in-memory mission histories.
+    #[tokio::test]
+    async fn test_search_missions_in_memory_history() {
+        let store = InMemoryMissionStore::new();
+        let mission = store
+            .create_mission(None, None, None, None, None)
+            .await
+            .unwrap();
+        store
+            .update_mission_history(
+                mission.id,
+                &[MissionHistoryEntry {
+                    role: "assistant".to_string(),
+                    content: "Rotated the TLS certificate with certbot.".to_string(),
+                }],
+            )
+            .await
+            .unwrap();
+
+        let hits = store.search_missions("certbot tls", 5).await.unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].snippet, "Rotated the TLS certificate with certbot.");
+        assert!(store.search_missions("", 5).await.unwrap().is_empty());
+    }
 }
@@ -1,8 +1,8 @@
 //! SQLite-based mission store with full event logging.
 
 use super::{
-    now_string, sanitize_filename, Mission, MissionHistoryEntry, MissionStatus, MissionStore,
-    StoredEvent,
+    now_string, sanitize_filename, search_terms, Mission, MissionHistoryEntry, MissionSearchHit,
+    MissionStatus, MissionStore, StoredEvent,
 };
 use crate::api::control::{AgentEvent, AgentTreeNode, DesktopSessionInfo};
 use async_trait::async_trait;
@@ -78,6 +78,29 @@ CREATE TABLE IF NOT EXISTS mission_summaries (
 CREATE INDEX IF NOT EXISTS idx_summaries_mission ON mission_summaries(mission_id);
 "#;
 
+/// Full-text index over user/assistant message content, kept in sync by triggers.
+/// Content offloaded to files (over `CONTENT_SIZE_THRESHOLD`) is not indexed.
+const SEARCH_SCHEMA: &str = r#"
+CREATE VIRTUAL TABLE IF NOT EXISTS mission_events_fts USING fts5(
+    content,
+    content = 'mission_events',
+    content_rowid = 'id'
+);
+
+CREATE TRIGGER IF NOT EXISTS mission_events_fts_insert AFTER INSERT ON mission_events
+WHEN new.event_type IN ('user_message', 'assistant_message') AND new.content IS NOT NULL
+BEGIN
+    INSERT INTO mission_events_fts(rowid, content) VALUES (new.id, new.content);
+END;
+
+CREATE TRIGGER IF NOT EXISTS mission_events_fts_delete AFTER DELETE ON mission_events
+WHEN old.event_type IN ('user_message', 'assistant_message') AND old.content IS NOT NULL
+BEGIN
+    INSERT INTO mission_events_fts(mission_events_fts, rowid, content)
+    VALUES ('delete', old.id, old.content);
+END;
+"#;
+
 /// Content size threshold for inline storage (64KB).
 const CONTENT_SIZE_THRESHOLD: usize = 64 * 1024;
 
@@ -215,6 +238,87 @@ impl SqliteMissionStore {
                 .map_err(|e| format!("Failed to add terminal_reason column: {}", e))?;
         }
 
+        // Check if snapshot columns exist in missions table
+        let has_snapshot_columns: bool = conn
+            .prepare("SELECT 1 FROM pragma_table_info('missions') WHERE name = 'snapshot_id'")
+            .map_err(|e| format!("Failed to check for snapshot_id column: {}", e))?
+            .exists([])
+            .map_err(|e| format!("Failed to query table info: {}", e))?;
+
+        if !has_snapshot_columns {
+            tracing::info!("Running migration: adding snapshot columns to missions table");
+            conn.execute_batch(
+                "ALTER TABLE missions ADD COLUMN snapshot_before_run INTEGER NOT NULL DEFAULT 0;
+                 ALTER TABLE missions ADD COLUMN snapshot_id TEXT;",
+            )
+            .map_err(|e| format!("Failed to add snapshot columns: {}", e))?;
+        }
+
+        // Check if backend_profile column exists in missions table
+        let has_backend_profile: bool = conn
+            .prepare("SELECT 1 FROM pragma_table_info('missions') WHERE name = 'backend_profile'")
+            .map_err(|e| format!("Failed to check for backend_profile column: {}", e))?
+            .exists([])
+            .map_err(|e| format!("Failed to query table info: {}", e))?;
+
+        if !has_backend_profile {
+            tracing::info!("Running migration: adding backend_profile column to missions table");
+            conn.execute("ALTER TABLE missions ADD COLUMN backend_profile TEXT", [])
+                .map_err(|e| format!("Failed to add backend_profile column: {}", e))?;
+        }
+
+        // Check if safe_mode column exists in missions table
+        let has_safe_mode: bool = conn
+            .prepare("SELECT 1 FROM pragma_table_info('missions') WHERE name = 'safe_mode'")
+            .map_err(|e| format!("Failed to check for safe_mode column: {}", e))?
+            .exists([])
+            .map_err(|e| format!("Failed to query table info: {}", e))?;
+
+        if !has_safe_mode {
+            tracing::info!("Running migration: adding safe_mode column to missions table");
+            conn.execute(
+                "ALTER TABLE missions ADD COLUMN safe_mode INTEGER NOT NULL DEFAULT 0",
+                [],
+            )
+            .map_err(|e| format!("Failed to add safe_mode column: {}", e))?;
+        }
+
+        // Check if priority column exists in missions table
+        let has_priority: bool = conn
+            .prepare("SELECT 1 FROM pragma_table_info('missions') WHERE name = 'priority'")
+            .map_err(|e| format!("Failed to check for priority column: {}", e))?
+            .exists([])
+            .map_err(|e| format!("Failed to query table info: {}", e))?;
+
+        if !has_priority {
+            tracing::info!("Running migration: adding priority column to missions table");
+            conn.execute(
+                "ALTER TABLE missions ADD COLUMN priority INTEGER NOT NULL DEFAULT 1",
+                [],
+            )
+            .map_err(|e| format!("Failed to add priority column: {}", e))?;
+        }
+
+        // Check if the full-text search index exists
+        let has_search_index: bool = conn
+            .prepare("SELECT 1 FROM sqlite_master WHERE name = 'mission_events_fts'")
+            .map_err(|e| format!("Failed to check for search index: {}", e))?
+            .exists([])
+            .map_err(|e| format!("Failed to query schema: {}", e))?;
+
+        if !has_search_index {
+            tracing::info!("Running migration: indexing mission messages for full-text search");
+            conn.execute_batch(SEARCH_SCHEMA)
+                .map_err(|e| format!("Failed to create search index: {}", e))?;
+            conn.execute(
+                "INSERT INTO mission_events_fts(rowid, content)
+                 SELECT id, content FROM mission_events
+                 WHERE event_type IN ('user_message', 'assistant_message') AND content IS NOT NULL",
+                [],
+            )
+            .map_err(|e| format!("Failed to backfill search index: {}", e))?;
+        }
+
         Ok(())
     }
 }
@@ -258,7 +362,8 @@ impl MissionStore for SqliteMissionStore {
                 .prepare(
                     "SELECT id, status, title, workspace_id, workspace_name, agent, model_override,
                             created_at, updated_at, interrupted_at, resumable, desktop_sessions,
-                            COALESCE(backend, 'opencode') as backend, session_id, terminal_reason
+                            COALESCE(backend, 'opencode') as backend, session_id, terminal_reason,
+                            snapshot_before_run, snapshot_id, backend_profile, safe_mode, priority
                      FROM missions
                      ORDER BY updated_at DESC
                      LIMIT ?1 OFFSET ?2",
@@ -274,6 +379,11 @@ impl MissionStore for SqliteMissionStore {
                     let backend: String = row.get(12)?;
                     let session_id: Option<String> = row.get(13)?;
                     let terminal_reason: Option<String> = row.get(14)?;
+                    let snapshot_before_run: Option<i32> = row.get(15)?;
+                    let snapshot_id: Option<String> = row.get(16)?;
+                    let backend_profile: Option<String> = row.get(17)?;
+                    let safe_mode: Option<i32> = row.get(18)?;
+                    let priority: Option<i64> = row.get(19)?;
 
                     Ok(Mission {
                         id: Uuid::parse_str(&id_str).unwrap_or_default(),
@@ -295,6 +405,11 @@ impl MissionStore for SqliteMissionStore {
                             .unwrap_or_default(),
                         session_id,
                         terminal_reason,
+                        snapshot_before_run: snapshot_before_run.unwrap_or(0) != 0,
+                        snapshot_id,
+                        backend_profile,
+                        safe_mode: safe_mode.unwrap_or(0) != 0,
+                        priority: priority.map_or(1, |p| p.clamp(1, u32::MAX as i64) as u32),
                     })
                 })
                 .map_err(|e| e.to_string())?
@@ -319,7 +434,8 @@ impl MissionStore for SqliteMissionStore {
                 .prepare(
                     "SELECT id, status, title, workspace_id, workspace_name, agent, model_override,
                             created_at, updated_at, interrupted_at, resumable, desktop_sessions,
-                            COALESCE(backend, 'opencode') as backend, session_id, terminal_reason
+                            COALESCE(backend, 'opencode') as backend, session_id, terminal_reason,
+                            snapshot_before_run, snapshot_id, backend_profile, safe_mode, priority
                      FROM missions WHERE id = ?1",
                 )
                 .map_err(|e| e.to_string())?;
@@ -333,6 +449,11 @@ impl MissionStore for SqliteMissionStore {
                     let backend: String = row.get(12)?;
                     let session_id: Option<String> = row.get(13)?;
                     let terminal_reason: Option<String> = row.get(14)?;
+                    let snapshot_before_run: Option<i32> = row.get(15)?;
+                    let snapshot_id: Option<String> = row.get(16)?;
+                    let backend_profile: Option<String> = row.get(17)?;
+                    let safe_mode: Option<i32> = row.get(18)?;
+                    let priority: Option<i64> = row.get(19)?;
 
                     Ok(Mission {
                         id: Uuid::parse_str(&id_str).unwrap_or_default(),
@@ -354,6 +475,11 @@ impl MissionStore for SqliteMissionStore {
                             .unwrap_or_default(),
                         session_id,
                         terminal_reason,
+                        snapshot_before_run: snapshot_before_run.unwrap_or(0) != 0,
+                        snapshot_id,
+                        backend_profile,
+                        safe_mode: safe_mode.unwrap_or(0) != 0,
+                        priority: priority.map_or(1, |p| p.clamp(1, u32::MAX as i64) as u32),
                     })
                 })
                 .optional()
@@ -437,6 +563,11 @@ impl MissionStore for SqliteMissionStore {
             desktop_sessions: Vec::new(),
             session_id: Some(session_id.clone()),
             terminal_reason: None,
+            snapshot_before_run: false,
+            snapshot_id: None,
+            backend_profile: None,
+            safe_mode: false,
+            priority: 1,
         };
 
         let m = mission.clone();
@@ -599,6 +730,97 @@ impl MissionStore for SqliteMissionStore {
         .map_err(|e| e.to_string())?
     }
 
+    async fn set_mission_snapshot_before_run(&self, id: Uuid, enabled: bool) -> Result<(), String> {
+        let conn = self.conn.clone();
+        let now = now_string();
+
+        tokio::task::spawn_blocking(move || {
+            let conn = conn.blocking_lock();
+            conn.execute(
+                "UPDATE missions SET snapshot_before_run = ?1, updated_at = ?2 WHERE id = ?3",
+                params![enabled as i32, now, id.to_string()],
+            )
+            .map_err(|e| e.to_string())?;
+            Ok(())
+        })
+        .await
+        .map_err(|e| e.to_string())?
+    }
+
+    async fn set_mission_safe_mode(&self, id: Uuid, enabled: bool) -> Result<(), String> {
+        let conn = self.conn.clone();
+        let now = now_string();
+
+        tokio::task::spawn_blocking(move || {
+            let conn = conn.blocking_lock();
+            conn.execute(
+                "UPDATE missions SET safe_mode = ?1, updated_at = ?2 WHERE id = ?3",
+                params![enabled as i32, now, id.to_string()],
+            )
+            .map_err(|e| e.to_string())?;
+            Ok(())
+        })
+        .await
+        .map_err(|e| e.to_string())?
+    }
+
+    async fn set_mission_priority(&self, id: Uuid, priority: u32) -> Result<(), String> {
+        let conn = self.conn.clone();
+        let now = now_string();
+
+        tokio::task::spawn_blocking(move || {
+            let conn = conn.blocking_lock();
+            conn.execute(
+                "UPDATE missions SET priority = ?1, updated_at = ?2 WHERE id = ?3",
+                params![priority.max(1), now, id.to_string()],
+            )
+            .map_err(|e| e.to_string())?;
+            Ok(())
+        })
+        .await
+        .map_err(|e| e.to_string())?
+    }
+
+    async fn set_mission_backend_profile(
+        &self,
+        id: Uuid,
+        profile: Option<&str>,
+    ) -> Result<(), String> {
+        let conn = self.conn.clone();
+        let now = now_string();
+        let profile = profile.map(str::to_string);
+
+        tokio::task::spawn_blocking(move || {
+            let conn = conn.blocking_lock();
+            conn.execute(
+                "UPDATE missions SET backend_profile = ?1, updated_at = ?2 WHERE id = ?3",
+                params![profile, now, id.to_string()],
+            )
+            .map_err(|e| e.to_string())?;
+            Ok(())
+        })
+        .await
+        .map_err(|e| e.to_string())?
+    }
+
+    async fn update_mission_snapshot_id(&self, id: Uuid, snapshot_id: &str) -> Result<(), String> {
+        let conn = self.conn.clone();
+        let now = now_string();
+        let snapshot_id = snapshot_id.to_string();
+
+        tokio::task::spawn_blocking(move || {
+            let conn = conn.blocking_lock();
+            conn.execute(
+                "UPDATE missions SET snapshot_id = ?1, updated_at = ?2 WHERE id = ?3",
+                params![snapshot_id, now, id.to_string()],
+            )
+            .map_err(|e| e.to_string())?;
+            Ok(())
+        })
+        .await
+        .map_err(|e| e.to_string())?
+    }
+
     async fn update_mission_tree(&self, id: Uuid, tree: &AgentTreeNode) -> Result<(), String> {
         let conn = self.conn.clone();
         let now = now_string();
@@ -750,6 +972,11 @@ impl MissionStore for SqliteMissionStore {
                             .unwrap_or_default(),
                         session_id: None, // Not needed for stale mission checks
                         terminal_reason: None,
+                        snapshot_before_run: false,
+                        snapshot_id: None,
+                        backend_profile: None,
+                        safe_mode: false,
+                        priority: 1,
                     })
                 })
                 .map_err(|e| e.to_string())?
@@ -805,6 +1032,11 @@ impl MissionStore for SqliteMissionStore {
                             .unwrap_or_default(),
                         session_id: None,
                         terminal_reason: None,
+                        snapshot_before_run: false,
+                        snapshot_id: None,
+                        backend_profile: None,
+                        safe_mode: false,
+                        priority: 1,
                     })
                 })
                 .map_err(|e| e.to_string())?
@@ -877,6 +1109,8 @@ impl MissionStore for SqliteMissionStore {
                 content,
                 success,
                 cost_cents,
+                prompt_tokens,
+                completion_tokens,
                 model,
                 shared_files,
                 resumable,
@@ -890,6 +1124,8 @@ impl MissionStore for SqliteMissionStore {
                 serde_json::json!({
                     "success": success,
                     "cost_cents": cost_cents,
+                    "prompt_tokens": prompt_tokens,
+                    "completion_tokens": completion_tokens,
                     "model": model,
                     "shared_files": shared_files,
                     "resumable": resumable,
@@ -956,7 +1192,11 @@ impl MissionStore for SqliteMissionStore {
             | AgentEvent::Progress { .. }
             | AgentEvent::SessionIdUpdate { .. }
             | AgentEvent::TextDelta { .. }
-            | AgentEvent::MissionActivity { .. } => return Ok(()),
+            | AgentEvent::MissionActivity { .. }
+            | AgentEvent::MissionComplete { .. }
+            | AgentEvent::TodosUpdated { .. }
+            | AgentEvent::ProgressSummary { .. }
+            | AgentEvent::UsageUpdate { .. } => return Ok(()),
         };
 
         let event_type = event_type.to_string();
@@ -1031,6 +1271,69 @@ impl MissionStore for SqliteMissionStore {
         .map_err(|e| e.to_string())?
     }
 
+    async fn search_missions(
+        &self,
+        query: &str,
+        limit: usize,
+    ) -> Result<Vec<MissionSearchHit>, String> {
+        // Quote each keyword so FTS syntax in the query is matched literally;
+        // the trailing `*` makes it a prefix match ("deploy" finds "deployment").
+        let fts_query = search_terms(query)
+            .iter()
+            .map(|term| format!("\"{}\"*", term.replace('"', "\"\"")))
+            .collect::<Vec<_>>()
+            .join(" ");
+        if fts_query.is_empty() {
+            return Ok(vec![]);
+        }
+        let conn = self.conn.clone();
+
+        tokio::task::spawn_blocking(move || {
+            let conn = conn.blocking_lock();
+            let mut stmt = conn
+                .prepare(
+                    "SELECT e.mission_id, m.title, m.status, m.updated_at, e.event_type,
+                            snippet(mission_events_fts, 0, '', '', '…', 24)
+                     FROM mission_events_fts
+                     JOIN mission_events e ON e.id = mission_events_fts.rowid
+                     JOIN missions m ON m.id = e.mission_id
+                     WHERE mission_events_fts MATCH ?1
+                     ORDER BY bm25(mission_events_fts)",
+                )
+                .map_err(|e| e.to_string())?;
+            let rows = stmt
+                .query_map(params![fts_query], |row| {
+                    let mid: String = row.get(0)?;
+                    let status: String = row.get(2)?;
+                    let event_type: String = row.get(4)?;
+                    Ok(MissionSearchHit {
+                        mission_id: Uuid::parse_str(&mid).unwrap_or_default(),
+                        title: row.get(1)?,
+                        status: parse_status(&status),
+                        updated_at: row.get(3)?,
+                        role: event_type.trim_end_matches("_message").to_string(),
+                        snippet: row.get(5)?,
+                        matches: 1,
+                    })
+                })
+                .map_err(|e| e.to_string())?;
+
+            // Rows come best match first; keep each mission's best and count the rest.
+            let mut hits: Vec<MissionSearchHit> = Vec::new();
+            for row in rows {
+                let hit = row.map_err(|e| e.to_string())?;
+                match hits.iter_mut().find(|h| h.mission_id == hit.mission_id) {
+                    Some(existing) => existing.matches += 1,
+                    None => hits.push(hit),
+                }
+            }
+            hits.truncate(limit);
+            Ok(hits)
+        })
+        .await
+        .map_err(|e| e.to_string())?
+    }
+
     async fn get_events(
         &self,
         mission_id: Uuid,
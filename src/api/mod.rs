@@ -22,17 +22,22 @@ mod console;
 pub mod control;
 pub mod desktop;
 mod desktop_stream;
-mod fs;
+pub mod fs;
 pub mod library;
 pub mod mcp;
+pub mod mission_report;
 pub mod mission_runner;
+pub mod mission_slots;
 pub mod mission_store;
 mod monitoring;
 pub mod opencode;
+pub mod progress_summary;
 mod providers;
 mod routes;
+pub mod schedules;
 pub mod secrets;
 pub mod settings;
+pub mod snapshot;
 pub mod system;
 pub mod types;
 pub mod workspaces;
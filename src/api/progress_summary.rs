@@ -0,0 +1,265 @@
+//! Periodic one-line progress summaries for running missions.
+//!
+//! Long missions stream a lot of thinking and tool output. A background task
+//! watches the event stream and, every `MissionConfig::progress_summary_interval`,
+//! condenses what each mission did since the previous tick into a single line
+//! (latest activity, tool calls, subtask progress, errors), broadcast as
+//! `AgentEvent::ProgressSummary`. Missions with no new activity, or whose
+//! summary would repeat the previous one, are skipped.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use tokio::sync::broadcast;
+use uuid::Uuid;
+
+use super::control::{activity_label_from_tool_call, AgentEvent, EventSender, StampedEvent};
+
+/// Tool names listed individually before the rest are folded into "+N more".
+const MAX_TOOL_NAMES: usize = 3;
+
+/// Activity of one mission since its last summary.
+#[derive(Debug, Default)]
+struct MissionActivity {
+    /// Latest activity label (from the last tool call)
+    latest: Option<String>,
+    /// Tool calls per tool name, in first-seen order
+    tool_calls: Vec<(String, usize)>,
+    thinking: bool,
+    errors: usize,
+    subtasks: Option<(usize, usize)>,
+    /// Whether anything happened since the last tick
+    dirty: bool,
+    last_summary: Option<String>,
+}
+
+impl MissionActivity {
+    fn summary(&self) -> String {
+        let mut parts = Vec::new();
+        match (&self.latest, self.thinking) {
+            (Some(latest), _) => parts.push(latest.clone()),
+            (None, true) => parts.push("Thinking".to_string()),
+            (None, false) => {}
+        }
+        let total: usize = self.tool_calls.iter().map(|(_, n)| n).sum();
+        if total > 0 {
+            let mut names: Vec<String> = self
+                .tool_calls
+                .iter()
+                .take(MAX_TOOL_NAMES)
+                .map(|(name, n)| {
+                    if *n > 1 {
+                        format!("{} ×{}", name, n)
+                    } else {
+                        name.clone()
+                    }
+                })
+                .collect();
+            if self.tool_calls.len() > MAX_TOOL_NAMES {
+                names.push(format!("+{} more", self.tool_calls.len() - MAX_TOOL_NAMES));
+            }
+            parts.push(format!(
+                "{} tool call{} ({})",
+                total,
+                if total == 1 { "" } else { "s" },
+                names.join(", ")
+            ));
+        }
+        if let Some((done, total)) = self.subtasks.filter(|(_, total)| *total > 0) {
+            parts.push(format!("{}/{} subtasks done", done, total));
+        }
+        if self.errors > 0 {
+            parts.push(format!(
+                "{} error{}",
+                self.errors,
+                if self.errors == 1 { "" } else { "s" }
+            ));
+        }
+        parts.join(" · ")
+    }
+
+    /// Start a new period, keeping the progress that carries over.
+    fn reset(&mut self) {
+        self.latest = None;
+        self.tool_calls.clear();
+        self.thinking = false;
+        self.errors = 0;
+        self.dirty = false;
+    }
+}
+
+/// Accumulates mission events into periodic summaries.
+#[derive(Debug, Default)]
+pub struct ProgressSummarizer {
+    missions: HashMap<Uuid, MissionActivity>,
+}
+
+impl ProgressSummarizer {
+    /// Record one event.
+    pub fn observe(&mut self, event: &AgentEvent) {
+        let Some(mission_id) = event.mission_id() else {
+            return;
+        };
+        if let AgentEvent::AssistantMessage { .. } = event {
+            // Turn finished; the final answer speaks for itself.
+            self.missions.remove(&mission_id);
+            return;
+        }
+        let activity = self.missions.entry(mission_id).or_default();
+        match event {
+            AgentEvent::ToolCall { name, args, .. } => {
+                activity.latest = Some(activity_label_from_tool_call(name, args));
+                match activity.tool_calls.iter_mut().find(|(n, _)| n == name) {
+                    Some((_, count)) => *count += 1,
+                    None => activity.tool_calls.push((name.clone(), 1)),
+                }
+            }
+            AgentEvent::Thinking { .. } => activity.thinking = true,
+            AgentEvent::Error { .. } => activity.errors += 1,
+            AgentEvent::Progress {
+                total_subtasks,
+                completed_subtasks,
+                ..
+            } => activity.subtasks = Some((*completed_subtasks, *total_subtasks)),
+            _ => return,
+        }
+        activity.dirty = true;
+    }
+
+    /// Summaries for missions with new activity, skipping repeats of the previous one.
+    pub fn summarize(&mut self) -> Vec<(Uuid, String)> {
+        let mut summaries = Vec::new();
+        for (mission_id, activity) in &mut self.missions {
+            if !activity.dirty {
+                continue;
+            }
+            let text = activity.summary();
+            activity.reset();
+            if text.is_empty() || activity.last_summary.as_deref() == Some(text.as_str()) {
+                continue;
+            }
+            activity.last_summary = Some(text.clone());
+            summaries.push((*mission_id, text));
+        }
+        summaries.sort();
+        summaries
+    }
+}
+
+/// Background task broadcasting a progress summary per active mission every `interval`.
+pub async fn progress_summary_loop(
+    events_tx: EventSender,
+    mut events_rx: broadcast::Receiver<StampedEvent>,
+    interval: Duration,
+) {
+    let mut ticker = tokio::time::interval(interval);
+    ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    let mut summarizer = ProgressSummarizer::default();
+
+    loop {
+        tokio::select! {
+            event = events_rx.recv() => match event {
+                Ok(StampedEvent { event, .. }) => summarizer.observe(&event),
+                Err(broadcast::error::RecvError::Lagged(n)) => {
+                    tracing::debug!("Progress summarizer lagged by {} events", n);
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            },
+            _ = ticker.tick() => {
+                for (mission_id, text) in summarizer.summarize() {
+                    let _ = events_tx.send(AgentEvent::ProgressSummary {
+                        text,
+                        mission_id: Some(mission_id),
+                    });
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tool_call(mission_id: Uuid, name: &str, args: serde_json::Value) -> AgentEvent {
+        AgentEvent::ToolCall {
+            tool_call_id: Uuid::new_v4().to_string(),
+            name: name.to_string(),
+            args,
+            mission_id: Some(mission_id),
+        }
+    }
+
+    async fn next_summary(rx: &mut broadcast::Receiver<StampedEvent>) -> (Option<Uuid>, String) {
+        loop {
+            if let AgentEvent::ProgressSummary { text, mission_id } = rx.recv().await.unwrap().event
+            {
+                return (mission_id, text);
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_summary_emitted_periodically_during_activity() {
+        let (events_tx, loop_rx) = EventSender::channel(64);
+        let mut rx = events_tx.subscribe();
+        tokio::spawn(progress_summary_loop(
+            events_tx.clone(),
+            loop_rx,
+            Duration::from_millis(50),
+        ));
+        let mission_id = Uuid::new_v4();
+
+        for _ in 0..2 {
+            events_tx
+                .send(tool_call(
+                    mission_id,
+                    "bash",
+                    serde_json::json!({ "command": "cargo test" }),
+                ))
+                .unwrap();
+        }
+        let (id, text) = tokio::time::timeout(Duration::from_secs(2), next_summary(&mut rx))
+            .await
+            .expect("first summary");
+        assert_eq!(id, Some(mission_id));
+        assert_eq!(text, "Running: cargo test · 2 tool calls (bash ×2)");
+
+        // Nothing new: no summary for a few ticks.
+        assert!(
+            tokio::time::timeout(Duration::from_millis(200), next_summary(&mut rx))
+                .await
+                .is_err()
+        );
+
+        events_tx
+            .send(tool_call(
+                mission_id,
+                "read_file",
+                serde_json::json!({ "path": "src/main.rs" }),
+            ))
+            .unwrap();
+        let (_, text) = tokio::time::timeout(Duration::from_secs(2), next_summary(&mut rx))
+            .await
+            .expect("second summary");
+        assert!(text.ends_with("1 tool call (read_file)"), "{}", text);
+    }
+
+    #[test]
+    fn test_repeated_summary_is_skipped() {
+        let mission_id = Uuid::new_v4();
+        let mut summarizer = ProgressSummarizer::default();
+        let thinking = AgentEvent::Thinking {
+            content: "hmm".to_string(),
+            done: false,
+            mission_id: Some(mission_id),
+        };
+        summarizer.observe(&thinking);
+        assert_eq!(
+            summarizer.summarize(),
+            vec![(mission_id, "Thinking".to_string())]
+        );
+        summarizer.observe(&thinking);
+        assert!(summarizer.summarize().is_empty());
+    }
+}
@@ -51,6 +51,7 @@ use super::library as library_api;
 use super::mcp as mcp_api;
 use super::monitoring;
 use super::opencode as opencode_api;
+use super::schedules as schedules_api;
 use super::secrets as secrets_api;
 use super::settings as settings_api;
 use super::system as system_api;
@@ -90,13 +91,19 @@ pub struct AppState {
     pub backend_registry: Arc<RwLock<BackendRegistry>>,
     /// Backend configuration store
     pub backend_configs: Arc<crate::backend_config::BackendConfigStore>,
+    /// Recurring mission schedules
+    pub schedules: crate::schedules::SharedScheduleStore,
 }
 
+/// Bytes allowed on top of `max_upload_bytes` for multipart boundaries and headers.
+const UPLOAD_MULTIPART_OVERHEAD: u64 = 1024 * 1024;
+
 /// Start the HTTP server.
 pub async fn serve(config: Config) -> anyhow::Result<()> {
     let mut config = config;
     // Start monitoring background collector early so clients get history immediately
     monitoring::init_monitoring();
+    crate::backend::circuit_breaker::init(config.circuit_breaker.clone());
 
     // Initialize MCP registry
     let mcp = Arc::new(McpRegistry::new(&config.working_dir).await);
@@ -233,8 +240,14 @@ pub async fn serve(config: Config) -> anyhow::Result<()> {
         opencode_permissive,
     ));
     backend_registry.register(crate::backend::claudecode::registry_entry());
-    backend_registry.register(crate::backend::amp::registry_entry());
+    let amp_config = backend_configs
+        .get("amp")
+        .await
+        .map(|entry| crate::backend::amp::AmpConfig::from_settings(&entry.settings))
+        .unwrap_or_default();
+    backend_registry.register(crate::backend::amp::registry_entry(amp_config));
     let backend_registry = Arc::new(RwLock::new(backend_registry));
+    crate::backend::registry::install(Arc::clone(&backend_registry));
     tracing::info!("Backend registry initialized with {} backends", 3);
 
     // Note: No central OpenCode server cleanup needed - missions use per-workspace CLI execution
@@ -299,6 +312,8 @@ pub async fn serve(config: Config) -> anyhow::Result<()> {
         tracing::info!("Configuration library disabled (no remote configured)");
     }
 
+    let schedules = Arc::new(crate::schedules::ScheduleStore::new(&config.working_dir).await);
+
     // Spawn the single global control session actor.
     let control_state = control::ControlHub::new(
         config.clone(),
@@ -326,6 +341,7 @@ pub async fn serve(config: Config) -> anyhow::Result<()> {
         settings,
         backend_registry,
         backend_configs,
+        schedules,
     });
 
     // Start background desktop session cleanup task
@@ -336,6 +352,14 @@ pub async fn serve(config: Config) -> anyhow::Result<()> {
         });
     }
 
+    // Start background scheduler for recurring missions
+    {
+        let state_clone = Arc::clone(&state);
+        tokio::spawn(async move {
+            schedules_api::start_scheduler_task(state_clone).await;
+        });
+    }
+
     let public_routes = Router::new()
         .route("/api/health", get(health))
         .route("/api/auth/login", post(auth::login))
@@ -354,11 +378,17 @@ pub async fn serve(config: Config) -> anyhow::Result<()> {
         // WebSocket system monitoring uses subprotocol-based auth
         .route("/api/monitoring/ws", get(monitoring::monitoring_ws));
 
-    // File upload routes with increased body limit (10GB)
+    // File upload routes with the body limit raised to the configured upload cap
+    // (plus room for the multipart framing around the file)
+    let upload_body_limit = config
+        .max_upload_bytes
+        .saturating_add(UPLOAD_MULTIPART_OVERHEAD);
     let upload_route = Router::new()
         .route("/api/fs/upload", post(fs::upload))
         .route("/api/fs/upload-chunk", post(fs::upload_chunk))
-        .layer(DefaultBodyLimit::max(10 * 1024 * 1024 * 1024));
+        .layer(DefaultBodyLimit::max(
+            usize::try_from(upload_body_limit).unwrap_or(usize::MAX),
+        ));
 
     let protected_routes = Router::new()
         .route("/api/stats", get(get_stats))
@@ -397,6 +427,10 @@ pub async fn serve(config: Config) -> anyhow::Result<()> {
             "/api/control/missions/current",
             get(control::get_current_mission),
         )
+        .route(
+            "/api/control/missions/search",
+            get(control::search_missions),
+        )
         .route("/api/control/missions/:id", get(control::get_mission))
         .route(
             "/api/control/missions/:id/tree",
@@ -414,6 +448,10 @@ pub async fn serve(config: Config) -> anyhow::Result<()> {
             "/api/control/missions/:id/status",
             post(control::set_mission_status),
         )
+        .route(
+            "/api/control/missions/:id/log_level",
+            post(control::set_mission_log_level),
+        )
         .route(
             "/api/control/missions/:id/cancel",
             post(control::cancel_mission),
@@ -426,6 +464,30 @@ pub async fn serve(config: Config) -> anyhow::Result<()> {
             "/api/control/missions/:id/parallel",
             post(control::start_mission_parallel),
         )
+        .route(
+            "/api/control/missions/:id/cost",
+            get(control::get_mission_cost),
+        )
+        .route(
+            "/api/control/missions/:id/deliverables",
+            get(control::get_mission_deliverables),
+        )
+        .route(
+            "/api/control/missions/:id/tool_output",
+            post(control::post_tool_output),
+        )
+        .route(
+            "/api/control/missions/:id/confirm_action",
+            post(control::confirm_action),
+        )
+        .route(
+            "/api/control/missions/:id/ui_form",
+            post(control::post_ui_form),
+        )
+        .route(
+            "/api/control/missions/:id/report",
+            post(super::mission_report::generate_mission_report),
+        )
         .route(
             "/api/control/missions/:id",
             axum::routing::delete(control::delete_mission),
@@ -435,11 +497,13 @@ pub async fn serve(config: Config) -> anyhow::Result<()> {
             "/api/control/missions/cleanup",
             post(control::cleanup_empty_missions),
         )
+        // Scheduled (recurring) mission endpoints
+        .nest("/api/schedules", schedules_api::routes())
         // Parallel execution endpoints
         .route("/api/control/running", get(control::list_running_missions))
         .route(
             "/api/control/parallel/config",
-            get(control::get_parallel_config),
+            get(control::get_parallel_config).put(control::update_parallel_config),
         )
         // Memory endpoints
         .route("/api/runs", get(list_runs))
@@ -628,6 +692,7 @@ async fn health(State(state): State<Arc<AppState>>) -> Json<HealthResponse> {
         auth_mode: auth_mode.to_string(),
         max_iterations: state.config.max_iterations,
         library_remote,
+        backends: crate::backend::circuit_breaker::global().statuses(),
     })
 }
 
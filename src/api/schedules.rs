@@ -0,0 +1,248 @@
+//! Scheduled (recurring) mission endpoints and background scheduler.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    routing::{delete, get},
+    Extension, Json, Router,
+};
+use chrono::Utc;
+use serde::Deserialize;
+use tokio::sync::{mpsc, oneshot};
+use uuid::Uuid;
+
+use crate::schedules::{MissionSchedule, NewMissionSchedule};
+
+use super::auth::AuthUser;
+use super::control::ControlCommand;
+use super::routes::AppState;
+
+/// How often the scheduler checks for due schedules.
+const SCHEDULER_TICK: Duration = Duration::from_secs(30);
+
+/// Create the schedule API routes.
+pub fn routes() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/", get(list_schedules).post(create_schedule))
+        .route("/:id", delete(cancel_schedule))
+}
+
+/// Request to register a recurring mission.
+#[derive(Debug, Deserialize)]
+pub struct CreateScheduleRequest {
+    /// 5-field cron expression, evaluated in UTC (e.g. `0 9 * * *`)
+    pub cron: String,
+    /// Message sent to each submitted mission
+    pub content: String,
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default)]
+    pub workspace_id: Option<Uuid>,
+    #[serde(default)]
+    pub agent: Option<String>,
+    #[serde(default)]
+    pub model_override: Option<String>,
+    #[serde(default)]
+    pub backend: Option<String>,
+}
+
+/// GET /api/schedules
+async fn list_schedules(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<AuthUser>,
+) -> Json<Vec<MissionSchedule>> {
+    Json(state.schedules.list(&user.id).await)
+}
+
+/// POST /api/schedules
+async fn create_schedule(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<AuthUser>,
+    Json(req): Json<CreateScheduleRequest>,
+) -> Result<Json<MissionSchedule>, (StatusCode, String)> {
+    let content = req.content.trim().to_string();
+    if content.is_empty() {
+        return Err((StatusCode::BAD_REQUEST, "content is required".to_string()));
+    }
+
+    let model_override = super::auth::resolve_allowed_model(
+        &state.config.auth,
+        &user,
+        req.model_override
+            .as_deref()
+            .filter(|m| !m.trim().is_empty()),
+        None,
+    )
+    .map_err(|e| (StatusCode::FORBIDDEN, e))?;
+
+    state
+        .schedules
+        .create(NewMissionSchedule {
+            cron: req.cron,
+            content,
+            title: req.title,
+            workspace_id: req.workspace_id,
+            agent: req.agent,
+            model_override,
+            backend: req.backend,
+            user_id: user.id,
+            username: user.username,
+        })
+        .await
+        .map(Json)
+        .map_err(|e| (StatusCode::BAD_REQUEST, e))
+}
+
+/// DELETE /api/schedules/:id
+async fn cancel_schedule(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<AuthUser>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
+    match state.schedules.cancel(&user.id, id).await {
+        Ok(true) => Ok(Json(serde_json::json!({ "ok": true, "id": id }))),
+        Ok(false) => Err((StatusCode::NOT_FOUND, format!("Schedule {} not found", id))),
+        Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, e)),
+    }
+}
+
+/// Background task: submit a mission for every schedule that comes due.
+pub async fn start_scheduler_task(state: Arc<AppState>) {
+    tracing::info!("Starting mission scheduler background task");
+
+    loop {
+        for schedule in state.schedules.take_due(Utc::now()).await {
+            match submit_scheduled_mission(&state, &schedule).await {
+                Ok(mission_id) => tracing::info!(
+                    schedule_id = %schedule.id,
+                    mission_id = %mission_id,
+                    "Submitted scheduled mission"
+                ),
+                Err(e) => tracing::warn!(
+                    schedule_id = %schedule.id,
+                    error = %e,
+                    "Failed to submit scheduled mission"
+                ),
+            }
+        }
+        tokio::time::sleep(SCHEDULER_TICK).await;
+    }
+}
+
+/// Create a mission from the schedule's template and send its initial message.
+async fn submit_scheduled_mission(
+    state: &Arc<AppState>,
+    schedule: &MissionSchedule,
+) -> Result<Uuid, String> {
+    let user = AuthUser {
+        id: schedule.user_id.clone(),
+        username: schedule.username.clone(),
+    };
+    let control = state.control.get_or_spawn(&user).await;
+    send_scheduled_mission(&control.cmd_tx, schedule).await
+}
+
+/// Ask the control session behind `cmd_tx` to create the schedule's mission and
+/// queue its initial message.
+async fn send_scheduled_mission(
+    cmd_tx: &mpsc::Sender<ControlCommand>,
+    schedule: &MissionSchedule,
+) -> Result<Uuid, String> {
+    let (tx, rx) = oneshot::channel();
+    cmd_tx
+        .send(ControlCommand::CreateMission {
+            title: schedule.title.clone(),
+            workspace_id: schedule.workspace_id,
+            agent: schedule.agent.clone(),
+            model_override: schedule.model_override.clone(),
+            backend: schedule.backend.clone(),
+            snapshot_before_run: false,
+            backend_profile: None,
+            safe_mode: false,
+            priority: 1,
+            respond: tx,
+        })
+        .await
+        .map_err(|_| "control session unavailable".to_string())?;
+    let mission = rx
+        .await
+        .map_err(|_| "Failed to receive response".to_string())??;
+
+    let (queued_tx, _queued_rx) = oneshot::channel();
+    cmd_tx
+        .send(ControlCommand::UserMessage {
+            id: Uuid::new_v4(),
+            content: schedule.content.clone(),
+            agent: None,
+            target_mission_id: Some(mission.id),
+            respond: queued_tx,
+        })
+        .await
+        .map_err(|_| "control session unavailable".to_string())?;
+
+    Ok(mission.id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::mission_store::{InMemoryMissionStore, MissionStore};
+
+    #[tokio::test]
+    async fn test_scheduled_mission_is_created_and_messaged() {
+        let schedule = MissionSchedule {
+            id: Uuid::new_v4(),
+            cron: "0 9 * * *".to_string(),
+            content: "Summarize yesterday's commits".to_string(),
+            title: Some("Daily summary".to_string()),
+            workspace_id: None,
+            agent: None,
+            model_override: None,
+            backend: Some("claudecode".to_string()),
+            user_id: "alice".to_string(),
+            username: "alice".to_string(),
+            created_at: Utc::now(),
+            last_run_at: None,
+            next_run_at: None,
+        };
+
+        // Stand-in for the control actor: create the mission, record the message.
+        let (cmd_tx, mut cmd_rx) = mpsc::channel(4);
+        let actor = tokio::spawn(async move {
+            let store = InMemoryMissionStore::new();
+            let mut created = None;
+            while let Some(cmd) = cmd_rx.recv().await {
+                match cmd {
+                    ControlCommand::CreateMission {
+                        title,
+                        backend,
+                        respond,
+                        ..
+                    } => {
+                        let mission = store
+                            .create_mission(title.as_deref(), None, None, None, backend.as_deref())
+                            .await;
+                        created = mission.as_ref().ok().map(|m| m.id);
+                        let _ = respond.send(mission);
+                    }
+                    ControlCommand::UserMessage {
+                        content,
+                        target_mission_id,
+                        ..
+                    } => return (created, content, target_mission_id),
+                    _ => {}
+                }
+            }
+            panic!("no message was sent to the scheduled mission");
+        });
+
+        let mission_id = send_scheduled_mission(&cmd_tx, &schedule).await.unwrap();
+        let (created, content, target) = actor.await.unwrap();
+        assert_eq!(created, Some(mission_id));
+        assert_eq!(content, "Summarize yesterday's commits");
+        assert_eq!(target, Some(mission_id));
+    }
+}
@@ -0,0 +1,288 @@
+//! Workspace snapshots taken before a mission's first turn.
+//!
+//! Missions created with `snapshot_before_run` get a snapshot of their
+//! workspace before the agent touches it, and the snapshot id is stored on the
+//! mission record so destructive changes can be rolled back:
+//!
+//! - Git repositories: all tracked and untracked (non-ignored) files are
+//!   committed through a temporary index into a detached commit kept alive by
+//!   `refs/openagent/snapshots/<mission-id>`. The working tree, index, branches
+//!   and stash are left untouched. Id `git:<sha>`; restore with
+//!   `git checkout <sha> -- .`.
+//! - Other directories: a gzipped tarball under `.openagent/snapshots/`.
+//!   Id `tar:<path>`; restore with `tar -xzf <path> -C <workspace>`.
+//!
+//! Mission directories (`workspaces/`) and `.openagent/` itself are excluded.
+
+use std::path::Path;
+use std::process::Command;
+use std::sync::Arc;
+
+use uuid::Uuid;
+
+use super::mission_store::MissionStore;
+use crate::config::Config;
+use crate::workspace::{self, WorkspaceType};
+
+/// Directory (relative to the workspace) holding tarball snapshots.
+pub const SNAPSHOT_DIR: &str = ".openagent/snapshots";
+
+/// Paths left out of snapshots: mission working dirs and snapshots themselves.
+const EXCLUDED: &[&str] = &["workspaces", ".openagent"];
+
+fn git(root: &Path, args: &[&str], index: Option<&Path>) -> Result<String, String> {
+    let mut cmd = Command::new("git");
+    cmd.arg("-C")
+        .arg(root)
+        .args(args)
+        .env("GIT_AUTHOR_NAME", "Open Agent")
+        .env("GIT_AUTHOR_EMAIL", "open-agent@localhost")
+        .env("GIT_COMMITTER_NAME", "Open Agent")
+        .env("GIT_COMMITTER_EMAIL", "open-agent@localhost");
+    if let Some(index) = index {
+        cmd.env("GIT_INDEX_FILE", index);
+    }
+    let output = cmd
+        .output()
+        .map_err(|e| format!("Failed to run git: {}", e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "git {} failed: {}",
+            args.first().copied().unwrap_or_default(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+fn git_snapshot(root: &Path, mission_id: Uuid) -> Result<String, String> {
+    let index = std::env::temp_dir().join(format!("openagent-snapshot-{}.index", mission_id));
+    let mut add = vec!["add", "--all", "--", "."];
+    let excludes: Vec<String> = EXCLUDED
+        .iter()
+        .map(|p| format!(":(exclude){}", p))
+        .collect();
+    add.extend(excludes.iter().map(String::as_str));
+
+    let result = (|| {
+        git(root, &add, Some(&index))?;
+        let tree = git(root, &["write-tree"], Some(&index))?;
+        let message = format!("Open Agent snapshot before mission {}", mission_id);
+        let commit = match git(root, &["rev-parse", "--verify", "--quiet", "HEAD"], None) {
+            Ok(head) => git(
+                root,
+                &["commit-tree", &tree, "-p", &head, "-m", &message],
+                None,
+            )?,
+            // Repository without commits yet
+            Err(_) => git(root, &["commit-tree", &tree, "-m", &message], None)?,
+        };
+        let reference = format!("refs/openagent/snapshots/{}", mission_id);
+        git(root, &["update-ref", &reference, &commit], None)?;
+        Ok(format!("git:{}", commit))
+    })();
+    let _ = std::fs::remove_file(&index);
+    result
+}
+
+fn tar_snapshot(root: &Path, mission_id: Uuid) -> Result<String, String> {
+    let dir = root.join(SNAPSHOT_DIR);
+    std::fs::create_dir_all(&dir)
+        .map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    let name = format!(
+        "{}-{}.tar.gz",
+        mission_id,
+        chrono::Utc::now().format("%Y%m%dT%H%M%SZ")
+    );
+    let archive = dir.join(&name);
+
+    let mut cmd = Command::new("tar");
+    cmd.arg("-czf").arg(&archive);
+    for excluded in EXCLUDED {
+        cmd.arg(format!("--exclude=./{}", excluded));
+    }
+    let output = cmd
+        .arg("-C")
+        .arg(root)
+        .arg(".")
+        .output()
+        .map_err(|e| format!("Failed to run tar: {}", e))?;
+    if !output.status.success() {
+        let _ = std::fs::remove_file(&archive);
+        return Err(format!(
+            "tar failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(format!("tar:{}/{}", SNAPSHOT_DIR, name))
+}
+
+/// Snapshot `root`, returning the snapshot id.
+pub fn create_snapshot(root: &Path, mission_id: Uuid) -> Result<String, String> {
+    if !root.is_dir() {
+        return Err(format!("Workspace {} does not exist", root.display()));
+    }
+    let in_repo = git(root, &["rev-parse", "--is-inside-work-tree"], None)
+        .map(|out| out == "true")
+        .unwrap_or(false);
+    if in_repo {
+        git_snapshot(root, mission_id)
+    } else {
+        tar_snapshot(root, mission_id)
+    }
+}
+
+/// Take the pre-run snapshot for a mission that asked for one and has none yet.
+///
+/// Called at the start of every turn; only the first turn of an opted-in
+/// mission does any work. Failures are logged and never block the turn.
+pub async fn snapshot_before_first_turn(
+    mission_store: &Arc<dyn MissionStore>,
+    workspaces: &workspace::SharedWorkspaceStore,
+    config: &Config,
+    mission_id: Uuid,
+) -> Option<String> {
+    let mission = match mission_store.get_mission(mission_id).await {
+        Ok(Some(mission)) => mission,
+        _ => return None,
+    };
+    if !mission.snapshot_before_run || mission.snapshot_id.is_some() {
+        return None;
+    }
+
+    let ws = workspace::resolve_workspace(workspaces, config, Some(mission.workspace_id)).await;
+    if ws.workspace_type == WorkspaceType::Container {
+        // The container root is a whole filesystem; too large to archive per mission.
+        tracing::warn!(
+            "Skipping pre-run snapshot for mission {}: container workspaces are not snapshotted",
+            mission_id
+        );
+        return None;
+    }
+
+    let root = ws.path.clone();
+    let snapshot = tokio::task::spawn_blocking(move || create_snapshot(&root, mission_id))
+        .await
+        .map_err(|e| e.to_string())
+        .and_then(|r| r);
+    match snapshot {
+        Ok(snapshot_id) => {
+            tracing::info!(
+                "Snapshot {} taken of {} before mission {}",
+                snapshot_id,
+                ws.path.display(),
+                mission_id
+            );
+            if let Err(e) = mission_store
+                .update_mission_snapshot_id(mission_id, &snapshot_id)
+                .await
+            {
+                tracing::warn!(
+                    "Failed to record snapshot for mission {}: {}",
+                    mission_id,
+                    e
+                );
+            }
+            Some(snapshot_id)
+        }
+        Err(e) => {
+            tracing::warn!(
+                "Failed to snapshot workspace before mission {}: {}",
+                mission_id,
+                e
+            );
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::mission_store::InMemoryMissionStore;
+
+    #[tokio::test]
+    async fn test_snapshot_created_before_first_turn_when_enabled() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("data.txt"), "precious").unwrap();
+        std::fs::create_dir_all(dir.path().join("workspaces/mission-1")).unwrap();
+        let config = Config::new(dir.path().to_path_buf());
+        let workspaces = Arc::new(workspace::WorkspaceStore::new(dir.path().to_path_buf()).await);
+        let store: Arc<dyn MissionStore> = Arc::new(InMemoryMissionStore::new());
+
+        let plain = store
+            .create_mission(None, None, None, None, None)
+            .await
+            .unwrap();
+        assert!(
+            snapshot_before_first_turn(&store, &workspaces, &config, plain.id)
+                .await
+                .is_none()
+        );
+
+        let mission = store
+            .create_mission(None, None, None, None, None)
+            .await
+            .unwrap();
+        store
+            .set_mission_snapshot_before_run(mission.id, true)
+            .await
+            .unwrap();
+        let snapshot_id = snapshot_before_first_turn(&store, &workspaces, &config, mission.id)
+            .await
+            .expect("snapshot taken");
+        let recorded = store.get_mission(mission.id).await.unwrap().unwrap();
+        assert_eq!(recorded.snapshot_id.as_deref(), Some(snapshot_id.as_str()));
+
+        // Not a git repo: the workspace is archived without mission dirs.
+        let archive = dir.path().join(snapshot_id.strip_prefix("tar:").unwrap());
+        let listing = Command::new("tar")
+            .arg("-tzf")
+            .arg(&archive)
+            .output()
+            .unwrap();
+        let listing = String::from_utf8_lossy(&listing.stdout);
+        assert!(listing.contains("./data.txt"));
+        assert!(!listing.contains("workspaces"));
+
+        // Later turns keep the original snapshot.
+        assert!(
+            snapshot_before_first_turn(&store, &workspaces, &config, mission.id)
+                .await
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_git_snapshot_leaves_working_tree_untouched() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        git(root, &["init", "-q"], None).unwrap();
+        std::fs::write(root.join("tracked.txt"), "v1").unwrap();
+        git(root, &["add", "tracked.txt"], None).unwrap();
+        git(root, &["commit", "-q", "-m", "init"], None).unwrap();
+        std::fs::write(root.join("tracked.txt"), "v2").unwrap();
+        std::fs::write(root.join("new.txt"), "untracked").unwrap();
+
+        let mission_id = Uuid::new_v4();
+        let snapshot_id = create_snapshot(root, mission_id).unwrap();
+        let sha = snapshot_id.strip_prefix("git:").unwrap();
+
+        assert_eq!(
+            git(root, &["show", &format!("{}:tracked.txt", sha)], None).unwrap(),
+            "v2"
+        );
+        assert_eq!(
+            git(root, &["show", &format!("{}:new.txt", sha)], None).unwrap(),
+            "untracked"
+        );
+        // Nothing staged or stashed; the untracked file is still untracked.
+        assert_eq!(
+            git(root, &["status", "--porcelain"], None).unwrap(),
+            "M tracked.txt\n?? new.txt"
+        );
+        assert_eq!(git(root, &["stash", "list"], None).unwrap(), "");
+        let reference = format!("refs/openagent/snapshots/{}", mission_id);
+        assert_eq!(git(root, &["rev-parse", &reference], None).unwrap(), sha);
+    }
+}
@@ -155,6 +155,9 @@ pub struct HealthResponse {
     /// Configured library remote URL (from LIBRARY_REMOTE env var)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub library_remote: Option<String>,
+
+    /// Circuit breaker state of each backend that has run a turn
+    pub backends: Vec<crate::backend::circuit_breaker::BackendBreakerStatus>,
 }
 
 /// Login request for dashboard auth.
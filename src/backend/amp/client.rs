@@ -1,9 +1,11 @@
 use anyhow::{anyhow, Result};
+use serde_json::Value;
 use std::process::Stdio;
 use std::sync::Arc;
 use tokio::io::{AsyncBufReadExt, BufReader};
-use tokio::process::Command;
+use tokio::process::{Child, Command};
 use tokio::sync::{mpsc, Mutex};
+use tokio_util::sync::CancellationToken;
 use tracing::{debug, warn};
 use uuid::Uuid;
 
@@ -13,6 +15,13 @@ pub use crate::backend::shared::{
 };
 
 /// Configuration for the Amp CLI client.
+///
+/// Sampling options are only passed to the CLI when set, so by default Amp
+/// keeps its own defaults. Flags passed through (see [`AmpConfig::cli_args`]):
+///
+/// - `temperature` → `--temperature <value>` (0.0 to 2.0)
+/// - `max_tokens` → `--max-tokens <value>`
+/// - `extra_args` → appended verbatim after the built-in flags
 #[derive(Debug, Clone, Default)]
 pub struct AmpConfig {
     /// Path to the amp CLI binary (default: "amp")
@@ -23,6 +32,69 @@ pub struct AmpConfig {
     pub default_mode: Option<String>,
     /// Amp API key for authentication
     pub api_key: Option<String>,
+    /// Sampling temperature (unset: Amp's default)
+    pub temperature: Option<f32>,
+    /// Maximum output tokens per response (unset: Amp's default)
+    pub max_tokens: Option<u32>,
+    /// Extra CLI arguments, e.g. `["--log-level", "debug"]`
+    pub extra_args: Vec<String>,
+}
+
+impl AmpConfig {
+    /// Build a config from the `amp` backend settings.
+    ///
+    /// Reads `cli_path`, `default_mode`, `temperature`, `max_tokens` and
+    /// `extra_args`; out-of-range or malformed values are ignored.
+    pub fn from_settings(settings: &Value) -> Self {
+        let string = |key: &str| {
+            settings
+                .get(key)
+                .and_then(|v| v.as_str())
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+        };
+        Self {
+            cli_path: string("cli_path"),
+            default_mode: string("default_mode"),
+            temperature: settings
+                .get("temperature")
+                .and_then(|v| v.as_f64())
+                .filter(|t| (0.0..=2.0).contains(t))
+                .map(|t| t as f32),
+            max_tokens: settings
+                .get("max_tokens")
+                .and_then(|v| v.as_u64())
+                .filter(|n| *n > 0)
+                .and_then(|n| u32::try_from(n).ok()),
+            extra_args: settings
+                .get("extra_args")
+                .and_then(|v| v.as_array())
+                .map(|args| {
+                    args.iter()
+                        .filter_map(|a| a.as_str())
+                        .filter(|a| !a.trim().is_empty())
+                        .map(str::to_string)
+                        .collect()
+                })
+                .unwrap_or_default(),
+            ..Self::default()
+        }
+    }
+
+    /// CLI arguments for the sampling options and extra args, in that order.
+    pub fn cli_args(&self) -> Vec<String> {
+        let mut args = Vec::new();
+        if let Some(temperature) = self.temperature {
+            args.push("--temperature".to_string());
+            args.push(temperature.to_string());
+        }
+        if let Some(max_tokens) = self.max_tokens {
+            args.push("--max-tokens".to_string());
+            args.push(max_tokens.to_string());
+        }
+        args.extend(self.extra_args.iter().cloned());
+        args
+    }
 }
 
 /// Client for interacting with the Amp CLI.
@@ -51,13 +123,15 @@ impl AmpClient {
     /// Execute a message using the Amp CLI.
     ///
     /// Returns a receiver for streaming events and a handle to the process.
+    /// Cancelling `cancel` kills the CLI and closes the receiver. Amp has no model
+    /// flag; the model follows from `mode`.
     pub async fn execute_message(
         &self,
         working_dir: &str,
         message: &str,
-        model: Option<&str>,
         mode: Option<&str>,
         session_id: Option<&str>,
+        cancel: CancellationToken,
     ) -> Result<(mpsc::Receiver<AmpEvent>, AmpProcessHandle)> {
         let cli_path = self
             .config
@@ -79,13 +153,12 @@ impl AmpClient {
             cmd.arg(m);
         }
 
+        // Sampling options and extra flags from the config
+        cmd.args(self.config.cli_args());
+
         // The message is passed as the final argument
         cmd.arg(message);
 
-        cmd.stdin(Stdio::piped());
-        cmd.stdout(Stdio::piped());
-        cmd.stderr(Stdio::piped());
-
         debug!(
             cli_path = %cli_path,
             working_dir = %working_dir,
@@ -93,7 +166,7 @@ impl AmpClient {
             "Starting Amp CLI process"
         );
 
-        let mut child = cmd.spawn().map_err(|e| {
+        let child = spawn_piped(&mut cmd).map_err(|e| {
             anyhow!(
                 "Failed to spawn Amp CLI at '{}': {}. Is Amp installed?",
                 cli_path,
@@ -101,74 +174,19 @@ impl AmpClient {
             )
         })?;
 
-        let stdout = child
-            .stdout
-            .take()
-            .ok_or_else(|| anyhow!("Failed to capture Amp stdout"))?;
-
-        let stderr = child.stderr.take();
-
-        let child_arc = Arc::new(Mutex::new(Some(child)));
-        let child_for_task = Arc::clone(&child_arc);
-
-        let (tx, rx) = mpsc::channel(256);
-
-        // Spawn stderr reader for debugging
-        if let Some(stderr) = stderr {
-            tokio::spawn(async move {
-                let reader = BufReader::new(stderr);
-                let mut lines = reader.lines();
-                while let Ok(Some(line)) = lines.next_line().await {
-                    if !line.trim().is_empty() {
-                        debug!(stderr = %line, "Amp CLI stderr");
-                    }
-                }
-            });
-        }
-
-        // Spawn stdout reader for events
-        let task_handle = tokio::spawn(async move {
-            let reader = BufReader::new(stdout);
-            let mut lines = reader.lines();
-
-            while let Ok(Some(line)) = lines.next_line().await {
-                if line.is_empty() {
-                    continue;
-                }
-
-                match serde_json::from_str::<AmpEvent>(&line) {
-                    Ok(event) => {
-                        if tx.send(event).await.is_err() {
-                            debug!("Amp event receiver dropped");
-                            break;
-                        }
-                    }
-                    Err(e) => {
-                        warn!(
-                            error = %e,
-                            line = %if line.len() > 200 { &line[..200] } else { &line },
-                            "Failed to parse Amp event"
-                        );
-                    }
-                }
-            }
-
-            // Wait for child to finish
-            if let Some(mut child) = child_for_task.lock().await.take() {
-                let _ = child.wait().await;
-            }
-        });
-
-        Ok((rx, AmpProcessHandle::new(child_arc, task_handle)))
+        stream_events(child, cancel)
     }
 
     /// Continue an existing thread with a new message.
+    ///
+    /// Cancelling `cancel` kills the CLI and closes the receiver.
     pub async fn continue_thread(
         &self,
         working_dir: &str,
         thread_id: &str,
         message: &str,
         mode: Option<&str>,
+        cancel: CancellationToken,
     ) -> Result<(mpsc::Receiver<AmpEvent>, AmpProcessHandle)> {
         let cli_path = self
             .config
@@ -195,13 +213,11 @@ impl AmpClient {
             cmd.arg(m);
         }
 
+        cmd.args(self.config.cli_args());
+
         // Message
         cmd.arg(message);
 
-        cmd.stdin(Stdio::piped());
-        cmd.stdout(Stdio::piped());
-        cmd.stderr(Stdio::piped());
-
         debug!(
             cli_path = %cli_path,
             working_dir = %working_dir,
@@ -209,62 +225,104 @@ impl AmpClient {
             "Continuing Amp thread"
         );
 
-        let mut child = cmd
-            .spawn()
-            .map_err(|e| anyhow!("Failed to spawn Amp CLI: {}", e))?;
+        let child = spawn_piped(&mut cmd).map_err(|e| anyhow!("Failed to spawn Amp CLI: {}", e))?;
 
-        let stdout = child
-            .stdout
-            .take()
-            .ok_or_else(|| anyhow!("Failed to capture Amp stdout"))?;
-
-        let stderr = child.stderr.take();
-
-        let child_arc = Arc::new(Mutex::new(Some(child)));
-        let child_for_task = Arc::clone(&child_arc);
-
-        let (tx, rx) = mpsc::channel(256);
+        stream_events(child, cancel)
+    }
+}
 
-        if let Some(stderr) = stderr {
-            tokio::spawn(async move {
-                let reader = BufReader::new(stderr);
-                let mut lines = reader.lines();
-                while let Ok(Some(line)) = lines.next_line().await {
-                    if !line.trim().is_empty() {
-                        debug!(stderr = %line, "Amp CLI stderr");
-                    }
-                }
-            });
-        }
+/// Spawn the CLI with piped stdio; the process is killed if its handle is dropped.
+fn spawn_piped(cmd: &mut Command) -> std::io::Result<Child> {
+    cmd.stdin(Stdio::piped());
+    cmd.stdout(Stdio::piped());
+    cmd.stderr(Stdio::piped());
+    cmd.kill_on_drop(true);
+    cmd.spawn()
+}
 
-        let task_handle = tokio::spawn(async move {
-            let reader = BufReader::new(stdout);
+/// Read NDJSON events from the CLI's stdout until it exits or `cancel` fires.
+///
+/// On cancel the process is killed and the reader stops at once, closing
+/// the event channel.
+fn stream_events(
+    mut child: Child,
+    cancel: CancellationToken,
+) -> Result<(mpsc::Receiver<AmpEvent>, AmpProcessHandle)> {
+    let stdout = child
+        .stdout
+        .take()
+        .ok_or_else(|| anyhow!("Failed to capture Amp stdout"))?;
+
+    let stderr = child.stderr.take();
+
+    let child_arc = Arc::new(Mutex::new(Some(child)));
+    let child_for_task = Arc::clone(&child_arc);
+
+    let (tx, rx) = mpsc::channel(256);
+
+    // Spawn stderr reader for debugging
+    if let Some(stderr) = stderr {
+        tokio::spawn(async move {
+            let reader = BufReader::new(stderr);
             let mut lines = reader.lines();
-
             while let Ok(Some(line)) = lines.next_line().await {
-                if line.is_empty() {
-                    continue;
+                if !line.trim().is_empty() {
+                    debug!(stderr = %line, "Amp CLI stderr");
                 }
+            }
+        });
+    }
 
-                match serde_json::from_str::<AmpEvent>(&line) {
-                    Ok(event) => {
-                        if tx.send(event).await.is_err() {
-                            break;
+    // Spawn stdout reader for events
+    let task_handle = tokio::spawn(async move {
+        let reader = BufReader::new(stdout);
+        let mut lines = reader.lines();
+
+        loop {
+            let line = tokio::select! {
+                _ = cancel.cancelled() => {
+                    if let Some(mut child) = child_for_task.lock().await.take() {
+                        if let Err(e) = child.kill().await {
+                            warn!(error = %e, "Failed to kill cancelled Amp CLI");
+                        } else {
+                            debug!("Amp CLI killed on cancel");
                         }
                     }
-                    Err(e) => {
-                        warn!(error = %e, "Failed to parse Amp event");
-                    }
+                    return;
                 }
+                line = lines.next_line() => line,
+            };
+            let Ok(Some(line)) = line else {
+                break;
+            };
+            if line.is_empty() {
+                continue;
             }
 
-            if let Some(mut child) = child_for_task.lock().await.take() {
-                let _ = child.wait().await;
+            match serde_json::from_str::<AmpEvent>(&line) {
+                Ok(event) => {
+                    if tx.send(event).await.is_err() {
+                        debug!("Amp event receiver dropped");
+                        break;
+                    }
+                }
+                Err(e) => {
+                    warn!(
+                        error = %e,
+                        line = %if line.len() > 200 { &line[..200] } else { &line },
+                        "Failed to parse Amp event"
+                    );
+                }
             }
-        });
+        }
 
-        Ok((rx, AmpProcessHandle::new(child_arc, task_handle)))
-    }
+        // Wait for child to finish
+        if let Some(mut child) = child_for_task.lock().await.take() {
+            let _ = child.wait().await;
+        }
+    });
+
+    Ok((rx, AmpProcessHandle::new(child_arc, task_handle)))
 }
 
 impl Default for AmpClient {
@@ -314,4 +372,37 @@ mod tests {
             _ => panic!("Expected Result event"),
         }
     }
+
+    #[test]
+    fn test_config_translates_to_cli_args() {
+        assert!(AmpConfig::default().cli_args().is_empty());
+
+        let config = AmpConfig::from_settings(&serde_json::json!({
+            "default_mode": "rush",
+            "temperature": 0.25,
+            "max_tokens": 4096,
+            "extra_args": ["--log-level", "debug", " "],
+        }));
+        assert_eq!(config.default_mode.as_deref(), Some("rush"));
+        assert_eq!(
+            config.cli_args(),
+            vec![
+                "--temperature",
+                "0.25",
+                "--max-tokens",
+                "4096",
+                "--log-level",
+                "debug"
+            ]
+        );
+
+        let config = AmpConfig::from_settings(&serde_json::json!({
+            "temperature": 7.5,
+            "max_tokens": 0,
+        }));
+        assert!(
+            config.cli_args().is_empty(),
+            "out-of-range values are ignored"
+        );
+    }
 }
@@ -6,13 +6,16 @@ use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::{mpsc, RwLock};
 use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
 use tracing::debug;
 
 use crate::backend::events::ExecutionEvent;
 use crate::backend::shared::convert_cli_event;
 use crate::backend::{AgentInfo, Backend, Session, SessionConfig};
 
-use client::{AmpClient, AmpConfig};
+pub use client::AmpConfig;
+
+use client::AmpClient;
 
 /// Amp backend that spawns the Amp CLI for mission execution.
 pub struct AmpBackend {
@@ -97,14 +100,15 @@ impl Backend for AmpBackend {
     ) -> Result<(mpsc::Receiver<ExecutionEvent>, JoinHandle<()>), Error> {
         let config = self.config.read().await.clone();
         let client = AmpClient::with_config(config);
+        let cancel = CancellationToken::new();
 
         let (mut amp_rx, amp_handle) = client
             .execute_message(
                 &session.directory,
                 message,
-                session.model.as_deref(),
                 session.agent.as_deref(), // mode
                 Some(&session.id),
+                cancel.clone(),
             )
             .await?;
 
@@ -113,15 +117,17 @@ impl Backend for AmpBackend {
 
         // Spawn event conversion task
         let handle = tokio::spawn(async move {
+            // Aborting this task (or the receiver going away) kills the CLI.
+            let _cancel_on_exit = cancel.drop_guard();
             let mut pending_tools: HashMap<String, String> = HashMap::new();
 
-            while let Some(event) = amp_rx.recv().await {
+            'events: while let Some(event) = amp_rx.recv().await {
                 let exec_events = convert_cli_event(event, &mut pending_tools);
 
                 for exec_event in exec_events {
                     if tx.send(exec_event).await.is_err() {
                         debug!("ExecutionEvent receiver dropped");
-                        break;
+                        break 'events;
                     }
                 }
             }
@@ -141,14 +147,71 @@ impl Backend for AmpBackend {
 }
 
 /// Create a registry entry for the Amp backend.
-pub fn registry_entry() -> Arc<dyn Backend> {
-    Arc::new(AmpBackend::new())
+pub fn registry_entry(config: AmpConfig) -> Arc<dyn Backend> {
+    Arc::new(AmpBackend::with_config(config))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[tokio::test]
+    async fn test_aborting_the_stream_kills_the_cli() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let pid_file = dir.path().join("amp.pid");
+        let script = dir.path().join("amp");
+        std::fs::write(
+            &script,
+            format!(
+                "#!/bin/sh\necho $$ > {}.tmp\nmv {0}.tmp {0}\nexec sleep 30\n",
+                pid_file.display()
+            ),
+        )
+        .unwrap();
+        std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        let backend = AmpBackend::with_config(AmpConfig {
+            cli_path: Some(script.display().to_string()),
+            ..AmpConfig::default()
+        });
+        let session = backend
+            .create_session(SessionConfig {
+                directory: dir.path().display().to_string(),
+                title: None,
+                model: None,
+                agent: None,
+            })
+            .await
+            .unwrap();
+        let (_rx, handle) = backend
+            .send_message_streaming(&session, "hello")
+            .await
+            .unwrap();
+
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
+        while !pid_file.exists() {
+            assert!(
+                std::time::Instant::now() < deadline,
+                "Amp CLI never started"
+            );
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        }
+        let pid = std::fs::read_to_string(&pid_file).unwrap();
+        let proc_dir = std::path::PathBuf::from(format!("/proc/{}", pid.trim()));
+        assert!(proc_dir.exists());
+
+        handle.abort();
+        while proc_dir.exists() {
+            assert!(
+                std::time::Instant::now() < deadline,
+                "Amp CLI still running after abort"
+            );
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        }
+    }
+
     #[tokio::test]
     async fn test_list_agents() {
         let backend = AmpBackend::new();
@@ -0,0 +1,382 @@
+//! Per-backend circuit breakers.
+//!
+//! Each backend (`claudecode`, `opencode`, `amp`, ...) has a breaker that
+//! counts backend failures (model errors, missing CLI or credentials) in a
+//! sliding window. Once the configured count is reached the breaker opens and
+//! new turns fail fast with `TerminalReason::BackendUnavailable` instead of
+//! hammering a backend that is down. After the cooldown one probe turn is
+//! admitted (half-open): success closes the breaker, failure re-opens it.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+
+use crate::agents::{AgentResult, TerminalReason};
+use crate::config::CircuitBreakerConfig;
+
+/// How a finished turn counts towards its backend's breaker.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Outcome {
+    Success,
+    Failure,
+    /// Neither (e.g. cancelled or stalled); only releases a pending probe
+    Inconclusive,
+}
+
+impl Outcome {
+    fn of(result: &AgentResult) -> Self {
+        if result.success {
+            return Self::Success;
+        }
+        match result.terminal_reason {
+            Some(
+                TerminalReason::LlmError | TerminalReason::CliMissing | TerminalReason::AuthMissing,
+            ) => Self::Failure,
+            _ => Self::Inconclusive,
+        }
+    }
+}
+
+/// Whether a new turn may run on a backend.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Admission {
+    Allowed,
+    /// Half-open: this turn is the single probe deciding whether to close
+    Probe,
+    Rejected {
+        retry_after: Duration,
+    },
+}
+
+#[derive(Debug, Default)]
+struct Breaker {
+    failures: VecDeque<Instant>,
+    opened_at: Option<Instant>,
+    probing: bool,
+    trips: u64,
+}
+
+impl Breaker {
+    fn prune(&mut self, window: Duration, now: Instant) {
+        while let Some(&oldest) = self.failures.front() {
+            if now.saturating_duration_since(oldest) < window {
+                break;
+            }
+            self.failures.pop_front();
+        }
+    }
+}
+
+/// Breaker state reported by the health endpoint.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BackendBreakerStatus {
+    pub backend: String,
+    /// "closed", "open" or "half_open"
+    pub state: &'static str,
+    /// Failures within the current window
+    pub recent_failures: usize,
+    /// Seconds until a probe turn is admitted (open breakers only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retry_after_secs: Option<u64>,
+    /// Times the breaker has opened since startup
+    pub trips: u64,
+}
+
+/// Circuit breakers for all backends.
+#[derive(Debug)]
+pub struct CircuitBreakers {
+    config: CircuitBreakerConfig,
+    breakers: Mutex<HashMap<String, Breaker>>,
+}
+
+static GLOBAL: OnceLock<CircuitBreakers> = OnceLock::new();
+
+/// Install the process-wide breakers at server startup.
+pub fn init(config: CircuitBreakerConfig) {
+    if GLOBAL.set(CircuitBreakers::new(config)).is_err() {
+        tracing::warn!("Backend circuit breakers already initialized");
+    }
+}
+
+/// Process-wide breakers (configured from the environment if `init` was not called).
+pub fn global() -> &'static CircuitBreakers {
+    GLOBAL.get_or_init(|| CircuitBreakers::new(CircuitBreakerConfig::from_env()))
+}
+
+/// Admission ticket for one turn; records its outcome on the breaker.
+///
+/// Dropping a ticket without [`TurnGuard::finish`] (e.g. the turn future was
+/// cancelled) releases a pending probe so the breaker cannot stay half-open.
+pub struct TurnGuard<'a> {
+    breakers: &'a CircuitBreakers,
+    backend: String,
+    finished: bool,
+}
+
+impl TurnGuard<'_> {
+    /// Record the turn's result on the backend's breaker.
+    pub fn finish(mut self, result: &AgentResult) {
+        self.finished = true;
+        self.breakers
+            .record_at(&self.backend, Outcome::of(result), Instant::now());
+    }
+}
+
+impl Drop for TurnGuard<'_> {
+    fn drop(&mut self) {
+        if !self.finished {
+            self.breakers
+                .record_at(&self.backend, Outcome::Inconclusive, Instant::now());
+        }
+    }
+}
+
+impl CircuitBreakers {
+    pub fn new(config: CircuitBreakerConfig) -> Self {
+        Self {
+            config,
+            breakers: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn enabled(&self) -> bool {
+        self.config.failure_threshold > 0
+    }
+
+    /// Ask to run a turn on `backend`.
+    ///
+    /// Returns a guard to report the turn's result with, or, while the breaker
+    /// is open, a failed `AgentResult` with `TerminalReason::BackendUnavailable`.
+    pub fn admit(&self, backend: &str) -> Result<TurnGuard<'_>, Box<AgentResult>> {
+        match self.admit_at(backend, Instant::now()) {
+            Admission::Rejected { retry_after } => Err(Box::new(
+                AgentResult::failure(
+                format!(
+                    "Backend '{}' is unavailable after repeated failures (circuit open); retry in {}s",
+                    backend,
+                    retry_after.as_secs().max(1)
+                ),
+                0,
+            )
+                .with_terminal_reason(TerminalReason::BackendUnavailable),
+            )),
+            admission => {
+                if admission == Admission::Probe {
+                    tracing::info!("Circuit half-open for backend '{}': sending probe", backend);
+                }
+                Ok(TurnGuard {
+                    breakers: self,
+                    backend: backend.to_string(),
+                    finished: false,
+                })
+            }
+        }
+    }
+
+    /// Run `turn` on `backend` unless its breaker is open, recording the result.
+    pub async fn run<Fut>(&self, backend: &str, turn: Fut) -> AgentResult
+    where
+        Fut: std::future::Future<Output = AgentResult>,
+    {
+        let guard = match self.admit(backend) {
+            Ok(guard) => guard,
+            Err(rejected) => return *rejected,
+        };
+        let result = turn.await;
+        guard.finish(&result);
+        result
+    }
+
+    fn admit_at(&self, backend: &str, now: Instant) -> Admission {
+        if !self.enabled() {
+            return Admission::Allowed;
+        }
+        let mut breakers = self.breakers.lock().unwrap();
+        let breaker = breakers.entry(backend.to_string()).or_default();
+        let Some(opened_at) = breaker.opened_at else {
+            return Admission::Allowed;
+        };
+        let elapsed = now.saturating_duration_since(opened_at);
+        if breaker.probing {
+            Admission::Rejected {
+                retry_after: self.config.cooldown,
+            }
+        } else if elapsed >= self.config.cooldown {
+            breaker.probing = true;
+            Admission::Probe
+        } else {
+            Admission::Rejected {
+                retry_after: self.config.cooldown - elapsed,
+            }
+        }
+    }
+
+    fn record_at(&self, backend: &str, outcome: Outcome, now: Instant) {
+        if !self.enabled() {
+            return;
+        }
+        let mut breakers = self.breakers.lock().unwrap();
+        let breaker = breakers.entry(backend.to_string()).or_default();
+        match outcome {
+            Outcome::Success => {
+                if breaker.opened_at.is_some() {
+                    tracing::info!("Circuit closed for backend '{}'", backend);
+                }
+                breaker.failures.clear();
+                breaker.opened_at = None;
+                breaker.probing = false;
+            }
+            Outcome::Failure => {
+                breaker.failures.push_back(now);
+                breaker.prune(self.config.window, now);
+                if breaker.probing {
+                    tracing::warn!("Probe failed; circuit re-opened for backend '{}'", backend);
+                    breaker.probing = false;
+                    breaker.opened_at = Some(now);
+                    breaker.trips += 1;
+                } else if breaker.opened_at.is_none()
+                    && breaker.failures.len() >= self.config.failure_threshold
+                {
+                    tracing::warn!(
+                        "Circuit opened for backend '{}' after {} failures in {}s",
+                        backend,
+                        breaker.failures.len(),
+                        self.config.window.as_secs()
+                    );
+                    breaker.opened_at = Some(now);
+                    breaker.trips += 1;
+                }
+            }
+            Outcome::Inconclusive => breaker.probing = false,
+        }
+    }
+
+    /// Current state of every backend that has run a turn.
+    pub fn statuses(&self) -> Vec<BackendBreakerStatus> {
+        self.statuses_at(Instant::now())
+    }
+
+    fn statuses_at(&self, now: Instant) -> Vec<BackendBreakerStatus> {
+        let mut breakers = self.breakers.lock().unwrap();
+        let mut statuses: Vec<_> = breakers
+            .iter_mut()
+            .map(|(backend, breaker)| {
+                breaker.prune(self.config.window, now);
+                let (state, retry_after_secs) = match breaker.opened_at {
+                    None => ("closed", None),
+                    Some(opened_at) => {
+                        let elapsed = now.saturating_duration_since(opened_at);
+                        if breaker.probing || elapsed >= self.config.cooldown {
+                            ("half_open", None)
+                        } else {
+                            ("open", Some((self.config.cooldown - elapsed).as_secs()))
+                        }
+                    }
+                };
+                BackendBreakerStatus {
+                    backend: backend.clone(),
+                    state,
+                    recent_failures: breaker.failures.len(),
+                    retry_after_secs,
+                    trips: breaker.trips,
+                }
+            })
+            .collect();
+        statuses.sort_by(|a, b| a.backend.cmp(&b.backend));
+        statuses
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_breaker_trips_and_recovers_through_probe() {
+        let breakers = CircuitBreakers::new(CircuitBreakerConfig {
+            failure_threshold: 3,
+            window: Duration::from_secs(60),
+            cooldown: Duration::from_secs(30),
+        });
+        let start = Instant::now();
+        let at = |secs| start + Duration::from_secs(secs);
+
+        // Failures outside the window don't add up; other outcomes don't count.
+        breakers.record_at("claudecode", Outcome::Failure, at(0));
+        breakers.record_at("claudecode", Outcome::Inconclusive, at(10));
+        breakers.record_at("claudecode", Outcome::Failure, at(70));
+        breakers.record_at("claudecode", Outcome::Failure, at(80));
+        assert_eq!(breakers.admit_at("claudecode", at(81)), Admission::Allowed);
+
+        breakers.record_at("claudecode", Outcome::Failure, at(90));
+        assert_eq!(
+            breakers.admit_at("claudecode", at(100)),
+            Admission::Rejected {
+                retry_after: Duration::from_secs(20)
+            }
+        );
+        assert_eq!(breakers.admit_at("opencode", at(100)), Admission::Allowed);
+        let status = &breakers.statuses_at(at(100))[0];
+        assert_eq!(
+            (status.state, status.retry_after_secs, status.trips),
+            ("open", Some(20), 1)
+        );
+
+        // Cooldown over: one probe, concurrent turns still rejected.
+        assert_eq!(breakers.admit_at("claudecode", at(120)), Admission::Probe);
+        assert!(matches!(
+            breakers.admit_at("claudecode", at(121)),
+            Admission::Rejected { .. }
+        ));
+        assert_eq!(breakers.statuses_at(at(121))[0].state, "half_open");
+
+        // A failed probe re-opens for another cooldown.
+        breakers.record_at("claudecode", Outcome::Failure, at(125));
+        assert!(matches!(
+            breakers.admit_at("claudecode", at(150)),
+            Admission::Rejected { .. }
+        ));
+        assert_eq!(breakers.admit_at("claudecode", at(155)), Admission::Probe);
+
+        // A successful probe closes the breaker.
+        breakers.record_at("claudecode", Outcome::Success, at(160));
+        assert_eq!(breakers.admit_at("claudecode", at(161)), Admission::Allowed);
+        let status = &breakers.statuses_at(at(161))[0];
+        assert_eq!(
+            (status.state, status.recent_failures, status.trips),
+            ("closed", 0, 2)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_open_breaker_short_circuits_turns() {
+        let breakers = CircuitBreakers::new(CircuitBreakerConfig {
+            failure_threshold: 1,
+            window: Duration::from_secs(60),
+            cooldown: Duration::from_secs(60),
+        });
+        let result = breakers
+            .run("amp", async {
+                AgentResult::failure("API down".to_string(), 0)
+                    .with_terminal_reason(TerminalReason::LlmError)
+            })
+            .await;
+        assert_eq!(result.terminal_reason, Some(TerminalReason::LlmError));
+
+        let mut ran = false;
+        let result = breakers
+            .run("amp", async {
+                ran = true;
+                AgentResult::success("done".to_string(), 0)
+            })
+            .await;
+        assert!(!ran);
+        assert!(!result.success);
+        assert_eq!(
+            result.terminal_reason,
+            Some(TerminalReason::BackendUnavailable)
+        );
+    }
+}
@@ -0,0 +1,197 @@
+//! Authentication strategies for the Claude CLI.
+//!
+//! A strategy knows how to recognize a credential and which environment
+//! variables hand it to the CLI. Credentials are matched against the registered
+//! strategies in order, falling back to a plain Anthropic API key. Schemes that
+//! cannot be recognized from the credential alone (e.g. Vertex AI) are selected
+//! with `CLAUDE_CODE_AUTH_STRATEGY=<name>`.
+
+/// How one kind of credential is passed to the Claude CLI.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuthStrategy {
+    pub name: &'static str,
+    /// Credential prefixes that identify this scheme.
+    pub prefixes: &'static [&'static str],
+    /// Variable receiving the credential itself.
+    pub env_var: &'static str,
+    /// Fixed variables the CLI needs to enable this scheme.
+    pub extra_env: &'static [(&'static str, &'static str)],
+    /// Whether the credential is a (refreshable) OAuth access token.
+    pub is_oauth: bool,
+}
+
+impl AuthStrategy {
+    pub fn matches(&self, credential: &str) -> bool {
+        self.prefixes.iter().any(|p| credential.starts_with(p))
+    }
+
+    /// Environment variables that authenticate the CLI with `credential`.
+    pub fn env(&self, credential: &str) -> Vec<(String, String)> {
+        std::iter::once((self.env_var.to_string(), credential.to_string()))
+            .chain(
+                self.extra_env
+                    .iter()
+                    .map(|(k, v)| (k.to_string(), v.to_string())),
+            )
+            .collect()
+    }
+}
+
+/// Claude.ai subscription OAuth access tokens.
+pub const ANTHROPIC_OAUTH: AuthStrategy = AuthStrategy {
+    name: "oauth",
+    prefixes: &["sk-ant-oat"],
+    env_var: "CLAUDE_CODE_OAUTH_TOKEN",
+    extra_env: &[],
+    is_oauth: true,
+};
+
+/// Anthropic Console API keys (also the fallback for unrecognized credentials).
+pub const ANTHROPIC_API_KEY: AuthStrategy = AuthStrategy {
+    name: "api_key",
+    prefixes: &["sk-ant-api"],
+    env_var: "ANTHROPIC_API_KEY",
+    extra_env: &[],
+    is_oauth: false,
+};
+
+/// Amazon Bedrock API keys.
+pub const BEDROCK: AuthStrategy = AuthStrategy {
+    name: "bedrock",
+    prefixes: &["ABSK"],
+    env_var: "AWS_BEARER_TOKEN_BEDROCK",
+    extra_env: &[("CLAUDE_CODE_USE_BEDROCK", "1")],
+    is_oauth: false,
+};
+
+/// Google Vertex AI: the credential is the GCP project id (auth comes from gcloud).
+pub const VERTEX: AuthStrategy = AuthStrategy {
+    name: "vertex",
+    prefixes: &[],
+    env_var: "ANTHROPIC_VERTEX_PROJECT_ID",
+    extra_env: &[("CLAUDE_CODE_USE_VERTEX", "1")],
+    is_oauth: false,
+};
+
+/// Ordered set of strategies used to authenticate the Claude CLI.
+#[derive(Debug, Clone)]
+pub struct AuthStrategies {
+    strategies: Vec<AuthStrategy>,
+    /// Strategy forced by configuration, bypassing detection.
+    forced: Option<String>,
+}
+
+impl Default for AuthStrategies {
+    fn default() -> Self {
+        Self {
+            strategies: vec![ANTHROPIC_OAUTH, ANTHROPIC_API_KEY, BEDROCK, VERTEX],
+            forced: None,
+        }
+    }
+}
+
+impl AuthStrategies {
+    /// Built-in strategies, honouring `CLAUDE_CODE_AUTH_STRATEGY`.
+    pub fn from_env() -> Self {
+        let forced = std::env::var("CLAUDE_CODE_AUTH_STRATEGY")
+            .ok()
+            .map(|v| v.trim().to_lowercase())
+            .filter(|v| !v.is_empty());
+        Self::default().with_forced(forced)
+    }
+
+    pub fn with_forced(mut self, name: Option<String>) -> Self {
+        self.forced = name;
+        self
+    }
+
+    /// Add a strategy, replacing any existing one with the same name.
+    pub fn register(&mut self, strategy: AuthStrategy) {
+        self.strategies.retain(|s| s.name != strategy.name);
+        self.strategies.push(strategy);
+    }
+
+    pub fn get(&self, name: &str) -> Option<&AuthStrategy> {
+        self.strategies.iter().find(|s| s.name == name)
+    }
+
+    /// Strategy to use for `credential`.
+    pub fn detect(&self, credential: &str) -> &AuthStrategy {
+        if let Some(forced) = &self.forced {
+            match self.get(forced) {
+                Some(strategy) => return strategy,
+                None => tracing::warn!(
+                    "Unknown CLAUDE_CODE_AUTH_STRATEGY '{}', detecting from the credential",
+                    forced
+                ),
+            }
+        }
+        self.strategies
+            .iter()
+            .find(|s| s.matches(credential))
+            .unwrap_or(&ANTHROPIC_API_KEY)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn env_of(strategies: &AuthStrategies, credential: &str) -> Vec<(String, String)> {
+        strategies.detect(credential).env(credential)
+    }
+
+    fn pair(k: &str, v: &str) -> (String, String) {
+        (k.to_string(), v.to_string())
+    }
+
+    #[test]
+    fn test_each_strategy_maps_credential_to_env_var() {
+        let strategies = AuthStrategies::default();
+
+        let oauth = strategies.detect("sk-ant-oat01-abc");
+        assert!(oauth.is_oauth);
+        assert_eq!(
+            oauth.env("sk-ant-oat01-abc"),
+            vec![pair("CLAUDE_CODE_OAUTH_TOKEN", "sk-ant-oat01-abc")]
+        );
+        assert_eq!(
+            env_of(&strategies, "sk-ant-api03-abc"),
+            vec![pair("ANTHROPIC_API_KEY", "sk-ant-api03-abc")]
+        );
+        assert_eq!(
+            env_of(&strategies, "ABSKQmVkcm9jaw"),
+            vec![
+                pair("AWS_BEARER_TOKEN_BEDROCK", "ABSKQmVkcm9jaw"),
+                pair("CLAUDE_CODE_USE_BEDROCK", "1"),
+            ]
+        );
+        // Unrecognized credentials are treated as Anthropic API keys.
+        assert_eq!(strategies.detect("custom-key").name, "api_key");
+
+        let vertex = AuthStrategies::default().with_forced(Some("vertex".to_string()));
+        assert_eq!(
+            env_of(&vertex, "my-gcp-project"),
+            vec![
+                pair("ANTHROPIC_VERTEX_PROJECT_ID", "my-gcp-project"),
+                pair("CLAUDE_CODE_USE_VERTEX", "1"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_registered_strategy_is_detected() {
+        let mut strategies = AuthStrategies::default();
+        strategies.register(AuthStrategy {
+            name: "gateway",
+            prefixes: &["gw-"],
+            env_var: "ANTHROPIC_AUTH_TOKEN",
+            extra_env: &[],
+            is_oauth: false,
+        });
+        assert_eq!(
+            env_of(&strategies, "gw-123"),
+            vec![pair("ANTHROPIC_AUTH_TOKEN", "gw-123")]
+        );
+    }
+}
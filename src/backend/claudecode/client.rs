@@ -7,6 +7,8 @@ use tokio::sync::{mpsc, Mutex};
 use tracing::{debug, error, info, warn};
 use uuid::Uuid;
 
+use super::auth::AuthStrategies;
+
 // Re-export shared types with Claude-specific aliases for backward compat.
 pub use crate::backend::shared::{
     CliEvent as ClaudeEvent, ContentBlock, ProcessHandle as ClaudeProcessHandle, StreamEvent,
@@ -76,18 +78,14 @@ impl ClaudeCodeClient {
         // Note: --dangerously-skip-permissions cannot be used when running as root
 
         // Set API key or OAuth token if configured
-        // OAuth tokens start with "sk-ant-oat" and must use CLAUDE_CODE_OAUTH_TOKEN
-        // API keys start with "sk-ant-api" and use ANTHROPIC_API_KEY
         if let Some(ref key) = self.config.api_key {
-            if key.starts_with("sk-ant-oat") {
-                // OAuth access token
-                cmd.env("CLAUDE_CODE_OAUTH_TOKEN", key);
-                debug!("Using OAuth token for Claude CLI authentication");
-            } else {
-                // Regular API key
-                cmd.env("ANTHROPIC_API_KEY", key);
-                debug!("Using API key for Claude CLI authentication");
-            }
+            let strategies = AuthStrategies::from_env();
+            let strategy = strategies.detect(key);
+            cmd.envs(strategy.env(key));
+            debug!(
+                "Using {} credentials for Claude CLI authentication",
+                strategy.name
+            );
         }
 
         // Model selection
@@ -283,4 +281,52 @@ mod tests {
             _ => panic!("Expected Result event"),
         }
     }
+    #[test]
+    fn test_parse_structured_tool_results() {
+        use crate::backend::shared::{tool_result_payload, StructuredToolResult};
+
+        let json = r#"{"type":"user","message":{"content":[{"type":"tool_result","tool_use_id":"toolu_1","content":"ok"}]},"session_id":"abc123","tool_use_result":{"filePath":"/src/main.rs","oldString":"a","newString":"b","structuredPatch":[{"oldStart":1,"oldLines":2,"newStart":1,"newLines":3,"lines":[" fn main() {","-    a();","+    b();","+    c();"]}]}}"#;
+        let event: ClaudeEvent = serde_json::from_str(json).unwrap();
+        let ClaudeEvent::User(evt) = event else {
+            panic!("Expected User event");
+        };
+        let payload = tool_result_payload("ok".to_string(), false, evt.tool_use_result.as_ref());
+        assert_eq!(payload["structured"]["kind"], "file_change");
+        assert_eq!(payload["structured"]["file_path"], "/src/main.rs");
+        assert_eq!(payload["structured"]["lines_added"], 2);
+        assert_eq!(payload["structured"]["lines_removed"], 1);
+        assert!(payload["structured"]["diff_preview"]
+            .as_str()
+            .unwrap()
+            .contains("+    c();"));
+
+        let bash = serde_json::json!({ "stdout": "hi", "stderr": "", "interrupted": false, "isImage": false });
+        let payload = tool_result_payload("hi".to_string(), false, Some(&bash));
+        assert_eq!(payload["stdout"], "hi");
+        assert_eq!(payload["structured"]["kind"], "command");
+
+        let search = serde_json::json!({ "mode": "files_with_matches", "filenames": ["a.rs", "b.rs"], "numFiles": 2 });
+        assert_eq!(
+            StructuredToolResult::parse(&search),
+            StructuredToolResult::Search {
+                filenames: vec!["a.rs".to_string(), "b.rs".to_string()],
+                num_files: 2,
+                num_lines: None,
+                truncated: false,
+            }
+        );
+
+        // Errors come as a plain string and stay as-is.
+        let error = serde_json::json!("Error: File does not exist.");
+        assert_eq!(
+            StructuredToolResult::parse(&error),
+            StructuredToolResult::Raw {
+                value: error.clone()
+            }
+        );
+        assert_eq!(
+            tool_result_payload("x".to_string(), true, None),
+            serde_json::json!("x")
+        );
+    }
 }
@@ -1,3 +1,4 @@
+pub mod auth;
 pub mod client;
 
 use anyhow::Error;
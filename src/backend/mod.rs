@@ -1,4 +1,5 @@
 pub mod amp;
+pub mod circuit_breaker;
 pub mod claudecode;
 pub mod events;
 pub mod opencode;
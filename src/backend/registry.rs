@@ -1,8 +1,30 @@
+//! Registry of the backends missions can run on, keyed by backend id.
+//!
+//! The server builds one registry at startup and [`install`]s it, so mission
+//! turns can resolve any registered backend by id: the built-in CLIs have
+//! dedicated runners, every other backend runs through the [`Backend`] trait.
+
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::sync::{Arc, OnceLock};
+
+use tokio::sync::RwLock;
 
 use super::Backend;
 
+static SHARED: OnceLock<Arc<RwLock<BackendRegistry>>> = OnceLock::new();
+
+/// Make `registry` the one mission turns resolve backends from.
+pub fn install(registry: Arc<RwLock<BackendRegistry>>) {
+    if SHARED.set(registry).is_err() {
+        tracing::warn!("Backend registry already installed");
+    }
+}
+
+/// Look up a registered backend by id (`None` until [`install`] is called).
+pub async fn lookup(id: &str) -> Option<Arc<dyn Backend>> {
+    SHARED.get()?.read().await.get(id)
+}
+
 #[derive(Debug, Clone)]
 pub struct BackendInfo {
     pub id: String,
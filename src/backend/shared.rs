@@ -168,6 +168,18 @@ pub struct Usage {
     pub cache_read_input_tokens: Option<u64>,
 }
 
+impl Usage {
+    /// Token counts for cost accounting (missing counts are 0).
+    pub fn token_usage(&self) -> crate::cost::TokenUsage {
+        crate::cost::TokenUsage {
+            input_tokens: self.input_tokens.unwrap_or(0),
+            output_tokens: self.output_tokens.unwrap_or(0),
+            cache_creation_input_tokens: self.cache_creation_input_tokens,
+            cache_read_input_tokens: self.cache_read_input_tokens,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(tag = "type")]
 pub enum ContentBlock {
@@ -238,8 +250,10 @@ pub struct UserEvent {
     pub session_id: String,
     #[serde(default)]
     pub parent_tool_use_id: Option<String>,
+    /// Structured result of the tool (shape depends on the tool, a plain
+    /// string for errors); see [`StructuredToolResult`].
     #[serde(default)]
-    pub tool_use_result: Option<ToolUseResultInfo>,
+    pub tool_use_result: Option<Value>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -250,16 +264,148 @@ pub struct UserMessage {
     pub role: Option<String>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
-pub struct ToolUseResultInfo {
-    #[serde(default)]
-    pub stdout: Option<String>,
-    #[serde(default)]
-    pub stderr: Option<String>,
-    #[serde(default)]
-    pub interrupted: Option<bool>,
-    #[serde(default, rename = "isImage")]
-    pub is_image: Option<bool>,
+/// Diff lines kept in a file change preview.
+const DIFF_PREVIEW_LINES: usize = 40;
+
+/// Typed view of the `tool_use_result` Claude Code attaches to tool results,
+/// for the shapes the dashboard renders as tool cards.
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum StructuredToolResult {
+    /// Bash and other commands.
+    Command {
+        stdout: String,
+        stderr: String,
+        interrupted: bool,
+    },
+    /// Edit, MultiEdit and Write.
+    FileChange {
+        file_path: String,
+        created: bool,
+        lines_added: usize,
+        lines_removed: usize,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        diff_preview: Option<String>,
+    },
+    /// Read.
+    FileRead {
+        file_path: String,
+        num_lines: u64,
+        start_line: u64,
+        total_lines: u64,
+    },
+    /// Grep and Glob.
+    Search {
+        filenames: Vec<String>,
+        num_files: u64,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        num_lines: Option<u64>,
+        truncated: bool,
+    },
+    /// Any other shape, forwarded as-is.
+    Raw { value: Value },
+}
+
+impl StructuredToolResult {
+    pub fn parse(value: &Value) -> Self {
+        let str_field =
+            |v: &Value, key: &str| v.get(key).and_then(|s| s.as_str()).map(String::from);
+        let u64_field = |v: &Value, key: &str| v.get(key).and_then(|n| n.as_u64());
+
+        if value.get("stdout").is_some() || value.get("stderr").is_some() {
+            return Self::Command {
+                stdout: str_field(value, "stdout").unwrap_or_default(),
+                stderr: str_field(value, "stderr").unwrap_or_default(),
+                interrupted: value["interrupted"].as_bool().unwrap_or(false),
+            };
+        }
+
+        if let Some(file) = value.get("file").filter(|f| f.get("filePath").is_some()) {
+            return Self::FileRead {
+                file_path: str_field(file, "filePath").unwrap_or_default(),
+                num_lines: u64_field(file, "numLines").unwrap_or(0),
+                start_line: u64_field(file, "startLine").unwrap_or(1),
+                total_lines: u64_field(file, "totalLines").unwrap_or(0),
+            };
+        }
+
+        if let Some(file_path) = str_field(value, "filePath") {
+            let created = value["type"].as_str() == Some("create");
+            let diff: Vec<&str> = value["structuredPatch"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(|hunk| hunk["lines"].as_array())
+                .flatten()
+                .filter_map(|line| line.as_str())
+                .collect();
+            if !diff.is_empty() || created {
+                let count = |prefix: char| diff.iter().filter(|l| l.starts_with(prefix)).count();
+                let lines_added = if diff.is_empty() {
+                    value["content"].as_str().map_or(0, |c| c.lines().count())
+                } else {
+                    count('+')
+                };
+                return Self::FileChange {
+                    file_path,
+                    created,
+                    lines_added,
+                    lines_removed: count('-'),
+                    diff_preview: (!diff.is_empty())
+                        .then(|| diff[..diff.len().min(DIFF_PREVIEW_LINES)].join("\n")),
+                };
+            }
+        }
+
+        if let Some(filenames) = value["filenames"].as_array() {
+            let filenames: Vec<String> = filenames
+                .iter()
+                .filter_map(|f| f.as_str().map(String::from))
+                .collect();
+            return Self::Search {
+                num_files: u64_field(value, "numFiles").unwrap_or(filenames.len() as u64),
+                num_lines: u64_field(value, "numLines"),
+                truncated: value["truncated"].as_bool().unwrap_or(false),
+                filenames,
+            };
+        }
+
+        Self::Raw {
+            value: value.clone(),
+        }
+    }
+}
+
+/// Build the `ToolResult` event payload for a tool result block.
+///
+/// Without a `tool_use_result` the payload is the text content; with one it is
+/// an object with the content, the typed `structured` result and, for commands,
+/// `stdout` / `stderr` / `interrupted` at the top level.
+pub fn tool_result_payload(
+    content: String,
+    is_error: bool,
+    tool_use_result: Option<&Value>,
+) -> Value {
+    let Some(extra) = tool_use_result else {
+        return Value::String(content);
+    };
+    let structured = StructuredToolResult::parse(extra);
+    let mut payload = serde_json::json!({
+        "content": content,
+        "is_error": is_error,
+    });
+    if let StructuredToolResult::Command {
+        stdout,
+        stderr,
+        interrupted,
+    } = &structured
+    {
+        payload["stdout"] = Value::from(stdout.as_str());
+        payload["stderr"] = Value::from(stderr.as_str());
+        payload["interrupted"] = Value::from(*interrupted);
+    }
+    payload["structured"] = serde_json::to_value(&structured).unwrap_or(Value::Null);
+    payload
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -276,6 +422,9 @@ pub struct ResultEvent {
     pub duration_ms: Option<u64>,
     #[serde(default)]
     pub num_turns: Option<u32>,
+    /// Tokens used by the whole run.
+    #[serde(default)]
+    pub usage: Option<Usage>,
     /// Amp extension: separate error field.
     #[serde(default)]
     pub error: Option<String>,
@@ -398,17 +547,8 @@ pub fn convert_cli_event(
 
                     let content_str = content.to_string_lossy();
 
-                    let result_value = if let Some(ref extra) = evt.tool_use_result {
-                        serde_json::json!({
-                            "content": content_str,
-                            "stdout": extra.stdout,
-                            "stderr": extra.stderr,
-                            "is_error": is_error,
-                            "interrupted": extra.interrupted,
-                        })
-                    } else {
-                        Value::String(content_str)
-                    };
+                    let result_value =
+                        tool_result_payload(content_str, is_error, evt.tool_use_result.as_ref());
 
                     results.push(ExecutionEvent::ToolResult {
                         id: tool_use_id,
@@ -5,6 +5,7 @@ use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tokio::sync::RwLock;
+use uuid::Uuid;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BackendConfigEntry {
@@ -149,3 +150,155 @@ impl BackendConfigStore {
 }
 
 pub type SharedBackendConfigStore = Arc<BackendConfigStore>;
+
+/// CLI settings a backend runs a mission turn with, after overrides.
+///
+/// Besides its own `cli_path`, a backend's `settings` may carry per-workspace
+/// overrides and named profiles (selected per mission with `backend_profile`):
+///
+/// ```json
+/// {
+///   "cli_path": "claude",
+///   "workspaces": {
+///     "<workspace id>": { "cli_path": "/opt/claude-1.0.30/bin/claude" }
+///   },
+///   "profiles": [
+///     { "name": "fork", "cli_path": "/src/claude-fork/cli.js", "model": "claude-sonnet-4-5", "extra_args": ["--debug"] }
+///   ]
+/// }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CliProfile {
+    /// CLI binary to run (unset: the backend's default lookup)
+    pub cli_path: Option<String>,
+    /// Default model for missions created with this profile
+    pub model: Option<String>,
+    /// Extra CLI arguments appended after the built-in ones
+    pub extra_args: Vec<String>,
+}
+
+impl CliProfile {
+    /// Resolve the settings for a workspace and optional profile.
+    ///
+    /// Layers, later wins per field: the backend settings, the workspace
+    /// override, the named profile. Errors if `profile` is not defined.
+    pub fn resolve(
+        settings: &serde_json::Value,
+        workspace_id: Option<Uuid>,
+        profile: Option<&str>,
+    ) -> Result<Self, String> {
+        let mut resolved = Self::default();
+        resolved.apply(settings);
+        if let Some(workspace_id) = workspace_id {
+            if let Some(layer) = settings
+                .get("workspaces")
+                .and_then(|w| w.get(workspace_id.to_string()))
+            {
+                resolved.apply(layer);
+            }
+        }
+        if let Some(name) = profile {
+            let layer = settings
+                .get("profiles")
+                .and_then(|p| p.as_array())
+                .and_then(|profiles| {
+                    profiles
+                        .iter()
+                        .find(|p| p.get("name").and_then(|n| n.as_str()) == Some(name))
+                })
+                .ok_or_else(|| {
+                    let known = profile_names(settings);
+                    if known.is_empty() {
+                        format!("Unknown backend profile '{}' (none defined)", name)
+                    } else {
+                        format!(
+                            "Unknown backend profile '{}' (defined: {})",
+                            name,
+                            known.join(", ")
+                        )
+                    }
+                })?;
+            resolved.apply(layer);
+        }
+        Ok(resolved)
+    }
+
+    fn apply(&mut self, layer: &serde_json::Value) {
+        let string = |key: &str| {
+            layer
+                .get(key)
+                .and_then(|v| v.as_str())
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+        };
+        if let Some(cli_path) = string("cli_path") {
+            self.cli_path = Some(cli_path);
+        }
+        if let Some(model) = string("model") {
+            self.model = Some(model);
+        }
+        if let Some(args) = layer.get("extra_args").and_then(|v| v.as_array()) {
+            self.extra_args = args
+                .iter()
+                .filter_map(|a| a.as_str())
+                .map(str::to_string)
+                .collect();
+        }
+    }
+}
+
+/// Names of the profiles defined in a backend's settings.
+pub fn profile_names(settings: &serde_json::Value) -> Vec<String> {
+    settings
+        .get("profiles")
+        .and_then(|p| p.as_array())
+        .map(|profiles| {
+            profiles
+                .iter()
+                .filter_map(|p| p.get("name").and_then(|n| n.as_str()))
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cli_profile_layers_workspace_and_profile() {
+        let workspace_id = Uuid::new_v4();
+        let settings = serde_json::json!({
+            "cli_path": "claude",
+            "workspaces": {
+                workspace_id.to_string(): { "cli_path": "/opt/claude-pinned", "extra_args": ["--debug"] }
+            },
+            "profiles": [
+                { "name": "fork", "cli_path": "/src/fork/cli.js", "model": "claude-fork" },
+                { "name": "upstream" }
+            ]
+        });
+
+        let base = CliProfile::resolve(&settings, Some(Uuid::new_v4()), None).unwrap();
+        assert_eq!(base.cli_path.as_deref(), Some("claude"));
+        assert!(base.extra_args.is_empty());
+
+        let pinned = CliProfile::resolve(&settings, Some(workspace_id), Some("upstream")).unwrap();
+        assert_eq!(pinned.cli_path.as_deref(), Some("/opt/claude-pinned"));
+        assert_eq!(pinned.extra_args, vec!["--debug"]);
+
+        let fork = CliProfile::resolve(&settings, Some(workspace_id), Some("fork")).unwrap();
+        assert_eq!(
+            fork,
+            CliProfile {
+                cli_path: Some("/src/fork/cli.js".to_string()),
+                model: Some("claude-fork".to_string()),
+                extra_args: vec!["--debug".to_string()],
+            }
+        );
+
+        let err = CliProfile::resolve(&settings, None, Some("nope")).unwrap_err();
+        assert!(err.contains("defined: fork, upstream"), "{}", err);
+    }
+}
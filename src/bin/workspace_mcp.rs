@@ -581,28 +581,316 @@ impl Tool for UpdateInitScriptTool {
     }
 }
 
-fn tool_set() -> HashMap<String, Arc<dyn Tool>> {
+/// Tool: search_missions
+///
+/// Full-text searches the conversations of past missions via the backend API
+/// so a new mission can reuse earlier work.
+struct SearchMissionsTool;
+
+#[async_trait]
+impl Tool for SearchMissionsTool {
+    fn name(&self) -> &str {
+        "search_missions"
+    }
+
+    fn description(&self) -> &str {
+        "Search past missions' conversations (user requests and agent answers) by keyword. \
+         Returns matching mission ids, titles and snippets, best match first. Use this to find \
+         how a similar problem was solved before."
+    }
+
+    fn parameters_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "query": {
+                    "type": "string",
+                    "description": "Keywords that must all appear in one message (case-insensitive)"
+                },
+                "limit": {
+                    "type": "integer",
+                    "description": "Maximum number of missions to return (default: 10)"
+                }
+            },
+            "required": ["query"]
+        })
+    }
+
+    async fn execute(&self, args: Value, _working_dir: &Path) -> anyhow::Result<String> {
+        let query = args["query"]
+            .as_str()
+            .ok_or_else(|| anyhow::anyhow!("Missing 'query' argument"))?;
+        let limit = args["limit"].as_u64().unwrap_or(10);
+
+        // Get backend API URL (defaults to localhost in dev)
+        let api_base = std::env::var("OPEN_AGENT_API_URL")
+            .unwrap_or_else(|_| "http://127.0.0.1:3000".to_string());
+
+        // Get auth token if set
+        let auth_token = std::env::var("OPEN_AGENT_API_TOKEN").ok();
+
+        let client = reqwest::Client::builder()
+            .timeout(std::time::Duration::from_secs(30))
+            .build()?;
+
+        let mut params = vec![("q", query.to_string()), ("limit", limit.to_string())];
+        // Leave out the mission this tool is running in
+        if let Ok(mission_id) = std::env::var("OPEN_AGENT_MISSION_ID") {
+            params.push(("exclude", mission_id));
+        }
+        let mut request = client
+            .get(format!("{}/api/control/missions/search", api_base))
+            .query(&params);
+
+        if let Some(token) = auth_token {
+            request = request.header("Authorization", format!("Bearer {}", token));
+        }
+
+        let response = request.send().await?;
+        let status = response.status();
+
+        if status.is_success() {
+            let hits: Value = response.json().await?;
+            if hits.as_array().is_some_and(|hits| hits.is_empty()) {
+                return Ok(format!("No past missions match '{}'.", query));
+            }
+            Ok(serde_json::to_string_pretty(&hits)?)
+        } else {
+            let error_text = response.text().await.unwrap_or_default();
+            Err(anyhow::anyhow!(
+                "Failed to search missions: {} - {}",
+                status,
+                error_text
+            ))
+        }
+    }
+}
+
+/// Reports the mission's deliverables. They are tracked by the mission runner
+/// in the backend, so this asks its API.
+struct DeliverableStatusTool;
+
+#[async_trait]
+impl Tool for DeliverableStatusTool {
+    fn name(&self) -> &str {
+        "deliverable_status"
+    }
+
+    fn description(&self) -> &str {
+        "Check which of the mission's required deliverables (files, directories, reports) exist yet. Call this before complete_mission to avoid completing with missing outputs."
+    }
+
+    fn parameters_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {},
+            "required": []
+        })
+    }
+
+    async fn execute(&self, _args: Value, _working_dir: &Path) -> anyhow::Result<String> {
+        let Ok(mission_id) = std::env::var("OPEN_AGENT_MISSION_ID") else {
+            return Ok(
+                "Mission control not available in this context. No deliverables are tracked."
+                    .to_string(),
+            );
+        };
+        let api_base = std::env::var("OPEN_AGENT_API_URL")
+            .unwrap_or_else(|_| "http://127.0.0.1:3000".to_string());
+        let auth_token = std::env::var("OPEN_AGENT_API_TOKEN").ok();
+
+        let client = reqwest::Client::builder()
+            .timeout(std::time::Duration::from_secs(30))
+            .build()?;
+        let mut request = client.get(format!(
+            "{}/api/control/missions/{}/deliverables",
+            api_base, mission_id
+        ));
+        if let Some(token) = auth_token {
+            request = request.header("Authorization", format!("Bearer {}", token));
+        }
+
+        let response = request.send().await?;
+        let status = response.status();
+        if status.is_success() {
+            let report: Value = response.json().await?;
+            if report["deliverables"]
+                .as_array()
+                .is_some_and(|items| items.is_empty())
+            {
+                return Ok("No explicit deliverables were detected for this mission.".to_string());
+            }
+            Ok(serde_json::to_string_pretty(&report)?)
+        } else {
+            let error_text = response.text().await.unwrap_or_default();
+            Err(anyhow::anyhow!(
+                "Failed to get deliverable status: {} - {}",
+                status,
+                error_text
+            ))
+        }
+    }
+}
+
+/// `ui_form` for MCP clients: the form is shown by the backend, which waits for
+/// the user's answer in the dashboard.
+struct UiFormTool;
+
+#[async_trait]
+impl Tool for UiFormTool {
+    fn name(&self) -> &str {
+        tools::UiForm.name()
+    }
+
+    fn description(&self) -> &str {
+        tools::UiForm.description()
+    }
+
+    fn parameters_schema(&self) -> Value {
+        tools::UiForm.parameters_schema()
+    }
+
+    async fn execute(&self, args: Value, _working_dir: &Path) -> anyhow::Result<String> {
+        let Ok(mission_id) = std::env::var("OPEN_AGENT_MISSION_ID") else {
+            return Err(anyhow::anyhow!(
+                "ui_form needs a mission to show the form in (OPEN_AGENT_MISSION_ID is not set)"
+            ));
+        };
+        let api_base = std::env::var("OPEN_AGENT_API_URL")
+            .unwrap_or_else(|_| "http://127.0.0.1:3000".to_string());
+        let auth_token = std::env::var("OPEN_AGENT_API_TOKEN").ok();
+
+        // No timeout: the user may take a while to answer.
+        let mut request = reqwest::Client::new()
+            .post(format!(
+                "{}/api/control/missions/{}/ui_form",
+                api_base, mission_id
+            ))
+            .json(&args);
+        if let Some(token) = auth_token {
+            request = request.header("Authorization", format!("Bearer {}", token));
+        }
+
+        let response = request.send().await?;
+        let status = response.status();
+        if status.is_success() {
+            let values: Value = response.json().await?;
+            Ok(values.to_string())
+        } else {
+            let error_text = response.text().await.unwrap_or_default();
+            Err(anyhow::anyhow!(
+                "Failed to show form: {} - {}",
+                status,
+                error_text
+            ))
+        }
+    }
+}
+
+/// Safe-mode gate asking the backend, which waits for the user's decision.
+/// Installed only for missions running in safe mode (`OPEN_AGENT_SAFE_MODE`).
+struct ApiSafeModeGate;
+
+#[async_trait]
+impl tools::safe_mode::ConfirmAction for ApiSafeModeGate {
+    async fn confirm(&self, tool_name: &str, args: &Value, _reason: &str) -> anyhow::Result<bool> {
+        let mission_id = std::env::var("OPEN_AGENT_MISSION_ID")
+            .map_err(|_| anyhow::anyhow!("safe mode is on but OPEN_AGENT_MISSION_ID is not set"))?;
+        let api_base = std::env::var("OPEN_AGENT_API_URL")
+            .unwrap_or_else(|_| "http://127.0.0.1:3000".to_string());
+        let auth_token = std::env::var("OPEN_AGENT_API_TOKEN").ok();
+        tools::safe_mode::confirm_via_api(
+            &api_base,
+            auth_token.as_deref(),
+            &mission_id,
+            tool_name,
+            args,
+        )
+        .await
+    }
+}
+
+fn tool_set() -> tools::ToolRegistry {
     let mut tools: HashMap<String, Arc<dyn Tool>> = HashMap::new();
 
     tools.insert("read_file".to_string(), Arc::new(tools::ReadFile));
+    tools.insert(
+        "read_many_files".to_string(),
+        Arc::new(tools::ReadManyFiles),
+    );
     tools.insert("write_file".to_string(), Arc::new(tools::WriteFile));
+    tools.insert("edit_file".to_string(), Arc::new(tools::EditFile));
     tools.insert("delete_file".to_string(), Arc::new(tools::DeleteFile));
+    tools.insert("move_file".to_string(), Arc::new(tools::MoveFile));
+    tools.insert("copy_file".to_string(), Arc::new(tools::CopyFile));
+    tools.insert("normalize_file".to_string(), Arc::new(tools::NormalizeFile));
+    tools.insert("yaml_path".to_string(), Arc::new(tools::YamlPath));
+    tools.insert("config_promote".to_string(), Arc::new(tools::ConfigPromote));
     tools.insert("list_directory".to_string(), Arc::new(tools::ListDirectory));
     tools.insert("search_files".to_string(), Arc::new(tools::SearchFiles));
     tools.insert("grep_search".to_string(), Arc::new(tools::GrepSearch));
+    tools.insert("code_metrics".to_string(), Arc::new(tools::CodeMetrics));
+    tools.insert("logs".to_string(), Arc::new(tools::Logs));
+    tools.insert("run_command".to_string(), Arc::new(tools::RunCommand));
+    tools.insert(
+        "terminal_session".to_string(),
+        Arc::new(tools::TerminalSession::default()),
+    );
+    tools.insert("check_compile".to_string(), Arc::new(tools::CheckCompile));
+    tools.insert("run_migrations".to_string(), Arc::new(tools::RunMigrations));
     tools.insert("fetch_url".to_string(), Arc::new(tools::FetchUrl));
+    tools.insert(
+        "http_request".to_string(),
+        Arc::new(tools::HttpRequest::default()),
+    );
+    tools.insert("screenshot_url".to_string(), Arc::new(tools::ScreenshotUrl));
+    tools.insert(
+        "render_markdown".to_string(),
+        Arc::new(tools::RenderMarkdown),
+    );
+    tools.insert("git_branch".to_string(), Arc::new(tools::GitBranch));
+    tools.insert("git_checkout".to_string(), Arc::new(tools::GitCheckout));
+    tools.insert("git_commit".to_string(), Arc::new(tools::GitCommit));
+    tools.insert("git_overview".to_string(), Arc::new(tools::GitOverview));
+    tools.insert("git_remote".to_string(), Arc::new(tools::GitRemote));
+    tools.insert("git_stash".to_string(), Arc::new(tools::GitStash));
+    tools.insert("git_worktree".to_string(), Arc::new(tools::GitWorktree));
     tools.insert("update_skill".to_string(), Arc::new(UpdateSkillTool));
     tools.insert(
         "update_init_script".to_string(),
         Arc::new(UpdateInitScriptTool),
     );
-
-    tools
+    tools.insert("search_missions".to_string(), Arc::new(SearchMissionsTool));
+    tools.insert(
+        "deliverable_status".to_string(),
+        Arc::new(DeliverableStatusTool),
+    );
+    tools.insert("ui_form".to_string(), Arc::new(UiFormTool));
+    tools.insert("todo_add".to_string(), Arc::new(tools::TodoAdd));
+    tools.insert("todo_complete".to_string(), Arc::new(tools::TodoComplete));
+    tools.insert("todo_list".to_string(), Arc::new(tools::TodoList));
+
+    // Calls go through the registry so the allowlist, rate limits and
+    // `OPEN_AGENT_TOOL_LOG` observer apply here too.
+    let registry = tools::ToolRegistry::from_tools(tools);
+    let safe_mode = std::env::var("OPEN_AGENT_SAFE_MODE")
+        .map(|v| {
+            matches!(
+                v.trim().to_lowercase().as_str(),
+                "1" | "true" | "yes" | "on"
+            )
+        })
+        .unwrap_or(false);
+    if safe_mode {
+        registry.with_safe_mode(ApiSafeModeGate)
+    } else {
+        registry
+    }
 }
 
-fn tool_definitions(tools: &HashMap<String, Arc<dyn Tool>>) -> Vec<ToolDefinition> {
+fn tool_definitions(tools: &tools::ToolRegistry) -> Vec<ToolDefinition> {
     let mut defs = Vec::new();
-    for tool in tools.values() {
+    for tool in tools.allowed_tools() {
         defs.push(ToolDefinition {
             name: tool.name().to_string(),
             description: tool.description().to_string(),
@@ -615,21 +903,23 @@ fn tool_definitions(tools: &HashMap<String, Arc<dyn Tool>>) -> Vec<ToolDefinitio
 
 fn execute_tool(
     runtime: &tokio::runtime::Runtime,
-    tools: &HashMap<String, Arc<dyn Tool>>,
+    tools: &tools::ToolRegistry,
     name: &str,
     args: &Value,
     working_dir: &Path,
 ) -> ToolResult {
-    let Some(tool) = tools.get(name) else {
-        return ToolResult {
-            content: vec![ToolContent::Text {
-                text: format!("Unknown tool: {}", name),
-            }],
-            is_error: true,
-        };
+    let result = match std::env::var("OPEN_AGENT_MISSION_ID") {
+        Ok(mission_id) => runtime.block_on(async {
+            let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+            let forwarder = tokio::spawn(forward_tool_output(mission_id, rx));
+            let result = tools
+                .execute_with_output(name, args.clone(), working_dir, tx)
+                .await;
+            let _ = forwarder.await;
+            result
+        }),
+        Err(_) => runtime.block_on(tools.execute(name, args.clone(), working_dir)),
     };
-
-    let result = runtime.block_on(tool.execute(args.clone(), working_dir));
     match result {
         Ok(text) => ToolResult {
             content: vec![ToolContent::Text { text }],
@@ -644,10 +934,48 @@ fn execute_tool(
     }
 }
 
+/// Post a running tool's output lines to the backend, which shows them as
+/// `thinking` events of the mission. Lines are batched to keep requests few;
+/// delivery is best effort and never fails the tool.
+async fn forward_tool_output(
+    mission_id: String,
+    mut rx: tokio::sync::mpsc::UnboundedReceiver<String>,
+) {
+    let api_base =
+        std::env::var("OPEN_AGENT_API_URL").unwrap_or_else(|_| "http://127.0.0.1:3000".to_string());
+    let auth_token = std::env::var("OPEN_AGENT_API_TOKEN").ok();
+    let Ok(client) = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(10))
+        .build()
+    else {
+        while rx.recv().await.is_some() {}
+        return;
+    };
+    let url = format!(
+        "{}/api/control/missions/{}/tool_output",
+        api_base, mission_id
+    );
+
+    while let Some(first) = rx.recv().await {
+        tokio::time::sleep(std::time::Duration::from_millis(250)).await;
+        let mut lines = vec![first];
+        while let Ok(line) = rx.try_recv() {
+            lines.push(line);
+        }
+        let mut request = client.post(&url).json(&json!({ "lines": lines }));
+        if let Some(token) = auth_token.as_ref() {
+            request = request.header("Authorization", format!("Bearer {}", token));
+        }
+        if let Err(e) = request.send().await {
+            eprintln!("[workspace-mcp] Failed to forward tool output: {}", e);
+        }
+    }
+}
+
 fn handle_request(
     request: &JsonRpcRequest,
     runtime: &tokio::runtime::Runtime,
-    tools: &HashMap<String, Arc<dyn Tool>>,
+    tools: &tools::ToolRegistry,
     working_dir: &Arc<RwLock<PathBuf>>,
 ) -> Option<JsonRpcResponse> {
     match request.method.as_str() {
@@ -11,9 +11,21 @@
 //! - `OPENCODE_AGENT` - Optional. Default OpenCode agent name (e.g., `Sisyphus`, `oracle`).
 //! - `OPENCODE_PERMISSIVE` - Optional. If true, auto-allows all permissions for OpenCode sessions (default: true).
 //! - `OPEN_AGENT_USERS` - Optional. JSON array of user accounts for multi-user auth.
+//!   Each account may set `allowed_models` to restrict which models it can run.
 //! - `LIBRARY_GIT_SSH_KEY` - Optional. SSH key path for library git operations. If set to a path, uses that key.
 //!   If set to empty string, ignores ~/.ssh/config (useful when the config specifies a non-existent key).
 //!   If unset, uses default SSH behavior.
+//! - `MAX_UPLOAD_BYTES` - Optional. Maximum size of a single file upload via `/api/fs/upload`. Defaults to 10 GiB.
+//!   Also sets the request body limit of the upload routes.
+//! - `BACKEND_BREAKER_FAILURES` - Optional. Backend failures within the window that open its circuit breaker.
+//!   Defaults to `5`; `0` disables the breaker.
+//! - `BACKEND_BREAKER_WINDOW_SECS` / `BACKEND_BREAKER_COOLDOWN_SECS` - Optional. Failure window (default `300`)
+//!   and how long an open breaker rejects turns before a probe (default `60`).
+//! - `MISSION_STALL_TIMEOUT_SECS` - Optional. Seconds without events before a running mission is reported
+//!   as stalled (default `60`). `MISSION_TOOL_STALL_TIMEOUT_SECS` applies instead while a tool call is
+//!   in flight (default `900`).
+//! - `PROGRESS_SUMMARY_INTERVAL_SECS` - Optional. Seconds between one-line progress summaries of running
+//!   missions (default `60`; `0` disables them).
 //! - `LIBRARY_REMOTE` - Optional. Initial library remote URL (can be changed via Settings in the dashboard).
 //!   This environment variable is used as the initial default when no settings file exists.
 //!
@@ -22,8 +34,12 @@
 
 use serde::Deserialize;
 use std::path::PathBuf;
+use std::time::Duration;
 use thiserror::Error;
 
+/// Default maximum upload size (10 GiB, matching the upload route body limit).
+pub const DEFAULT_MAX_UPLOAD_BYTES: u64 = 10 * 1024 * 1024 * 1024;
+
 #[derive(Debug, Error)]
 pub enum ConfigError {
     #[error("Missing required environment variable: {0}")]
@@ -178,6 +194,107 @@ impl ContextConfig {
     }
 }
 
+/// Per-backend circuit breaker configuration.
+///
+/// A backend's breaker opens after `failure_threshold` backend failures within
+/// `window`; new turns are rejected until `cooldown` has elapsed, after which a
+/// single probe turn decides whether it closes again.
+#[derive(Debug, Clone)]
+pub struct CircuitBreakerConfig {
+    /// Failures within the window that open the breaker (0 = disabled)
+    pub failure_threshold: usize,
+    /// Sliding window in which failures are counted
+    pub window: Duration,
+    /// Time an open breaker waits before admitting a probe turn
+    pub cooldown: Duration,
+}
+
+impl Default for CircuitBreakerConfig {
+    fn default() -> Self {
+        Self {
+            failure_threshold: 5,
+            window: Duration::from_secs(300),
+            cooldown: Duration::from_secs(60),
+        }
+    }
+}
+
+impl CircuitBreakerConfig {
+    /// Load from environment variables, falling back to defaults.
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+
+        if let Ok(v) = std::env::var("BACKEND_BREAKER_FAILURES") {
+            if let Ok(n) = v.parse() {
+                config.failure_threshold = n;
+            }
+        }
+        if let Ok(v) = std::env::var("BACKEND_BREAKER_WINDOW_SECS") {
+            if let Ok(n) = v.parse() {
+                config.window = Duration::from_secs(n);
+            }
+        }
+        if let Ok(v) = std::env::var("BACKEND_BREAKER_COOLDOWN_SECS") {
+            if let Ok(n) = v.parse() {
+                config.cooldown = Duration::from_secs(n);
+            }
+        }
+
+        config
+    }
+}
+
+/// Mission monitoring configuration.
+///
+/// A running mission is reported as stalled after `stall_timeout` without
+/// events, or `tool_stall_timeout` while one of its tool calls (a long build,
+/// a large download) is still running. Every `progress_summary_interval` a
+/// one-line progress summary is broadcast for missions with new activity.
+#[derive(Debug, Clone, Copy)]
+pub struct MissionConfig {
+    /// Quiet time before a mission waiting on the model is considered stalled
+    pub stall_timeout: Duration,
+    /// Quiet time before a mission waiting on a tool call is considered stalled
+    pub tool_stall_timeout: Duration,
+    /// Interval between progress summaries (zero = disabled)
+    pub progress_summary_interval: Duration,
+}
+
+impl Default for MissionConfig {
+    fn default() -> Self {
+        Self {
+            stall_timeout: Duration::from_secs(60),
+            tool_stall_timeout: Duration::from_secs(900),
+            progress_summary_interval: Duration::from_secs(60),
+        }
+    }
+}
+
+impl MissionConfig {
+    /// Load from environment variables, falling back to defaults.
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+
+        if let Ok(v) = std::env::var("MISSION_STALL_TIMEOUT_SECS") {
+            if let Ok(n) = v.parse() {
+                config.stall_timeout = Duration::from_secs(n);
+            }
+        }
+        if let Ok(v) = std::env::var("MISSION_TOOL_STALL_TIMEOUT_SECS") {
+            if let Ok(n) = v.parse() {
+                config.tool_stall_timeout = Duration::from_secs(n);
+            }
+        }
+        if let Ok(v) = std::env::var("PROGRESS_SUMMARY_INTERVAL_SECS") {
+            if let Ok(n) = v.parse() {
+                config.progress_summary_interval = Duration::from_secs(n);
+            }
+        }
+
+        config
+    }
+}
+
 /// Agent configuration.
 #[derive(Debug, Clone)]
 pub struct Config {
@@ -197,12 +314,20 @@ pub struct Config {
     /// Maximum iterations for the agent loop
     pub max_iterations: usize,
 
+    /// Maximum tool calls within a single turn (0 = unlimited).
+    /// Enforced for the Claude Code and Amp backends only.
+    pub max_tool_calls: usize,
+
     /// Hours of inactivity after which an active mission is auto-closed (0 = disabled)
     pub stale_mission_hours: u64,
 
-    /// Maximum number of missions that can run in parallel (1 = sequential only)
+    /// Maximum number of missions that can run at once across all users (1 = sequential only).
+    /// Further missions wait in the queue; adjustable at runtime via the API.
     pub max_parallel_missions: usize,
 
+    /// Maximum size in bytes of a single file upload (enforced while streaming)
+    pub max_upload_bytes: u64,
+
     /// Development mode (disables auth; more permissive defaults)
     pub dev_mode: bool,
 
@@ -212,6 +337,12 @@ pub struct Config {
     /// Context injection configuration
     pub context: ContextConfig,
 
+    /// Per-backend circuit breaker configuration
+    pub circuit_breaker: CircuitBreakerConfig,
+
+    /// Mission health-check configuration
+    pub mission: MissionConfig,
+
     /// DEPRECATED: OpenCode server base URL (no longer used for mission execution)
     pub opencode_base_url: String,
 
@@ -269,6 +400,9 @@ pub struct UserAccount {
     pub id: String,
     pub username: String,
     pub password: String,
+    /// Models this user may run (`provider/model`, or `provider/*`). Empty = any model.
+    #[serde(default)]
+    pub allowed_models: Vec<String>,
 }
 
 impl AuthConfig {
@@ -341,6 +475,14 @@ impl Config {
                 ConfigError::InvalidValue("MAX_ITERATIONS".to_string(), format!("{}", e))
             })?;
 
+        // Maximum tool calls per turn before the model must answer (default: 0 = unlimited)
+        let max_tool_calls = std::env::var("MAX_TOOL_CALLS_PER_TURN")
+            .unwrap_or_else(|_| "0".to_string())
+            .parse()
+            .map_err(|e| {
+                ConfigError::InvalidValue("MAX_TOOL_CALLS_PER_TURN".to_string(), format!("{}", e))
+            })?;
+
         // Hours of inactivity after which an active mission is auto-closed.
         // Default: 2 hours. Set to 0 to disable.
         // Note: orphaned missions (process died) are detected every 5 minutes
@@ -360,6 +502,16 @@ impl Config {
                 ConfigError::InvalidValue("MAX_PARALLEL_MISSIONS".to_string(), format!("{}", e))
             })?;
 
+        let max_upload_bytes = std::env::var("MAX_UPLOAD_BYTES")
+            .ok()
+            .map(|v| {
+                v.parse::<u64>().map_err(|e| {
+                    ConfigError::InvalidValue("MAX_UPLOAD_BYTES".to_string(), format!("{}", e))
+                })
+            })
+            .transpose()?
+            .unwrap_or(DEFAULT_MAX_UPLOAD_BYTES);
+
         let dev_mode = std::env::var("DEV_MODE")
             .ok()
             .map(|v| {
@@ -445,6 +597,8 @@ impl Config {
         }
 
         let context = ContextConfig::from_env();
+        let circuit_breaker = CircuitBreakerConfig::from_env();
+        let mission = MissionConfig::from_env();
 
         // Library configuration
         // Note: library_remote is now managed via the settings module (persisted to disk)
@@ -458,11 +612,15 @@ impl Config {
             host,
             port,
             max_iterations,
+            max_tool_calls,
             stale_mission_hours,
             max_parallel_missions,
+            max_upload_bytes,
             dev_mode,
             auth,
             context,
+            circuit_breaker,
+            mission,
             opencode_base_url,
             opencode_agent,
             opencode_permissive,
@@ -470,6 +628,11 @@ impl Config {
         })
     }
 
+    /// Tool-call limit per turn, `None` when unlimited.
+    pub fn max_tool_calls_per_turn(&self) -> Option<usize> {
+        Some(self.max_tool_calls).filter(|&max| max > 0)
+    }
+
     /// Create a config with custom values (useful for testing).
     pub fn new(working_dir: PathBuf) -> Self {
         let library_path = working_dir.join(".openagent/library");
@@ -479,11 +642,15 @@ impl Config {
             host: "127.0.0.1".to_string(),
             port: 3000,
             max_iterations: 50,
+            max_tool_calls: 0,
             stale_mission_hours: 2,
             max_parallel_missions: 1,
+            max_upload_bytes: DEFAULT_MAX_UPLOAD_BYTES,
             dev_mode: true,
             auth: AuthConfig::default(),
             context: ContextConfig::default(),
+            circuit_breaker: CircuitBreakerConfig::default(),
+            mission: MissionConfig::default(),
             opencode_base_url: "http://127.0.0.1:4096".to_string(),
             opencode_agent: None,
             opencode_permissive: true,
@@ -2,6 +2,13 @@
 //!
 //! This module provides a single source of truth for computing API costs
 //! from token usage across all backends (Claude Code, Amp, OpenCode).
+//!
+//! Models without known pricing are costed with a fallback price configured via
+//! `FALLBACK_INPUT_PRICE_PER_MTOK` / `FALLBACK_OUTPUT_PRICE_PER_MTOK` (USD per
+//! 1M tokens). Without it, their usage costs 0.
+
+use std::collections::HashSet;
+use std::sync::{Mutex, OnceLock};
 
 /// Model pricing in nanodollars per token (1 USD = 1_000_000_000 nanodollars).
 /// Using nanodollars avoids floating-point rounding issues.
@@ -18,7 +25,7 @@ pub struct ModelPricing {
 }
 
 /// Token usage from an API call.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
 pub struct TokenUsage {
     pub input_tokens: u64,
     pub output_tokens: u64,
@@ -31,6 +38,31 @@ impl TokenUsage {
     pub fn has_usage(&self) -> bool {
         self.input_tokens > 0 || self.output_tokens > 0
     }
+
+    /// Input tokens including cache reads and writes.
+    pub fn prompt_tokens(&self) -> u64 {
+        self.input_tokens
+            + self.cache_creation_input_tokens.unwrap_or(0)
+            + self.cache_read_input_tokens.unwrap_or(0)
+    }
+
+    /// Add another call's usage to this one.
+    pub fn add(&mut self, other: &TokenUsage) {
+        fn sum(a: Option<u64>, b: Option<u64>) -> Option<u64> {
+            match (a, b) {
+                (None, None) => None,
+                (a, b) => Some(a.unwrap_or(0) + b.unwrap_or(0)),
+            }
+        }
+        self.input_tokens += other.input_tokens;
+        self.output_tokens += other.output_tokens;
+        self.cache_creation_input_tokens = sum(
+            self.cache_creation_input_tokens,
+            other.cache_creation_input_tokens,
+        );
+        self.cache_read_input_tokens =
+            sum(self.cache_read_input_tokens, other.cache_read_input_tokens);
+    }
 }
 
 /// Normalize model names to canonical form for pricing lookup.
@@ -221,19 +253,92 @@ pub fn pricing_for_model(model: &str) -> Option<ModelPricing> {
     }
 }
 
+/// Parse a USD-per-1M-tokens price into nanodollars per token.
+fn parse_price_per_mtok(value: &str) -> Option<u64> {
+    let usd = value.trim().parse::<f64>().ok()?;
+    if !usd.is_finite() || usd < 0.0 {
+        return None;
+    }
+    // $1/1M tokens = 1_000 nanodollars per token
+    Some((usd * 1_000.0).round() as u64)
+}
+
+/// Build fallback pricing from input/output prices in USD per 1M tokens.
+fn fallback_pricing_from(input: Option<&str>, output: Option<&str>) -> Option<ModelPricing> {
+    let input = input.and_then(parse_price_per_mtok);
+    let output = output.and_then(parse_price_per_mtok);
+    if input.is_none() && output.is_none() {
+        return None;
+    }
+    Some(ModelPricing {
+        input_nano_per_token: input.unwrap_or(0),
+        output_nano_per_token: output.unwrap_or(0),
+        cache_create_nano_per_token: None,
+        cache_read_nano_per_token: None,
+    })
+}
+
+/// Fallback pricing for models without known pricing, read once from
+/// `FALLBACK_INPUT_PRICE_PER_MTOK` and `FALLBACK_OUTPUT_PRICE_PER_MTOK`.
+pub fn fallback_pricing() -> Option<ModelPricing> {
+    static FALLBACK: OnceLock<Option<ModelPricing>> = OnceLock::new();
+    *FALLBACK.get_or_init(|| {
+        fallback_pricing_from(
+            std::env::var("FALLBACK_INPUT_PRICE_PER_MTOK")
+                .ok()
+                .as_deref(),
+            std::env::var("FALLBACK_OUTPUT_PRICE_PER_MTOK")
+                .ok()
+                .as_deref(),
+        )
+    })
+}
+
+/// Whether this is the first time `model` falls back (so the warning is logged once).
+fn first_fallback_for(model: &str) -> bool {
+    static WARNED: OnceLock<Mutex<HashSet<String>>> = OnceLock::new();
+    WARNED
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(model.to_string())
+}
+
 /// Calculate cost in cents from token usage and model.
 ///
-/// Returns 0 if:
-/// - Model is unknown (logs a warning once per unknown model)
-/// - No token usage provided
+/// Unknown models are costed with [`fallback_pricing`] (a warning is logged
+/// once per model). Returns 0 if no token usage is provided, or if the model is
+/// unknown and no fallback is configured.
 pub fn cost_cents_from_usage(model: &str, usage: &TokenUsage) -> u64 {
+    cost_cents_with_fallback(model, usage, fallback_pricing())
+}
+
+fn cost_cents_with_fallback(
+    model: &str,
+    usage: &TokenUsage,
+    fallback: Option<ModelPricing>,
+) -> u64 {
     if !usage.has_usage() {
         return 0;
     }
 
-    let Some(pricing) = pricing_for_model(model) else {
-        // Log warning for unknown models (in production, consider rate-limiting this)
-        tracing::warn!(model = %model, "Unknown model for cost calculation, using 0 cost");
+    let Some(pricing) = pricing_for_model(model).or_else(|| {
+        if first_fallback_for(model) {
+            match &fallback {
+                Some(p) => tracing::warn!(
+                    model = %model,
+                    input_nano_per_token = p.input_nano_per_token,
+                    output_nano_per_token = p.output_nano_per_token,
+                    "Unknown model for cost calculation, using configured fallback pricing"
+                ),
+                None => tracing::warn!(
+                    model = %model,
+                    "Unknown model for cost calculation and no fallback pricing configured, using 0 cost"
+                ),
+            }
+        }
+        fallback
+    }) else {
         return 0;
     };
 
@@ -366,4 +471,32 @@ mod tests {
         let cost = cost_cents_from_usage("completely-unknown-model", &usage);
         assert_eq!(cost, 0);
     }
+
+    #[test]
+    fn test_configured_fallback_used_for_unknown_model() {
+        // $2/1M input, $10/1M output
+        let fallback = fallback_pricing_from(Some("2"), Some("10.0")).unwrap();
+        assert_eq!(fallback.input_nano_per_token, 2_000);
+        assert_eq!(fallback.output_nano_per_token, 10_000);
+        assert!(fallback_pricing_from(Some("abc"), None).is_none());
+
+        let usage = TokenUsage {
+            input_tokens: 100_000,
+            output_tokens: 10_000,
+            cache_creation_input_tokens: None,
+            cache_read_input_tokens: None,
+        };
+        // (100000 * 2000 + 10000 * 10000) / 10_000_000 = 30 cents
+        let cost = cost_cents_with_fallback("self-hosted-model", &usage, Some(fallback));
+        assert_eq!(cost, 30);
+        assert_eq!(
+            cost_cents_with_fallback("self-hosted-model", &usage, None),
+            0
+        );
+        // Known models ignore the fallback.
+        assert_eq!(
+            cost_cents_with_fallback("claude-3-5-sonnet", &usage, Some(fallback)),
+            45
+        );
+    }
 }
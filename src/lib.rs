@@ -42,9 +42,11 @@ pub mod config;
 pub mod cost;
 pub mod library;
 pub mod mcp;
+pub mod mission_log;
 pub mod nspawn;
 pub mod opencode;
 pub mod opencode_config;
+pub mod schedules;
 pub mod secrets;
 pub mod settings;
 pub mod skills_registry;
@@ -3,15 +3,20 @@
 //! Uses AES-256-GCM with a static key stored in PRIVATE_KEY environment variable.
 //! Encrypted values are wrapped in `<encrypted v="1">BASE64</encrypted>` format
 //! for autodetection. Plaintext values (no wrapper) are treated as legacy.
+//!
+//! Whole files (e.g. `.env`-style templates) use a chunked binary format with
+//! the same key; see [`encrypt_stream`].
 
 use aes_gcm::{
-    aead::{Aead, KeyInit},
+    aead::{Aead, KeyInit, Payload},
     Aes256Gcm, Nonce,
 };
 use anyhow::{anyhow, Context, Result};
 use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
 use rand::RngCore;
 use std::collections::HashMap;
+use std::io::{Read, Write};
+use std::path::Path;
 use tokio::fs;
 
 /// Key length in bytes (256 bits for AES-256)
@@ -23,17 +28,24 @@ const NONCE_LENGTH: usize = 12;
 /// Environment variable name for the encryption key
 pub const PRIVATE_KEY_ENV: &str = "PRIVATE_KEY";
 
-/// Current encryption format version
+/// Current encryption format version (used for new values)
 const ENCRYPTION_VERSION: &str = "1";
 
+/// Encryption format versions this build can decrypt
+const SUPPORTED_VERSIONS: &[&str] = &[ENCRYPTION_VERSION];
+
 /// Wrapper prefix for encrypted values
 const ENCRYPTED_PREFIX: &str = "<encrypted v=\"";
 const ENCRYPTED_SUFFIX: &str = "</encrypted>";
 
-/// Check if a value is encrypted (has the wrapper format).
+/// Check if a value is encrypted (has the wrapper format with a supported version).
 pub fn is_encrypted(value: &str) -> bool {
-    let trimmed = value.trim();
-    trimmed.starts_with(ENCRYPTED_PREFIX) && trimmed.ends_with(ENCRYPTED_SUFFIX)
+    encryption_version(value).is_some_and(|v| SUPPORTED_VERSIONS.contains(&v))
+}
+
+/// Version attribute of an encrypted value (`"1"` for `<encrypted v="1">`), if wrapped.
+pub fn encryption_version(value: &str) -> Option<&str> {
+    parse_encrypted(value).map(|(version, _)| version)
 }
 
 /// Parse an encrypted value, returning (version, base64_payload).
@@ -99,15 +111,18 @@ pub fn decrypt_value(key: &[u8; KEY_LENGTH], value: &str) -> Result<String> {
         None => return Ok(value.to_string()),
     };
 
-    // Validate version
-    if version != ENCRYPTION_VERSION {
-        return Err(anyhow!(
-            "Unsupported encryption version: {}. Expected: {}",
+    match version {
+        "1" => decrypt_v1(key, payload),
+        _ => Err(anyhow!(
+            "Unsupported encryption version: {}. Supported: {}",
             version,
-            ENCRYPTION_VERSION
-        ));
+            SUPPORTED_VERSIONS.join(", ")
+        )),
     }
+}
 
+/// Decrypt a version 1 payload: BASE64(nonce || AES-256-GCM ciphertext).
+fn decrypt_v1(key: &[u8; KEY_LENGTH], payload: &str) -> Result<String> {
     // Decode base64
     let combined = BASE64
         .decode(payload)
@@ -158,6 +173,43 @@ pub fn decrypt_env_vars(
     Ok(decrypted)
 }
 
+/// Re-encrypt a value under `new_key`.
+///
+/// Encrypted values are decrypted with `old_key` first; plaintext values are
+/// encrypted as-is. The result always uses the current format version.
+pub fn reencrypt_string(
+    old_key: &[u8; KEY_LENGTH],
+    new_key: &[u8; KEY_LENGTH],
+    value: &str,
+) -> Result<String> {
+    let plaintext = decrypt_value(old_key, value)?;
+    encrypt_value(new_key, &plaintext)
+}
+
+/// Re-encrypt every value of `values` from `old_key` to `new_key` (see [`reencrypt_string`]).
+///
+/// All-or-nothing: if any value fails to decrypt, `values` is left unchanged
+/// and the error names the offending entry. Returns the number of values that
+/// were encrypted under `old_key`.
+pub fn rotate_keys(
+    old_key: &[u8; KEY_LENGTH],
+    new_key: &[u8; KEY_LENGTH],
+    values: &mut HashMap<String, String>,
+) -> Result<usize> {
+    let mut rotated = HashMap::with_capacity(values.len());
+    let mut previously_encrypted = 0;
+    for (name, value) in values.iter() {
+        if is_encrypted(value) {
+            previously_encrypted += 1;
+        }
+        let reencrypted = reencrypt_string(old_key, new_key, value)
+            .with_context(|| format!("Failed to re-encrypt {}", name))?;
+        rotated.insert(name.clone(), reencrypted);
+    }
+    *values = rotated;
+    Ok(previously_encrypted)
+}
+
 /// Load the encryption key from environment.
 /// Returns None if PRIVATE_KEY is not set.
 pub fn load_private_key_from_env() -> Result<Option<[u8; KEY_LENGTH]>> {
@@ -418,6 +470,248 @@ pub fn decrypt_content_tags(key: &[u8; KEY_LENGTH], content: &str) -> Result<Str
     Ok(result)
 }
 
+// ─────────────────────────────────────────────────────────────────────────────
+// File encryption (for whole template files)
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Header of an encrypted file (format version 1).
+const FILE_MAGIC: &[u8; 8] = b"OAENCF01";
+
+/// Plaintext bytes per encrypted chunk.
+const FILE_CHUNK_SIZE: usize = 64 * 1024;
+
+/// AES-GCM authentication tag length in bytes
+const TAG_LENGTH: usize = 16;
+
+/// Check if data starts with the encrypted file header.
+pub fn is_encrypted_bytes(data: &[u8]) -> bool {
+    data.starts_with(FILE_MAGIC)
+}
+
+/// Fill `buf` from `reader`, stopping early only at end of input.
+fn read_full(reader: &mut impl Read, buf: &mut [u8]) -> std::io::Result<usize> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..]) {
+            Ok(0) => break,
+            Ok(n) => filled += n,
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(filled)
+}
+
+/// Associated data binding a chunk to its position: index (u64 BE) and final flag.
+fn chunk_aad(index: u64, last: bool) -> [u8; 9] {
+    let mut aad = [0u8; 9];
+    aad[..8].copy_from_slice(&index.to_be_bytes());
+    aad[8] = u8::from(last);
+    aad
+}
+
+/// Encrypt everything read from `reader` into `writer`, one chunk at a time.
+///
+/// Output is `OAENCF01` followed by chunks of `u32 BE length || nonce || ciphertext`,
+/// each holding up to 64 KiB of plaintext under a fresh nonce. The chunk index
+/// and a final-chunk flag are authenticated, so reordered, dropped or
+/// truncated chunks fail to decrypt. The final chunk is shorter than 64 KiB
+/// (empty if needed). Input that already starts with the header is copied
+/// unchanged.
+pub fn encrypt_stream<R: Read, W: Write>(
+    key: &[u8; KEY_LENGTH],
+    mut reader: R,
+    mut writer: W,
+) -> Result<()> {
+    let cipher =
+        Aes256Gcm::new_from_slice(key).map_err(|e| anyhow!("Failed to create cipher: {}", e))?;
+    let mut buf = vec![0u8; FILE_CHUNK_SIZE];
+    let mut index: u64 = 0;
+
+    loop {
+        let n = read_full(&mut reader, &mut buf).context("Failed to read plaintext")?;
+        if index == 0 {
+            // Don't double-encrypt
+            if is_encrypted_bytes(&buf[..n]) {
+                writer.write_all(&buf[..n])?;
+                std::io::copy(&mut reader, &mut writer)?;
+                writer.flush()?;
+                return Ok(());
+            }
+            writer.write_all(FILE_MAGIC)?;
+        }
+
+        let last = n < FILE_CHUNK_SIZE;
+        let mut nonce_bytes = [0u8; NONCE_LENGTH];
+        rand::thread_rng().fill_bytes(&mut nonce_bytes);
+        let aad = chunk_aad(index, last);
+        let ciphertext = cipher
+            .encrypt(
+                Nonce::from_slice(&nonce_bytes),
+                Payload {
+                    msg: &buf[..n],
+                    aad: &aad,
+                },
+            )
+            .map_err(|e| anyhow!("Encryption failed: {}", e))?;
+
+        writer.write_all(&((NONCE_LENGTH + ciphertext.len()) as u32).to_be_bytes())?;
+        writer.write_all(&nonce_bytes)?;
+        writer.write_all(&ciphertext)?;
+        if last {
+            break;
+        }
+        index += 1;
+    }
+
+    writer.flush()?;
+    Ok(())
+}
+
+/// Decrypt a stream produced by [`encrypt_stream`].
+///
+/// Input without the encrypted file header is copied unchanged. On error,
+/// chunks before the failing one may already have been written.
+pub fn decrypt_stream<R: Read, W: Write>(
+    key: &[u8; KEY_LENGTH],
+    mut reader: R,
+    mut writer: W,
+) -> Result<()> {
+    // Passthrough plaintext input
+    let mut magic = [0u8; FILE_MAGIC.len()];
+    let n = read_full(&mut reader, &mut magic).context("Failed to read encrypted data")?;
+    if !is_encrypted_bytes(&magic[..n]) {
+        writer.write_all(&magic[..n])?;
+        std::io::copy(&mut reader, &mut writer)?;
+        writer.flush()?;
+        return Ok(());
+    }
+
+    let cipher =
+        Aes256Gcm::new_from_slice(key).map_err(|e| anyhow!("Failed to create cipher: {}", e))?;
+    let max_chunk = NONCE_LENGTH + FILE_CHUNK_SIZE + TAG_LENGTH;
+    let mut chunk = Vec::with_capacity(max_chunk);
+    let mut index: u64 = 0;
+
+    loop {
+        let mut len_bytes = [0u8; 4];
+        if read_full(&mut reader, &mut len_bytes)? < len_bytes.len() {
+            return Err(anyhow!("Encrypted data is truncated"));
+        }
+        let len = u32::from_be_bytes(len_bytes) as usize;
+        if !(NONCE_LENGTH + TAG_LENGTH..=max_chunk).contains(&len) {
+            return Err(anyhow!("Encrypted data has an invalid chunk length"));
+        }
+        chunk.resize(len, 0);
+        if read_full(&mut reader, &mut chunk)? < len {
+            return Err(anyhow!("Encrypted data is truncated"));
+        }
+
+        let (nonce_bytes, ciphertext) = chunk.split_at(NONCE_LENGTH);
+        let last = ciphertext.len() - TAG_LENGTH < FILE_CHUNK_SIZE;
+        let aad = chunk_aad(index, last);
+        let plaintext = cipher
+            .decrypt(
+                Nonce::from_slice(nonce_bytes),
+                Payload {
+                    msg: ciphertext,
+                    aad: &aad,
+                },
+            )
+            .map_err(|_| anyhow!("Decryption failed: invalid key or corrupted data"))?;
+        writer.write_all(&plaintext)?;
+        if last {
+            break;
+        }
+        index += 1;
+    }
+
+    if read_full(&mut reader, &mut [0u8; 1])? > 0 {
+        return Err(anyhow!("Unexpected data after the final encrypted chunk"));
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// Encrypt a byte buffer into the chunked file format.
+pub fn encrypt_bytes(key: &[u8; KEY_LENGTH], data: &[u8]) -> Result<Vec<u8>> {
+    let chunks = data.len() / FILE_CHUNK_SIZE + 1;
+    let mut out = Vec::with_capacity(
+        FILE_MAGIC.len() + data.len() + chunks * (4 + NONCE_LENGTH + TAG_LENGTH),
+    );
+    encrypt_stream(key, data, &mut out)?;
+    Ok(out)
+}
+
+/// Decrypt a byte buffer produced by [`encrypt_bytes`].
+/// Data without the encrypted file header is returned unchanged.
+pub fn decrypt_bytes(key: &[u8; KEY_LENGTH], data: &[u8]) -> Result<Vec<u8>> {
+    let mut out = Vec::with_capacity(data.len());
+    decrypt_stream(key, data, &mut out)?;
+    Ok(out)
+}
+
+/// Encrypt the file at `src` into `dst` (which may be the same path).
+///
+/// Streams chunk by chunk; `dst` is replaced only once the whole file is written.
+pub async fn encrypt_file(key: &[u8; KEY_LENGTH], src: &Path, dst: &Path) -> Result<()> {
+    let key = *key;
+    transform_file(src, dst, move |reader, writer| {
+        encrypt_stream(&key, reader, writer)
+    })
+    .await
+}
+
+/// Decrypt the file at `src` into `dst` (which may be the same path).
+///
+/// Files without the encrypted header are copied unchanged; `dst` is left
+/// untouched if decryption fails.
+pub async fn decrypt_file(key: &[u8; KEY_LENGTH], src: &Path, dst: &Path) -> Result<()> {
+    let key = *key;
+    transform_file(src, dst, move |reader, writer| {
+        decrypt_stream(&key, reader, writer)
+    })
+    .await
+}
+
+/// Stream `src` through `transform` into a temp file next to `dst`, then rename it over `dst`.
+async fn transform_file<F>(src: &Path, dst: &Path, transform: F) -> Result<()>
+where
+    F: FnOnce(
+            &mut std::io::BufReader<std::fs::File>,
+            &mut std::io::BufWriter<std::fs::File>,
+        ) -> Result<()>
+        + Send
+        + 'static,
+{
+    let src = src.to_path_buf();
+    let dst = dst.to_path_buf();
+    tokio::task::spawn_blocking(move || {
+        let input = std::fs::File::open(&src)
+            .with_context(|| format!("Failed to open {}", src.display()))?;
+        let mut tmp = dst.clone().into_os_string();
+        tmp.push(".tmp");
+        let tmp = std::path::PathBuf::from(tmp);
+        let output = std::fs::File::create(&tmp)
+            .with_context(|| format!("Failed to create {}", tmp.display()))?;
+
+        let mut reader = std::io::BufReader::new(input);
+        let mut writer = std::io::BufWriter::new(output);
+        let result = transform(&mut reader, &mut writer)
+            .and_then(|()| Ok(writer.into_inner()?.sync_all()?))
+            .and_then(|()| {
+                std::fs::rename(&tmp, &dst)
+                    .with_context(|| format!("Failed to write {}", dst.display()))
+            });
+        if result.is_err() {
+            let _ = std::fs::remove_file(&tmp);
+        }
+        result
+    })
+    .await
+    .context("File encryption task failed")?
+}
+
 /// Get the hex-encoded private key from environment (for backup export).
 /// Returns None if no key is configured.
 pub fn get_private_key_hex() -> Option<String> {
@@ -467,6 +761,47 @@ mod tests {
         assert!(!is_encrypted("plaintext"));
         assert!(!is_encrypted("<encrypted>missing version</encrypted>"));
         assert!(!is_encrypted("<encrypted v=\"1\">no closing tag"));
+        assert!(!is_encrypted("<encrypted v=\"99\">abc123</encrypted>"));
+        assert_eq!(
+            encryption_version("<encrypted v=\"99\">abc123</encrypted>"),
+            Some("99")
+        );
+    }
+
+    #[test]
+    fn test_unsupported_version_fails() {
+        let key = test_key();
+        let err = decrypt_value(&key, "<encrypted v=\"99\">abc123</encrypted>").unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Unsupported encryption version: 99"));
+    }
+
+    #[test]
+    fn test_rotate_keys() {
+        let old_key = test_key();
+        let mut new_key = test_key();
+        new_key[0] = 255;
+
+        let mut values = HashMap::new();
+        values.insert(
+            "API_KEY".to_string(),
+            encrypt_value(&old_key, "sk-12345").unwrap(),
+        );
+        values.insert("REGION".to_string(), "eu-west-1".to_string());
+
+        assert_eq!(rotate_keys(&old_key, &new_key, &mut values).unwrap(), 1);
+        assert!(values.values().all(|v| is_encrypted(v)));
+        assert!(decrypt_value(&old_key, &values["API_KEY"]).is_err());
+        let decrypted = decrypt_env_vars(&new_key, &values).unwrap();
+        assert_eq!(decrypted["API_KEY"], "sk-12345");
+        assert_eq!(decrypted["REGION"], "eu-west-1");
+
+        // Rotating with the wrong old key fails and leaves the values untouched
+        let before = values.clone();
+        let err = rotate_keys(&old_key, &new_key, &mut values).unwrap_err();
+        assert!(format!("{:#}", err).contains("Failed to re-encrypt"));
+        assert_eq!(values, before);
     }
 
     #[test]
@@ -796,6 +1131,104 @@ export API_KEY=another-secret
         );
     }
 
+    fn random_bytes(len: usize) -> Vec<u8> {
+        let mut data = vec![0u8; len];
+        rand::thread_rng().fill_bytes(&mut data);
+        data
+    }
+
+    #[test]
+    fn test_encrypt_decrypt_bytes_roundtrip() {
+        let key = test_key();
+        for len in [
+            0,
+            1,
+            FILE_CHUNK_SIZE - 1,
+            FILE_CHUNK_SIZE,
+            3 * 1024 * 1024 + 17,
+        ] {
+            let data = random_bytes(len);
+            let encrypted = encrypt_bytes(&key, &data).unwrap();
+            assert!(is_encrypted_bytes(&encrypted));
+            assert_eq!(
+                decrypt_bytes(&key, &encrypted).unwrap(),
+                data,
+                "len {}",
+                len
+            );
+
+            // No double encryption
+            assert_eq!(encrypt_bytes(&key, &encrypted).unwrap(), encrypted);
+        }
+
+        let plaintext = b"API_KEY=sk-12345\n";
+        assert_eq!(decrypt_bytes(&key, plaintext).unwrap(), plaintext);
+    }
+
+    #[test]
+    fn test_encrypted_bytes_tampering_fails() {
+        let key = test_key();
+        let data = random_bytes(2 * FILE_CHUNK_SIZE + 100);
+        let encrypted = encrypt_bytes(&key, &data).unwrap();
+        let chunk_len = 4 + NONCE_LENGTH + FILE_CHUNK_SIZE + TAG_LENGTH;
+        let header = FILE_MAGIC.len();
+
+        let mut wrong_key = test_key();
+        wrong_key[0] = 255;
+        assert!(decrypt_bytes(&wrong_key, &encrypted).is_err());
+
+        // Truncated after a full chunk, so no final chunk
+        assert!(decrypt_bytes(&key, &encrypted[..header + chunk_len]).is_err());
+
+        // First two chunks swapped
+        let mut swapped = encrypted[..header].to_vec();
+        swapped.extend_from_slice(&encrypted[header + chunk_len..header + 2 * chunk_len]);
+        swapped.extend_from_slice(&encrypted[header..header + chunk_len]);
+        swapped.extend_from_slice(&encrypted[header + 2 * chunk_len..]);
+        assert!(decrypt_bytes(&key, &swapped).is_err());
+
+        let mut flipped = encrypted.clone();
+        let last = flipped.len() - 1;
+        flipped[last] ^= 1;
+        assert!(decrypt_bytes(&key, &flipped).is_err());
+
+        let mut trailing = encrypted;
+        trailing.push(0);
+        assert!(decrypt_bytes(&key, &trailing).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_encrypt_decrypt_file_roundtrip() {
+        let key = test_key();
+        let dir = tempfile::tempdir().unwrap();
+
+        for (name, data) in [
+            ("empty.env", Vec::new()),
+            ("large.env", random_bytes(5 * 1024 * 1024 + 3)),
+        ] {
+            let path = dir.path().join(name);
+            std::fs::write(&path, &data).unwrap();
+
+            // In place
+            encrypt_file(&key, &path, &path).await.unwrap();
+            let encrypted = std::fs::read(&path).unwrap();
+            assert!(is_encrypted_bytes(&encrypted));
+
+            let decrypted_path = dir.path().join(format!("{}.plain", name));
+            decrypt_file(&key, &path, &decrypted_path).await.unwrap();
+            assert_eq!(std::fs::read(&decrypted_path).unwrap(), data);
+        }
+
+        // A failed decryption leaves the destination untouched
+        let path = dir.path().join("large.env");
+        let dst = dir.path().join("large.env.plain");
+        let mut wrong_key = key;
+        wrong_key[0] = 255;
+        assert!(decrypt_file(&wrong_key, &path, &dst).await.is_err());
+        assert_eq!(std::fs::read(&dst).unwrap().len(), 5 * 1024 * 1024 + 3);
+        assert!(!dir.path().join("large.env.plain.tmp").exists());
+    }
+
     #[test]
     fn test_full_encryption_flow_with_strip() {
         // This tests the complete flow:
@@ -2,19 +2,17 @@
 //!
 //! Starts the HTTP server that exposes the agent API.
 
-use open_agent::{api, config::Config, library::env_crypto};
+use open_agent::{api, config::Config, library::env_crypto, mission_log::MissionLogFilter};
 use tracing::{info, warn};
-use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, Layer};
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
-    // Initialize logging
+    // Initialize logging (per-mission overrides can raise verbosity inside a mission span)
+    let env_filter = tracing_subscriber::EnvFilter::try_from_default_env()
+        .unwrap_or_else(|_| "open_agent=debug,tower_http=debug".into());
     tracing_subscriber::registry()
-        .with(
-            tracing_subscriber::EnvFilter::try_from_default_env()
-                .unwrap_or_else(|_| "open_agent=debug,tower_http=debug".into()),
-        )
-        .with(tracing_subscriber::fmt::layer())
+        .with(tracing_subscriber::fmt::layer().with_filter(MissionLogFilter::new(env_filter)))
         .init();
 
     // Load configuration
@@ -0,0 +1,247 @@
+//! Per-mission log verbosity overrides.
+//!
+//! Mission turns run inside a `mission` span (see [`mission_span`]). Wrapping the
+//! process-wide filter in [`MissionLogFilter`] lets a single mission log at
+//! DEBUG/TRACE (via [`set_level`]) while everything else stays at the global
+//! level. Overrides only raise verbosity for this crate's targets, so
+//! dependencies stay quiet.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{OnceLock, RwLock};
+
+use tracing::field::{Field, Visit};
+use tracing::level_filters::LevelFilter;
+use tracing::span;
+use tracing::subscriber::Interest;
+use tracing::{Metadata, Subscriber};
+use tracing_subscriber::layer::{Context, Filter};
+use tracing_subscriber::registry::LookupSpan;
+use uuid::Uuid;
+
+/// Name of the span wrapping a mission turn.
+pub const MISSION_SPAN: &str = "mission";
+
+/// Only events from these targets are raised by an override.
+const OVERRIDE_TARGET_PREFIX: &str = "open_agent";
+
+fn overrides() -> &'static RwLock<HashMap<Uuid, LevelFilter>> {
+    static OVERRIDES: OnceLock<RwLock<HashMap<Uuid, LevelFilter>>> = OnceLock::new();
+    OVERRIDES.get_or_init(Default::default)
+}
+
+/// Whether any override is set; without one the filter costs nothing extra.
+static ANY_OVERRIDE: AtomicBool = AtomicBool::new(false);
+
+/// Set (or clear with `None`) the log level override for a mission.
+pub fn set_level(mission_id: Uuid, level: Option<LevelFilter>) {
+    let mut map = overrides().write().unwrap_or_else(|e| e.into_inner());
+    match level {
+        Some(level) => {
+            map.insert(mission_id, level);
+        }
+        None => {
+            map.remove(&mission_id);
+        }
+    }
+    let active = !map.is_empty();
+    if ANY_OVERRIDE.swap(active, Ordering::Relaxed) != active {
+        // Callsite interest and the max level depend on whether overrides exist.
+        tracing::callsite::rebuild_interest_cache();
+    }
+}
+
+/// Current log level override for a mission, if any.
+pub fn level(mission_id: Uuid) -> Option<LevelFilter> {
+    overrides()
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(&mission_id)
+        .copied()
+}
+
+/// Parse a level name (`error`..`trace`, or `off`).
+pub fn parse_level(value: &str) -> Option<LevelFilter> {
+    value.trim().parse().ok()
+}
+
+/// Span to instrument a mission turn with. Disabled when there is no mission.
+pub fn mission_span(mission_id: Option<Uuid>) -> tracing::Span {
+    match mission_id {
+        Some(id) => tracing::info_span!(MISSION_SPAN, mission_id = %id),
+        None => tracing::Span::none(),
+    }
+}
+
+/// Mission id stored in the extensions of a `mission` span.
+struct MissionScope(Uuid);
+
+#[derive(Default)]
+struct MissionIdVisitor(Option<Uuid>);
+
+impl Visit for MissionIdVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "mission_id" {
+            self.0 = format!("{:?}", value).parse().ok();
+        }
+    }
+}
+
+fn is_mission_span(meta: &Metadata<'_>) -> bool {
+    meta.is_span() && meta.name() == MISSION_SPAN && meta.target() == module_path!()
+}
+
+/// Per-layer filter that enables `inner`'s events plus anything allowed by the
+/// override of the enclosing mission span.
+pub struct MissionLogFilter<F> {
+    inner: F,
+}
+
+impl<F> MissionLogFilter<F> {
+    pub fn new(inner: F) -> Self {
+        Self { inner }
+    }
+
+    fn override_enables<S>(meta: &Metadata<'_>, cx: &Context<'_, S>) -> bool
+    where
+        S: Subscriber + for<'a> LookupSpan<'a>,
+    {
+        if !ANY_OVERRIDE.load(Ordering::Relaxed)
+            || !meta.target().starts_with(OVERRIDE_TARGET_PREFIX)
+        {
+            return false;
+        }
+        let Some(current) = cx.lookup_current() else {
+            return false;
+        };
+        let mission_id = current
+            .scope()
+            .find_map(|span| span.extensions().get::<MissionScope>().map(|s| s.0));
+        mission_id
+            .and_then(level)
+            .is_some_and(|level| level >= *meta.level())
+    }
+}
+
+impl<S, F> Filter<S> for MissionLogFilter<F>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    F: Filter<S>,
+{
+    fn enabled(&self, meta: &Metadata<'_>, cx: &Context<'_, S>) -> bool {
+        // Mission spans are always recorded so overrides can find them.
+        self.inner.enabled(meta, cx) || is_mission_span(meta) || Self::override_enables(meta, cx)
+    }
+
+    fn callsite_enabled(&self, meta: &'static Metadata<'static>) -> Interest {
+        if is_mission_span(meta) {
+            return Interest::always();
+        }
+        let interest = self.inner.callsite_enabled(meta);
+        // With an override active, callsites the inner filter rejects may still be enabled.
+        if ANY_OVERRIDE.load(Ordering::Relaxed)
+            && !interest.is_always()
+            && meta.target().starts_with(OVERRIDE_TARGET_PREFIX)
+        {
+            Interest::sometimes()
+        } else {
+            interest
+        }
+    }
+
+    fn max_level_hint(&self) -> Option<LevelFilter> {
+        if ANY_OVERRIDE.load(Ordering::Relaxed) {
+            return Some(LevelFilter::TRACE);
+        }
+        // Mission spans are INFO and must stay enabled.
+        self.inner
+            .max_level_hint()
+            .map(|hint| hint.max(LevelFilter::INFO))
+    }
+
+    fn on_new_span(&self, attrs: &span::Attributes<'_>, id: &span::Id, ctx: Context<'_, S>) {
+        if is_mission_span(attrs.metadata()) {
+            let mut visitor = MissionIdVisitor::default();
+            attrs.record(&mut visitor);
+            if let (Some(mission_id), Some(span)) = (visitor.0, ctx.span(id)) {
+                span.extensions_mut().insert(MissionScope(mission_id));
+            }
+        }
+        self.inner.on_new_span(attrs, id, ctx);
+    }
+
+    fn on_record(&self, id: &span::Id, values: &span::Record<'_>, ctx: Context<'_, S>) {
+        self.inner.on_record(id, values, ctx);
+    }
+
+    fn on_enter(&self, id: &span::Id, ctx: Context<'_, S>) {
+        self.inner.on_enter(id, ctx);
+    }
+
+    fn on_exit(&self, id: &span::Id, ctx: Context<'_, S>) {
+        self.inner.on_exit(id, ctx);
+    }
+
+    fn on_close(&self, id: span::Id, ctx: Context<'_, S>) {
+        self.inner.on_close(id, ctx);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use std::sync::{Arc, Mutex};
+    use tracing_subscriber::layer::SubscriberExt;
+    use tracing_subscriber::Layer;
+
+    #[derive(Clone, Default)]
+    struct Capture(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Capture {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_override_raises_only_targeted_mission() {
+        let capture = Capture::default();
+        let writer = capture.clone();
+        let subscriber = tracing_subscriber::registry().with(
+            tracing_subscriber::fmt::layer()
+                .with_ansi(false)
+                .with_writer(move || writer.clone())
+                .with_filter(MissionLogFilter::new(LevelFilter::INFO)),
+        );
+
+        let (debugged, quiet) = (Uuid::new_v4(), Uuid::new_v4());
+        set_level(debugged, parse_level("debug"));
+
+        tracing::subscriber::with_default(subscriber, || {
+            mission_span(Some(debugged)).in_scope(|| {
+                tracing::debug!("debug in targeted mission");
+                tracing::trace!("trace in targeted mission");
+            });
+            mission_span(Some(quiet)).in_scope(|| {
+                tracing::debug!("debug in other mission");
+                tracing::info!("info in other mission");
+            });
+            tracing::debug!("debug outside missions");
+        });
+        set_level(debugged, None);
+
+        let logs = String::from_utf8(capture.0.lock().unwrap().clone()).unwrap();
+        assert!(logs.contains("debug in targeted mission"));
+        assert!(logs.contains(&debugged.to_string()));
+        assert!(logs.contains("info in other mission"));
+        assert!(!logs.contains("trace in targeted mission"));
+        assert!(!logs.contains("debug in other mission"));
+        assert!(!logs.contains("debug outside missions"));
+        assert_eq!(level(debugged), None);
+    }
+}
@@ -0,0 +1,398 @@
+//! Recurring mission schedules and persistence.
+//!
+//! A schedule pairs a 5-field cron expression (`minute hour day-of-month month day-of-week`,
+//! evaluated in UTC) with a mission template. Schedules are stored in
+//! `{working_dir}/.openagent/schedules.json`; the background scheduler in
+//! `api::schedules` submits a mission whenever one comes due.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use chrono::{DateTime, Datelike, NaiveTime, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+/// How far ahead to look for the next matching time before giving up (e.g. `0 0 30 2 *`).
+const MAX_LOOKAHEAD_DAYS: i64 = 366 * 5;
+
+/// A parsed 5-field cron expression.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CronSchedule {
+    minutes: Vec<u32>,
+    hours: Vec<u32>,
+    days_of_month: Vec<u32>,
+    months: Vec<u32>,
+    days_of_week: Vec<u32>,
+    dom_restricted: bool,
+    dow_restricted: bool,
+}
+
+impl CronSchedule {
+    /// Parse an expression such as `0 9 * * 1-5` or `*/15 * * * *`.
+    pub fn parse(expr: &str) -> Result<Self, String> {
+        let fields: Vec<&str> = expr.split_whitespace().collect();
+        if fields.len() != 5 {
+            return Err(format!(
+                "Expected 5 cron fields (minute hour day month weekday), got {}",
+                fields.len()
+            ));
+        }
+
+        let mut days_of_week = parse_field(fields[4], 0, 7, "day of week")?;
+        // Both 0 and 7 mean Sunday.
+        for d in days_of_week.iter_mut() {
+            if *d == 7 {
+                *d = 0;
+            }
+        }
+        days_of_week.sort_unstable();
+        days_of_week.dedup();
+
+        Ok(Self {
+            minutes: parse_field(fields[0], 0, 59, "minute")?,
+            hours: parse_field(fields[1], 0, 23, "hour")?,
+            days_of_month: parse_field(fields[2], 1, 31, "day of month")?,
+            months: parse_field(fields[3], 1, 12, "month")?,
+            days_of_week,
+            dom_restricted: fields[2] != "*",
+            dow_restricted: fields[4] != "*",
+        })
+    }
+
+    fn day_matches(&self, date: chrono::NaiveDate) -> bool {
+        if !self.months.contains(&date.month()) {
+            return false;
+        }
+        let dom = self.days_of_month.contains(&date.day());
+        let dow = self
+            .days_of_week
+            .contains(&date.weekday().num_days_from_sunday());
+        // Standard cron semantics: when both day fields are restricted, either may match.
+        match (self.dom_restricted, self.dow_restricted) {
+            (true, true) => dom || dow,
+            (true, false) => dom,
+            (false, true) => dow,
+            (false, false) => true,
+        }
+    }
+
+    /// The first matching minute strictly after `after`.
+    pub fn next_after(&self, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let mut date = after.date_naive();
+        for _ in 0..MAX_LOOKAHEAD_DAYS {
+            if self.day_matches(date) {
+                for &hour in &self.hours {
+                    for &minute in &self.minutes {
+                        let time = NaiveTime::from_hms_opt(hour, minute, 0)?;
+                        let candidate = Utc.from_utc_datetime(&date.and_time(time));
+                        if candidate > after {
+                            return Some(candidate);
+                        }
+                    }
+                }
+            }
+            date = date.succ_opt()?;
+        }
+        None
+    }
+}
+
+/// Parse a single cron field (`*`, `n`, `a-b`, `*/s`, `a-b/s`, and comma lists).
+fn parse_field(field: &str, min: u32, max: u32, name: &str) -> Result<Vec<u32>, String> {
+    let mut values = Vec::new();
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => {
+                let step: u32 = step
+                    .parse()
+                    .map_err(|_| format!("Invalid step '{}' in {} field", step, name))?;
+                if step == 0 {
+                    return Err(format!("Step must be positive in {} field", name));
+                }
+                (range, step)
+            }
+            None => (part, 1),
+        };
+
+        let (start, end) = if range == "*" {
+            (min, max)
+        } else if let Some((a, b)) = range.split_once('-') {
+            (parse_value(a, name)?, parse_value(b, name)?)
+        } else {
+            let v = parse_value(range, name)?;
+            // `5/10` means "from 5 to max, every 10".
+            (v, if step > 1 { max } else { v })
+        };
+
+        if start < min || end > max || start > end {
+            return Err(format!(
+                "Value out of range in {} field: '{}' (allowed {}-{})",
+                name, part, min, max
+            ));
+        }
+        values.extend((start..=end).step_by(step as usize));
+    }
+    values.sort_unstable();
+    values.dedup();
+    Ok(values)
+}
+
+fn parse_value(value: &str, name: &str) -> Result<u32, String> {
+    value
+        .parse()
+        .map_err(|_| format!("Invalid value '{}' in {} field", value, name))
+}
+
+/// A recurring mission registered by a user.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MissionSchedule {
+    pub id: Uuid,
+    /// Cron expression (UTC)
+    pub cron: String,
+    /// Initial message sent to each submitted mission
+    pub content: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub workspace_id: Option<Uuid>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub agent: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model_override: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub backend: Option<String>,
+    /// Owner of the schedule (missions are submitted to this user's control session)
+    pub user_id: String,
+    pub username: String,
+    pub created_at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_run_at: Option<DateTime<Utc>>,
+    /// None when the expression never matches again
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_run_at: Option<DateTime<Utc>>,
+}
+
+/// Mission template and owner for a new schedule.
+#[derive(Debug, Clone, Default)]
+pub struct NewMissionSchedule {
+    pub cron: String,
+    pub content: String,
+    pub title: Option<String>,
+    pub workspace_id: Option<Uuid>,
+    pub agent: Option<String>,
+    pub model_override: Option<String>,
+    pub backend: Option<String>,
+    pub user_id: String,
+    pub username: String,
+}
+
+/// Persistent store of mission schedules.
+#[derive(Debug)]
+pub struct ScheduleStore {
+    schedules: RwLock<HashMap<Uuid, MissionSchedule>>,
+    storage_path: PathBuf,
+}
+
+impl ScheduleStore {
+    pub async fn new(working_dir: &Path) -> Self {
+        Self::with_path(working_dir.join(".openagent").join("schedules.json")).await
+    }
+
+    pub async fn with_path(storage_path: PathBuf) -> Self {
+        let schedules = if storage_path.exists() {
+            match Self::load_from_disk(&storage_path) {
+                Ok(loaded) => loaded,
+                Err(e) => {
+                    tracing::warn!("Failed to load mission schedules: {}", e);
+                    HashMap::new()
+                }
+            }
+        } else {
+            HashMap::new()
+        };
+
+        Self {
+            schedules: RwLock::new(schedules),
+            storage_path,
+        }
+    }
+
+    fn load_from_disk(path: &Path) -> Result<HashMap<Uuid, MissionSchedule>, std::io::Error> {
+        let contents = std::fs::read_to_string(path)?;
+        let entries: Vec<MissionSchedule> = serde_json::from_str(&contents)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        Ok(entries.into_iter().map(|s| (s.id, s)).collect())
+    }
+
+    async fn save_to_disk(&self) -> Result<(), std::io::Error> {
+        let schedules = self.schedules.read().await;
+        let mut entries: Vec<MissionSchedule> = schedules.values().cloned().collect();
+        entries.sort_by_key(|s| s.created_at);
+
+        if let Some(parent) = self.storage_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let contents = serde_json::to_string_pretty(&entries)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        std::fs::write(&self.storage_path, contents)?;
+        Ok(())
+    }
+
+    /// List schedules owned by `user_id`, oldest first.
+    pub async fn list(&self, user_id: &str) -> Vec<MissionSchedule> {
+        let schedules = self.schedules.read().await;
+        let mut list: Vec<_> = schedules
+            .values()
+            .filter(|s| s.user_id == user_id)
+            .cloned()
+            .collect();
+        list.sort_by_key(|s| s.created_at);
+        list
+    }
+
+    /// Register a new schedule. Fails if the cron expression is invalid.
+    pub async fn create(&self, new: NewMissionSchedule) -> Result<MissionSchedule, String> {
+        let cron = CronSchedule::parse(&new.cron)?;
+        let now = Utc::now();
+        let schedule = MissionSchedule {
+            id: Uuid::new_v4(),
+            cron: new.cron.trim().to_string(),
+            content: new.content,
+            title: new.title,
+            workspace_id: new.workspace_id,
+            agent: new.agent,
+            model_override: new.model_override,
+            backend: new.backend,
+            user_id: new.user_id,
+            username: new.username,
+            created_at: now,
+            last_run_at: None,
+            next_run_at: cron.next_after(now),
+        };
+        self.insert(schedule.clone()).await?;
+        Ok(schedule)
+    }
+
+    async fn insert(&self, schedule: MissionSchedule) -> Result<(), String> {
+        self.schedules.write().await.insert(schedule.id, schedule);
+        self.save_to_disk().await.map_err(|e| e.to_string())
+    }
+
+    /// Cancel (delete) a schedule owned by `user_id`. Returns false if not found.
+    pub async fn cancel(&self, user_id: &str, id: Uuid) -> Result<bool, String> {
+        {
+            let mut schedules = self.schedules.write().await;
+            match schedules.get(&id) {
+                Some(s) if s.user_id == user_id => {
+                    schedules.remove(&id);
+                }
+                _ => return Ok(false),
+            }
+        }
+        self.save_to_disk().await.map_err(|e| e.to_string())?;
+        Ok(true)
+    }
+
+    /// Collect schedules due at `now` and advance each to its next run.
+    ///
+    /// A schedule that missed several runs (e.g. while the server was down) fires once.
+    pub async fn take_due(&self, now: DateTime<Utc>) -> Vec<MissionSchedule> {
+        let due: Vec<MissionSchedule> = {
+            let mut schedules = self.schedules.write().await;
+            let mut due = Vec::new();
+            for schedule in schedules.values_mut() {
+                match schedule.next_run_at {
+                    Some(next) if next <= now => {}
+                    _ => continue,
+                }
+                due.push(schedule.clone());
+                schedule.last_run_at = Some(now);
+                schedule.next_run_at = CronSchedule::parse(&schedule.cron)
+                    .ok()
+                    .and_then(|c| c.next_after(now));
+            }
+            due
+        };
+
+        if !due.is_empty() {
+            if let Err(e) = self.save_to_disk().await {
+                tracing::warn!("Failed to persist mission schedules: {}", e);
+            }
+        }
+        due
+    }
+}
+
+pub type SharedScheduleStore = Arc<ScheduleStore>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn at(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_cron_next_after() {
+        let daily = CronSchedule::parse("30 9 * * *").unwrap();
+        assert_eq!(
+            daily.next_after(at("2026-01-01T10:00:00Z")),
+            Some(at("2026-01-02T09:30:00Z"))
+        );
+
+        let weekdays = CronSchedule::parse("0 8 * * 1-5").unwrap();
+        // 2026-01-03 is a Saturday.
+        assert_eq!(
+            weekdays.next_after(at("2026-01-03T00:00:00Z")),
+            Some(at("2026-01-05T08:00:00Z"))
+        );
+
+        let quarter = CronSchedule::parse("*/15 * * * *").unwrap();
+        assert_eq!(
+            quarter.next_after(at("2026-01-01T10:00:00Z")),
+            Some(at("2026-01-01T10:15:00Z"))
+        );
+
+        assert!(CronSchedule::parse("* * *").is_err());
+        assert!(CronSchedule::parse("61 * * * *").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_past_due_schedule_submits_once() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = ScheduleStore::with_path(dir.path().join("schedules.json")).await;
+        let mut schedule = store
+            .create(NewMissionSchedule {
+                cron: "0 9 * * *".to_string(),
+                content: "Write the daily report".to_string(),
+                user_id: "default".to_string(),
+                username: "default".to_string(),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+
+        // Pretend the schedule was due yesterday.
+        let now = Utc::now();
+        schedule.next_run_at = Some(now - Duration::days(1));
+        store.insert(schedule.clone()).await.unwrap();
+
+        let due = store.take_due(now).await;
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].id, schedule.id);
+        assert!(store.take_due(now).await.is_empty());
+
+        // Persisted state survives a reload.
+        let reloaded = ScheduleStore::with_path(dir.path().join("schedules.json")).await;
+        let listed = reloaded.list("default").await;
+        assert_eq!(listed.len(), 1);
+        assert!(listed[0].next_run_at.unwrap() > now);
+        assert!(reloaded.cancel("default", schedule.id).await.unwrap());
+        assert!(reloaded.list("default").await.is_empty());
+    }
+}
@@ -4,10 +4,11 @@
 //! that must exist for a task to be considered complete.
 
 use regex::Regex;
+use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
 /// A deliverable that the user expects from the task.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum Deliverable {
     /// A file that should be created at a specific path.
     File {
@@ -54,7 +55,7 @@ impl Deliverable {
 }
 
 /// Result of deliverable extraction.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct DeliverableSet {
     pub deliverables: Vec<Deliverable>,
     /// Keywords that suggest the task is research/analysis (may not have file deliverables)
@@ -85,6 +86,23 @@ impl DeliverableSet {
         true
     }
 
+    /// Split deliverable paths into `(present, missing)`.
+    pub async fn partition_paths(&self) -> (Vec<String>, Vec<String>) {
+        let mut present = Vec::new();
+        let mut missing = Vec::new();
+        for d in &self.deliverables {
+            if let Some(path) = d.path() {
+                let path = path.display().to_string();
+                if d.exists().await {
+                    present.push(path);
+                } else {
+                    missing.push(path);
+                }
+            }
+        }
+        (present, missing)
+    }
+
     /// Get paths of missing deliverables.
     pub async fn missing_paths(&self) -> Vec<String> {
         let mut paths = Vec::new();
@@ -0,0 +1,270 @@
+//! Headless browser screenshots of URLs and local HTML files.
+//!
+//! Uses a Chromium-compatible browser found on PATH (or `OPEN_AGENT_CHROMIUM_PATH`)
+//! in `--headless` mode and saves a PNG into the workspace.
+
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use serde_json::{json, Value};
+use tokio::process::Command;
+
+use super::{resolve_path_simple, Tool};
+
+/// Browser binaries tried in order when `OPEN_AGENT_CHROMIUM_PATH` is unset.
+const BROWSER_CANDIDATES: &[&str] = &[
+    "chromium",
+    "chromium-browser",
+    "google-chrome",
+    "google-chrome-stable",
+    "chrome-headless-shell",
+];
+
+const DEFAULT_WIDTH: u32 = 1280;
+const DEFAULT_HEIGHT: u32 = 800;
+const MAX_DIMENSION: u32 = 8192;
+const CAPTURE_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Locate a headless-capable browser.
+pub(super) fn find_browser() -> Option<PathBuf> {
+    if let Ok(path) = std::env::var("OPEN_AGENT_CHROMIUM_PATH") {
+        let path = PathBuf::from(path);
+        if path.is_file() {
+            return Some(path);
+        }
+    }
+    let path_var = std::env::var("PATH").unwrap_or_default();
+    BROWSER_CANDIDATES.iter().find_map(|name| {
+        path_var
+            .split(':')
+            .filter(|d| !d.is_empty())
+            .map(|d| Path::new(d).join(name))
+            .find(|p| p.is_file())
+    })
+}
+
+/// Turn the `url` argument into something the browser can load.
+/// Anything without a scheme is treated as a local file relative to the workspace.
+fn target_url(url: &str, working_dir: &Path) -> anyhow::Result<String> {
+    if url.contains("://") || url.starts_with("data:") {
+        return Ok(url.to_string());
+    }
+    let path = resolve_path_simple(url, working_dir)?;
+    if !path.is_file() {
+        return Err(anyhow::anyhow!(
+            "Local file not found: {} (resolved to: {})",
+            url,
+            path.display()
+        ));
+    }
+    Ok(format!("file://{}", path.display()))
+}
+
+fn browser_args(url: &str, output: &Path, width: u32, height: u32) -> Vec<String> {
+    vec![
+        "--headless=new".to_string(),
+        "--disable-gpu".to_string(),
+        "--no-sandbox".to_string(),
+        "--hide-scrollbars".to_string(),
+        format!("--window-size={},{}", width, height),
+        format!("--screenshot={}", output.display()),
+        url.to_string(),
+    ]
+}
+
+/// Read width and height from a PNG's IHDR chunk.
+fn png_dimensions(bytes: &[u8]) -> Option<(u32, u32)> {
+    const SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+    if bytes.len() < 24 || &bytes[..8] != SIGNATURE || &bytes[12..16] != b"IHDR" {
+        return None;
+    }
+    let width = u32::from_be_bytes(bytes[16..20].try_into().ok()?);
+    let height = u32::from_be_bytes(bytes[20..24].try_into().ok()?);
+    Some((width, height))
+}
+
+/// Run `browser` to capture `url` into `output`, returning the image dimensions.
+async fn capture(
+    browser: &Path,
+    url: &str,
+    output: &Path,
+    width: u32,
+    height: u32,
+) -> anyhow::Result<(u32, u32)> {
+    if let Some(parent) = output.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+    // Remove a stale file so a failed capture isn't mistaken for success.
+    let _ = tokio::fs::remove_file(output).await;
+    let mut cmd = Command::new(browser);
+    cmd.args(browser_args(url, output, width, height))
+        .kill_on_drop(true);
+    let result = tokio::time::timeout(CAPTURE_TIMEOUT, cmd.output())
+        .await
+        .map_err(|_| anyhow::anyhow!("Browser timed out after {}s", CAPTURE_TIMEOUT.as_secs()))??;
+
+    if !output.is_file() {
+        return Err(anyhow::anyhow!(
+            "Browser did not produce a screenshot (exit code {}): {}",
+            result.status.code().unwrap_or(-1),
+            String::from_utf8_lossy(&result.stderr).trim()
+        ));
+    }
+    let bytes = tokio::fs::read(output).await?;
+    Ok(png_dimensions(&bytes).unwrap_or((width, height)))
+}
+
+/// Print a local HTML file to PDF with `browser`.
+pub(super) async fn print_to_pdf(browser: &Path, html: &Path, output: &Path) -> anyhow::Result<()> {
+    let _ = tokio::fs::remove_file(output).await;
+    let mut cmd = Command::new(browser);
+    cmd.args([
+        "--headless=new".to_string(),
+        "--disable-gpu".to_string(),
+        "--no-sandbox".to_string(),
+        "--no-pdf-header-footer".to_string(),
+        format!("--print-to-pdf={}", output.display()),
+        format!("file://{}", html.display()),
+    ])
+    .kill_on_drop(true);
+    let result = tokio::time::timeout(CAPTURE_TIMEOUT, cmd.output())
+        .await
+        .map_err(|_| anyhow::anyhow!("Browser timed out after {}s", CAPTURE_TIMEOUT.as_secs()))??;
+
+    if !output.is_file() {
+        return Err(anyhow::anyhow!(
+            "Browser did not produce a PDF (exit code {}): {}",
+            result.status.code().unwrap_or(-1),
+            String::from_utf8_lossy(&result.stderr).trim()
+        ));
+    }
+    Ok(())
+}
+
+/// Render a URL or local HTML file in a headless browser and save a PNG.
+pub struct ScreenshotUrl;
+
+#[async_trait]
+impl Tool for ScreenshotUrl {
+    fn name(&self) -> &str {
+        "screenshot_url"
+    }
+
+    fn description(&self) -> &str {
+        "Render a web page (URL) or local HTML file in a headless browser and save a PNG screenshot to the workspace. Returns the path and image dimensions. Set return_image=true to SEE the result (vision)."
+    }
+
+    fn parameters_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "url": {
+                    "type": "string",
+                    "description": "URL to render (https://...), or a workspace path to a local HTML file (e.g., 'output/index.html')"
+                },
+                "width": {
+                    "type": "integer",
+                    "description": "Viewport width in pixels (default: 1280)"
+                },
+                "height": {
+                    "type": "integer",
+                    "description": "Viewport height in pixels (default: 800)"
+                },
+                "output": {
+                    "type": "string",
+                    "description": "Where to save the PNG (default: screenshots/page_<timestamp>.png)"
+                },
+                "return_image": {
+                    "type": "boolean",
+                    "description": "If true, the screenshot is included in your context (requires vision model). Default: false"
+                }
+            },
+            "required": ["url"]
+        })
+    }
+
+    async fn execute(&self, args: Value, working_dir: &Path) -> anyhow::Result<String> {
+        let url = args["url"]
+            .as_str()
+            .ok_or_else(|| anyhow::anyhow!("Missing 'url' argument"))?;
+        let dimension = |key: &str, default: u32| {
+            args[key]
+                .as_u64()
+                .map(|v| (v as u32).clamp(1, MAX_DIMENSION))
+                .unwrap_or(default)
+        };
+        let width = dimension("width", DEFAULT_WIDTH);
+        let height = dimension("height", DEFAULT_HEIGHT);
+
+        let browser = find_browser().ok_or_else(|| {
+            anyhow::anyhow!(
+                "No headless browser found. Install chromium (e.g. `apt-get install chromium`) \
+                 or set OPEN_AGENT_CHROMIUM_PATH to a Chrome/Chromium binary."
+            )
+        })?;
+
+        let target = target_url(url, working_dir)?;
+        let output = match args["output"].as_str() {
+            Some(path) => resolve_path_simple(path, working_dir)?,
+            None => working_dir.join("screenshots").join(format!(
+                "page_{}.png",
+                chrono::Utc::now().format("%Y%m%d_%H%M%S")
+            )),
+        };
+
+        tracing::info!(url = %target, path = %output.display(), "Capturing page screenshot");
+        let (img_width, img_height) = capture(&browser, &target, &output, width, height).await?;
+
+        let mut result = json!({
+            "success": true,
+            "path": output.display().to_string(),
+            "width": img_width,
+            "height": img_height,
+        })
+        .to_string();
+        if args["return_image"].as_bool().unwrap_or(false) {
+            result.push_str(&format!("\n\n[VISION_IMAGE:file://{}]", output.display()));
+        }
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_capture_with_mock_browser() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        // Fake browser: writes a PNG header (640x480) to the --screenshot= path.
+        let browser = dir.path().join("fake-chromium");
+        std::fs::write(
+            &browser,
+            "#!/bin/sh\nfor a in \"$@\"; do case \"$a\" in --screenshot=*) out=\"${a#--screenshot=}\";; esac; done\n\
+             printf '\\211PNG\\r\\n\\032\\n\\000\\000\\000\\rIHDR\\000\\000\\002\\200\\000\\000\\001\\340' > \"$out\"\n",
+        )
+        .unwrap();
+        std::fs::set_permissions(&browser, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        std::fs::write(dir.path().join("index.html"), "<h1>hi</h1>").unwrap();
+        let url = target_url("index.html", dir.path()).unwrap();
+        assert_eq!(
+            url,
+            format!("file://{}", dir.path().join("index.html").display())
+        );
+
+        let output = dir.path().join("shots/nested/page.png");
+        let dims = capture(&browser, &url, &output, 800, 600).await.unwrap();
+        assert!(output.is_file());
+        assert_eq!(dims, (640, 480));
+
+        assert!(target_url("missing.html", dir.path()).is_err());
+        assert_eq!(
+            target_url("https://example.com", dir.path()).unwrap(),
+            "https://example.com"
+        );
+    }
+}
@@ -0,0 +1,327 @@
+//! Compile check tool: verify code compiles without building artifacts or running it.
+//!
+//! The checker is detected from the project directory (workspace by default):
+//! - `Cargo.toml` → `cargo check --all-targets --message-format=short`
+//! - `tsconfig.json` → `npx tsc --noEmit --pretty false`
+//! - Python project files → `python -m py_compile` on every `.py` file
+//!
+//! Compiler output is parsed into `file:line:column` diagnostics. Execution is
+//! delegated to `run_command` so container workspaces behave the same way.
+
+use std::path::Path;
+use std::sync::OnceLock;
+
+use async_trait::async_trait;
+use regex::Regex;
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use super::{resolve_path_simple as resolve_path, terminal::RunCommand, Tool};
+
+/// Cap on diagnostics returned to the model.
+const MAX_DIAGNOSTICS: usize = 50;
+
+/// A supported compile checker.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Checker {
+    Cargo,
+    TypeScript,
+    Python,
+}
+
+impl Checker {
+    fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "cargo" | "rust" => Some(Self::Cargo),
+            "tsc" | "typescript" => Some(Self::TypeScript),
+            "python" | "py_compile" => Some(Self::Python),
+            _ => None,
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Cargo => "cargo",
+            Self::TypeScript => "tsc",
+            Self::Python => "python",
+        }
+    }
+
+    /// Shell command that type-checks / compiles the project without running it.
+    pub fn command(&self) -> &'static str {
+        match self {
+            Self::Cargo => "cargo check --all-targets --message-format=short",
+            Self::TypeScript => "npx tsc --noEmit --pretty false",
+            Self::Python => {
+                "find . -name '*.py' -not -path '*/.*' -not -path '*/node_modules/*' \
+                 -not -path '*/venv/*' -print0 | xargs -0 -r python -m py_compile"
+            }
+        }
+    }
+}
+
+/// Detect the compile checker for the project at `dir`.
+pub fn detect_checker(dir: &Path) -> Option<Checker> {
+    if dir.join("Cargo.toml").exists() {
+        return Some(Checker::Cargo);
+    }
+    if dir.join("tsconfig.json").exists() {
+        return Some(Checker::TypeScript);
+    }
+    let python_markers = [
+        "pyproject.toml",
+        "setup.cfg",
+        "setup.py",
+        "requirements.txt",
+    ];
+    if python_markers.iter().any(|m| dir.join(m).exists()) {
+        return Some(Checker::Python);
+    }
+    None
+}
+
+/// One compiler error or warning.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Diagnostic {
+    pub file: String,
+    pub line: u32,
+    pub column: Option<u32>,
+    pub severity: String,
+    pub code: Option<String>,
+    pub message: String,
+}
+
+fn short_format_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    // `src/main.rs:1:25: error[E0308]: mismatched types`
+    RE.get_or_init(|| {
+        Regex::new(
+            r"^(?P<file>[^\s:][^:]*):(?P<line>\d+):(?P<col>\d+): (?P<sev>error|warning)(?:\[(?P<code>[^\]]+)\])?: (?P<msg>.*)$",
+        )
+        .expect("valid short-format regex")
+    })
+}
+
+fn tsc_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    // `src/app.ts(3,7): error TS2322: Type 'string' is not assignable to type 'number'.`
+    RE.get_or_init(|| {
+        Regex::new(
+            r"^(?P<file>.+?)\((?P<line>\d+),(?P<col>\d+)\): (?P<sev>error|warning) (?P<code>TS\d+): (?P<msg>.*)$",
+        )
+        .expect("valid tsc regex")
+    })
+}
+
+fn python_location_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    // `  File "pkg/mod.py", line 12` followed later by `SyntaxError: invalid syntax`
+    RE.get_or_init(|| {
+        Regex::new(r#"^\s*File "(?P<file>[^"]+)", line (?P<line>\d+)"#)
+            .expect("valid python location regex")
+    })
+}
+
+fn diagnostic_from(caps: &regex::Captures<'_>) -> Diagnostic {
+    Diagnostic {
+        file: caps["file"].trim_start_matches("./").to_string(),
+        line: caps["line"].parse().unwrap_or(0),
+        column: caps.name("col").and_then(|c| c.as_str().parse().ok()),
+        severity: caps["sev"].to_string(),
+        code: caps.name("code").map(|c| c.as_str().to_string()),
+        message: caps["msg"].trim().to_string(),
+    }
+}
+
+/// Parse compiler output into diagnostics.
+pub fn parse_diagnostics(checker: Checker, output: &str) -> Vec<Diagnostic> {
+    match checker {
+        Checker::Cargo => output
+            .lines()
+            .filter_map(|line| short_format_regex().captures(line))
+            .map(|caps| diagnostic_from(&caps))
+            .collect(),
+        Checker::TypeScript => output
+            .lines()
+            .filter_map(|line| tsc_regex().captures(line))
+            .map(|caps| diagnostic_from(&caps))
+            .collect(),
+        Checker::Python => {
+            let mut diagnostics = Vec::new();
+            let mut location: Option<(String, u32)> = None;
+            for line in output.lines() {
+                if let Some(caps) = python_location_regex().captures(line) {
+                    let file = caps["file"].trim_start_matches("./").to_string();
+                    location = Some((file, caps["line"].parse().unwrap_or(0)));
+                    continue;
+                }
+                let Some((kind, message)) = line.split_once(": ") else {
+                    continue;
+                };
+                if !kind.ends_with("Error") || kind.contains(char::is_whitespace) {
+                    continue;
+                }
+                if let Some((file, line)) = location.take() {
+                    diagnostics.push(Diagnostic {
+                        file,
+                        line,
+                        column: None,
+                        severity: "error".to_string(),
+                        code: Some(kind.to_string()),
+                        message: message.trim().to_string(),
+                    });
+                }
+            }
+            diagnostics
+        }
+    }
+}
+
+/// Check that the project compiles, without building artifacts or running it.
+pub struct CheckCompile;
+
+#[async_trait]
+impl Tool for CheckCompile {
+    fn name(&self) -> &str {
+        "check_compile"
+    }
+
+    fn description(&self) -> &str {
+        "Check that the project compiles without running it (cargo check, tsc --noEmit, python -m py_compile — auto-detected). Much faster than a full build or test run. Returns structured errors with file, line and message."
+    }
+
+    fn parameters_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "path": {
+                    "type": "string",
+                    "description": "Project directory. Defaults to workspace ('.')."
+                },
+                "checker": {
+                    "type": "string",
+                    "enum": ["cargo", "tsc", "python"],
+                    "description": "Optional: override checker auto-detection."
+                },
+                "timeout_secs": {
+                    "type": "integer",
+                    "description": "Timeout in seconds (default: command default)."
+                }
+            },
+            "required": []
+        })
+    }
+
+    async fn execute(&self, args: Value, working_dir: &Path) -> anyhow::Result<String> {
+        let path = args["path"].as_str().unwrap_or(".");
+        let project_dir = resolve_path(path, working_dir)?;
+
+        let checker = match args["checker"].as_str() {
+            Some(name) => Checker::parse(name)
+                .ok_or_else(|| anyhow::anyhow!("Unsupported checker: {}", name))?,
+            None => detect_checker(&project_dir).ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Could not detect a compile checker in {}. Pass 'checker' explicitly or use run_command.",
+                    project_dir.display()
+                )
+            })?,
+        };
+
+        let command = checker.command();
+        tracing::info!("Running {} compile check: {}", checker.as_str(), command);
+
+        let mut run_args = json!({
+            "command": command,
+            "cwd": project_dir.to_string_lossy(),
+            "max_output_chars": 50000,
+        });
+        if let Some(timeout) = args["timeout_secs"].as_u64() {
+            run_args["timeout_secs"] = json!(timeout);
+        }
+
+        let output = RunCommand.execute(run_args, working_dir).await?;
+        let exit_code = output
+            .lines()
+            .next()
+            .and_then(|l| l.strip_prefix("Exit code: "))
+            .and_then(|c| c.trim().parse::<i32>().ok())
+            .unwrap_or(-1);
+
+        let mut diagnostics = parse_diagnostics(checker, &output);
+        // Errors first so the cap never hides them behind warnings.
+        diagnostics.sort_by_key(|d| d.severity != "error");
+        let error_count = diagnostics.iter().filter(|d| d.severity == "error").count();
+        let warning_count = diagnostics.len() - error_count;
+        let truncated = diagnostics.len() > MAX_DIAGNOSTICS;
+        diagnostics.truncate(MAX_DIAGNOSTICS);
+
+        let mut result = json!({
+            "ok": exit_code == 0,
+            "checker": checker.as_str(),
+            "command": command,
+            "exit_code": exit_code,
+            "error_count": error_count,
+            "warning_count": warning_count,
+            "diagnostics": diagnostics,
+            "truncated": truncated,
+        });
+        // Failures we could not parse (missing toolchain, config errors) need the raw text.
+        if exit_code != 0 && error_count == 0 {
+            let tail: Vec<&str> = output.lines().rev().take(40).collect();
+            result["output"] = json!(tail.into_iter().rev().collect::<Vec<_>>().join("\n"));
+        }
+        Ok(serde_json::to_string_pretty(&result)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cargo_selected_and_errors_parsed() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("requirements.txt"), "").unwrap();
+        assert_eq!(detect_checker(dir.path()), Some(Checker::Python));
+        std::fs::write(dir.path().join("Cargo.toml"), "[package]").unwrap();
+        assert_eq!(detect_checker(dir.path()), Some(Checker::Cargo));
+        assert!(Checker::Cargo.command().starts_with("cargo check"));
+
+        let output = "Exit code: 101\n\n--- stderr ---\n    Checking demo v0.1.0 (/tmp/demo)\n\
+             src/main.rs:1:16: warning: unused variable: `x`\n\
+             src/main.rs:1:25: error[E0308]: mismatched types: expected `u32`, found `&str`\n\
+             error: could not compile `demo` (bin \"demo\") due to 1 previous error\n";
+        let diagnostics = parse_diagnostics(Checker::Cargo, output);
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(
+            diagnostics[1],
+            Diagnostic {
+                file: "src/main.rs".to_string(),
+                line: 1,
+                column: Some(25),
+                severity: "error".to_string(),
+                code: Some("E0308".to_string()),
+                message: "mismatched types: expected `u32`, found `&str`".to_string(),
+            }
+        );
+        assert_eq!(diagnostics[0].severity, "warning");
+    }
+
+    #[test]
+    fn test_parse_tsc_and_python_errors() {
+        let tsc =
+            "src/app.ts(3,7): error TS2322: Type 'string' is not assignable to type 'number'.\n";
+        let diagnostics = parse_diagnostics(Checker::TypeScript, tsc);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].code.as_deref(), Some("TS2322"));
+        assert_eq!(diagnostics[0].column, Some(7));
+
+        let py =
+            "  File \"./pkg/bad.py\", line 4\n    def (\n        ^\nSyntaxError: invalid syntax\n";
+        let diagnostics = parse_diagnostics(Checker::Python, py);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].file, "pkg/bad.py");
+        assert_eq!(diagnostics[0].line, 4);
+        assert_eq!(diagnostics[0].code.as_deref(), Some("SyntaxError"));
+    }
+}
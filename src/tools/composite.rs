@@ -7,7 +7,8 @@ use async_trait::async_trait;
 use serde_json::{json, Value};
 use std::path::Path;
 
-use super::Tool;
+use super::git::run_git;
+use super::{resolve_path, Tool};
 
 /// Analyze a codebase by listing structure and searching for key patterns.
 pub struct AnalyzeCodebase;
@@ -43,11 +44,7 @@ impl Tool for AnalyzeCodebase {
         let path_str = args.get("path").and_then(|v| v.as_str()).unwrap_or(".");
         let max_depth = args.get("max_depth").and_then(|v| v.as_u64()).unwrap_or(3) as usize;
 
-        let target_path = if Path::new(path_str).is_absolute() {
-            Path::new(path_str).to_path_buf()
-        } else {
-            working_dir.join(path_str)
-        };
+        let target_path = resolve_path(path_str, working_dir)?.resolved;
 
         if !target_path.exists() {
             return Ok(format!(
@@ -225,11 +222,7 @@ impl Tool for DeepSearch {
             .and_then(|v| v.as_u64())
             .unwrap_or(2);
 
-        let target_path = if Path::new(path_str).is_absolute() {
-            Path::new(path_str).to_path_buf()
-        } else {
-            working_dir.join(path_str)
-        };
+        let target_path = resolve_path(path_str, working_dir)?.resolved;
 
         let mut result = String::new();
         result.push_str(&format!("# Search Results for: `{}`\n\n", pattern));
@@ -333,11 +326,7 @@ impl Tool for PrepareProject {
     async fn execute(&self, args: Value, working_dir: &Path) -> anyhow::Result<String> {
         let path_str = args.get("path").and_then(|v| v.as_str()).unwrap_or(".");
 
-        let target_path = if Path::new(path_str).is_absolute() {
-            Path::new(path_str).to_path_buf()
-        } else {
-            working_dir.join(path_str)
-        };
+        let target_path = resolve_path(path_str, working_dir)?.resolved;
 
         let mut result = String::new();
         result.push_str(&format!(
@@ -485,11 +474,7 @@ impl Tool for DebugError {
 
         let path_str = args.get("path").and_then(|v| v.as_str()).unwrap_or(".");
 
-        let target_path = if Path::new(path_str).is_absolute() {
-            Path::new(path_str).to_path_buf()
-        } else {
-            working_dir.join(path_str)
-        };
+        let target_path = resolve_path(path_str, working_dir)?.resolved;
 
         let mut result = String::new();
         result.push_str("# Error Analysis\n\n");
@@ -598,3 +583,135 @@ impl Tool for DebugError {
         Ok(result)
     }
 }
+
+/// One-shot repository overview: status, diff stat and recent log, fetched concurrently.
+pub struct GitOverview;
+
+fn git_section(result: Result<String, String>) -> Value {
+    match result {
+        Ok(output) => json!({ "ok": true, "output": output }),
+        Err(error) => json!({ "ok": false, "error": error }),
+    }
+}
+
+#[async_trait]
+impl Tool for GitOverview {
+    fn name(&self) -> &str {
+        "git_overview"
+    }
+
+    fn description(&self) -> &str {
+        "Get a one-shot snapshot of a git repository: branch/status, a stat-only diff of uncommitted changes, and the most recent commits. Runs the read-only git commands concurrently and returns combined JSON."
+    }
+
+    fn parameters_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "path": {
+                    "type": "string",
+                    "description": "Path to the repository (default: current directory)"
+                },
+                "log_count": {
+                    "type": "integer",
+                    "description": "Number of recent commits to include (default: 5)"
+                }
+            },
+            "required": []
+        })
+    }
+
+    async fn execute(&self, args: Value, working_dir: &Path) -> anyhow::Result<String> {
+        let path_str = args.get("path").and_then(|v| v.as_str()).unwrap_or(".");
+        let log_count = args
+            .get("log_count")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(5)
+            .clamp(1, 100);
+
+        let repo = resolve_path(path_str, working_dir)?.resolved;
+
+        let log_limit = format!("-{}", log_count);
+        let log_args = ["log", log_limit.as_str(), "--oneline", "--decorate"];
+        let (status, diff_stat, log) = tokio::join!(
+            run_git(&repo, &["status", "--short", "--branch"]),
+            run_git(&repo, &["diff", "HEAD", "--stat"]),
+            run_git(&repo, &log_args),
+        );
+
+        let overview = json!({
+            "path": repo.display().to_string(),
+            "status": git_section(status),
+            "diff_stat": git_section(diff_stat),
+            "log": git_section(log),
+        });
+        Ok(serde_json::to_string_pretty(&overview)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_git_overview_contains_all_sections() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = dir.path();
+        for args in [
+            vec!["init", "-q"],
+            vec!["config", "user.email", "test@example.com"],
+            vec!["config", "user.name", "Test"],
+        ] {
+            run_git(repo, &args).await.unwrap();
+        }
+        std::fs::write(repo.join("a.txt"), "one\n").unwrap();
+        run_git(repo, &["add", "a.txt"]).await.unwrap();
+        run_git(repo, &["commit", "-qm", "initial commit"])
+            .await
+            .unwrap();
+        std::fs::write(repo.join("a.txt"), "one\ntwo\n").unwrap();
+
+        let output = GitOverview.execute(json!({}), repo).await.unwrap();
+        let overview: Value = serde_json::from_str(&output).unwrap();
+
+        assert!(overview["status"]["output"]
+            .as_str()
+            .unwrap()
+            .contains("a.txt"));
+        assert!(overview["diff_stat"]["output"]
+            .as_str()
+            .unwrap()
+            .contains("1 file changed"));
+        assert!(overview["log"]["output"]
+            .as_str()
+            .unwrap()
+            .contains("initial commit"));
+    }
+
+    #[tokio::test]
+    async fn test_paths_outside_workspace_are_rejected() {
+        if crate::tools::PathPolicy::global().allow_workspace_escape {
+            return;
+        }
+        let dir = tempfile::tempdir().unwrap();
+        let workspace = dir.path().join("ws");
+        std::fs::create_dir(&workspace).unwrap();
+        let tools: [&dyn Tool; 5] = [
+            &AnalyzeCodebase,
+            &DeepSearch,
+            &PrepareProject,
+            &DebugError,
+            &GitOverview,
+        ];
+        for tool in tools {
+            let args = json!({ "path": "..", "pattern": "x", "error_message": "x" });
+            let err = tool.execute(args, &workspace).await.unwrap_err();
+            assert!(
+                err.to_string().contains("outside the workspace"),
+                "{}: {}",
+                tool.name(),
+                err
+            );
+        }
+    }
+}
@@ -0,0 +1,468 @@
+//! Config promotion tool: diff two configuration files (or directories) and
+//! copy selected changes from a source environment to a target environment.
+//!
+//! Files are compared as flattened key paths (`database.pool.size`); arrays
+//! are compared whole. Keys that only exist in the target are always kept.
+//! JSON, YAML and TOML are supported and may be mixed. YAML targets are edited
+//! in place through the `yaml_path` editor so comments survive; JSON and TOML
+//! targets are re-serialized.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use async_trait::async_trait;
+use serde_json::{json, Map, Value};
+
+use super::yaml::{self, Segment};
+use super::{resolve_path_simple, Tool};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Json,
+    Yaml,
+    Toml,
+}
+
+impl Format {
+    fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()?.to_lowercase().as_str() {
+            "json" => Some(Format::Json),
+            "yaml" | "yml" => Some(Format::Yaml),
+            "toml" => Some(Format::Toml),
+            _ => None,
+        }
+    }
+
+    fn parse(self, text: &str) -> anyhow::Result<Value> {
+        let value: Value = match self {
+            Format::Json => serde_json::from_str(text)?,
+            Format::Yaml => serde_yaml::from_str(text)?,
+            Format::Toml => toml::from_str(text)?,
+        };
+        match value {
+            Value::Null => Ok(Value::Object(Map::new())),
+            Value::Object(_) => Ok(value),
+            _ => Err(anyhow::anyhow!("top level must be a mapping")),
+        }
+    }
+
+    fn serialize(self, value: &Value) -> anyhow::Result<String> {
+        Ok(match self {
+            Format::Json => format!("{}\n", serde_json::to_string_pretty(value)?),
+            Format::Yaml => serde_yaml::to_string(value)?,
+            Format::Toml => toml::to_string_pretty(value)?,
+        })
+    }
+}
+
+/// Display form of a key path: dotted, with `["..."]` for keys containing dots.
+fn display_path(keys: &[String]) -> String {
+    let mut out = String::new();
+    for key in keys {
+        if key.contains(['.', '[', ']']) || key.is_empty() {
+            out.push_str(&format!("[\"{}\"]", key));
+        } else {
+            if !out.is_empty() {
+                out.push('.');
+            }
+            out.push_str(key);
+        }
+    }
+    out
+}
+
+/// Leaf values keyed by their display path.
+type Leaves = BTreeMap<String, (Vec<String>, Value)>;
+
+fn flatten(value: &Value, prefix: &mut Vec<String>, out: &mut Leaves) {
+    match value {
+        Value::Object(map) if !map.is_empty() || prefix.is_empty() => {
+            for (key, child) in map {
+                prefix.push(key.clone());
+                flatten(child, prefix, out);
+                prefix.pop();
+            }
+        }
+        leaf => {
+            out.insert(display_path(prefix), (prefix.clone(), leaf.clone()));
+        }
+    }
+}
+
+fn leaves(value: &Value) -> Leaves {
+    let mut out = Leaves::new();
+    flatten(value, &mut Vec::new(), &mut out);
+    out
+}
+
+/// A key whose source value differs from (or is missing in) the target.
+#[derive(Debug, Clone)]
+struct Change {
+    path: String,
+    keys: Vec<String>,
+    from: Option<Value>,
+    to: Value,
+}
+
+/// Differences between a source and target document.
+#[derive(Debug, Default)]
+struct ConfigDiff {
+    changes: Vec<Change>,
+    target_only: Vec<String>,
+}
+
+fn diff(source: &Value, target: &Value) -> ConfigDiff {
+    let source = leaves(source);
+    let target = leaves(target);
+    let mut result = ConfigDiff::default();
+    for (path, (keys, value)) in &source {
+        let current = target.get(path).map(|(_, v)| v);
+        if current != Some(value) {
+            result.changes.push(Change {
+                path: path.clone(),
+                keys: keys.clone(),
+                from: current.cloned(),
+                to: value.clone(),
+            });
+        }
+    }
+    result.target_only = target
+        .keys()
+        .filter(|path| {
+            !source.contains_key(*path)
+                // A leaf replaced by (or replacing) a nested mapping is a change, not target-only.
+                && !source.keys().any(|s| s.starts_with(&format!("{}.", path)))
+                && !source.keys().any(|s| path.starts_with(&format!("{}.", s)))
+        })
+        .cloned()
+        .collect();
+    result
+}
+
+/// Whether `path` was selected (exactly, or through a parent path).
+fn is_selected(path: &str, selection: Option<&[String]>) -> bool {
+    match selection {
+        None => true,
+        Some(keys) => keys.iter().any(|key| {
+            let key = key.trim_start_matches('.');
+            path == key || path.starts_with(&format!("{}.", key))
+        }),
+    }
+}
+
+fn set_json(node: &mut Value, keys: &[String], value: Value) {
+    let Some((first, rest)) = keys.split_first() else {
+        *node = value;
+        return;
+    };
+    if !node.is_object() {
+        *node = Value::Object(Map::new());
+    }
+    if let Value::Object(map) = node {
+        set_json(map.entry(first.clone()).or_insert(Value::Null), rest, value);
+    }
+}
+
+/// Write `changes` into the target text. Returns the new text and whether
+/// its formatting (comments, key order) was preserved.
+fn apply_changes(
+    target_text: &str,
+    target: &Value,
+    format: Format,
+    changes: &[&Change],
+) -> anyhow::Result<(String, bool)> {
+    if format == Format::Yaml {
+        let mut text = target_text.to_string();
+        let mut preserved = true;
+        for change in changes {
+            let path: Vec<Segment> = change.keys.iter().cloned().map(Segment::Key).collect();
+            let edited = yaml::set_in_document(&text, &path, serde_yaml::to_value(&change.to)?)?;
+            text = edited.content;
+            preserved &= edited.preserved;
+        }
+        return Ok((text, preserved));
+    }
+    let mut value = target.clone();
+    for change in changes {
+        set_json(&mut value, &change.keys, change.to.clone());
+    }
+    let text = format.serialize(&value)?;
+    // Re-serializing drops comments; at least make sure the result parses.
+    format.parse(&text)?;
+    Ok((text, false))
+}
+
+fn config_files(dir: &Path) -> BTreeMap<PathBuf, PathBuf> {
+    walkdir::WalkDir::new(dir)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file() && Format::from_path(e.path()).is_some())
+        .filter_map(|e| {
+            let relative = e.path().strip_prefix(dir).ok()?.to_path_buf();
+            Some((relative, e.into_path()))
+        })
+        .collect()
+}
+
+/// Diff one file pair and optionally apply the selected changes.
+async fn promote_file(
+    source: &Path,
+    target: &Path,
+    selection: Option<&[String]>,
+    apply: bool,
+) -> anyhow::Result<Value> {
+    let read = |path: &Path| {
+        let path = path.to_path_buf();
+        async move {
+            let format = Format::from_path(&path).ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Unsupported config format: {} (expected .json, .yaml, .yml or .toml)",
+                    path.display()
+                )
+            })?;
+            let text = tokio::fs::read_to_string(&path)
+                .await
+                .map_err(|e| anyhow::anyhow!("Failed to read {}: {}", path.display(), e))?;
+            let value = format
+                .parse(&text)
+                .map_err(|e| anyhow::anyhow!("Failed to parse {}: {}", path.display(), e))?;
+            Ok::<_, anyhow::Error>((format, text, value))
+        }
+    };
+    let (_, _, source_value) = read(source).await?;
+    let (target_format, target_text, target_value) = read(target).await?;
+
+    let diff = diff(&source_value, &target_value);
+    let selected: Vec<&Change> = diff
+        .changes
+        .iter()
+        .filter(|c| is_selected(&c.path, selection))
+        .collect();
+
+    let mut report = json!({
+        "source": source.display().to_string(),
+        "target": target.display().to_string(),
+        "changes": diff.changes.iter().map(|c| json!({
+            "key": c.path,
+            "target": c.from,
+            "source": c.to,
+            "selected": is_selected(&c.path, selection),
+        })).collect::<Vec<_>>(),
+        "target_only": diff.target_only,
+    });
+    if apply && !selected.is_empty() {
+        let (text, preserved) =
+            apply_changes(&target_text, &target_value, target_format, &selected)?;
+        tokio::fs::write(target, text).await?;
+        report["applied"] = json!(selected.iter().map(|c| &c.path).collect::<Vec<_>>());
+        report["formatting"] = json!(if preserved {
+            "preserved"
+        } else {
+            "reserialized (comments removed)"
+        });
+    }
+    Ok(report)
+}
+
+/// Diff configuration between environments and promote selected changes.
+pub struct ConfigPromote;
+
+#[async_trait]
+impl Tool for ConfigPromote {
+    fn name(&self) -> &str {
+        "config_promote"
+    }
+
+    fn description(&self) -> &str {
+        "Promote configuration between environments (e.g. staging -> prod). Compares two JSON/YAML/TOML files or directories key by key and lists keys that differ or are missing in the target. With apply=true, copies the selected keys (default: all differences) into the target. Keys that only exist in the target are never removed. Dry-run by default."
+    }
+
+    fn parameters_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "source": {
+                    "type": "string",
+                    "description": "Source config file or directory (the environment to promote from)"
+                },
+                "target": {
+                    "type": "string",
+                    "description": "Target config file or directory (the environment to update)"
+                },
+                "keys": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "Key paths to promote, e.g. ['database.pool_size', 'features']; a path selects everything below it. Default: all differences."
+                },
+                "apply": {
+                    "type": "boolean",
+                    "description": "Write the selected changes to the target (default: false, dry-run)"
+                }
+            },
+            "required": ["source", "target"]
+        })
+    }
+
+    async fn execute(&self, args: Value, working_dir: &Path) -> anyhow::Result<String> {
+        let source = args["source"]
+            .as_str()
+            .ok_or_else(|| anyhow::anyhow!("Missing 'source' argument"))?;
+        let target = args["target"]
+            .as_str()
+            .ok_or_else(|| anyhow::anyhow!("Missing 'target' argument"))?;
+        let apply = args["apply"].as_bool().unwrap_or(false);
+        let selection: Option<Vec<String>> = args["keys"].as_array().map(|keys| {
+            keys.iter()
+                .filter_map(|k| k.as_str().map(str::to_string))
+                .collect()
+        });
+        let selection = selection.as_deref();
+
+        let source_path = resolve_path_simple(source, working_dir)?;
+        let target_path = resolve_path_simple(target, working_dir)?;
+        if !source_path.exists() {
+            return Err(anyhow::anyhow!("Source not found: {}", source));
+        }
+
+        if !source_path.is_dir() {
+            if !target_path.is_file() {
+                return Err(anyhow::anyhow!("Target file not found: {}", target));
+            }
+            let mut report = promote_file(&source_path, &target_path, selection, apply).await?;
+            report["dry_run"] = json!(!apply);
+            return Ok(serde_json::to_string_pretty(&report)?);
+        }
+
+        if !target_path.is_dir() {
+            return Err(anyhow::anyhow!(
+                "Target must be a directory when the source is: {}",
+                target
+            ));
+        }
+        let source_files = config_files(&source_path);
+        let target_files = config_files(&target_path);
+        let mut files = Vec::new();
+        let mut new_files = Vec::new();
+        for (relative, source_file) in &source_files {
+            match target_files.get(relative) {
+                Some(target_file) => {
+                    files.push(promote_file(source_file, target_file, selection, apply).await?)
+                }
+                None => {
+                    // Whole files are only copied when promoting everything.
+                    if apply && selection.is_none() {
+                        let destination = target_path.join(relative);
+                        if let Some(parent) = destination.parent() {
+                            tokio::fs::create_dir_all(parent).await?;
+                        }
+                        tokio::fs::copy(source_file, &destination).await?;
+                    }
+                    new_files.push(relative.display().to_string());
+                }
+            }
+        }
+        let target_only_files: Vec<String> = target_files
+            .keys()
+            .filter(|relative| !source_files.contains_key(*relative))
+            .map(|relative| relative.display().to_string())
+            .collect();
+
+        Ok(serde_json::to_string_pretty(&json!({
+            "dry_run": !apply,
+            "files": files,
+            "new_files": new_files,
+            "new_files_copied": apply && selection.is_none() && !new_files.is_empty(),
+            "target_only_files": target_only_files,
+        }))?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_promote_changed_key_dry_run_then_apply() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("staging.yaml"),
+            "database:\n  host: staging-db\n  pool_size: 20\nfeatures:\n  search: true\n",
+        )
+        .unwrap();
+        let prod = "# production settings\ndatabase:\n  host: prod-db # keep\n  pool_size: 10\nreplicas: 3\n";
+        std::fs::write(dir.path().join("prod.yaml"), prod).unwrap();
+
+        let args = json!({
+            "source": "staging.yaml",
+            "target": "prod.yaml",
+            "keys": ["database.pool_size"]
+        });
+        let out = ConfigPromote
+            .execute(args.clone(), dir.path())
+            .await
+            .unwrap();
+        let report: Value = serde_json::from_str(&out).unwrap();
+        assert_eq!(report["dry_run"], true);
+        let changes = report["changes"].as_array().unwrap();
+        let keys: Vec<&str> = changes.iter().map(|c| c["key"].as_str().unwrap()).collect();
+        assert_eq!(
+            keys,
+            ["database.host", "database.pool_size", "features.search"]
+        );
+        assert_eq!(changes[1]["target"], 10);
+        assert_eq!(changes[1]["source"], 20);
+        assert_eq!(changes[1]["selected"], true);
+        assert_eq!(changes[0]["selected"], false);
+        assert_eq!(report["target_only"], json!(["replicas"]));
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("prod.yaml")).unwrap(),
+            prod
+        );
+
+        let mut args = args;
+        args["apply"] = json!(true);
+        let out = ConfigPromote.execute(args, dir.path()).await.unwrap();
+        let report: Value = serde_json::from_str(&out).unwrap();
+        assert_eq!(report["applied"], json!(["database.pool_size"]));
+        assert_eq!(report["formatting"], "preserved");
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("prod.yaml")).unwrap(),
+            prod.replace("pool_size: 10", "pool_size: 20")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_promote_directory_across_formats() {
+        let dir = tempfile::tempdir().unwrap();
+        let staging = dir.path().join("staging");
+        let prod = dir.path().join("prod");
+        std::fs::create_dir_all(&staging).unwrap();
+        std::fs::create_dir_all(&prod).unwrap();
+        std::fs::write(
+            staging.join("app.toml"),
+            "[server]\nport = 8080\nworkers = 8\n",
+        )
+        .unwrap();
+        std::fs::write(prod.join("app.toml"), "[server]\nport = 80\nworkers = 4\n").unwrap();
+        std::fs::write(staging.join("flags.json"), "{\"beta\": true}").unwrap();
+        std::fs::write(prod.join("secrets.json"), "{\"token\": \"x\"}").unwrap();
+
+        let out = ConfigPromote
+            .execute(
+                json!({ "source": "staging", "target": "prod", "keys": ["server.workers"], "apply": true }),
+                dir.path(),
+            )
+            .await
+            .unwrap();
+        let report: Value = serde_json::from_str(&out).unwrap();
+        assert_eq!(report["new_files"], json!(["flags.json"]));
+        assert_eq!(report["new_files_copied"], false);
+        assert_eq!(report["target_only_files"], json!(["secrets.json"]));
+
+        let app: toml::Value =
+            toml::from_str(&std::fs::read_to_string(prod.join("app.toml")).unwrap()).unwrap();
+        assert_eq!(app["server"]["workers"].as_integer(), Some(8));
+        assert_eq!(app["server"]["port"].as_integer(), Some(80));
+        assert!(!prod.join("flags.json").exists());
+    }
+}
@@ -4,16 +4,22 @@
 //!
 //! These tools work relative to the workspace by default:
 //! - `src/` → lists `{workspace}/src/`
-//! - `/var/log` → absolute path for system directories
+//! - `/var/log` → absolute path for system directories, only when
+//!   `OPEN_AGENT_ALLOW_WORKSPACE_ESCAPE` is set
 
 use std::path::Path;
 
 use async_trait::async_trait;
+use ignore::WalkBuilder;
 use serde_json::{json, Value};
 use walkdir::WalkDir;
 
+use super::workspace_ignore::WorkspaceIgnore;
 use super::{resolve_path, Tool};
 
+/// Default cap on entries returned by `list_directory`.
+const DEFAULT_MAX_ENTRIES: usize = 500;
+
 /// List contents of a directory.
 pub struct ListDirectory;
 
@@ -24,7 +30,7 @@ impl Tool for ListDirectory {
     }
 
     fn description(&self) -> &str {
-        "List files and directories. Use '.' for current workspace, relative paths like 'src/', or absolute paths for system directories."
+        "List files and directories as an indented tree. Use '.' for current workspace or relative paths like 'src/'. Respects .gitignore and .openagentignore (plus heavy dirs like node_modules and target) and hides dotfiles by default."
     }
 
     fn parameters_schema(&self) -> Value {
@@ -33,11 +39,31 @@ impl Tool for ListDirectory {
             "properties": {
                 "path": {
                     "type": "string",
-                    "description": "Directory path. Use '.' for workspace root, relative paths (e.g., 'src/', 'output/') for subdirectories, or absolute paths for system dirs."
+                    "description": "Directory path. Use '.' for workspace root, relative paths (e.g., 'src/', 'output/') for subdirectories."
+                },
+                "recursive": {
+                    "type": "boolean",
+                    "description": "Descend into subdirectories (default: true). false lists only the top level."
                 },
                 "max_depth": {
                     "type": "integer",
-                    "description": "Maximum depth to traverse (default: 3)"
+                    "description": "Maximum depth to traverse when recursive (default: 3)"
+                },
+                "show_hidden": {
+                    "type": "boolean",
+                    "description": "Include dotfiles and dot-directories (default: false)"
+                },
+                "ignore_gitignore": {
+                    "type": "boolean",
+                    "description": "List files excluded by .gitignore too (default: false)"
+                },
+                "respect_ignore": {
+                    "type": "boolean",
+                    "description": "Skip paths matched by .openagentignore and the built-in heavy dirs (node_modules, target, .git, ...) (default: true)"
+                },
+                "max_entries": {
+                    "type": "integer",
+                    "description": "Maximum number of entries to return (default: 500)"
                 }
             },
             "required": ["path"]
@@ -48,9 +74,19 @@ impl Tool for ListDirectory {
         let path = args["path"]
             .as_str()
             .ok_or_else(|| anyhow::anyhow!("Missing 'path' argument"))?;
-        let max_depth = args["max_depth"].as_u64().unwrap_or(3) as usize;
+        let max_depth = if args["recursive"].as_bool().unwrap_or(true) {
+            args["max_depth"].as_u64().unwrap_or(3) as usize
+        } else {
+            1
+        };
+        let show_hidden = args["show_hidden"].as_bool().unwrap_or(false);
+        let respect_gitignore = !args["ignore_gitignore"].as_bool().unwrap_or(false);
+        let max_entries = args["max_entries"]
+            .as_u64()
+            .map(|n| n as usize)
+            .unwrap_or(DEFAULT_MAX_ENTRIES);
 
-        let resolution = resolve_path(path, working_dir);
+        let resolution = resolve_path(path, working_dir)?;
 
         if !resolution.resolved.exists() {
             return Err(anyhow::anyhow!(
@@ -65,35 +101,121 @@ impl Tool for ListDirectory {
         }
 
         let full_path = resolution.resolved;
+        let ignore = WorkspaceIgnore::for_call(&args, working_dir, &full_path);
 
         let mut entries = Vec::new();
-        let walker = WalkDir::new(&full_path)
-            .max_depth(max_depth)
-            .sort_by_file_name();
+        let mut omitted = 0usize;
+        let mut builder = WalkBuilder::new(&full_path);
+        builder
+            .max_depth(Some(max_depth))
+            .hidden(!show_hidden)
+            .git_ignore(respect_gitignore)
+            .git_global(respect_gitignore)
+            .git_exclude(respect_gitignore)
+            .ignore(respect_gitignore)
+            .parents(respect_gitignore)
+            // Honour .gitignore files even when the directory is not a git checkout.
+            .require_git(false)
+            .sort_by_file_name(|a, b| a.cmp(b));
+        if let Some(ignore) = ignore {
+            builder.filter_entry(move |entry| {
+                let is_dir = entry.file_type().is_some_and(|t| t.is_dir());
+                !ignore.is_ignored(entry.path(), is_dir)
+            });
+        }
+        let walker = builder.build();
 
-        for entry in walker.into_iter().filter_map(|e| e.ok()) {
+        for entry in walker.filter_map(|e| e.ok()) {
             let depth = entry.depth();
-            let path = entry.path();
-            let relative = path.strip_prefix(&full_path).unwrap_or(path);
-
-            if relative.as_os_str().is_empty() {
+            if depth == 0 {
+                continue;
+            }
+            if entries.len() >= max_entries {
+                omitted += 1;
                 continue;
             }
 
-            let prefix = "  ".repeat(depth.saturating_sub(1));
-            let name = relative
-                .file_name()
-                .map(|n| n.to_string_lossy().to_string())
-                .unwrap_or_default();
-
-            let suffix = if path.is_dir() { "/" } else { "" };
+            let prefix = "  ".repeat(depth - 1);
+            let name = entry.file_name().to_string_lossy();
+            let suffix = if entry.path().is_dir() { "/" } else { "" };
             entries.push(format!("{}{}{}", prefix, name, suffix));
         }
 
         if entries.is_empty() {
-            Ok("Directory is empty".to_string())
-        } else {
-            Ok(entries.join("\n"))
+            return Ok("Directory is empty".to_string());
+        }
+        if omitted > 0 {
+            entries.push(format!("… truncated, {} more entries", omitted));
+        }
+        Ok(entries.join("\n"))
+    }
+}
+
+/// Maximum number of paths returned by `search_files`.
+const MAX_SEARCH_RESULTS: usize = 100;
+
+/// How `search_files` interprets its pattern.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SearchMode {
+    /// Case-insensitive substring of the file name.
+    Substring,
+    /// Glob syntax (`*.rs`, `**/*.test.ts`, `src/{a,b}/*.json`).
+    Glob,
+}
+
+impl SearchMode {
+    fn from_args(mode: Option<&str>, pattern: &str) -> anyhow::Result<Self> {
+        match mode {
+            Some("glob") => Ok(Self::Glob),
+            Some("substring") => Ok(Self::Substring),
+            Some(other) => Err(anyhow::anyhow!(
+                "Invalid mode '{}': expected 'glob' or 'substring'",
+                other
+            )),
+            // Plain words keep the historical substring behaviour.
+            None if pattern.contains(['*', '?', '[', '{']) => Ok(Self::Glob),
+            None => Ok(Self::Substring),
+        }
+    }
+}
+
+/// Compiled file matcher for `search_files`.
+enum FileMatcher {
+    Substring(String),
+    /// Patterns without a `/` match the file name; others the relative path.
+    Glob {
+        matcher: globset::GlobMatcher,
+        match_path: bool,
+    },
+}
+
+impl FileMatcher {
+    fn new(pattern: &str, mode: SearchMode) -> anyhow::Result<Self> {
+        match mode {
+            SearchMode::Substring => Ok(Self::Substring(pattern.to_lowercase())),
+            SearchMode::Glob => {
+                let glob = globset::GlobBuilder::new(pattern)
+                    .case_insensitive(true)
+                    .literal_separator(true)
+                    .build()
+                    .map_err(|e| anyhow::anyhow!("Invalid glob pattern '{}': {}", pattern, e))?;
+                Ok(Self::Glob {
+                    matcher: glob.compile_matcher(),
+                    match_path: pattern.contains('/'),
+                })
+            }
+        }
+    }
+
+    fn is_match(&self, relative: &Path) -> bool {
+        let file_name = relative.file_name().unwrap_or_default();
+        match self {
+            Self::Substring(needle) => file_name.to_string_lossy().to_lowercase().contains(needle),
+            Self::Glob {
+                matcher,
+                match_path: true,
+            } => matcher.is_match(relative),
+            Self::Glob { matcher, .. } => matcher.is_match(file_name),
         }
     }
 }
@@ -108,7 +230,7 @@ impl Tool for SearchFiles {
     }
 
     fn description(&self) -> &str {
-        "Search for files by name pattern (glob-style). Searches workspace by default, or specify a path."
+        "Search for files by name. Supports glob patterns ('*.rs', '**/*.test.ts') or plain substrings. Searches workspace by default, or specify a path, skipping .openagentignore paths and heavy dirs like node_modules. Results are sorted and relative to the workspace."
     }
 
     fn parameters_schema(&self) -> Value {
@@ -117,11 +239,28 @@ impl Tool for SearchFiles {
             "properties": {
                 "pattern": {
                     "type": "string",
-                    "description": "File name pattern to search for (e.g., '*.rs', 'test_*.py', 'README*')"
+                    "description": "Glob pattern or name fragment (e.g., '*.rs', '**/*.test.ts', 'src/**/mod.rs', 'README'). Patterns without '/' match the file name at any depth."
                 },
                 "path": {
                     "type": "string",
                     "description": "Directory to search in. Defaults to workspace ('.'). Use relative paths or absolute for system-wide search."
+                },
+                "mode": {
+                    "type": "string",
+                    "enum": ["glob", "substring"],
+                    "description": "How to interpret 'pattern'. Defaults to 'glob' when the pattern contains *, ?, [ or {, otherwise 'substring'."
+                },
+                "max_depth": {
+                    "type": "integer",
+                    "description": "Maximum directory depth to descend (1 = only direct children). Default: unlimited"
+                },
+                "follow_symlinks": {
+                    "type": "boolean",
+                    "description": "Follow symbolic links to directories (default: false)"
+                },
+                "respect_ignore": {
+                    "type": "boolean",
+                    "description": "Skip paths matched by .openagentignore and the built-in heavy dirs (node_modules, target, .git, ...) (default: true)"
                 }
             },
             "required": ["pattern"]
@@ -133,8 +272,11 @@ impl Tool for SearchFiles {
             .as_str()
             .ok_or_else(|| anyhow::anyhow!("Missing 'pattern' argument"))?;
         let path = args["path"].as_str().unwrap_or(".");
+        let mode = SearchMode::from_args(args["mode"].as_str(), pattern)?;
+        let max_depth = args["max_depth"].as_u64().map(|d| d as usize);
+        let follow_symlinks = args["follow_symlinks"].as_bool().unwrap_or(false);
 
-        let resolution = resolve_path(path, working_dir);
+        let resolution = resolve_path(path, working_dir)?;
         let full_path = resolution.resolved;
 
         if !full_path.exists() {
@@ -145,78 +287,180 @@ impl Tool for SearchFiles {
             ));
         }
 
-        // Convert glob pattern to simple matching
-        let pattern_lower = pattern.to_lowercase();
-        let is_glob = pattern.contains('*');
+        let matcher = FileMatcher::new(pattern, mode)?;
+        let ignore = WorkspaceIgnore::for_call(&args, working_dir, &full_path);
 
-        let mut matches = Vec::new();
-        let walker = WalkDir::new(&full_path).into_iter().filter_map(|e| e.ok());
+        let mut walker = WalkDir::new(&full_path)
+            .follow_links(follow_symlinks)
+            .sort_by_file_name();
+        if let Some(depth) = max_depth {
+            walker = walker.max_depth(depth);
+        }
 
-        for entry in walker {
+        let mut matches = Vec::new();
+        let mut truncated = false;
+        let walker = walker.into_iter().filter_entry(|entry| {
+            !ignore
+                .as_ref()
+                .is_some_and(|i| i.is_ignored(entry.path(), entry.file_type().is_dir()))
+        });
+        for entry in walker.filter_map(|e| e.ok()) {
             if !entry.file_type().is_file() {
                 continue;
             }
-
-            let file_name = entry.file_name().to_string_lossy().to_lowercase();
-
-            let matched = if is_glob {
-                // Simple glob matching
-                glob_match(&pattern_lower, &file_name)
-            } else {
-                file_name.contains(&pattern_lower)
-            };
-
-            if matched {
-                // Show absolute path for system-wide clarity
-                matches.push(entry.path().to_string_lossy().to_string());
+            let relative = entry
+                .path()
+                .strip_prefix(&full_path)
+                .unwrap_or(entry.path());
+            if !matcher.is_match(relative) {
+                continue;
             }
-
-            // Limit results
-            if matches.len() >= 100 {
-                matches.push("... (results truncated, showing first 100)".to_string());
+            if matches.len() >= MAX_SEARCH_RESULTS {
+                truncated = true;
                 break;
             }
+            // Paths inside the workspace are shown relative to it; others stay absolute.
+            let display = entry
+                .path()
+                .strip_prefix(working_dir)
+                .unwrap_or(entry.path());
+            matches.push(display.to_string_lossy().to_string());
         }
 
         if matches.is_empty() {
-            Ok(format!("No files matching '{}' found", pattern))
-        } else {
-            Ok(matches.join("\n"))
+            return Ok(format!("No files matching '{}' found", pattern));
+        }
+        matches.sort();
+        if truncated {
+            matches.push(format!(
+                "... (results truncated, showing first {})",
+                MAX_SEARCH_RESULTS
+            ));
         }
+        Ok(matches.join("\n"))
     }
 }
 
-/// Simple glob pattern matching.
-fn glob_match(pattern: &str, text: &str) -> bool {
-    let parts: Vec<&str> = pattern.split('*').collect();
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_search_files_glob_and_substring() {
+        let dir = tempfile::tempdir().unwrap();
+        for file in [
+            "b.test.ts",
+            "src/app.test.ts",
+            "src/app.ts",
+            "src/nested/deep/a.test.ts",
+            "README.md",
+        ] {
+            let path = dir.path().join(file);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, "").unwrap();
+        }
+
+        let search = |args: Value| SearchFiles.execute(args, dir.path());
+
+        let out = search(json!({ "pattern": "**/*.test.ts" })).await.unwrap();
+        assert_eq!(out, "b.test.ts\nsrc/app.test.ts\nsrc/nested/deep/a.test.ts");
+
+        let out = search(json!({ "pattern": "src/*.ts" })).await.unwrap();
+        assert_eq!(out, "src/app.test.ts\nsrc/app.ts");
 
-    if parts.len() == 1 {
-        // No wildcards
-        return pattern == text;
+        let out = search(json!({ "pattern": "*.TEST.ts", "max_depth": 2 }))
+            .await
+            .unwrap();
+        assert_eq!(out, "b.test.ts\nsrc/app.test.ts");
+
+        let out = search(json!({ "pattern": "readme" })).await.unwrap();
+        assert_eq!(out, "README.md");
+
+        let out = search(json!({ "pattern": "app", "path": "src", "mode": "substring" }))
+            .await
+            .unwrap();
+        assert_eq!(out, "src/app.test.ts\nsrc/app.ts");
+
+        assert!(search(json!({ "pattern": "x", "mode": "regex" }))
+            .await
+            .is_err());
     }
 
-    let mut pos = 0;
-    for (i, part) in parts.iter().enumerate() {
-        if part.is_empty() {
-            continue;
+    #[tokio::test]
+    async fn test_list_directory_tree_respects_gitignore_and_cap() {
+        let dir = tempfile::tempdir().unwrap();
+        for file in [
+            "src/main.rs",
+            "src/util/mod.rs",
+            "target/debug/app",
+            ".env",
+            "README.md",
+        ] {
+            let path = dir.path().join(file);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, "").unwrap();
         }
+        std::fs::write(dir.path().join(".gitignore"), "target/\n").unwrap();
 
-        match text[pos..].find(part) {
-            Some(idx) => {
-                // First part must be at start if pattern doesn't start with *
-                if i == 0 && idx != 0 {
-                    return false;
-                }
-                pos += idx + part.len();
-            }
-            None => return false,
-        }
-    }
+        let list = |args: Value| ListDirectory.execute(args, dir.path());
+
+        let out = list(json!({ "path": "." })).await.unwrap();
+        assert_eq!(out, "README.md\nsrc/\n  main.rs\n  util/\n    mod.rs");
 
-    // Last part must be at end if pattern doesn't end with *
-    if !pattern.ends_with('*') && !parts.last().unwrap().is_empty() {
-        return text.ends_with(parts.last().unwrap());
+        let out = list(json!({ "path": ".", "recursive": false }))
+            .await
+            .unwrap();
+        assert_eq!(out, "README.md\nsrc/");
+
+        let out = list(json!({ "path": ".", "recursive": false, "show_hidden": true, "ignore_gitignore": true, "respect_ignore": false }))
+            .await
+            .unwrap();
+        assert_eq!(out, ".env\n.gitignore\nREADME.md\nsrc/\ntarget/");
+
+        let out = list(json!({ "path": ".", "max_entries": 2 }))
+            .await
+            .unwrap();
+        assert_eq!(out, "README.md\nsrc/\n… truncated, 3 more entries");
     }
 
-    true
+    #[tokio::test]
+    async fn test_traversal_honours_openagentignore() {
+        let dir = tempfile::tempdir().unwrap();
+        for file in [
+            "src/main.rs",
+            "node_modules/pkg/main.rs",
+            "fixtures/main.rs",
+            "target/main.rs",
+        ] {
+            let path = dir.path().join(file);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, "").unwrap();
+        }
+        std::fs::write(dir.path().join(".openagentignore"), "fixtures/\n!target/\n").unwrap();
+
+        let out = ListDirectory
+            .execute(json!({ "path": ".", "recursive": false }), dir.path())
+            .await
+            .unwrap();
+        assert_eq!(out, "src/\ntarget/");
+
+        let search = |args: Value| SearchFiles.execute(args, dir.path());
+        let out = search(json!({ "pattern": "main.rs" })).await.unwrap();
+        assert_eq!(out, "src/main.rs\ntarget/main.rs");
+
+        let out = search(json!({ "pattern": "main.rs", "respect_ignore": false }))
+            .await
+            .unwrap();
+        assert_eq!(
+            out,
+            "fixtures/main.rs\nnode_modules/pkg/main.rs\nsrc/main.rs\ntarget/main.rs"
+        );
+
+        // Asking for an ignored directory by name lists it.
+        let out = ListDirectory
+            .execute(json!({ "path": "node_modules" }), dir.path())
+            .await
+            .unwrap();
+        assert_eq!(out, "pkg/\n  main.rs");
+    }
 }
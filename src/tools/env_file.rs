@@ -0,0 +1,425 @@
+//! `.env` file tools: merge key/value pairs into a file and diff two files.
+//!
+//! Merging is line-based so comments, blank lines and key order survive:
+//! existing keys are updated in place and new keys are appended. Values can
+//! optionally be encrypted on write with the workspace template key
+//! (`library::env_crypto`).
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use async_trait::async_trait;
+use serde_json::{json, Value};
+
+use super::{resolve_path, Tool};
+use crate::library::env_crypto;
+
+/// Whether `key` can be written as a variable name (`[A-Za-z0-9_.]+`).
+fn is_valid_key(key: &str) -> bool {
+    !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.')
+}
+
+/// Split an assignment line into `(key, raw_value)`. Returns `None` for comments,
+/// blank lines and anything that is not `KEY=VALUE` (optionally `export KEY=VALUE`).
+fn parse_assignment(line: &str) -> Option<(&str, &str)> {
+    let trimmed = line.trim_start();
+    if trimmed.is_empty() || trimmed.starts_with('#') {
+        return None;
+    }
+    let trimmed = trimmed.strip_prefix("export ").unwrap_or(trimmed);
+    let (key, value) = trimmed.split_once('=')?;
+    let key = key.trim();
+    if !is_valid_key(key) {
+        return None;
+    }
+    Some((key, value))
+}
+
+/// Split a raw value from its trailing inline comment (` # ...`, outside quotes).
+/// The comment keeps its leading whitespace so it can be written back as is.
+fn split_comment(raw: &str) -> (&str, Option<&str>) {
+    let rest = raw.trim_start();
+    let value_end = match rest.chars().next() {
+        Some(q @ ('\'' | '"')) => {
+            let mut escaped = false;
+            let close = rest.char_indices().skip(1).find(|&(_, c)| {
+                let close = c == q && !escaped;
+                escaped = q == '"' && c == '\\' && !escaped;
+                close
+            });
+            match close {
+                Some((idx, _)) => idx + 1,
+                None => return (raw, None),
+            }
+        }
+        _ => 0,
+    };
+    let start = raw.len() - rest.len() + value_end;
+    let tail = &raw[start..];
+    let comment_at = if value_end > 0 && tail.trim_start().starts_with('#') {
+        Some(0)
+    } else {
+        tail.char_indices()
+            .find(|&(i, c)| c == '#' && tail[..i].ends_with([' ', '\t']))
+            .map(|(i, _)| i - 1)
+    };
+    match comment_at {
+        Some(idx) => (&raw[..start + idx], Some(&tail[idx..])),
+        None => (raw, None),
+    }
+}
+
+/// Strip surrounding quotes and trailing inline comments.
+fn unquote(raw: &str) -> String {
+    let raw = split_comment(raw).0.trim();
+    if raw.len() >= 2 && raw.starts_with('\'') && raw.ends_with('\'') {
+        return raw[1..raw.len() - 1].to_string();
+    }
+    if raw.len() >= 2 && raw.starts_with('"') && raw.ends_with('"') {
+        let mut out = String::new();
+        let mut chars = raw[1..raw.len() - 1].chars();
+        while let Some(c) = chars.next() {
+            if c != '\\' {
+                out.push(c);
+                continue;
+            }
+            match chars.next() {
+                Some('n') => out.push('\n'),
+                Some(other) => out.push(other),
+                None => out.push('\\'),
+            }
+        }
+        return out;
+    }
+    raw.to_string()
+}
+
+/// Quote a value when it contains characters a dotenv parser would mangle.
+fn quote(value: &str) -> String {
+    let plain = !value.is_empty()
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "_-.,:/@+=%".contains(c));
+    if plain {
+        value.to_string()
+    } else if !value.contains('\'') && !value.contains('\n') {
+        format!("'{}'", value)
+    } else {
+        format!(
+            "\"{}\"",
+            value
+                .replace('\\', "\\\\")
+                .replace('"', "\\\"")
+                .replace('\n', "\\n")
+        )
+    }
+}
+
+/// Parse `.env` content into key/value pairs (later assignments win).
+pub fn parse_env(content: &str) -> BTreeMap<String, String> {
+    content
+        .lines()
+        .filter_map(parse_assignment)
+        .map(|(k, v)| (k.to_string(), unquote(v)))
+        .collect()
+}
+
+/// Merge `updates` into `.env` content, preserving comments (inline ones on
+/// updated lines too) and ordering.
+///
+/// Returns the new content plus the keys that were updated and appended.
+pub fn merge_env(
+    content: &str,
+    updates: &[(String, String)],
+) -> (String, Vec<String>, Vec<String>) {
+    let mut lines: Vec<String> = content.lines().map(str::to_string).collect();
+    let mut updated = Vec::new();
+    let mut added = Vec::new();
+
+    for (key, value) in updates {
+        let assignment = format!("{}={}", key, quote(value));
+        let existing = lines
+            .iter()
+            .rposition(|line| parse_assignment(line).is_some_and(|(k, _)| k == key));
+        match existing {
+            Some(idx) => {
+                let prefix = if lines[idx].trim_start().starts_with("export ") {
+                    "export "
+                } else {
+                    ""
+                };
+                let comment = parse_assignment(&lines[idx])
+                    .and_then(|(_, raw)| split_comment(raw).1)
+                    .unwrap_or("");
+                lines[idx] = format!("{}{}{}", prefix, assignment, comment);
+                updated.push(key.clone());
+            }
+            None => {
+                lines.push(assignment);
+                added.push(key.clone());
+            }
+        }
+    }
+
+    let mut merged = lines.join("\n");
+    if !merged.is_empty() {
+        merged.push('\n');
+    }
+    (merged, updated, added)
+}
+
+/// Merge key/value pairs into a `.env` file.
+pub struct EnvMerge;
+
+#[async_trait]
+impl Tool for EnvMerge {
+    fn name(&self) -> &str {
+        "env_merge"
+    }
+
+    fn description(&self) -> &str {
+        "Merge key=value pairs into a .env file. Existing keys are updated in place, new keys are appended, and comments are preserved. Set 'encrypt' to store values encrypted with the workspace template key."
+    }
+
+    fn parameters_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "path": {
+                    "type": "string",
+                    "description": "Path to the .env file (created if missing)"
+                },
+                "values": {
+                    "type": "object",
+                    "description": "Variables to set, e.g. {\"API_URL\": \"https://example.com\"}",
+                    "additionalProperties": { "type": "string" }
+                },
+                "encrypt": {
+                    "type": "boolean",
+                    "description": "Encrypt the written values (default: false)"
+                }
+            },
+            "required": ["path", "values"]
+        })
+    }
+
+    async fn execute(&self, args: Value, working_dir: &Path) -> anyhow::Result<String> {
+        let path = args["path"]
+            .as_str()
+            .ok_or_else(|| anyhow::anyhow!("Missing 'path' argument"))?;
+        let values = args["values"]
+            .as_object()
+            .ok_or_else(|| anyhow::anyhow!("'values' must be an object"))?;
+        let encrypt = args["encrypt"].as_bool().unwrap_or(false);
+
+        let key = if encrypt {
+            Some(env_crypto::ensure_private_key().await?)
+        } else {
+            None
+        };
+
+        let mut updates = Vec::with_capacity(values.len());
+        for (name, value) in values {
+            if !is_valid_key(name) {
+                anyhow::bail!(
+                    "Invalid variable name '{}': use letters, digits, '_' and '.'",
+                    name
+                );
+            }
+            let value = match value {
+                Value::String(s) => s.clone(),
+                Value::Null => String::new(),
+                other => other.to_string(),
+            };
+            let value = match &key {
+                Some(key) => env_crypto::encrypt_value(key, &value)?,
+                None => value,
+            };
+            updates.push((name.clone(), value));
+        }
+
+        let resolution = resolve_path(path, working_dir)?;
+        let content = match tokio::fs::read_to_string(&resolution.resolved).await {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(e.into()),
+        };
+
+        let (merged, updated, added) = merge_env(&content, &updates);
+        if let Some(parent) = resolution.resolved.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        tokio::fs::write(&resolution.resolved, merged).await?;
+
+        Ok(json!({
+            "path": resolution.resolved.display().to_string(),
+            "updated": updated,
+            "added": added,
+            "encrypted": encrypt,
+        })
+        .to_string())
+    }
+}
+
+/// Report differences between two `.env` files.
+pub struct EnvDiff;
+
+#[async_trait]
+impl Tool for EnvDiff {
+    fn name(&self) -> &str {
+        "env_diff"
+    }
+
+    fn description(&self) -> &str {
+        "Compare two .env files and report keys only in one of them and keys whose values differ. Values are hidden unless 'show_values' is true."
+    }
+
+    fn parameters_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "left": {
+                    "type": "string",
+                    "description": "Path to the first .env file (e.g. '.env.example')"
+                },
+                "right": {
+                    "type": "string",
+                    "description": "Path to the second .env file (e.g. '.env')"
+                },
+                "show_values": {
+                    "type": "boolean",
+                    "description": "Include values of changed keys in the output (default: false)"
+                }
+            },
+            "required": ["left", "right"]
+        })
+    }
+
+    async fn execute(&self, args: Value, working_dir: &Path) -> anyhow::Result<String> {
+        let left = args["left"]
+            .as_str()
+            .ok_or_else(|| anyhow::anyhow!("Missing 'left' argument"))?;
+        let right = args["right"]
+            .as_str()
+            .ok_or_else(|| anyhow::anyhow!("Missing 'right' argument"))?;
+        let show_values = args["show_values"].as_bool().unwrap_or(false);
+
+        let read = |p: &str| {
+            let resolved = resolve_path(p, working_dir).map(|r| r.resolved);
+            async move {
+                let resolved = resolved?;
+                tokio::fs::read_to_string(&resolved)
+                    .await
+                    .map_err(|e| anyhow::anyhow!("Failed to read {}: {}", resolved.display(), e))
+            }
+        };
+        let left_vars = parse_env(&read(left).await?);
+        let right_vars = parse_env(&read(right).await?);
+
+        let only_in_left: Vec<&String> = left_vars
+            .keys()
+            .filter(|k| !right_vars.contains_key(*k))
+            .collect();
+        let only_in_right: Vec<&String> = right_vars
+            .keys()
+            .filter(|k| !left_vars.contains_key(*k))
+            .collect();
+        let changed: Vec<Value> = left_vars
+            .iter()
+            .filter_map(|(k, lv)| {
+                let rv = right_vars.get(k)?;
+                (lv != rv).then(|| {
+                    if show_values {
+                        json!({ "key": k, "left": lv, "right": rv })
+                    } else {
+                        json!({ "key": k })
+                    }
+                })
+            })
+            .collect();
+
+        Ok(json!({
+            "identical": only_in_left.is_empty() && only_in_right.is_empty() && changed.is_empty(),
+            "only_in_left": only_in_left,
+            "only_in_right": only_in_right,
+            "changed": changed,
+        })
+        .to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_merge_preserves_comments() {
+        let dir = tempfile::tempdir().unwrap();
+        let original = "# Database settings\nDB_HOST=localhost\n\n# API\nexport API_KEY=old # rotate monthly\n";
+        std::fs::write(dir.path().join(".env"), original).unwrap();
+
+        let out = EnvMerge
+            .execute(
+                json!({
+                    "path": ".env",
+                    "values": { "API_KEY": "new key", "PORT": "8080" }
+                }),
+                dir.path(),
+            )
+            .await
+            .unwrap();
+        let result: Value = serde_json::from_str(&out).unwrap();
+        assert_eq!(result["updated"], json!(["API_KEY"]));
+        assert_eq!(result["added"], json!(["PORT"]));
+
+        let merged = std::fs::read_to_string(dir.path().join(".env")).unwrap();
+        assert_eq!(
+            merged,
+            "# Database settings\nDB_HOST=localhost\n\n# API\nexport API_KEY='new key' # rotate monthly\nPORT=8080\n"
+        );
+        let vars = parse_env(&merged);
+        assert_eq!(vars["API_KEY"], "new key");
+        assert_eq!(vars["DB_HOST"], "localhost");
+    }
+
+    #[tokio::test]
+    async fn test_merge_rejects_invalid_keys() {
+        let dir = tempfile::tempdir().unwrap();
+        for name in ["BAD KEY", "X=1\nINJECTED", ""] {
+            let err = EnvMerge
+                .execute(
+                    json!({ "path": ".env", "values": { name: "v" } }),
+                    dir.path(),
+                )
+                .await
+                .unwrap_err();
+            assert!(err.to_string().contains("Invalid variable name"), "{}", err);
+        }
+        assert!(!dir.path().join(".env").exists());
+    }
+
+    #[test]
+    fn test_merge_keeps_inline_comments_outside_quotes() {
+        let content = "A=\"x # not a comment\" # real\nB='y' #tight\nC=1\t# tab\n";
+        let updates = [
+            ("A".to_string(), "1".to_string()),
+            ("B".to_string(), "2".to_string()),
+            ("C".to_string(), "3".to_string()),
+        ];
+        let (merged, _, _) = merge_env(content, &updates);
+        assert_eq!(merged, "A=1 # real\nB=2 #tight\nC=3\t# tab\n");
+        assert_eq!(parse_env(content)["A"], "x # not a comment");
+    }
+
+    #[test]
+    fn test_parse_env_values() {
+        let vars = parse_env("A=1 # comment\nB=\"x \\\"y\\\" \\\\n\"\nC='z'\n# D=4\nnot a var\n");
+        assert_eq!(vars.len(), 3);
+        assert_eq!(vars["A"], "1");
+        assert_eq!(vars["B"], "x \"y\" \\n");
+        assert_eq!(vars["C"], "z");
+    }
+}
@@ -1,15 +1,19 @@
-//! File operation tools: read, write, delete files.
+//! File operation tools: read (one or many), write, edit, delete, move, copy files.
 //!
 //! ## Workspace-First Design
 //!
 //! These tools work relative to the workspace by default:
 //! - `output/report.md` → writes to `{workspace}/output/report.md`
-//! - `/etc/hosts` → absolute path for system access (escape hatch)
+//! - `/etc/hosts` → absolute path for system access, only when
+//!   `OPEN_AGENT_ALLOW_WORKSPACE_ESCAPE` is set (see [`super::PathPolicy`])
+//!
+//! `delete_file` never deletes outside the workspace, even when escaping is allowed.
 
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use async_trait::async_trait;
 use serde_json::{json, Value};
+use walkdir::WalkDir;
 
 use super::{resolve_path, Tool};
 
@@ -23,7 +27,7 @@ impl Tool for ReadFile {
     }
 
     fn description(&self) -> &str {
-        "Read a file's contents. Use relative paths like 'src/main.rs' (recommended) or absolute paths like '/etc/hosts' for system files."
+        "Read a file's contents. Use relative paths like 'src/main.rs'; paths outside the workspace are rejected unless host access is enabled."
     }
 
     fn parameters_schema(&self) -> Value {
@@ -32,7 +36,7 @@ impl Tool for ReadFile {
             "properties": {
                 "path": {
                     "type": "string",
-                    "description": "File path. Use relative paths (e.g., 'output/data.json') for workspace files, or absolute paths (e.g., '/var/log/app.log') for system files."
+                    "description": "File path. Use relative paths (e.g., 'output/data.json') for workspace files."
                 },
                 "start_line": {
                     "type": "integer",
@@ -52,7 +56,7 @@ impl Tool for ReadFile {
             .as_str()
             .ok_or_else(|| anyhow::anyhow!("Missing 'path' argument"))?;
 
-        let resolution = resolve_path(path, working_dir);
+        let resolution = resolve_path(path, working_dir)?;
 
         if !resolution.resolved.exists() {
             return Err(anyhow::anyhow!(
@@ -112,7 +116,16 @@ impl Tool for ReadFile {
                 .map(|(i, line)| format!("{:4}| {}", start + i + 1, line))
                 .collect();
 
-            return Ok(selected.join("\n"));
+            let mut output = selected.join("\n");
+            if end_line.is_some_and(|n| n > total_lines) {
+                output.push_str(&format!(
+                    "\n\n[Range clamped: file has {} lines, showing {}-{}]",
+                    total_lines,
+                    start + 1,
+                    end
+                ));
+            }
+            return Ok(output);
         }
 
         // Return with line numbers for context
@@ -126,6 +139,131 @@ impl Tool for ReadFile {
     }
 }
 
+/// Default per-file limit for `read_many_files`.
+const READ_MANY_DEFAULT_BYTES_EACH: u64 = 32 * 1024;
+
+/// Default (and maximum) size of a whole `read_many_files` result.
+const READ_MANY_MAX_TOTAL_BYTES: u64 = 256 * 1024;
+
+/// Read several files in one call.
+pub struct ReadManyFiles;
+
+impl ReadManyFiles {
+    /// Body of one file's section: its (possibly truncated) text, or why there is none.
+    async fn read_section(path: &str, working_dir: &Path, limit: u64) -> String {
+        use tokio::io::AsyncReadExt;
+
+        let resolved = match resolve_path(path, working_dir) {
+            Ok(resolution) => resolution.resolved,
+            Err(e) => return format!("[error: {}]", e),
+        };
+        let file = match tokio::fs::File::open(&resolved).await {
+            Ok(file) => file,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                return "[missing: file not found]".to_string()
+            }
+            Err(e) => return format!("[error: {}]", e),
+        };
+        let size = match file.metadata().await {
+            Ok(metadata) if metadata.is_dir() => return "[error: is a directory]".to_string(),
+            Ok(metadata) => metadata.len(),
+            Err(e) => return format!("[error: {}]", e),
+        };
+        let mut bytes = Vec::new();
+        if let Err(e) = file.take(limit).read_to_end(&mut bytes).await {
+            return format!("[error: {}]", e);
+        }
+        let text = match std::str::from_utf8(&bytes) {
+            Ok(text) => text,
+            // Cut in the middle of a character: keep the complete ones.
+            Err(e) if e.error_len().is_none() => {
+                std::str::from_utf8(&bytes[..e.valid_up_to()]).unwrap_or_default()
+            }
+            Err(_) => return format!("[binary file: {} bytes]", size),
+        };
+        let mut section = text.to_string();
+        if size > bytes.len() as u64 {
+            if !section.ends_with('\n') {
+                section.push('\n');
+            }
+            section.push_str(&format!(
+                "[truncated: showing {} of {} bytes]",
+                text.len(),
+                size
+            ));
+        }
+        section
+    }
+}
+
+#[async_trait]
+impl Tool for ReadManyFiles {
+    fn name(&self) -> &str {
+        "read_many_files"
+    }
+
+    fn description(&self) -> &str {
+        "Read several files in one call, e.g. when exploring a repo. Each file gets a '=== path ===' header; missing, binary and truncated files are noted instead of failing the call. The whole result is capped by 'max_total_bytes'."
+    }
+
+    fn parameters_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "paths": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "File paths, relative to the workspace (e.g., ['Cargo.toml', 'src/main.rs'])."
+                },
+                "max_bytes_each": {
+                    "type": "integer",
+                    "description": format!("Maximum bytes read from each file (default: {}).", READ_MANY_DEFAULT_BYTES_EACH)
+                },
+                "max_total_bytes": {
+                    "type": "integer",
+                    "description": format!("Maximum bytes of file content in the whole result (default and maximum: {}).", READ_MANY_MAX_TOTAL_BYTES)
+                }
+            },
+            "required": ["paths"]
+        })
+    }
+
+    async fn execute(&self, args: Value, working_dir: &Path) -> anyhow::Result<String> {
+        let paths: Vec<&str> = args["paths"]
+            .as_array()
+            .ok_or_else(|| anyhow::anyhow!("Missing 'paths' array"))?
+            .iter()
+            .filter_map(|path| path.as_str())
+            .collect();
+        if paths.is_empty() {
+            anyhow::bail!("No paths given");
+        }
+        let max_bytes_each = args["max_bytes_each"]
+            .as_u64()
+            .filter(|n| *n > 0)
+            .unwrap_or(READ_MANY_DEFAULT_BYTES_EACH);
+        let max_total_bytes = args["max_total_bytes"]
+            .as_u64()
+            .unwrap_or(READ_MANY_MAX_TOTAL_BYTES)
+            .min(READ_MANY_MAX_TOTAL_BYTES);
+
+        let mut remaining = max_total_bytes;
+        let mut sections = Vec::with_capacity(paths.len());
+        for path in paths {
+            let section = if remaining == 0 {
+                "[skipped: max_total_bytes reached]".to_string()
+            } else {
+                let limit = max_bytes_each.min(remaining);
+                let section = Self::read_section(path, working_dir, limit).await;
+                remaining = remaining.saturating_sub(section.len() as u64);
+                section
+            };
+            sections.push(format!("=== {} ===\n{}", path, section));
+        }
+        Ok(sections.join("\n\n"))
+    }
+}
+
 /// Write content to a file (create or overwrite).
 pub struct WriteFile;
 
@@ -164,7 +302,7 @@ impl Tool for WriteFile {
             .as_str()
             .ok_or_else(|| anyhow::anyhow!("Missing 'content' argument"))?;
 
-        let resolution = resolve_path(path, working_dir);
+        let resolution = resolve_path(path, working_dir)?;
 
         // Create parent directories if needed
         if let Some(parent) = resolution.resolved.parent() {
@@ -243,17 +381,17 @@ impl Tool for WriteFile {
     }
 }
 
-/// Delete a file.
-pub struct DeleteFile;
+/// Replace exact occurrences of a string in a file.
+pub struct EditFile;
 
 #[async_trait]
-impl Tool for DeleteFile {
+impl Tool for EditFile {
     fn name(&self) -> &str {
-        "delete_file"
+        "edit_file"
     }
 
     fn description(&self) -> &str {
-        "Delete a file. Use relative paths to delete workspace files, or absolute paths for system files (use with caution)."
+        "Edit a file by replacing exact occurrences of 'old_string' with 'new_string'. Prefer this over write_file for small changes to existing files. Fails if 'old_string' is not found, or if 'expected_count' is set and the number of matches differs."
     }
 
     fn parameters_schema(&self) -> Value {
@@ -262,10 +400,22 @@ impl Tool for DeleteFile {
             "properties": {
                 "path": {
                     "type": "string",
-                    "description": "File path. Use relative paths (e.g., 'temp/old_file.txt') for workspace files."
+                    "description": "File path. Use relative paths (e.g., 'src/main.rs') for workspace files."
+                },
+                "old_string": {
+                    "type": "string",
+                    "description": "Exact text to replace, including whitespace and indentation"
+                },
+                "new_string": {
+                    "type": "string",
+                    "description": "Replacement text"
+                },
+                "expected_count": {
+                    "type": "integer",
+                    "description": "Optional: number of occurrences expected. The edit is rejected if the actual count differs."
                 }
             },
-            "required": ["path"]
+            "required": ["path", "old_string", "new_string"]
         })
     }
 
@@ -273,9 +423,19 @@ impl Tool for DeleteFile {
         let path = args["path"]
             .as_str()
             .ok_or_else(|| anyhow::anyhow!("Missing 'path' argument"))?;
+        let old_string = args["old_string"]
+            .as_str()
+            .ok_or_else(|| anyhow::anyhow!("Missing 'old_string' argument"))?;
+        let new_string = args["new_string"]
+            .as_str()
+            .ok_or_else(|| anyhow::anyhow!("Missing 'new_string' argument"))?;
+        let expected_count = args["expected_count"].as_u64().map(|n| n as usize);
 
-        let resolution = resolve_path(path, working_dir);
+        if old_string.is_empty() {
+            return Err(anyhow::anyhow!("'old_string' must not be empty"));
+        }
 
+        let resolution = resolve_path(path, working_dir)?;
         if !resolution.resolved.exists() {
             return Err(anyhow::anyhow!(
                 "File not found: {} (resolved to: {})",
@@ -284,11 +444,788 @@ impl Tool for DeleteFile {
             ));
         }
 
-        tokio::fs::remove_file(&resolution.resolved).await?;
+        let content = tokio::fs::read_to_string(&resolution.resolved).await?;
+        let count = content.matches(old_string).count();
+        if count == 0 {
+            return Err(anyhow::anyhow!(
+                "'old_string' not found in {} (0 matches)",
+                resolution.resolved.display()
+            ));
+        }
+        if let Some(expected) = expected_count {
+            if count != expected {
+                return Err(anyhow::anyhow!(
+                    "Expected {} occurrence(s) of 'old_string' in {} but found {}",
+                    expected,
+                    resolution.resolved.display(),
+                    count
+                ));
+            }
+        }
+
+        let updated = content.replace(old_string, new_string);
+        tokio::fs::write(&resolution.resolved, updated).await?;
 
         Ok(format!(
-            "Successfully deleted {}",
+            "Successfully replaced {} occurrence(s) in {}",
+            count,
             resolution.resolved.display()
         ))
     }
 }
+
+/// Delete a file or directory inside the workspace.
+pub struct DeleteFile;
+
+/// Real location of `resolved`, refusing the workspace root and anything outside it.
+///
+/// The parent is canonicalized (so `..` and symlinked directories are seen
+/// through) while the final component is kept as-is, so a symlink is removed
+/// rather than the file it points to.
+fn confined_delete_target(
+    path: &str,
+    resolved: &Path,
+    working_dir: &Path,
+) -> anyhow::Result<PathBuf> {
+    let root = working_dir.canonicalize()?;
+    let target = match (resolved.parent(), resolved.file_name()) {
+        (Some(parent), Some(name)) if name != ".." => parent
+            .canonicalize()
+            .map_err(|e| anyhow::anyhow!("File not found: {} ({})", path, e))?
+            .join(name),
+        _ => resolved
+            .canonicalize()
+            .unwrap_or_else(|_| resolved.to_path_buf()),
+    };
+    if target == root {
+        anyhow::bail!("Refusing to delete the workspace root ({})", root.display());
+    }
+    if !target.starts_with(&root) {
+        anyhow::bail!(
+            "Refusing to delete {}: it resolves outside the workspace ({})",
+            path,
+            target.display()
+        );
+    }
+    Ok(target)
+}
+
+#[async_trait]
+impl Tool for DeleteFile {
+    fn name(&self) -> &str {
+        "delete_file"
+    }
+
+    fn description(&self) -> &str {
+        "Delete a file, or a directory with 'recursive': true. Only paths inside the workspace can be deleted, never the workspace itself. Use 'dry_run': true to list what would be deleted without deleting anything."
+    }
+
+    fn parameters_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "path": {
+                    "type": "string",
+                    "description": "File or directory path inside the workspace (e.g., 'temp/old_file.txt')."
+                },
+                "recursive": {
+                    "type": "boolean",
+                    "description": "Delete a directory and everything in it (default: false)."
+                },
+                "dry_run": {
+                    "type": "boolean",
+                    "description": "Only list the paths that would be deleted (default: false)."
+                }
+            },
+            "required": ["path"]
+        })
+    }
+
+    async fn execute(&self, args: Value, working_dir: &Path) -> anyhow::Result<String> {
+        let path = args["path"]
+            .as_str()
+            .ok_or_else(|| anyhow::anyhow!("Missing 'path' argument"))?;
+        let recursive = args["recursive"].as_bool().unwrap_or(false);
+        let dry_run = args["dry_run"].as_bool().unwrap_or(false);
+
+        let resolution = resolve_path(path, working_dir)?;
+        let target = confined_delete_target(path, &resolution.resolved, working_dir)?;
+
+        let metadata = tokio::fs::symlink_metadata(&target).await.map_err(|_| {
+            anyhow::anyhow!(
+                "File not found: {} (resolved to: {})",
+                path,
+                target.display()
+            )
+        })?;
+        let is_dir = metadata.is_dir();
+        if is_dir && !recursive {
+            anyhow::bail!(
+                "{} is a directory; pass 'recursive': true to delete it and its contents",
+                path
+            );
+        }
+
+        if dry_run {
+            let root = working_dir.canonicalize()?;
+            let mut paths = Vec::new();
+            for entry in WalkDir::new(&target)
+                .contents_first(true)
+                .sort_by_file_name()
+            {
+                let entry = entry?;
+                let relative = entry.path().strip_prefix(&root).unwrap_or(entry.path());
+                paths.push(relative.display().to_string());
+            }
+            return Ok(format!(
+                "Dry run: would delete {} path{}:\n{}",
+                paths.len(),
+                if paths.len() == 1 { "" } else { "s" },
+                paths.join("\n")
+            ));
+        }
+
+        if is_dir {
+            tokio::fs::remove_dir_all(&target).await?;
+        } else {
+            tokio::fs::remove_file(&target).await?;
+        }
+
+        Ok(format!("Successfully deleted {}", target.display()))
+    }
+}
+
+/// Move or rename a file or directory.
+pub struct MoveFile;
+
+#[async_trait]
+impl Tool for MoveFile {
+    fn name(&self) -> &str {
+        "move_file"
+    }
+
+    fn description(&self) -> &str {
+        "Move or rename a file or directory. Parent directories of the destination are created automatically. Fails if the destination exists unless 'overwrite' is true."
+    }
+
+    fn parameters_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "from": {
+                    "type": "string",
+                    "description": "Source path. Use relative paths (e.g., 'output/draft.md') for workspace files."
+                },
+                "to": {
+                    "type": "string",
+                    "description": "Destination path. Use relative paths (e.g., 'output/final.md') for workspace files."
+                },
+                "overwrite": {
+                    "type": "boolean",
+                    "description": "Replace the destination if it already exists (default: false)"
+                }
+            },
+            "required": ["from", "to"]
+        })
+    }
+
+    async fn execute(&self, args: Value, working_dir: &Path) -> anyhow::Result<String> {
+        let from = args["from"]
+            .as_str()
+            .ok_or_else(|| anyhow::anyhow!("Missing 'from' argument"))?;
+        let to = args["to"]
+            .as_str()
+            .ok_or_else(|| anyhow::anyhow!("Missing 'to' argument"))?;
+        let overwrite = args["overwrite"].as_bool().unwrap_or(false);
+
+        let source = resolve_path(from, working_dir)?.resolved;
+        let dest = resolve_path(to, working_dir)?.resolved;
+
+        let source_meta = tokio::fs::symlink_metadata(&source).await.map_err(|_| {
+            anyhow::anyhow!(
+                "Source not found: {} (resolved to: {})",
+                from,
+                source.display()
+            )
+        })?;
+
+        if absolute_path(&source) == absolute_path(&dest) {
+            return Err(anyhow::anyhow!(
+                "Source and destination are the same path: {}",
+                dest.display()
+            ));
+        }
+        if tokio::fs::symlink_metadata(&dest).await.is_ok() && !overwrite {
+            return Err(anyhow::anyhow!(
+                "Destination already exists: {} (resolved to: {}). Set 'overwrite' to true to replace it.",
+                to,
+                dest.display()
+            ));
+        }
+
+        if let Some(parent) = dest.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+
+        // Bring the source next to the destination first; the destination is
+        // only replaced once that worked.
+        let staged = sibling_temp_path(&dest);
+        let copied = if tokio::fs::rename(&source, &staged).await.is_ok() {
+            false
+        } else {
+            // rename fails across filesystems; fall back to copy (+ remove below).
+            if let Err(e) = copy_entry(&source, &staged, source_meta.is_dir()).await {
+                remove_entry(&staged).await;
+                return Err(e);
+            }
+            true
+        };
+        if let Err(e) = replace_entry(&staged, &dest).await {
+            if copied {
+                remove_entry(&staged).await;
+            } else {
+                let _ = tokio::fs::rename(&staged, &source).await;
+            }
+            return Err(anyhow::anyhow!(
+                "Failed to move {} to {}: {}",
+                source.display(),
+                dest.display(),
+                e
+            ));
+        }
+        if copied {
+            remove_entry(&source).await;
+        }
+
+        Ok(format!(
+            "Successfully moved {} to {}",
+            source.display(),
+            dest.display()
+        ))
+    }
+}
+
+/// Unused name next to `path`, for staging a replacement of it.
+fn sibling_temp_path(path: &Path) -> PathBuf {
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    path.with_file_name(format!(
+        ".{}.{}.tmp",
+        name,
+        &uuid::Uuid::new_v4().to_string()[..8]
+    ))
+}
+
+/// Copy a file or (with `cp -a`) a directory tree to `dest`, which must not exist.
+async fn copy_entry(source: &Path, dest: &Path, is_dir: bool) -> anyhow::Result<()> {
+    if !is_dir {
+        tokio::fs::copy(source, dest).await?;
+        return Ok(());
+    }
+    let status = tokio::process::Command::new("cp")
+        .arg("-a")
+        .arg(source)
+        .arg(dest)
+        .status()
+        .await?;
+    if !status.success() {
+        return Err(anyhow::anyhow!(
+            "Failed to copy directory {} to {}",
+            source.display(),
+            dest.display()
+        ));
+    }
+    Ok(())
+}
+
+/// Best-effort removal of a file, symlink or directory tree.
+async fn remove_entry(path: &Path) {
+    match tokio::fs::symlink_metadata(path).await {
+        Ok(meta) if meta.is_dir() => {
+            let _ = tokio::fs::remove_dir_all(path).await;
+        }
+        Ok(_) => {
+            let _ = tokio::fs::remove_file(path).await;
+        }
+        Err(_) => {}
+    }
+}
+
+/// Move `staged` to `dest`, replacing whatever is there. Files are replaced by
+/// an atomic rename; a directory (or a type change) is swapped via a backup
+/// that is restored if the swap fails.
+async fn replace_entry(staged: &Path, dest: &Path) -> std::io::Result<()> {
+    let staged_is_dir = tokio::fs::symlink_metadata(staged).await?.is_dir();
+    match tokio::fs::symlink_metadata(dest).await {
+        Ok(meta) if meta.is_dir() || staged_is_dir => {
+            let backup = sibling_temp_path(dest);
+            tokio::fs::rename(dest, &backup).await?;
+            if let Err(e) = tokio::fs::rename(staged, dest).await {
+                let _ = tokio::fs::rename(&backup, dest).await;
+                return Err(e);
+            }
+            remove_entry(&backup).await;
+            Ok(())
+        }
+        _ => tokio::fs::rename(staged, dest).await,
+    }
+}
+
+/// Absolute form of a path that may not exist yet, with symlinks in its
+/// existing parent resolved.
+fn absolute_path(path: &Path) -> PathBuf {
+    if let Ok(canonical) = path.canonicalize() {
+        return canonical;
+    }
+    match (path.parent(), path.file_name()) {
+        (Some(parent), Some(name)) => absolute_path(parent).join(name),
+        _ => path.to_path_buf(),
+    }
+}
+
+/// Recreate the tree at `source` under `dest`, returning the number of files copied.
+async fn copy_dir_recursive(source: &Path, dest: &Path, overwrite: bool) -> anyhow::Result<usize> {
+    let mut copied = 0;
+    for entry in WalkDir::new(source).sort_by_file_name() {
+        let entry = entry?;
+        let target = dest.join(entry.path().strip_prefix(source)?);
+        let file_type = entry.file_type();
+        if file_type.is_dir() {
+            tokio::fs::create_dir_all(&target).await?;
+            continue;
+        }
+        if !overwrite && tokio::fs::symlink_metadata(&target).await.is_ok() {
+            return Err(anyhow::anyhow!(
+                "Destination already exists: {}. Set 'overwrite' to true to replace it.",
+                target.display()
+            ));
+        }
+        if file_type.is_symlink() {
+            let link = tokio::fs::read_link(entry.path()).await?;
+            let _ = tokio::fs::remove_file(&target).await;
+            tokio::fs::symlink(link, &target).await?;
+        } else {
+            tokio::fs::copy(entry.path(), &target).await?;
+        }
+        copied += 1;
+    }
+    Ok(copied)
+}
+
+/// Copy a file, or a directory tree when `recursive` is set.
+pub struct CopyFile;
+
+#[async_trait]
+impl Tool for CopyFile {
+    fn name(&self) -> &str {
+        "copy_file"
+    }
+
+    fn description(&self) -> &str {
+        "Copy a file, or a whole directory when 'recursive' is true (the tree is recreated under 'to'). Parent directories of the destination are created automatically. Fails if the destination exists unless 'overwrite' is true. Reports the number of files copied."
+    }
+
+    fn parameters_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "from": {
+                    "type": "string",
+                    "description": "Source path. Use relative paths (e.g., 'templates/base') for workspace files."
+                },
+                "to": {
+                    "type": "string",
+                    "description": "Destination path. Use relative paths (e.g., 'output/site') for workspace files."
+                },
+                "recursive": {
+                    "type": "boolean",
+                    "description": "Required to copy a directory and everything in it (default: false)"
+                },
+                "overwrite": {
+                    "type": "boolean",
+                    "description": "Replace existing destination files (default: false)"
+                }
+            },
+            "required": ["from", "to"]
+        })
+    }
+
+    async fn execute(&self, args: Value, working_dir: &Path) -> anyhow::Result<String> {
+        let from = args["from"]
+            .as_str()
+            .ok_or_else(|| anyhow::anyhow!("Missing 'from' argument"))?;
+        let to = args["to"]
+            .as_str()
+            .ok_or_else(|| anyhow::anyhow!("Missing 'to' argument"))?;
+        let recursive = args["recursive"].as_bool().unwrap_or(false);
+        let overwrite = args["overwrite"].as_bool().unwrap_or(false);
+
+        let source = resolve_path(from, working_dir)?.resolved;
+        let dest = resolve_path(to, working_dir)?.resolved;
+
+        let source_meta = tokio::fs::metadata(&source).await.map_err(|_| {
+            anyhow::anyhow!(
+                "Source not found: {} (resolved to: {})",
+                from,
+                source.display()
+            )
+        })?;
+        let dest_exists = tokio::fs::symlink_metadata(&dest).await.is_ok();
+
+        let copied = if source_meta.is_dir() {
+            if !recursive {
+                return Err(anyhow::anyhow!(
+                    "{} is a directory. Set 'recursive' to true to copy it with its contents.",
+                    source.display()
+                ));
+            }
+            if absolute_path(&dest).starts_with(absolute_path(&source)) {
+                return Err(anyhow::anyhow!(
+                    "Cannot copy directory {} into itself ({})",
+                    source.display(),
+                    dest.display()
+                ));
+            }
+            copy_dir_recursive(&source, &dest, overwrite).await?
+        } else {
+            if dest_exists && !overwrite {
+                return Err(anyhow::anyhow!(
+                    "Destination already exists: {} (resolved to: {}). Set 'overwrite' to true to replace it.",
+                    to,
+                    dest.display()
+                ));
+            }
+            if let Some(parent) = dest.parent() {
+                tokio::fs::create_dir_all(parent).await?;
+            }
+            tokio::fs::copy(&source, &dest).await?;
+            1
+        };
+
+        Ok(format!(
+            "Successfully copied {} file(s) from {} to {}",
+            copied,
+            source.display(),
+            dest.display()
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_edit_file_counts_matches() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("a.rs"), "let a = 1;\nlet b = 1;\n").unwrap();
+
+        let err = EditFile
+            .execute(
+                json!({"path": "a.rs", "old_string": "= 1", "new_string": "= 2", "expected_count": 1}),
+                dir.path(),
+            )
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("found 2"));
+
+        let err = EditFile
+            .execute(
+                json!({"path": "a.rs", "old_string": "= 3", "new_string": "= 2"}),
+                dir.path(),
+            )
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("0 matches"));
+
+        EditFile
+            .execute(
+                json!({"path": "a.rs", "old_string": "= 1", "new_string": "= 2", "expected_count": 2}),
+                dir.path(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("a.rs")).unwrap(),
+            "let a = 2;\nlet b = 2;\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_delete_file_recursive_dry_run() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("build/obj")).unwrap();
+        std::fs::write(dir.path().join("build/obj/a.o"), "").unwrap();
+        std::fs::write(dir.path().join("build/log.txt"), "").unwrap();
+
+        let err = DeleteFile
+            .execute(json!({"path": "build"}), dir.path())
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("recursive"));
+
+        let out = DeleteFile
+            .execute(
+                json!({"path": "build", "recursive": true, "dry_run": true}),
+                dir.path(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            out,
+            "Dry run: would delete 4 paths:\nbuild/log.txt\nbuild/obj/a.o\nbuild/obj\nbuild"
+        );
+        assert!(dir.path().join("build/obj/a.o").exists());
+
+        DeleteFile
+            .execute(json!({"path": "build", "recursive": true}), dir.path())
+            .await
+            .unwrap();
+        assert!(!dir.path().join("build").exists());
+    }
+
+    #[tokio::test]
+    async fn test_delete_file_stays_in_workspace() {
+        let outer = tempfile::tempdir().unwrap();
+        let workspace = outer.path().join("ws");
+        std::fs::create_dir_all(workspace.join("sub")).unwrap();
+        std::fs::write(outer.path().join("secret.txt"), "").unwrap();
+
+        for path in [".", "sub/..", "", workspace.to_str().unwrap()] {
+            let err = DeleteFile
+                .execute(json!({"path": path, "recursive": true}), &workspace)
+                .await
+                .unwrap_err();
+            assert!(
+                err.to_string().contains("workspace root"),
+                "{}: {}",
+                path,
+                err
+            );
+        }
+        let secret = outer.path().join("secret.txt");
+        for path in [
+            "../secret.txt",
+            "sub/../../secret.txt",
+            secret.to_str().unwrap(),
+        ] {
+            let err = DeleteFile
+                .execute(json!({"path": path}), &workspace)
+                .await
+                .unwrap_err();
+            assert!(
+                err.to_string().contains("outside the workspace"),
+                "{}: {}",
+                path,
+                err
+            );
+        }
+
+        // Symlinks are not followed out of the workspace.
+        std::os::unix::fs::symlink(outer.path(), workspace.join("link")).unwrap();
+        for path in ["link/secret.txt", "link"] {
+            let err = DeleteFile
+                .execute(json!({"path": path, "recursive": true}), &workspace)
+                .await
+                .unwrap_err();
+            assert!(
+                err.to_string().contains("outside the workspace"),
+                "{}: {}",
+                path,
+                err
+            );
+        }
+        std::os::unix::fs::symlink("sub", workspace.join("local")).unwrap();
+        DeleteFile
+            .execute(json!({"path": "local"}), &workspace)
+            .await
+            .unwrap();
+        assert!(!workspace.join("local").exists());
+        assert!(workspace.join("sub").exists());
+        assert!(secret.exists());
+        assert!(workspace.exists());
+    }
+
+    #[tokio::test]
+    async fn test_read_many_files() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("a.txt"), "alpha\n").unwrap();
+        std::fs::write(dir.path().join("long.txt"), "0123456789".repeat(10)).unwrap();
+        std::fs::write(dir.path().join("bin"), [0xff, 0xfe, 0x00]).unwrap();
+
+        let out = ReadManyFiles
+            .execute(
+                json!({"paths": ["a.txt", "missing.txt", "long.txt", "bin"], "max_bytes_each": 20}),
+                dir.path(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            out,
+            "=== a.txt ===\nalpha\n\n\n=== missing.txt ===\n[missing: file not found]\n\n\
+             === long.txt ===\n01234567890123456789\n[truncated: showing 20 of 100 bytes]\n\n\
+             === bin ===\n[binary file: 3 bytes]"
+        );
+
+        // The total cap wins over the per-file limit.
+        let out = ReadManyFiles
+            .execute(
+                json!({"paths": ["long.txt", "a.txt"], "max_total_bytes": 10}),
+                dir.path(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            out,
+            "=== long.txt ===\n0123456789\n[truncated: showing 10 of 100 bytes]\n\n\
+             === a.txt ===\n[skipped: max_total_bytes reached]"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_read_file_line_range() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("a.txt"), "one\ntwo\nthree\nfour\n").unwrap();
+
+        let out = ReadFile
+            .execute(
+                json!({"path": "a.txt", "start_line": 2, "end_line": 3}),
+                dir.path(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(out, "   2| two\n   3| three");
+
+        let out = ReadFile
+            .execute(json!({"path": "a.txt", "start_line": 3}), dir.path())
+            .await
+            .unwrap();
+        assert_eq!(out, "   3| three\n   4| four");
+
+        let out = ReadFile
+            .execute(
+                json!({"path": "a.txt", "start_line": 4, "end_line": 99}),
+                dir.path(),
+            )
+            .await
+            .unwrap();
+        assert!(out.starts_with("   4| four"));
+        assert!(out.contains("file has 4 lines"));
+    }
+
+    #[tokio::test]
+    async fn test_move_file_respects_overwrite() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("a.txt"), "a").unwrap();
+        std::fs::write(dir.path().join("b.txt"), "b").unwrap();
+
+        let err = MoveFile
+            .execute(json!({"from": "a.txt", "to": "b.txt"}), dir.path())
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("already exists"));
+
+        MoveFile
+            .execute(
+                json!({"from": "a.txt", "to": "b.txt", "overwrite": true}),
+                dir.path(),
+            )
+            .await
+            .unwrap();
+        assert!(!dir.path().join("a.txt").exists());
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("b.txt")).unwrap(),
+            "a"
+        );
+
+        MoveFile
+            .execute(
+                json!({"from": "b.txt", "to": "nested/dir/c.txt"}),
+                dir.path(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("nested/dir/c.txt")).unwrap(),
+            "a"
+        );
+
+        // Moving a path onto itself must not delete it.
+        let err = MoveFile
+            .execute(
+                json!({"from": "nested/dir/c.txt", "to": "nested/../nested/dir/c.txt", "overwrite": true}),
+                dir.path(),
+            )
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("same path"));
+        assert!(dir.path().join("nested/dir/c.txt").exists());
+
+        // A directory replaces a non-empty directory, leaving no staging files behind.
+        std::fs::create_dir_all(dir.path().join("old")).unwrap();
+        std::fs::write(dir.path().join("old/stale.txt"), "stale").unwrap();
+        MoveFile
+            .execute(
+                json!({"from": "nested", "to": "old", "overwrite": true}),
+                dir.path(),
+            )
+            .await
+            .unwrap();
+        assert!(!dir.path().join("old/stale.txt").exists());
+        assert!(dir.path().join("old/dir/c.txt").exists());
+        let mut names: Vec<String> = std::fs::read_dir(dir.path())
+            .unwrap()
+            .map(|e| e.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        names.sort();
+        assert_eq!(names, vec!["old".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_copy_file_recursive() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("site/assets")).unwrap();
+        std::fs::write(dir.path().join("site/index.html"), "<h1>hi</h1>").unwrap();
+        std::fs::write(dir.path().join("site/assets/app.css"), "body {}").unwrap();
+
+        let err = CopyFile
+            .execute(json!({"from": "site", "to": "copy"}), dir.path())
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("recursive"));
+
+        let out = CopyFile
+            .execute(
+                json!({"from": "site", "to": "out/copy", "recursive": true}),
+                dir.path(),
+            )
+            .await
+            .unwrap();
+        assert!(out.contains("copied 2 file(s)"));
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("out/copy/assets/app.css")).unwrap(),
+            "body {}"
+        );
+        assert!(dir.path().join("site/index.html").exists());
+
+        let err = CopyFile
+            .execute(
+                json!({"from": "site", "to": "site/nested", "recursive": true}),
+                dir.path(),
+            )
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("into itself"));
+
+        let err = CopyFile
+            .execute(
+                json!({"from": "site/index.html", "to": "out/copy/index.html"}),
+                dir.path(),
+            )
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("already exists"));
+    }
+}
@@ -0,0 +1,1211 @@
+//! Git tools: commits, branch management, checkout, stashes, worktrees and
+//! remotes on top of the `git` CLI.
+//!
+//! All commands run as `git -C <repo> ...` so the tools work on any repository
+//! path, defaulting to the workspace.
+
+use std::path::{Component, Path, PathBuf};
+use std::process::Stdio;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use serde_json::{json, Value};
+
+use super::{resolve_path_simple, resolve_within_workspace, Tool};
+
+/// Default directory (inside the workspace) for new worktrees.
+const WORKTREES_DIR: &str = ".worktrees";
+
+/// Run a git command in `repo`, returning stdout or the error text.
+pub(super) async fn run_git(repo: &Path, args: &[&str]) -> Result<String, String> {
+    let output = tokio::process::Command::new("git")
+        .arg("-C")
+        .arg(repo)
+        .args(args)
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run git: {}", e))?;
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(&output.stdout)
+            .trim_end()
+            .to_string())
+    } else {
+        Err(String::from_utf8_lossy(&output.stderr).trim().to_string())
+    }
+}
+
+/// Like [`run_git`], but with the failing command in the error.
+async fn git(repo: &Path, args: &[&str]) -> anyhow::Result<String> {
+    run_git(repo, args)
+        .await
+        .map_err(|e| anyhow::anyhow!("git {} failed: {}", args.join(" "), e))
+}
+
+/// Resolve the optional `path` argument to a repository directory, under the
+/// same workspace policy as the file tools.
+fn repo_path(args: &Value, working_dir: &Path) -> anyhow::Result<PathBuf> {
+    let path_str = args.get("path").and_then(|v| v.as_str()).unwrap_or(".");
+    resolve_path_simple(path_str, working_dir)
+}
+
+/// Name of the checked-out branch, or `None` on a detached HEAD.
+async fn current_branch(repo: &Path) -> Result<Option<String>, String> {
+    let name = run_git(repo, &["branch", "--show-current"]).await?;
+    Ok(Some(name).filter(|n| !n.is_empty()))
+}
+
+/// Paths with uncommitted changes to tracked files, optionally limited to `paths`.
+async fn dirty_paths(repo: &Path, paths: &[&str]) -> anyhow::Result<Vec<String>> {
+    let mut args = vec!["status", "--porcelain", "--untracked-files=no"];
+    if !paths.is_empty() {
+        args.push("--");
+        args.extend_from_slice(paths);
+    }
+    let output = git(repo, &args).await?;
+    Ok(output
+        .lines()
+        .filter_map(|line| line.get(3..))
+        .map(str::to_string)
+        .collect())
+}
+
+/// Reject branch names git would misread as options.
+fn branch_name(args: &Value) -> anyhow::Result<&str> {
+    let name = args["name"]
+        .as_str()
+        .map(str::trim)
+        .filter(|n| !n.is_empty())
+        .ok_or_else(|| anyhow::anyhow!("Missing 'name' argument"))?;
+    if name.starts_with('-') {
+        return Err(anyhow::anyhow!("Invalid branch name: {}", name));
+    }
+    Ok(name)
+}
+
+/// List, create and delete local branches.
+pub struct GitBranch;
+
+#[async_trait]
+impl Tool for GitBranch {
+    fn name(&self) -> &str {
+        "git_branch"
+    }
+
+    fn description(&self) -> &str {
+        "Manage local git branches. action='list' shows all local branches with the current one marked; 'create' makes a new branch from HEAD (optionally checking it out); 'delete' removes a merged branch (the checked-out branch cannot be deleted)."
+    }
+
+    fn parameters_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["list", "create", "delete"],
+                    "description": "What to do"
+                },
+                "name": {
+                    "type": "string",
+                    "description": "Branch name (required for create and delete)"
+                },
+                "checkout": {
+                    "type": "boolean",
+                    "description": "Check out the branch after creating it (default: false)"
+                },
+                "path": {
+                    "type": "string",
+                    "description": "Path to the repository (default: current directory)"
+                }
+            },
+            "required": ["action"]
+        })
+    }
+
+    async fn execute(&self, args: Value, working_dir: &Path) -> anyhow::Result<String> {
+        let action = args["action"]
+            .as_str()
+            .ok_or_else(|| anyhow::anyhow!("Missing 'action' argument"))?;
+        let repo = repo_path(&args, working_dir)?;
+        match action {
+            "list" => {
+                let output = git(
+                    &repo,
+                    &["branch", "--list", "--format=%(HEAD) %(refname:short)"],
+                )
+                .await?;
+                if output.is_empty() {
+                    return Ok("No branches yet (repository has no commits)".to_string());
+                }
+                let lines: Vec<String> = output
+                    .lines()
+                    .map(|line| match line.strip_prefix("* ") {
+                        Some(name) => format!("* {} (current)", name),
+                        None => format!("  {}", line.trim_start()),
+                    })
+                    .collect();
+                Ok(lines.join("\n"))
+            }
+            "create" => {
+                let name = branch_name(&args)?;
+                if args["checkout"].as_bool().unwrap_or(false) {
+                    git(&repo, &["switch", "-c", name]).await?;
+                    Ok(format!("Created and switched to branch '{}'", name))
+                } else {
+                    git(&repo, &["branch", name]).await?;
+                    Ok(format!("Created branch '{}'", name))
+                }
+            }
+            "delete" => {
+                let name = branch_name(&args)?;
+                let current = current_branch(&repo)
+                    .await
+                    .map_err(|e| anyhow::anyhow!("Failed to determine current branch: {}", e))?;
+                if current.as_deref() == Some(name) {
+                    return Err(anyhow::anyhow!(
+                        "Cannot delete branch '{}': it is currently checked out. Switch to another branch first.",
+                        name
+                    ));
+                }
+                git(&repo, &["branch", "-d", name]).await?;
+                Ok(format!("Deleted branch '{}'", name))
+            }
+            other => Err(anyhow::anyhow!(
+                "Unknown action '{}': expected 'list', 'create' or 'delete'",
+                other
+            )),
+        }
+    }
+}
+
+/// Switch branches or restore paths, refusing to discard uncommitted work.
+pub struct GitCheckout;
+
+#[async_trait]
+impl Tool for GitCheckout {
+    fn name(&self) -> &str {
+        "git_checkout"
+    }
+
+    fn description(&self) -> &str {
+        "Switch to a branch/commit, or restore specific paths from it when 'paths' is given. Refuses to run when affected files have uncommitted changes unless force=true; the error lists the dirty paths so you can commit or stash them first."
+    }
+
+    fn parameters_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "target": {
+                    "type": "string",
+                    "description": "Branch, tag or commit to check out (e.g. 'main', 'HEAD~1')"
+                },
+                "paths": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "Optional: restore only these paths from 'target' instead of switching branches"
+                },
+                "force": {
+                    "type": "boolean",
+                    "description": "Proceed even if there are uncommitted changes (they may be lost). Default: false"
+                },
+                "path": {
+                    "type": "string",
+                    "description": "Path to the repository (default: current directory)"
+                }
+            },
+            "required": ["target"]
+        })
+    }
+
+    async fn execute(&self, args: Value, working_dir: &Path) -> anyhow::Result<String> {
+        let target = args["target"]
+            .as_str()
+            .map(str::trim)
+            .filter(|t| !t.is_empty())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'target' argument"))?;
+        if target.starts_with('-') {
+            return Err(anyhow::anyhow!("Invalid checkout target: {}", target));
+        }
+        let paths: Vec<&str> = args["paths"]
+            .as_array()
+            .map(|items| items.iter().filter_map(|p| p.as_str()).collect())
+            .unwrap_or_default();
+        let force = args["force"].as_bool().unwrap_or(false);
+        let repo = repo_path(&args, working_dir)?;
+
+        if !force {
+            let dirty = dirty_paths(&repo, &paths).await?;
+            if !dirty.is_empty() {
+                return Err(anyhow::anyhow!(
+                    "Refusing to check out '{}': uncommitted changes in:\n  {}\nCommit or stash them first, or pass force=true to proceed anyway.",
+                    target,
+                    dirty.join("\n  ")
+                ));
+            }
+        }
+
+        if paths.is_empty() {
+            git(&repo, &["checkout", target]).await?;
+            Ok(format!("Switched to '{}'", target))
+        } else {
+            let mut checkout_args = vec!["checkout", target, "--"];
+            checkout_args.extend_from_slice(&paths);
+            git(&repo, &checkout_args).await?;
+            Ok(format!(
+                "Restored {} path(s) from '{}': {}",
+                paths.len(),
+                target,
+                paths.join(", ")
+            ))
+        }
+    }
+}
+
+/// Stash reference for the optional `index` argument (default: the latest stash).
+fn stash_ref(args: &Value) -> String {
+    format!("stash@{{{}}}", args["index"].as_u64().unwrap_or(0))
+}
+
+/// Save, restore, list and drop stashed changes.
+pub struct GitStash;
+
+#[async_trait]
+impl Tool for GitStash {
+    fn name(&self) -> &str {
+        "git_stash"
+    }
+
+    fn description(&self) -> &str {
+        "Stash uncommitted changes before risky operations and restore them later. Actions: 'save' (optionally with a message), 'pop' (apply and drop a stash), 'list', 'drop' (remove a stash by index)."
+    }
+
+    fn parameters_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["save", "pop", "list", "drop"],
+                    "description": "Operation to perform"
+                },
+                "message": {
+                    "type": "string",
+                    "description": "Description of the stash (for 'save')"
+                },
+                "index": {
+                    "type": "integer",
+                    "description": "Stash index for 'pop' and 'drop' (default: 0, the latest stash)"
+                },
+                "path": {
+                    "type": "string",
+                    "description": "Path to the repository (default: current directory)"
+                }
+            },
+            "required": ["action"]
+        })
+    }
+
+    async fn execute(&self, args: Value, working_dir: &Path) -> anyhow::Result<String> {
+        let action = args["action"]
+            .as_str()
+            .ok_or_else(|| anyhow::anyhow!("Missing 'action' argument"))?;
+        let repo = repo_path(&args, working_dir)?;
+
+        match action {
+            "save" => {
+                let mut stash_args = vec!["stash", "push"];
+                if let Some(message) = args["message"].as_str().filter(|m| !m.is_empty()) {
+                    stash_args.extend_from_slice(&["-m", message]);
+                }
+                let output = git(&repo, &stash_args).await?;
+                if output.contains("No local changes to save") {
+                    return Ok("No local changes to save".to_string());
+                }
+                let summary = git(&repo, &["stash", "list", "-1", "--format=%gs"]).await?;
+                Ok(format!("Saved stash@{{0}}: {}", summary))
+            }
+            "pop" => {
+                let stash = stash_ref(&args);
+                git(&repo, &["stash", "pop", &stash]).await?;
+                Ok(format!("Applied and dropped {}", stash))
+            }
+            "list" => {
+                let output = git(&repo, &["stash", "list", "--format=%gd: %gs"]).await?;
+                if output.is_empty() {
+                    Ok("No stashes".to_string())
+                } else {
+                    Ok(output)
+                }
+            }
+            "drop" => {
+                let stash = stash_ref(&args);
+                git(&repo, &["stash", "drop", &stash]).await?;
+                Ok(format!("Dropped {}", stash))
+            }
+            other => Err(anyhow::anyhow!(
+                "Unknown action '{}': expected 'save', 'pop', 'list' or 'drop'",
+                other
+            )),
+        }
+    }
+}
+
+/// `-c` overrides for the optional `author_name` / `author_email` arguments.
+///
+/// They set `user.name` / `user.email` for this one command, so the commit is
+/// attributed even where no git identity is configured (e.g. in CI); missing
+/// fields fall back to the repository's config.
+fn identity_overrides(args: &Value) -> anyhow::Result<Vec<String>> {
+    let mut overrides = Vec::new();
+    for (arg, key) in [("author_name", "user.name"), ("author_email", "user.email")] {
+        let Some(value) = args[arg].as_str().map(str::trim).filter(|v| !v.is_empty()) else {
+            continue;
+        };
+        if value.contains(['<', '>', '\n']) {
+            return Err(anyhow::anyhow!("Invalid {}: {}", arg, value));
+        }
+        overrides.push("-c".to_string());
+        overrides.push(format!("{}={}", key, value));
+    }
+    Ok(overrides)
+}
+
+/// Commit staged changes, optionally signed, amended or with a custom author.
+pub struct GitCommit;
+
+#[async_trait]
+impl Tool for GitCommit {
+    fn name(&self) -> &str {
+        "git_commit"
+    }
+
+    fn description(&self) -> &str {
+        "Commit staged changes (or everything with all=true). Optionally set the author, GPG-sign the commit, amend the last commit, or skip commit hooks with no_verify."
+    }
+
+    fn parameters_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "message": {
+                    "type": "string",
+                    "description": "Commit message (optional with amend=true, which then keeps the previous message)"
+                },
+                "all": {
+                    "type": "boolean",
+                    "description": "Stage all changes, including new files, before committing. Default: false"
+                },
+                "author_name": {
+                    "type": "string",
+                    "description": "Author and committer name (default: git config user.name)"
+                },
+                "author_email": {
+                    "type": "string",
+                    "description": "Author and committer email (default: git config user.email)"
+                },
+                "sign": {
+                    "type": "boolean",
+                    "description": "GPG-sign the commit with the configured signing key. Default: false"
+                },
+                "amend": {
+                    "type": "boolean",
+                    "description": "Replace the last commit instead of creating a new one. Default: false"
+                },
+                "no_verify": {
+                    "type": "boolean",
+                    "description": "Skip pre-commit and commit-msg hooks. Default: false"
+                },
+                "path": {
+                    "type": "string",
+                    "description": "Path to the repository (default: current directory)"
+                }
+            },
+            "required": []
+        })
+    }
+
+    async fn execute(&self, args: Value, working_dir: &Path) -> anyhow::Result<String> {
+        let message = args["message"]
+            .as_str()
+            .map(str::trim)
+            .filter(|m| !m.is_empty());
+        let amend = args["amend"].as_bool().unwrap_or(false);
+        if message.is_none() && !amend {
+            return Err(anyhow::anyhow!("Missing 'message' argument"));
+        }
+        let identity = identity_overrides(&args)?;
+        let repo = repo_path(&args, working_dir)?;
+
+        if amend
+            && run_git(&repo, &["rev-parse", "--verify", "-q", "HEAD"])
+                .await
+                .is_err()
+        {
+            return Err(anyhow::anyhow!(
+                "Cannot amend: the repository has no commits yet"
+            ));
+        }
+        if args["all"].as_bool().unwrap_or(false) {
+            git(&repo, &["add", "-A"]).await?;
+        }
+
+        let mut commit_args: Vec<&str> = identity.iter().map(String::as_str).collect();
+        commit_args.extend_from_slice(&["commit", "-q"]);
+        if amend {
+            commit_args.push("--amend");
+            if !identity.is_empty() {
+                // Without this an amend keeps the original author.
+                commit_args.push("--reset-author");
+            }
+        }
+        if args["sign"].as_bool().unwrap_or(false) {
+            commit_args.push("-S");
+        }
+        if args["no_verify"].as_bool().unwrap_or(false) {
+            commit_args.push("--no-verify");
+        }
+        match message {
+            Some(message) => commit_args.extend_from_slice(&["-m", message]),
+            None => commit_args.push("--no-edit"),
+        }
+        git(&repo, &commit_args).await?;
+
+        let summary = git(&repo, &["log", "-1", "--format=%h %s (%an <%ae>)"]).await?;
+        Ok(format!(
+            "{} {}",
+            if amend { "Amended" } else { "Committed" },
+            summary
+        ))
+    }
+}
+
+/// How long a push, pull or fetch may run before it is abandoned.
+const NETWORK_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Whether git's error output means the remote rejected (or asked for) credentials.
+fn is_auth_failure(stderr: &str) -> bool {
+    let stderr = stderr.to_lowercase();
+    [
+        "authentication failed",
+        "could not read username",
+        "could not read password",
+        "terminal prompts disabled",
+        "invalid username or password",
+        "permission denied (publickey",
+        "host key verification failed",
+    ]
+    .iter()
+    .any(|pattern| stderr.contains(pattern))
+}
+
+/// Run a git command that talks to `remote`, never waiting on a credential prompt.
+async fn run_git_network(repo: &Path, remote: &str, args: &[&str]) -> anyhow::Result<String> {
+    let mut command = tokio::process::Command::new("git");
+    command
+        .arg("-C")
+        .arg(repo)
+        .args(args)
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .env("GCM_INTERACTIVE", "never")
+        .stdin(Stdio::null())
+        .kill_on_drop(true);
+    if std::env::var_os("GIT_SSH_COMMAND").is_none() {
+        command.env("GIT_SSH_COMMAND", "ssh -o BatchMode=yes");
+    }
+    let output = tokio::time::timeout(NETWORK_TIMEOUT, command.output())
+        .await
+        .map_err(|_| {
+            anyhow::anyhow!(
+                "git {} timed out after {}s",
+                args.join(" "),
+                NETWORK_TIMEOUT.as_secs()
+            )
+        })?
+        .map_err(|e| anyhow::anyhow!("Failed to run git: {}", e))?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if !output.status.success() {
+        if is_auth_failure(&stderr) {
+            return Err(anyhow::anyhow!(
+                "Authentication to remote '{}' failed (git may not prompt for credentials here). Configure a credential helper, a token in the remote URL or an SSH key.\n{}",
+                remote,
+                stderr.trim()
+            ));
+        }
+        return Err(anyhow::anyhow!(
+            "git {} failed: {}",
+            args.join(" "),
+            stderr.trim()
+        ));
+    }
+    // Push and fetch report progress and ref updates on stderr.
+    Ok([stdout.trim(), stderr.trim()]
+        .iter()
+        .filter(|part| !part.is_empty())
+        .copied()
+        .collect::<Vec<_>>()
+        .join("\n"))
+}
+
+/// Push to, pull from or fetch an existing remote.
+pub struct GitRemote;
+
+#[async_trait]
+impl Tool for GitRemote {
+    fn name(&self) -> &str {
+        "git_remote"
+    }
+
+    fn description(&self) -> &str {
+        "Publish or update work through an existing git remote. action='push' pushes 'branch' (default: the current branch), 'pull' fast-forwards the current branch from the remote, 'fetch' downloads remote refs. Never prompts for credentials: authentication problems are reported as errors."
+    }
+
+    fn parameters_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["push", "pull", "fetch"],
+                    "description": "What to do"
+                },
+                "remote": {
+                    "type": "string",
+                    "description": "Name of a configured remote (default: origin)"
+                },
+                "branch": {
+                    "type": "string",
+                    "description": "Branch to push or pull (default: the current branch); for fetch, fetch only this branch"
+                },
+                "set_upstream": {
+                    "type": "boolean",
+                    "description": "For push: make the remote branch the upstream of the local one. Default: false"
+                },
+                "path": {
+                    "type": "string",
+                    "description": "Path to the repository (default: current directory)"
+                }
+            },
+            "required": ["action"]
+        })
+    }
+
+    async fn execute(&self, args: Value, working_dir: &Path) -> anyhow::Result<String> {
+        let action = args["action"]
+            .as_str()
+            .ok_or_else(|| anyhow::anyhow!("Missing 'action' argument"))?;
+        if !matches!(action, "push" | "pull" | "fetch") {
+            return Err(anyhow::anyhow!(
+                "Unknown action '{}': expected 'push', 'pull' or 'fetch'",
+                action
+            ));
+        }
+        let remote = args["remote"]
+            .as_str()
+            .map(str::trim)
+            .filter(|r| !r.is_empty())
+            .unwrap_or("origin");
+        let branch = args["branch"]
+            .as_str()
+            .map(str::trim)
+            .filter(|b| !b.is_empty());
+        if remote.starts_with('-') || branch.is_some_and(|b| b.starts_with('-')) {
+            return Err(anyhow::anyhow!("Invalid remote or branch name"));
+        }
+        let repo = repo_path(&args, working_dir)?;
+
+        let remotes = git(&repo, &["remote"]).await?;
+        if !remotes.lines().any(|r| r == remote) {
+            return Err(anyhow::anyhow!(
+                "Remote '{}' does not exist (configured remotes: {})",
+                remote,
+                if remotes.is_empty() {
+                    "none".to_string()
+                } else {
+                    remotes.lines().collect::<Vec<_>>().join(", ")
+                }
+            ));
+        }
+
+        let current;
+        let branch = match (action, branch) {
+            ("fetch", branch) => branch,
+            (_, Some(branch)) => Some(branch),
+            (_, None) => {
+                current = current_branch(&repo)
+                    .await
+                    .map_err(|e| anyhow::anyhow!("git branch failed: {}", e))?
+                    .ok_or_else(|| anyhow::anyhow!("HEAD is detached: pass 'branch' explicitly"))?;
+                Some(current.as_str())
+            }
+        };
+
+        let mut git_args = vec![action];
+        match action {
+            "push" if args["set_upstream"].as_bool().unwrap_or(false) => git_args.push("-u"),
+            "pull" => git_args.push("--ff-only"),
+            _ => {}
+        }
+        git_args.push(remote);
+        git_args.extend(branch);
+        let output = run_git_network(&repo, remote, &git_args).await?;
+
+        let target = match branch {
+            Some(branch) => format!("{}/{}", remote, branch),
+            None => remote.to_string(),
+        };
+        let done = match action {
+            "push" => format!("Pushed to {}", target),
+            "pull" => format!("Pulled from {}", target),
+            _ => format!("Fetched {}", target),
+        };
+        Ok(if output.is_empty() {
+            done
+        } else {
+            format!("{}\n{}", done, output)
+        })
+    }
+}
+
+/// Resolve a worktree location, which must stay inside the workspace.
+fn worktree_path(path_str: &str, working_dir: &Path) -> anyhow::Result<PathBuf> {
+    if Path::new(path_str)
+        .components()
+        .any(|c| c == Component::ParentDir)
+    {
+        return Err(anyhow::anyhow!(
+            "Invalid worktree path '{}': '..' is not allowed",
+            path_str
+        ));
+    }
+    // The worktree usually doesn't exist yet; only its existing ancestors are canonicalized.
+    let workspace = working_dir
+        .canonicalize()
+        .unwrap_or_else(|_| working_dir.to_path_buf());
+    let canonical = resolve_path_simple(path_str, working_dir)
+        .and_then(|resolved| resolve_within_workspace(working_dir, &resolved.to_string_lossy()))
+        .ok()
+        .filter(|canonical| *canonical != workspace)
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "Worktree path '{}' must be inside the workspace ({})",
+                path_str,
+                workspace.display()
+            )
+        })?;
+    Ok(canonical)
+}
+
+/// Format `git worktree list --porcelain` output as one line per worktree.
+fn format_worktrees(porcelain: &str) -> String {
+    porcelain
+        .split("\n\n")
+        .filter_map(|block| {
+            let mut path = None;
+            let mut head = "(unknown)".to_string();
+            for line in block.lines() {
+                if let Some(p) = line.strip_prefix("worktree ") {
+                    path = Some(p);
+                } else if let Some(branch) = line.strip_prefix("branch ") {
+                    head = format!("[{}]", branch.trim_start_matches("refs/heads/"));
+                } else if line == "detached" {
+                    head = "(detached HEAD)".to_string();
+                } else if line == "bare" {
+                    head = "(bare)".to_string();
+                }
+            }
+            path.map(|p| format!("{}  {}", p, head))
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Create, list and remove git worktrees inside the workspace.
+pub struct GitWorktree;
+
+#[async_trait]
+impl Tool for GitWorktree {
+    fn name(&self) -> &str {
+        "git_worktree"
+    }
+
+    fn description(&self) -> &str {
+        "Manage git worktrees so a branch can be worked on in its own directory without disturbing the main checkout. action='add' checks out 'branch' into a new worktree (creating the branch from 'base' if it doesn't exist); 'list' shows all worktrees; 'remove' deletes a worktree. Worktree paths must be inside the workspace (default: .worktrees/<branch>)."
+    }
+
+    fn parameters_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["add", "list", "remove"],
+                    "description": "What to do"
+                },
+                "branch": {
+                    "type": "string",
+                    "description": "Branch to check out in the new worktree (required for add)"
+                },
+                "base": {
+                    "type": "string",
+                    "description": "Start point when 'branch' has to be created (default: HEAD)"
+                },
+                "worktree_path": {
+                    "type": "string",
+                    "description": "Worktree directory (required for remove; add defaults to .worktrees/<branch>)"
+                },
+                "force": {
+                    "type": "boolean",
+                    "description": "Remove the worktree even if it has uncommitted changes. Default: false"
+                },
+                "path": {
+                    "type": "string",
+                    "description": "Path to the repository (default: current directory)"
+                }
+            },
+            "required": ["action"]
+        })
+    }
+
+    async fn execute(&self, args: Value, working_dir: &Path) -> anyhow::Result<String> {
+        let action = args["action"]
+            .as_str()
+            .ok_or_else(|| anyhow::anyhow!("Missing 'action' argument"))?;
+        let repo = repo_path(&args, working_dir)?;
+        match action {
+            "list" => {
+                let output = git(&repo, &["worktree", "list", "--porcelain"]).await?;
+                Ok(format_worktrees(&output))
+            }
+            "add" => {
+                let branch = args["branch"]
+                    .as_str()
+                    .map(str::trim)
+                    .filter(|b| !b.is_empty())
+                    .ok_or_else(|| anyhow::anyhow!("Missing 'branch' argument"))?;
+                if branch.starts_with('-') {
+                    return Err(anyhow::anyhow!("Invalid branch name: {}", branch));
+                }
+                let default_path = format!("{}/{}", WORKTREES_DIR, branch.replace('/', "-"));
+                let path_str = args["worktree_path"].as_str().unwrap_or(&default_path);
+                let worktree = worktree_path(path_str, working_dir)?;
+                let worktree_str = worktree.to_string_lossy();
+
+                let branch_ref = format!("refs/heads/{}", branch);
+                let exists = run_git(&repo, &["rev-parse", "--verify", "--quiet", &branch_ref])
+                    .await
+                    .is_ok();
+                if exists {
+                    git(&repo, &["worktree", "add", &worktree_str, branch]).await?;
+                    Ok(format!(
+                        "Created worktree for branch '{}' at {}",
+                        branch, worktree_str
+                    ))
+                } else {
+                    let base = args["base"].as_str().unwrap_or("HEAD");
+                    if base.starts_with('-') {
+                        return Err(anyhow::anyhow!("Invalid base: {}", base));
+                    }
+                    git(
+                        &repo,
+                        &["worktree", "add", "-b", branch, &worktree_str, base],
+                    )
+                    .await?;
+                    Ok(format!(
+                        "Created branch '{}' from '{}' in new worktree at {}",
+                        branch, base, worktree_str
+                    ))
+                }
+            }
+            "remove" => {
+                let path_str = args["worktree_path"]
+                    .as_str()
+                    .ok_or_else(|| anyhow::anyhow!("Missing 'worktree_path' argument"))?;
+                let worktree = worktree_path(path_str, working_dir)?;
+                let worktree_str = worktree.to_string_lossy();
+                let mut remove_args = vec!["worktree", "remove"];
+                if args["force"].as_bool().unwrap_or(false) {
+                    remove_args.push("--force");
+                }
+                remove_args.push(&worktree_str);
+                git(&repo, &remove_args).await?;
+                Ok(format!("Removed worktree at {}", worktree_str))
+            }
+            other => Err(anyhow::anyhow!(
+                "Unknown action '{}': expected 'add', 'list' or 'remove'",
+                other
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_git_branch_lifecycle() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = dir.path();
+        for args in [
+            vec!["init", "-q", "-b", "main"],
+            vec!["config", "user.email", "test@example.com"],
+            vec!["config", "user.name", "Test"],
+            vec!["commit", "-q", "--allow-empty", "-m", "initial commit"],
+        ] {
+            run_git(repo, &args).await.unwrap();
+        }
+
+        let created = GitBranch
+            .execute(json!({ "action": "create", "name": "feature/x" }), repo)
+            .await
+            .unwrap();
+        assert_eq!(created, "Created branch 'feature/x'");
+
+        let list = GitBranch
+            .execute(json!({ "action": "list" }), repo)
+            .await
+            .unwrap();
+        assert_eq!(list, "  feature/x\n* main (current)");
+
+        GitBranch
+            .execute(
+                json!({ "action": "create", "name": "topic", "checkout": true }),
+                repo,
+            )
+            .await
+            .unwrap();
+        let err = GitBranch
+            .execute(json!({ "action": "delete", "name": "topic" }), repo)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("currently checked out"));
+
+        let deleted = GitBranch
+            .execute(json!({ "action": "delete", "name": "feature/x" }), repo)
+            .await
+            .unwrap();
+        assert_eq!(deleted, "Deleted branch 'feature/x'");
+
+        let outside = tempfile::tempdir().unwrap();
+        let err = GitBranch
+            .execute(
+                json!({ "action": "list", "path": outside.path().to_str().unwrap() }),
+                repo,
+            )
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("outside the workspace"));
+    }
+
+    #[tokio::test]
+    async fn test_git_commit_options() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = dir.path();
+        for args in [
+            vec!["init", "-q", "-b", "main"],
+            vec!["config", "user.email", "test@example.com"],
+            vec!["config", "user.name", "Test"],
+            vec!["config", "commit.gpgsign", "false"],
+        ] {
+            run_git(repo, &args).await.unwrap();
+        }
+
+        let err = GitCommit
+            .execute(json!({ "amend": true }), repo)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("no commits yet"));
+
+        std::fs::write(repo.join("a.txt"), "a\n").unwrap();
+        let committed = GitCommit
+            .execute(json!({ "message": "Add a", "all": true }), repo)
+            .await
+            .unwrap();
+        assert!(committed.starts_with("Committed "));
+        assert!(committed.ends_with(" Add a (Test <test@example.com>)"));
+
+        // A pre-commit hook that always fails is skipped with no_verify.
+        let hook = repo.join(".git/hooks/pre-commit");
+        std::fs::write(&hook, "#!/bin/sh\nexit 1\n").unwrap();
+        std::fs::set_permissions(&hook, std::os::unix::fs::PermissionsExt::from_mode(0o755))
+            .unwrap();
+        std::fs::write(repo.join("a.txt"), "b\n").unwrap();
+        run_git(repo, &["add", "a.txt"]).await.unwrap();
+        let amend = json!({
+            "amend": true,
+            "author_name": "CI Bot",
+            "author_email": "ci@example.com",
+        });
+        assert!(GitCommit.execute(amend.clone(), repo).await.is_err());
+        let mut amend = amend;
+        amend["no_verify"] = json!(true);
+        let amended = GitCommit.execute(amend, repo).await.unwrap();
+        assert!(amended.ends_with(" Add a (CI Bot <ci@example.com>)"));
+        assert_eq!(
+            run_git(repo, &["rev-list", "--count", "HEAD"])
+                .await
+                .unwrap(),
+            "1"
+        );
+
+        let err = GitCommit
+            .execute(json!({ "message": "x", "author_email": "<evil>" }), repo)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("Invalid author_email"));
+    }
+
+    #[tokio::test]
+    async fn test_git_remote_push_pull_fetch() {
+        let dir = tempfile::tempdir().unwrap();
+        let origin = dir.path().join("origin.git");
+        let repo = dir.path().join("work");
+        let clone = dir.path().join("clone");
+        std::fs::create_dir(&repo).unwrap();
+        run_git(
+            dir.path(),
+            &["init", "-q", "--bare", "-b", "main", "origin.git"],
+        )
+        .await
+        .unwrap();
+        for args in [
+            vec!["init", "-q", "-b", "main"],
+            vec!["config", "user.email", "test@example.com"],
+            vec!["config", "user.name", "Test"],
+            vec!["commit", "-q", "--allow-empty", "-m", "initial commit"],
+        ] {
+            run_git(&repo, &args).await.unwrap();
+        }
+
+        let err = GitRemote
+            .execute(json!({ "action": "push" }), &repo)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("Remote 'origin' does not exist"));
+
+        run_git(
+            &repo,
+            &["remote", "add", "origin", origin.to_str().unwrap()],
+        )
+        .await
+        .unwrap();
+        let pushed = GitRemote
+            .execute(json!({ "action": "push", "set_upstream": true }), &repo)
+            .await
+            .unwrap();
+        assert!(pushed.starts_with("Pushed to origin/main"));
+        assert_eq!(
+            run_git(&repo, &["rev-parse", "--abbrev-ref", "main@{upstream}"])
+                .await
+                .unwrap(),
+            "origin/main"
+        );
+
+        run_git(
+            dir.path(),
+            &["clone", "-q", origin.to_str().unwrap(), "clone"],
+        )
+        .await
+        .unwrap();
+        run_git(&repo, &["commit", "-q", "--allow-empty", "-m", "second"])
+            .await
+            .unwrap();
+        GitRemote
+            .execute(json!({ "action": "push" }), &repo)
+            .await
+            .unwrap();
+
+        GitRemote
+            .execute(json!({ "action": "fetch" }), &clone)
+            .await
+            .unwrap();
+        let pulled = GitRemote
+            .execute(json!({ "action": "pull", "branch": "main" }), &clone)
+            .await
+            .unwrap();
+        assert!(pulled.starts_with("Pulled from origin/main"));
+        assert_eq!(
+            run_git(&clone, &["log", "-1", "--format=%s"])
+                .await
+                .unwrap(),
+            "second"
+        );
+
+        assert!(is_auth_failure(
+            "fatal: could not read Username for 'https://github.com': terminal prompts disabled"
+        ));
+        assert!(!is_auth_failure("fatal: couldn't find remote ref nope"));
+    }
+
+    #[tokio::test]
+    async fn test_git_checkout_guards_uncommitted_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = dir.path();
+        std::fs::write(repo.join("a.txt"), "committed\n").unwrap();
+        for args in [
+            vec!["init", "-q", "-b", "main"],
+            vec!["config", "user.email", "test@example.com"],
+            vec!["config", "user.name", "Test"],
+            vec!["add", "a.txt"],
+            vec!["commit", "-qm", "initial commit"],
+            vec!["branch", "feature"],
+        ] {
+            run_git(repo, &args).await.unwrap();
+        }
+        std::fs::write(repo.join("a.txt"), "work in progress\n").unwrap();
+
+        let err = GitCheckout
+            .execute(json!({ "target": "feature" }), repo)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("uncommitted changes in:\n  a.txt"));
+        assert_eq!(current_branch(repo).await.unwrap().as_deref(), Some("main"));
+
+        let err = GitCheckout
+            .execute(json!({ "target": "HEAD", "paths": ["a.txt"] }), repo)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("a.txt"));
+        assert_eq!(
+            std::fs::read_to_string(repo.join("a.txt")).unwrap(),
+            "work in progress\n"
+        );
+
+        let out = GitCheckout
+            .execute(
+                json!({ "target": "HEAD", "paths": ["a.txt"], "force": true }),
+                repo,
+            )
+            .await
+            .unwrap();
+        assert_eq!(out, "Restored 1 path(s) from 'HEAD': a.txt");
+        assert_eq!(
+            std::fs::read_to_string(repo.join("a.txt")).unwrap(),
+            "committed\n"
+        );
+
+        GitCheckout
+            .execute(json!({ "target": "feature" }), repo)
+            .await
+            .unwrap();
+        assert_eq!(
+            current_branch(repo).await.unwrap().as_deref(),
+            Some("feature")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_git_stash_save_list_pop() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = dir.path();
+        std::fs::write(repo.join("a.txt"), "committed\n").unwrap();
+        for args in [
+            vec!["init", "-q", "-b", "main"],
+            vec!["config", "user.email", "test@example.com"],
+            vec!["config", "user.name", "Test"],
+            vec!["add", "a.txt"],
+            vec!["commit", "-qm", "initial commit"],
+        ] {
+            run_git(repo, &args).await.unwrap();
+        }
+        let stash = |args: Value| GitStash.execute(args, repo);
+
+        let out = stash(json!({ "action": "save" })).await.unwrap();
+        assert_eq!(out, "No local changes to save");
+        assert_eq!(
+            stash(json!({ "action": "list" })).await.unwrap(),
+            "No stashes"
+        );
+
+        std::fs::write(repo.join("a.txt"), "work in progress\n").unwrap();
+        let out = stash(json!({ "action": "save", "message": "before rebase" }))
+            .await
+            .unwrap();
+        assert_eq!(out, "Saved stash@{0}: On main: before rebase");
+        assert_eq!(
+            std::fs::read_to_string(repo.join("a.txt")).unwrap(),
+            "committed\n"
+        );
+        assert_eq!(
+            stash(json!({ "action": "list" })).await.unwrap(),
+            "stash@{0}: On main: before rebase"
+        );
+
+        let out = stash(json!({ "action": "pop" })).await.unwrap();
+        assert_eq!(out, "Applied and dropped stash@{0}");
+        assert_eq!(
+            std::fs::read_to_string(repo.join("a.txt")).unwrap(),
+            "work in progress\n"
+        );
+        assert!(stash(json!({ "action": "drop", "index": 0 }))
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_git_worktree_add_and_list() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = dir.path().canonicalize().unwrap();
+        for args in [
+            vec!["init", "-q", "-b", "main"],
+            vec!["config", "user.email", "test@example.com"],
+            vec!["config", "user.name", "Test"],
+            vec!["commit", "-q", "--allow-empty", "-m", "initial commit"],
+        ] {
+            run_git(&repo, &args).await.unwrap();
+        }
+
+        let out = GitWorktree
+            .execute(json!({ "action": "add", "branch": "feature/x" }), &repo)
+            .await
+            .unwrap();
+        let worktree = repo.join(".worktrees/feature-x");
+        assert_eq!(
+            out,
+            format!(
+                "Created branch 'feature/x' from 'HEAD' in new worktree at {}",
+                worktree.display()
+            )
+        );
+        assert_eq!(
+            current_branch(&worktree).await.unwrap().as_deref(),
+            Some("feature/x")
+        );
+        assert_eq!(
+            current_branch(&repo).await.unwrap().as_deref(),
+            Some("main")
+        );
+
+        let list = GitWorktree
+            .execute(json!({ "action": "list" }), &repo)
+            .await
+            .unwrap();
+        assert_eq!(
+            list,
+            format!(
+                "{}  [main]\n{}  [feature/x]",
+                repo.display(),
+                worktree.display()
+            )
+        );
+
+        let err = GitWorktree
+            .execute(
+                json!({ "action": "add", "branch": "other", "worktree_path": "/tmp/elsewhere" }),
+                &repo,
+            )
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("must be inside the workspace"));
+
+        GitWorktree
+            .execute(
+                json!({ "action": "remove", "worktree_path": ".worktrees/feature-x" }),
+                &repo,
+            )
+            .await
+            .unwrap();
+        assert!(!worktree.exists());
+    }
+}
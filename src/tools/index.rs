@@ -114,7 +114,7 @@ impl Tool for IndexFiles {
             })
             .unwrap_or_else(default_ignore_dirs);
 
-        let root = resolve_path(path, working_dir);
+        let root = resolve_path(path, working_dir)?;
         if !root.exists() {
             return Err(anyhow::anyhow!("Directory not found: {}", path));
         }
@@ -124,6 +124,7 @@ impl Tool for IndexFiles {
 
         let index_path = out_path
             .map(|p| resolve_path(p, working_dir))
+            .transpose()?
             .unwrap_or_else(|| default_index_file(working_dir));
 
         if let Some(parent) = index_path.parent() {
@@ -243,6 +244,7 @@ impl Tool for SearchFileIndex {
         let index_path = args["index_path"]
             .as_str()
             .map(|p| resolve_path(p, working_dir))
+            .transpose()?
             .unwrap_or_else(|| default_index_file(working_dir));
         let limit = args["limit"].as_u64().unwrap_or(100) as usize;
         let case_sensitive = args["case_sensitive"].as_bool().unwrap_or(false);
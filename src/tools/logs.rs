@@ -0,0 +1,338 @@
+//! Log inspection tool: find log files and tail them.
+//!
+//! Log files are searched in the workspace plus any directories listed in
+//! `OPEN_AGENT_LOG_DIRS` (comma-separated, e.g. `/var/log/myapp,/srv/logs`).
+//! A file counts as a log when it ends in `.log`, is a rotated log
+//! (`app.log.1`), or lives in a `log`/`logs` directory.
+
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use async_trait::async_trait;
+use serde_json::{json, Value};
+use walkdir::WalkDir;
+
+use super::{resolve_path_simple, Tool};
+
+/// Maximum number of log files listed.
+const MAX_LOG_FILES: usize = 200;
+/// How deep to look for log files below each root.
+const MAX_SEARCH_DEPTH: usize = 6;
+/// Directories that never contain service logs worth listing.
+const SKIPPED_DIRS: &[&str] = &[".git", "node_modules", "target", ".venv", "venv"];
+const DEFAULT_TAIL_LINES: usize = 100;
+const DEFAULT_MAX_OUTPUT_CHARS: usize = 10_000;
+const MAX_OUTPUT_CHARS_LIMIT: usize = 50_000;
+/// Bytes read from the end of a file when tailing.
+const TAIL_WINDOW_BYTES: u64 = 1024 * 1024;
+
+/// Extra log directories configured through `OPEN_AGENT_LOG_DIRS`.
+fn configured_log_dirs() -> Vec<PathBuf> {
+    std::env::var("OPEN_AGENT_LOG_DIRS")
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|d| !d.is_empty())
+        .map(PathBuf::from)
+        .collect()
+}
+
+fn is_log_file(path: &Path) -> bool {
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    let in_log_dir = path
+        .parent()
+        .and_then(|p| p.file_name())
+        .is_some_and(|d| d == "log" || d == "logs");
+    name.ends_with(".log") || name.contains(".log.") || in_log_dir
+}
+
+/// A log file found under one of the roots.
+struct LogFile {
+    path: PathBuf,
+    size: u64,
+    modified: SystemTime,
+}
+
+fn find_log_files(roots: &[PathBuf]) -> Vec<LogFile> {
+    let mut files = Vec::new();
+    for root in roots.iter().filter(|r| r.is_dir()) {
+        let walker = WalkDir::new(root)
+            .max_depth(MAX_SEARCH_DEPTH)
+            .into_iter()
+            .filter_entry(|e| {
+                e.depth() == 0
+                    || !e.file_type().is_dir()
+                    || !SKIPPED_DIRS.contains(&e.file_name().to_string_lossy().as_ref())
+            });
+        for entry in walker.filter_map(|e| e.ok()) {
+            if !entry.file_type().is_file() || !is_log_file(entry.path()) {
+                continue;
+            }
+            let Ok(meta) = entry.metadata() else {
+                continue;
+            };
+            files.push(LogFile {
+                path: entry.into_path(),
+                size: meta.len(),
+                modified: meta.modified().unwrap_or(SystemTime::UNIX_EPOCH),
+            });
+        }
+    }
+    // Most recently written first: that is usually the log being investigated.
+    files.sort_by(|a, b| b.modified.cmp(&a.modified).then(a.path.cmp(&b.path)));
+    files.dedup_by(|a, b| a.path == b.path);
+    files
+}
+
+/// Resolve a file to tail: a path in the workspace, or a log file under one of
+/// `log_dirs` (which `list` shows by absolute path).
+fn resolve_log_path(
+    path: &str,
+    working_dir: &Path,
+    log_dirs: &[PathBuf],
+) -> anyhow::Result<PathBuf> {
+    let candidate = Path::new(path);
+    if candidate.is_absolute() {
+        // Canonicalize both sides so `..` and symlinks can't leave the log dirs.
+        if let Ok(real) = candidate.canonicalize() {
+            let in_log_dir = log_dirs
+                .iter()
+                .filter_map(|dir| dir.canonicalize().ok())
+                .any(|dir| real.starts_with(dir));
+            if in_log_dir && is_log_file(&real) {
+                return Ok(real);
+            }
+        }
+    }
+    resolve_path_simple(path, working_dir)
+}
+
+/// Read the last `lines` lines of a file without loading all of it.
+fn tail_file(path: &Path, lines: usize) -> std::io::Result<(String, bool)> {
+    let mut file = std::fs::File::open(path)?;
+    let len = file.metadata()?.len();
+    let start = len.saturating_sub(TAIL_WINDOW_BYTES);
+    file.seek(SeekFrom::Start(start))?;
+    let mut bytes = Vec::new();
+    file.read_to_end(&mut bytes)?;
+    let text = String::from_utf8_lossy(&bytes);
+
+    let mut all: Vec<&str> = text.lines().collect();
+    if start > 0 && !all.is_empty() {
+        // The window most likely starts mid-line.
+        all.remove(0);
+    }
+    let skipped = all.len().saturating_sub(lines);
+    Ok((all[skipped..].join("\n"), start > 0 || skipped > 0))
+}
+
+/// Keep the end of `text` within `max_chars` (the newest log lines matter most).
+fn keep_tail(text: &str, max_chars: usize) -> (String, bool) {
+    let count = text.chars().count();
+    if count <= max_chars {
+        return (text.to_string(), false);
+    }
+    (text.chars().skip(count - max_chars).collect(), true)
+}
+
+fn format_size(bytes: u64) -> String {
+    match bytes {
+        b if b >= 1024 * 1024 => format!("{:.1} MB", b as f64 / (1024.0 * 1024.0)),
+        b if b >= 1024 => format!("{:.1} KB", b as f64 / 1024.0),
+        b => format!("{} B", b),
+    }
+}
+
+/// List log files and tail a selected one.
+pub struct Logs;
+
+#[async_trait]
+impl Tool for Logs {
+    fn name(&self) -> &str {
+        "logs"
+    }
+
+    fn description(&self) -> &str {
+        "Inspect service logs. action='list' finds log files (*.log, rotated logs, files in log/ or logs/ directories) in the workspace and configured log directories, newest first. action='tail' returns the last lines of one log file."
+    }
+
+    fn parameters_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["list", "tail"],
+                    "description": "'list' log files or 'tail' one (default: list, or tail when 'path' is given)"
+                },
+                "path": {
+                    "type": "string",
+                    "description": "Log file to tail (as returned by action='list')"
+                },
+                "lines": {
+                    "type": "integer",
+                    "description": "Number of lines to return when tailing (default: 100)"
+                },
+                "max_output_chars": {
+                    "type": "integer",
+                    "description": "Maximum characters of log output (default: 10000, max: 50000)"
+                }
+            },
+            "required": []
+        })
+    }
+
+    async fn execute(&self, args: Value, working_dir: &Path) -> anyhow::Result<String> {
+        let path = args["path"].as_str();
+        let action =
+            args["action"]
+                .as_str()
+                .unwrap_or(if path.is_some() { "tail" } else { "list" });
+
+        match action {
+            "list" => {
+                let mut roots = vec![working_dir.to_path_buf()];
+                roots.extend(configured_log_dirs());
+                let files = tokio::task::spawn_blocking(move || find_log_files(&roots)).await?;
+                if files.is_empty() {
+                    return Ok("No log files found".to_string());
+                }
+
+                let mut out: Vec<String> = files
+                    .iter()
+                    .take(MAX_LOG_FILES)
+                    .map(|f| {
+                        let shown = f.path.strip_prefix(working_dir).unwrap_or(&f.path);
+                        let modified: chrono::DateTime<chrono::Utc> = f.modified.into();
+                        format!(
+                            "{}  ({}, modified {})",
+                            shown.display(),
+                            format_size(f.size),
+                            modified.format("%Y-%m-%d %H:%M:%S UTC")
+                        )
+                    })
+                    .collect();
+                if files.len() > MAX_LOG_FILES {
+                    out.push(format!(
+                        "... and {} more log files",
+                        files.len() - MAX_LOG_FILES
+                    ));
+                }
+                Ok(out.join("\n"))
+            }
+            "tail" => {
+                let path =
+                    path.ok_or_else(|| anyhow::anyhow!("Missing 'path' argument for tail"))?;
+                let resolved = resolve_log_path(path, working_dir, &configured_log_dirs())?;
+                if !resolved.is_file() {
+                    return Err(anyhow::anyhow!(
+                        "Log file not found: {} (resolved to: {})",
+                        path,
+                        resolved.display()
+                    ));
+                }
+                let lines = args["lines"]
+                    .as_u64()
+                    .map(|n| n as usize)
+                    .unwrap_or(DEFAULT_TAIL_LINES)
+                    .max(1);
+                let max_chars = args["max_output_chars"]
+                    .as_u64()
+                    .map(|n| n as usize)
+                    .unwrap_or(DEFAULT_MAX_OUTPUT_CHARS)
+                    .clamp(1, MAX_OUTPUT_CHARS_LIMIT);
+
+                let (text, earlier) =
+                    tokio::task::spawn_blocking(move || tail_file(&resolved, lines)).await??;
+                if text.is_empty() {
+                    return Ok(format!("{} is empty", path));
+                }
+                let (text, truncated) = keep_tail(&text, max_chars);
+                if earlier || truncated {
+                    Ok(format!("... [earlier output truncated]\n{}", text))
+                } else {
+                    Ok(text)
+                }
+            }
+            other => Err(anyhow::anyhow!(
+                "Invalid action '{}': expected 'list' or 'tail'",
+                other
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_list_and_tail_log_fixture() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("logs")).unwrap();
+        std::fs::create_dir_all(dir.path().join("node_modules/pkg")).unwrap();
+        let log: String = (1..=300).map(|i| format!("request {}\n", i)).collect();
+        std::fs::write(dir.path().join("logs/server.out"), &log).unwrap();
+        std::fs::write(dir.path().join("worker.log.1"), "old\n").unwrap();
+        std::fs::write(dir.path().join("node_modules/pkg/install.log"), "").unwrap();
+        std::fs::write(dir.path().join("notes.txt"), "").unwrap();
+
+        let out = Logs.execute(json!({}), dir.path()).await.unwrap();
+        assert!(out.contains("logs/server.out"));
+        assert!(out.contains("worker.log.1"));
+        assert!(!out.contains("install.log"));
+        assert!(!out.contains("notes.txt"));
+
+        let out = Logs
+            .execute(json!({ "path": "logs/server.out", "lines": 3 }), dir.path())
+            .await
+            .unwrap();
+        assert_eq!(
+            out,
+            "... [earlier output truncated]\nrequest 298\nrequest 299\nrequest 300"
+        );
+
+        let out = Logs
+            .execute(
+                json!({ "action": "tail", "path": "logs/server.out", "max_output_chars": 11 }),
+                dir.path(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(out, "... [earlier output truncated]\nrequest 300");
+
+        let out = Logs
+            .execute(json!({ "path": "worker.log.1" }), dir.path())
+            .await
+            .unwrap();
+        assert_eq!(out, "old");
+    }
+
+    #[test]
+    fn test_tail_path_in_configured_log_dir() {
+        let workspace = tempfile::tempdir().unwrap();
+        let log_dir = tempfile::tempdir().unwrap();
+        let log = log_dir.path().join("app.log");
+        std::fs::write(&log, "started\n").unwrap();
+        std::fs::write(log_dir.path().join("secrets.env"), "").unwrap();
+        let log_dirs = vec![log_dir.path().to_path_buf()];
+
+        let resolved =
+            resolve_log_path(log.to_str().unwrap(), workspace.path(), &log_dirs).unwrap();
+        assert_eq!(resolved, log.canonicalize().unwrap());
+        assert_eq!(tail_file(&resolved, 10).unwrap().0, "started");
+
+        // Only log files, and only with the directory configured.
+        let other = log_dir.path().join("secrets.env");
+        let other = other.to_str().unwrap();
+        if crate::tools::PathPolicy::global().allow_workspace_escape {
+            return;
+        }
+        assert!(resolve_log_path(other, workspace.path(), &log_dirs).is_err());
+        assert!(resolve_log_path(log.to_str().unwrap(), workspace.path(), &[]).is_err());
+    }
+}
@@ -0,0 +1,372 @@
+//! Code metrics tool: line counts per file and language, plus cyclomatic
+//! complexity when `scc` is installed.
+//!
+//! Counter selection: `scc` (lines + complexity) → `tokei` (lines) → a built-in
+//! counter that recognizes common languages and their line comments. All of
+//! them skip files excluded by `.gitignore`.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use async_trait::async_trait;
+use ignore::WalkBuilder;
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use super::search::which_exists;
+use super::{resolve_path_simple, Tool};
+
+/// Cap on per-file entries returned to the model (largest files first).
+const MAX_FILES: usize = 100;
+
+/// Line counts for one file or an aggregate.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct LineCounts {
+    pub lines: u64,
+    pub code: u64,
+    pub comment: u64,
+    pub blank: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub complexity: Option<u64>,
+}
+
+impl LineCounts {
+    fn add(&mut self, other: &LineCounts) {
+        self.lines += other.lines;
+        self.code += other.code;
+        self.comment += other.comment;
+        self.blank += other.blank;
+        if let Some(c) = other.complexity {
+            *self.complexity.get_or_insert(0) += c;
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FileMetrics {
+    pub path: String,
+    pub language: String,
+    #[serde(flatten)]
+    pub counts: LineCounts,
+}
+
+/// Metrics for a directory tree.
+#[derive(Debug, Clone, Serialize)]
+pub struct CodeMetricsReport {
+    pub counter: &'static str,
+    pub total_files: usize,
+    pub totals: LineCounts,
+    pub languages: BTreeMap<String, LineCounts>,
+    pub files: Vec<FileMetrics>,
+}
+
+impl CodeMetricsReport {
+    fn from_files(counter: &'static str, mut files: Vec<FileMetrics>) -> Self {
+        let mut totals = LineCounts::default();
+        let mut languages: BTreeMap<String, LineCounts> = BTreeMap::new();
+        for file in &files {
+            totals.add(&file.counts);
+            languages
+                .entry(file.language.clone())
+                .or_default()
+                .add(&file.counts);
+        }
+        files.sort_by(|a, b| {
+            b.counts
+                .complexity
+                .cmp(&a.counts.complexity)
+                .then(b.counts.code.cmp(&a.counts.code))
+                .then(a.path.cmp(&b.path))
+        });
+        Self {
+            counter,
+            total_files: files.len(),
+            totals,
+            languages,
+            files,
+        }
+    }
+}
+
+/// Language name and line-comment prefixes for a file extension.
+fn language_for(path: &Path) -> Option<(&'static str, &'static [&'static str])> {
+    const SLASHES: &[&str] = &["//"];
+    const HASH: &[&str] = &["#"];
+    let ext = path.extension()?.to_str()?.to_lowercase();
+    Some(match ext.as_str() {
+        "rs" => ("Rust", SLASHES),
+        "ts" | "tsx" => ("TypeScript", SLASHES),
+        "js" | "jsx" | "mjs" | "cjs" => ("JavaScript", SLASHES),
+        "go" => ("Go", SLASHES),
+        "c" | "h" => ("C", SLASHES),
+        "cc" | "cpp" | "cxx" | "hpp" => ("C++", SLASHES),
+        "java" => ("Java", SLASHES),
+        "kt" | "kts" => ("Kotlin", SLASHES),
+        "swift" => ("Swift", SLASHES),
+        "cs" => ("C#", SLASHES),
+        "scala" => ("Scala", SLASHES),
+        "py" => ("Python", HASH),
+        "rb" => ("Ruby", HASH),
+        "sh" | "bash" | "zsh" => ("Shell", HASH),
+        "toml" => ("TOML", HASH),
+        "yaml" | "yml" => ("YAML", HASH),
+        "sql" => ("SQL", &["--"]),
+        "lua" => ("Lua", &["--"]),
+        "hs" => ("Haskell", &["--"]),
+        "php" => ("PHP", &["//", "#"]),
+        "css" | "scss" => ("CSS", &[]),
+        "html" | "htm" => ("HTML", &[]),
+        "md" => ("Markdown", &[]),
+        "json" => ("JSON", &[]),
+        _ => return None,
+    })
+}
+
+/// Count lines in `text` (comments are recognized by line prefix only).
+fn count_lines(text: &str, comment_prefixes: &[&str]) -> LineCounts {
+    let mut counts = LineCounts::default();
+    for line in text.lines() {
+        let trimmed = line.trim();
+        counts.lines += 1;
+        if trimmed.is_empty() {
+            counts.blank += 1;
+        } else if comment_prefixes.iter().any(|p| trimmed.starts_with(p)) {
+            counts.comment += 1;
+        } else {
+            counts.code += 1;
+        }
+    }
+    counts
+}
+
+/// Built-in counter used when neither `scc` nor `tokei` is installed.
+pub fn builtin_metrics(root: &Path) -> CodeMetricsReport {
+    let mut files = Vec::new();
+    for entry in WalkBuilder::new(root).require_git(false).build().flatten() {
+        let path = entry.path();
+        let Some((language, prefixes)) = language_for(path) else {
+            continue;
+        };
+        let Ok(text) = std::fs::read_to_string(path) else {
+            continue;
+        };
+        files.push(FileMetrics {
+            path: path
+                .strip_prefix(root)
+                .unwrap_or(path)
+                .display()
+                .to_string(),
+            language: language.to_string(),
+            counts: count_lines(&text, prefixes),
+        });
+    }
+    CodeMetricsReport::from_files("builtin", files)
+}
+
+fn json_u64(value: &Value, key: &str) -> u64 {
+    value[key].as_u64().unwrap_or(0)
+}
+
+/// Parse `scc --by-file --format json` output.
+fn parse_scc(output: &str, root: &Path) -> anyhow::Result<CodeMetricsReport> {
+    let languages: Vec<Value> = serde_json::from_str(output)?;
+    let mut files = Vec::new();
+    for language in &languages {
+        let name = language["Name"].as_str().unwrap_or("Unknown");
+        for file in language["Files"].as_array().into_iter().flatten() {
+            let location = file["Location"].as_str().unwrap_or_default();
+            files.push(FileMetrics {
+                path: Path::new(location)
+                    .strip_prefix(root)
+                    .unwrap_or(Path::new(location))
+                    .display()
+                    .to_string(),
+                language: name.to_string(),
+                counts: LineCounts {
+                    lines: json_u64(file, "Lines"),
+                    code: json_u64(file, "Code"),
+                    comment: json_u64(file, "Comment"),
+                    blank: json_u64(file, "Blank"),
+                    complexity: Some(json_u64(file, "Complexity")),
+                },
+            });
+        }
+    }
+    Ok(CodeMetricsReport::from_files("scc", files))
+}
+
+/// Parse `tokei --output json` output.
+fn parse_tokei(output: &str, root: &Path) -> anyhow::Result<CodeMetricsReport> {
+    let languages: serde_json::Map<String, Value> = serde_json::from_str(output)?;
+    let mut files = Vec::new();
+    for (name, language) in languages.iter().filter(|(name, _)| *name != "Total") {
+        for report in language["reports"].as_array().into_iter().flatten() {
+            let stats = &report["stats"];
+            let (code, comment, blank) = (
+                json_u64(stats, "code"),
+                json_u64(stats, "comments"),
+                json_u64(stats, "blanks"),
+            );
+            let location = report["name"].as_str().unwrap_or_default();
+            files.push(FileMetrics {
+                path: Path::new(location)
+                    .strip_prefix(root)
+                    .unwrap_or(Path::new(location))
+                    .display()
+                    .to_string(),
+                language: name.clone(),
+                counts: LineCounts {
+                    lines: code + comment + blank,
+                    code,
+                    comment,
+                    blank,
+                    complexity: None,
+                },
+            });
+        }
+    }
+    Ok(CodeMetricsReport::from_files("tokei", files))
+}
+
+/// Run an external counter, returning `None` (fall back) if it fails.
+async fn run_counter(program: &str, args: &[&str], root: &Path) -> Option<String> {
+    let output = tokio::process::Command::new(program)
+        .args(args)
+        .arg(root)
+        .output()
+        .await
+        .ok()?;
+    if !output.status.success() {
+        tracing::warn!(
+            "{} failed: {}",
+            program,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+        return None;
+    }
+    Some(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// Report line counts and complexity for a directory.
+pub struct CodeMetrics;
+
+#[async_trait]
+impl Tool for CodeMetrics {
+    fn name(&self) -> &str {
+        "code_metrics"
+    }
+
+    fn description(&self) -> &str {
+        "Measure code size: per-file, per-language and total line counts (code/comment/blank), plus cyclomatic complexity when scc is installed. Returns JSON with the largest/most complex files first. Respects .gitignore."
+    }
+
+    fn parameters_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "path": {
+                    "type": "string",
+                    "description": "Directory to measure (default: workspace '.')"
+                },
+                "max_files": {
+                    "type": "integer",
+                    "description": "Maximum number of per-file entries to return (default: 100)"
+                }
+            },
+            "required": []
+        })
+    }
+
+    async fn execute(&self, args: Value, working_dir: &Path) -> anyhow::Result<String> {
+        let path = args["path"].as_str().unwrap_or(".");
+        let root = resolve_path_simple(path, working_dir)?;
+        if !root.is_dir() {
+            return Err(anyhow::anyhow!("Not a directory: {}", path));
+        }
+        let max_files = args["max_files"]
+            .as_u64()
+            .map(|n| n as usize)
+            .unwrap_or(MAX_FILES);
+
+        let mut report = None;
+        if which_exists("scc") {
+            if let Some(output) =
+                run_counter("scc", &["--by-file", "--format", "json"], &root).await
+            {
+                report = parse_scc(&output, &root).ok();
+            }
+        }
+        if report.is_none() && which_exists("tokei") {
+            if let Some(output) =
+                run_counter("tokei", &["--files", "--output", "json"], &root).await
+            {
+                report = parse_tokei(&output, &root).ok();
+            }
+        }
+        let mut report = match report {
+            Some(report) => report,
+            None => {
+                let root = root.clone();
+                tokio::task::spawn_blocking(move || builtin_metrics(&root)).await?
+            }
+        };
+
+        let truncated = report.files.len() > max_files;
+        report.files.truncate(max_files);
+        let mut result = serde_json::to_value(&report)?;
+        result["files_truncated"] = json!(truncated);
+        Ok(serde_json::to_string_pretty(&result)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin_line_counts_over_fixture() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("src")).unwrap();
+        std::fs::create_dir_all(dir.path().join("build")).unwrap();
+        std::fs::write(
+            dir.path().join("src/main.rs"),
+            "// entry point\nfn main() {\n\n    println!(\"hi\");\n}\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("tool.py"),
+            "#!/usr/bin/env python\nimport sys\n\nprint(sys.argv)\n",
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("build/gen.rs"), "fn generated() {}\n").unwrap();
+        std::fs::write(dir.path().join(".gitignore"), "build/\n").unwrap();
+        std::fs::write(dir.path().join("notes.bin"), "ignored").unwrap();
+
+        let report = builtin_metrics(dir.path());
+        assert_eq!(report.total_files, 2);
+        assert_eq!(
+            report.totals,
+            LineCounts {
+                lines: 9,
+                code: 5,
+                comment: 2,
+                blank: 2,
+                complexity: None,
+            }
+        );
+        assert_eq!(report.languages["Rust"].code, 3);
+        assert_eq!(report.languages["Python"].lines, 4);
+        assert_eq!(report.files[0].path, "src/main.rs");
+    }
+
+    #[test]
+    fn test_parse_scc_by_file_json() {
+        let output = r#"[{"Name":"Go","Lines":12,"Code":10,"Complexity":4,"Files":[
+            {"Location":"/repo/a.go","Lines":8,"Code":7,"Comment":0,"Blank":1,"Complexity":3},
+            {"Location":"/repo/b.go","Lines":4,"Code":3,"Comment":1,"Blank":0,"Complexity":1}]}]"#;
+        let report = parse_scc(output, Path::new("/repo")).unwrap();
+        assert_eq!(report.files[0].path, "a.go");
+        assert_eq!(report.totals.complexity, Some(4));
+        assert_eq!(report.languages["Go"].code, 10);
+    }
+}
@@ -0,0 +1,570 @@
+//! Migration tool: apply (or inspect) a project's database migrations.
+//!
+//! The migration tool is detected from the project directory (workspace by default):
+//! - `prisma/schema.prisma` → `npx prisma migrate deploy`
+//! - `diesel.toml`, or `Cargo.toml` using diesel → `diesel migration run`
+//! - `alembic.ini` → `alembic upgrade head`
+//! - `Cargo.toml` using sqlx with a `migrations/` dir → `sqlx migrate run`
+//!
+//! Before applying, the pending migrations are listed with the tool's status
+//! command and scanned for destructive statements (`DROP TABLE`, `TRUNCATE`,
+//! `op.drop_column`, ...). Destructive migrations, rollbacks, resets and
+//! explicit commands that look destructive only run with `confirm: true`.
+//! Execution is delegated to `run_command` so container workspaces behave the
+//! same way.
+
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use async_trait::async_trait;
+use regex::Regex;
+use serde_json::{json, Value};
+
+use super::{resolve_path_simple as resolve_path, terminal::RunCommand, Tool};
+
+/// A supported migration tool.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Migrator {
+    Sqlx,
+    Diesel,
+    Alembic,
+    Prisma,
+}
+
+/// What to do with the migrations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MigrationAction {
+    /// Apply all pending migrations
+    Up,
+    /// List pending migrations without applying them
+    Status,
+    /// Revert the most recent migration
+    Down,
+    /// Drop the database and re-apply every migration
+    Reset,
+}
+
+impl MigrationAction {
+    fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "up" | "run" | "apply" => Some(Self::Up),
+            "status" | "pending" => Some(Self::Status),
+            "down" | "revert" | "rollback" => Some(Self::Down),
+            "reset" => Some(Self::Reset),
+            _ => None,
+        }
+    }
+
+    fn is_destructive(&self) -> bool {
+        matches!(self, Self::Down | Self::Reset)
+    }
+}
+
+impl Migrator {
+    fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "sqlx" => Some(Self::Sqlx),
+            "diesel" => Some(Self::Diesel),
+            "alembic" => Some(Self::Alembic),
+            "prisma" => Some(Self::Prisma),
+            _ => None,
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Sqlx => "sqlx",
+            Self::Diesel => "diesel",
+            Self::Alembic => "alembic",
+            Self::Prisma => "prisma",
+        }
+    }
+
+    /// Shell command performing `action`, if the tool supports it.
+    pub fn command(&self, action: MigrationAction) -> Option<&'static str> {
+        use MigrationAction::*;
+        Some(match (self, action) {
+            (Self::Sqlx, Up) => "sqlx migrate run",
+            (Self::Sqlx, Status) => "sqlx migrate info",
+            (Self::Sqlx, Down) => "sqlx migrate revert",
+            (Self::Sqlx, Reset) => "sqlx database reset -y",
+            (Self::Diesel, Up) => "diesel migration run",
+            (Self::Diesel, Status) => "diesel migration list",
+            (Self::Diesel, Down) => "diesel migration revert",
+            (Self::Diesel, Reset) => "diesel database reset",
+            (Self::Alembic, Up) => "alembic upgrade head",
+            (Self::Alembic, Status) => "alembic history -i -r current:head",
+            (Self::Alembic, Down) => "alembic downgrade -1",
+            (Self::Alembic, Reset) => "alembic downgrade base && alembic upgrade head",
+            (Self::Prisma, Up) => "npx prisma migrate deploy",
+            (Self::Prisma, Status) => "npx prisma migrate status",
+            // Prisma has no down migrations
+            (Self::Prisma, Down) => return None,
+            (Self::Prisma, Reset) => "npx prisma migrate reset --force",
+        })
+    }
+}
+
+/// Detect the migration tool for the project at `dir`.
+pub fn detect_migrator(dir: &Path) -> Option<Migrator> {
+    if dir.join("prisma/schema.prisma").exists() || dir.join("schema.prisma").exists() {
+        return Some(Migrator::Prisma);
+    }
+    if dir.join("diesel.toml").exists() {
+        return Some(Migrator::Diesel);
+    }
+    if dir.join("alembic.ini").exists() {
+        return Some(Migrator::Alembic);
+    }
+    let cargo = std::fs::read_to_string(dir.join("Cargo.toml")).unwrap_or_default();
+    if dir.join("migrations").is_dir() {
+        if cargo.contains("diesel") {
+            return Some(Migrator::Diesel);
+        }
+        if cargo.contains("sqlx") {
+            return Some(Migrator::Sqlx);
+        }
+    }
+    None
+}
+
+fn regex(cell: &'static OnceLock<Regex>, pattern: &str) -> &'static Regex {
+    cell.get_or_init(|| Regex::new(pattern).expect("valid migration regex"))
+}
+
+/// Migration names reported as applied in a migration command's output.
+pub fn parse_applied(migrator: Migrator, output: &str) -> Vec<String> {
+    static SQLX: OnceLock<Regex> = OnceLock::new();
+    static DIESEL: OnceLock<Regex> = OnceLock::new();
+    static ALEMBIC: OnceLock<Regex> = OnceLock::new();
+    static PRISMA: OnceLock<Regex> = OnceLock::new();
+    let re = match migrator {
+        // `Applied 20240101000000/migrate create users (4.1ms)`
+        Migrator::Sqlx => regex(&SQLX, r"Applied (\d+)/migrate ([^(]+?)\s*(?:\(|$)"),
+        // `Running migration 2024-01-01-000000_create_users`
+        Migrator::Diesel => regex(&DIESEL, r"Running migration (\S+)"),
+        // `INFO  [alembic.runtime.migration] Running upgrade a1 -> b2, create users`
+        Migrator::Alembic => regex(&ALEMBIC, r"Running upgrade \S* ?-> ([0-9A-Za-z_]+)"),
+        // ``Applying migration `20240101000000_init` ``
+        Migrator::Prisma => regex(&PRISMA, r"Applying migration `([^`]+)`"),
+    };
+    output
+        .lines()
+        .filter_map(|line| re.captures(line))
+        .map(|caps| match migrator {
+            Migrator::Sqlx => format!("{}_{}", &caps[1], caps[2].replace(' ', "_")),
+            _ => caps[1].to_string(),
+        })
+        .collect()
+}
+
+/// Pending migration names from the status command's output.
+///
+/// `None` when the output lists nothing and the command failed (e.g. the
+/// database does not exist yet), in which case every migration is pending.
+pub fn parse_pending(migrator: Migrator, exit_code: i32, output: &str) -> Option<Vec<String>> {
+    static SQLX: OnceLock<Regex> = OnceLock::new();
+    static DIESEL: OnceLock<Regex> = OnceLock::new();
+    static ALEMBIC: OnceLock<Regex> = OnceLock::new();
+    static PRISMA: OnceLock<Regex> = OnceLock::new();
+    let pending: Vec<String> = match migrator {
+        // `20240101000000/pending create users`
+        Migrator::Sqlx => output
+            .lines()
+            .filter_map(|line| regex(&SQLX, r"^(\d+)/pending (.+)$").captures(line.trim()))
+            .map(|caps| format!("{}_{}", &caps[1], caps[2].trim().replace(' ', "_")))
+            .collect(),
+        // `  [ ] 2024-01-01-000000_create_users`
+        Migrator::Diesel => output
+            .lines()
+            .filter_map(|line| regex(&DIESEL, r"^\[ \] (\S+)$").captures(line.trim()))
+            .map(|caps| caps[1].to_string())
+            .collect(),
+        // `a1 -> b2 (head), create users`; the current revision is already applied
+        Migrator::Alembic => output
+            .lines()
+            .filter(|line| !line.contains("(current)"))
+            .filter_map(|line| regex(&ALEMBIC, r"-> ([0-9A-Za-z_]+)").captures(line))
+            .map(|caps| caps[1].to_string())
+            .collect(),
+        // Names listed under "... have not yet been applied:"
+        Migrator::Prisma => output
+            .lines()
+            .skip_while(|line| !line.contains("not yet been applied"))
+            .skip(1)
+            .filter_map(|line| regex(&PRISMA, r"^(\d{14}_\S+)$").captures(line.trim()))
+            .map(|caps| caps[1].to_string())
+            .collect(),
+    };
+    if pending.is_empty() && exit_code != 0 {
+        None
+    } else {
+        Some(pending)
+    }
+}
+
+/// Every migration on disk as `(name, file with its forward statements)`.
+fn migration_files(migrator: Migrator, dir: &Path) -> Vec<(String, PathBuf)> {
+    let read_dir = |path: PathBuf| -> Vec<PathBuf> {
+        let mut entries: Vec<PathBuf> = std::fs::read_dir(path)
+            .map(|rd| rd.filter_map(|e| e.ok().map(|e| e.path())).collect())
+            .unwrap_or_default();
+        entries.sort();
+        entries
+    };
+    let file_name = |path: &Path| {
+        path.file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default()
+    };
+    match migrator {
+        Migrator::Sqlx => read_dir(dir.join("migrations"))
+            .into_iter()
+            .filter_map(|path| {
+                let name = file_name(&path);
+                let stem = name
+                    .strip_suffix(".up.sql")
+                    .or_else(|| name.strip_suffix(".sql"))
+                    .filter(|_| !name.ends_with(".down.sql"))?
+                    .to_string();
+                Some((stem, path))
+            })
+            .collect(),
+        Migrator::Diesel | Migrator::Prisma => {
+            let (root, file) = if migrator == Migrator::Diesel {
+                (dir.join("migrations"), "up.sql")
+            } else {
+                (dir.join("prisma/migrations"), "migration.sql")
+            };
+            read_dir(root)
+                .into_iter()
+                .filter(|path| path.join(file).is_file())
+                .map(|path| (file_name(&path), path.join(file)))
+                .collect()
+        }
+        Migrator::Alembic => {
+            static REVISION: OnceLock<Regex> = OnceLock::new();
+            let ini = std::fs::read_to_string(dir.join("alembic.ini")).unwrap_or_default();
+            let script_location = ini
+                .lines()
+                .filter_map(|line| line.split_once('='))
+                .find(|(key, _)| key.trim() == "script_location")
+                .map(|(_, value)| value.trim().replace("%(here)s", "."))
+                .unwrap_or_else(|| "alembic".to_string());
+            read_dir(dir.join(script_location).join("versions"))
+                .into_iter()
+                .filter(|path| path.extension().is_some_and(|ext| ext == "py"))
+                .filter_map(|path| {
+                    let source = std::fs::read_to_string(&path).ok()?;
+                    let re = regex(
+                        &REVISION,
+                        r#"(?m)^revision(?:\s*:\s*str)?\s*=\s*['"]([^'"]+)['"]"#,
+                    );
+                    let revision = re.captures(&source)?[1].to_string();
+                    Some((revision, path))
+                })
+                .collect()
+        }
+    }
+}
+
+/// Whether migration source (SQL or an alembic script) destroys data.
+pub fn is_destructive_source(source: &str) -> bool {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    regex(
+        &RE,
+        r"(?i)\bdrop\s+(table|column|schema|database|view|type)\b|\btruncate\b|\bdelete\s+from\b|\bop\.drop_(table|column)\b",
+    )
+    .is_match(source)
+}
+
+/// Whether an explicit migration command looks like it rolls back or wipes data.
+pub fn is_destructive_command(command: &str) -> bool {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    regex(
+        &RE,
+        r"(?i)\b(drop|reset|revert|rollback|downgrade|down|fresh|truncate)\b",
+    )
+    .is_match(command)
+}
+
+/// Pending migrations (all of them when `pending` is `None`) containing destructive statements.
+fn destructive_migrations(
+    migrator: Migrator,
+    dir: &Path,
+    pending: Option<&[String]>,
+) -> Vec<String> {
+    migration_files(migrator, dir)
+        .into_iter()
+        .filter(|(name, _)| pending.map(|p| p.contains(name)).unwrap_or(true))
+        .filter(|(_, path)| {
+            std::fs::read_to_string(path)
+                .map(|source| is_destructive_source(&source))
+                .unwrap_or(false)
+        })
+        .map(|(name, _)| name)
+        .collect()
+}
+
+async fn run(
+    command: &str,
+    project_dir: &Path,
+    working_dir: &Path,
+    timeout: Option<u64>,
+) -> anyhow::Result<(i32, String)> {
+    let mut run_args = json!({
+        "command": command,
+        "cwd": project_dir.to_string_lossy(),
+        "max_output_chars": 50000,
+    });
+    if let Some(timeout) = timeout {
+        run_args["timeout_secs"] = json!(timeout);
+    }
+    let output = RunCommand.execute(run_args, working_dir).await?;
+    let exit_code = output
+        .lines()
+        .next()
+        .and_then(|l| l.strip_prefix("Exit code: "))
+        .and_then(|c| c.trim().parse::<i32>().ok())
+        .unwrap_or(-1);
+    Ok((exit_code, output))
+}
+
+fn output_tail(output: &str) -> String {
+    let tail: Vec<&str> = output.lines().rev().take(40).collect();
+    tail.into_iter().rev().collect::<Vec<_>>().join("\n")
+}
+
+/// Apply or inspect database migrations.
+pub struct RunMigrations;
+
+#[async_trait]
+impl Tool for RunMigrations {
+    fn name(&self) -> &str {
+        "run_migrations"
+    }
+
+    fn description(&self) -> &str {
+        "Run the project's database migrations (sqlx, diesel, alembic, prisma — auto-detected) or an explicit migration command. Returns the applied migration names and status. Use action 'status' to list pending migrations. Rollbacks, resets and migrations that drop or truncate data require confirm: true."
+    }
+
+    fn parameters_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "path": {
+                    "type": "string",
+                    "description": "Project directory. Defaults to workspace ('.')."
+                },
+                "action": {
+                    "type": "string",
+                    "enum": ["up", "status", "down", "reset"],
+                    "description": "up (default): apply pending migrations; status: list pending; down: revert the last migration; reset: drop the database and re-apply all."
+                },
+                "tool": {
+                    "type": "string",
+                    "enum": ["sqlx", "diesel", "alembic", "prisma"],
+                    "description": "Optional: override migration tool auto-detection."
+                },
+                "command": {
+                    "type": "string",
+                    "description": "Optional: explicit migration command to run instead of the detected tool's."
+                },
+                "confirm": {
+                    "type": "boolean",
+                    "description": "Required to run destructive migrations, rollbacks or resets (default: false)."
+                },
+                "timeout_secs": {
+                    "type": "integer",
+                    "description": "Timeout in seconds (default: command default)."
+                }
+            },
+            "required": []
+        })
+    }
+
+    async fn execute(&self, args: Value, working_dir: &Path) -> anyhow::Result<String> {
+        let path = args["path"].as_str().unwrap_or(".");
+        let project_dir = resolve_path(path, working_dir)?;
+        let confirm = args["confirm"].as_bool().unwrap_or(false);
+        let timeout = args["timeout_secs"].as_u64();
+        let action = match args["action"].as_str() {
+            Some(name) => MigrationAction::parse(name)
+                .ok_or_else(|| anyhow::anyhow!("Unsupported action: {}", name))?,
+            None => MigrationAction::Up,
+        };
+        let migrator = match args["tool"].as_str() {
+            Some(name) => Some(
+                Migrator::parse(name)
+                    .ok_or_else(|| anyhow::anyhow!("Unsupported migration tool: {}", name))?,
+            ),
+            None => detect_migrator(&project_dir),
+        };
+
+        if let Some(command) = args["command"].as_str() {
+            if is_destructive_command(command) && !confirm {
+                anyhow::bail!(
+                    "Refusing to run '{}' without confirm: true; it looks like it rolls back or wipes data.",
+                    command
+                );
+            }
+            tracing::info!("Running explicit migration command: {}", command);
+            let (exit_code, output) = run(command, &project_dir, working_dir, timeout).await?;
+            let applied = migrator
+                .map(|m| parse_applied(m, &output))
+                .unwrap_or_default();
+            return Ok(serde_json::to_string_pretty(&json!({
+                "ok": exit_code == 0,
+                "tool": migrator.map(|m| m.as_str()),
+                "command": command,
+                "exit_code": exit_code,
+                "applied": applied,
+                "output": output_tail(&output),
+            }))?);
+        }
+
+        let migrator = migrator.ok_or_else(|| {
+            anyhow::anyhow!(
+                "Could not detect a migration tool in {}. Pass 'tool' or 'command' explicitly.",
+                project_dir.display()
+            )
+        })?;
+        let command = migrator.command(action).ok_or_else(|| {
+            anyhow::anyhow!("{} does not support action {:?}", migrator.as_str(), action)
+        })?;
+        if action.is_destructive() && !confirm {
+            anyhow::bail!(
+                "Refusing to run '{}' without confirm: true; it reverts applied migrations.",
+                command
+            );
+        }
+
+        let status_command = migrator
+            .command(MigrationAction::Status)
+            .expect("every migrator has a status command");
+        let pending = if matches!(action, MigrationAction::Up | MigrationAction::Status) {
+            let (exit_code, output) =
+                run(status_command, &project_dir, working_dir, timeout).await?;
+            let pending = parse_pending(migrator, exit_code, &output);
+            if action == MigrationAction::Status {
+                // `prisma migrate status` exits non-zero while migrations are pending
+                return Ok(serde_json::to_string_pretty(&json!({
+                    "ok": exit_code == 0 || pending.is_some(),
+                    "tool": migrator.as_str(),
+                    "command": status_command,
+                    "exit_code": exit_code,
+                    "pending": pending,
+                    "output": output_tail(&output),
+                }))?);
+            }
+            pending
+        } else {
+            None
+        };
+
+        if action == MigrationAction::Up && !confirm {
+            let destructive = destructive_migrations(migrator, &project_dir, pending.as_deref());
+            if !destructive.is_empty() {
+                anyhow::bail!(
+                    "Pending migrations drop or delete data: {}. Review them and re-run with confirm: true.",
+                    destructive.join(", ")
+                );
+            }
+        }
+
+        tracing::info!("Running {} migrations: {}", migrator.as_str(), command);
+        let (exit_code, output) = run(command, &project_dir, working_dir, timeout).await?;
+        let mut result = json!({
+            "ok": exit_code == 0,
+            "tool": migrator.as_str(),
+            "command": command,
+            "exit_code": exit_code,
+            "applied": parse_applied(migrator, &output),
+        });
+        if exit_code != 0 {
+            result["output"] = json!(output_tail(&output));
+        }
+        Ok(serde_json::to_string_pretty(&result)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_migration_command_selected_per_tooling() {
+        let up = |dir: &Path| detect_migrator(dir).and_then(|m| m.command(MigrationAction::Up));
+
+        let sqlx = tempfile::tempdir().unwrap();
+        std::fs::write(
+            sqlx.path().join("Cargo.toml"),
+            "[dependencies]\nsqlx = \"0.7\"",
+        )
+        .unwrap();
+        assert_eq!(up(sqlx.path()), None, "no migrations dir yet");
+        std::fs::create_dir(sqlx.path().join("migrations")).unwrap();
+        assert_eq!(up(sqlx.path()), Some("sqlx migrate run"));
+
+        let diesel = tempfile::tempdir().unwrap();
+        std::fs::write(diesel.path().join("diesel.toml"), "").unwrap();
+        assert_eq!(up(diesel.path()), Some("diesel migration run"));
+
+        let alembic = tempfile::tempdir().unwrap();
+        std::fs::write(alembic.path().join("alembic.ini"), "[alembic]").unwrap();
+        std::fs::write(alembic.path().join("requirements.txt"), "").unwrap();
+        assert_eq!(up(alembic.path()), Some("alembic upgrade head"));
+
+        let prisma = tempfile::tempdir().unwrap();
+        std::fs::create_dir(prisma.path().join("prisma")).unwrap();
+        std::fs::write(prisma.path().join("prisma/schema.prisma"), "").unwrap();
+        std::fs::write(prisma.path().join("package.json"), "{}").unwrap();
+        assert_eq!(up(prisma.path()), Some("npx prisma migrate deploy"));
+        assert_eq!(Migrator::Prisma.command(MigrationAction::Down), None);
+
+        assert_eq!(detect_migrator(tempfile::tempdir().unwrap().path()), None);
+    }
+
+    #[test]
+    fn test_only_pending_destructive_migrations_need_confirm() {
+        let dir = tempfile::tempdir().unwrap();
+        let migrations = dir.path().join("migrations");
+        std::fs::create_dir(&migrations).unwrap();
+        std::fs::write(
+            migrations.join("20240101000000_create_users.sql"),
+            "CREATE TABLE users (id INT); DROP TABLE legacy_users;",
+        )
+        .unwrap();
+        std::fs::write(
+            migrations.join("20240201000000_add_email.up.sql"),
+            "ALTER TABLE users ADD COLUMN email TEXT;",
+        )
+        .unwrap();
+        std::fs::write(
+            migrations.join("20240201000000_add_email.down.sql"),
+            "ALTER TABLE users DROP COLUMN email;",
+        )
+        .unwrap();
+
+        let info = "Exit code: 0\n\n--- stdout ---\n20240101000000/installed create users\n\
+                    20240201000000/pending add email\n";
+        let pending = parse_pending(Migrator::Sqlx, 0, info).unwrap();
+        assert_eq!(pending, vec!["20240201000000_add_email"]);
+        assert!(destructive_migrations(Migrator::Sqlx, dir.path(), Some(&pending)).is_empty());
+
+        // Database not created yet: everything is pending.
+        assert_eq!(parse_pending(Migrator::Sqlx, 1, "error: no database"), None);
+        assert_eq!(
+            destructive_migrations(Migrator::Sqlx, dir.path(), None),
+            vec!["20240101000000_create_users"]
+        );
+
+        let run = "Applied 20240201000000/migrate add email (3.2ms)\n";
+        assert_eq!(
+            parse_applied(Migrator::Sqlx, run),
+            vec!["20240201000000_add_email"]
+        );
+        assert!(is_destructive_command("alembic downgrade base"));
+        assert!(!is_destructive_command("npm run migrate"));
+    }
+}
@@ -1,4 +1,5 @@
-//! Mission control tool - allows the agent to complete or fail the current mission.
+//! Mission control tools - allow the agent to check deliverables and complete or fail the
+//! current mission.
 
 use async_trait::async_trait;
 use serde::Deserialize;
@@ -9,6 +10,7 @@ use tokio::sync::{mpsc, RwLock};
 use uuid::Uuid;
 
 use super::Tool;
+use crate::task::{Deliverable, DeliverableSet};
 
 /// Command sent by the mission tool to the control session.
 #[derive(Debug, Clone)]
@@ -48,6 +50,8 @@ impl std::fmt::Display for MissionStatusValue {
 pub struct MissionControl {
     pub current_mission_id: Arc<RwLock<Option<Uuid>>>,
     pub cmd_tx: mpsc::Sender<MissionControlCommand>,
+    /// Deliverables expected by the current turn (extracted from the user message)
+    pub deliverables: Arc<RwLock<DeliverableSet>>,
 }
 
 /// Tool that allows the agent to mark the current mission as completed or failed.
@@ -237,3 +241,132 @@ IMPORTANT: Use 'blocked' or 'not_feasible' instead of producing fake/placeholder
         Ok(format!("Mission marked as {}.{}", status, summary_msg))
     }
 }
+
+/// Tool that reports which of the mission's required deliverables exist yet.
+pub struct DeliverableStatus {
+    pub control: Option<MissionControl>,
+}
+
+impl Default for DeliverableStatus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DeliverableStatus {
+    pub fn new() -> Self {
+        Self { control: None }
+    }
+
+    pub fn with_control(control: MissionControl) -> Self {
+        Self {
+            control: Some(control),
+        }
+    }
+}
+
+/// Build the status report for a deliverable set.
+pub async fn deliverable_status_report(set: &DeliverableSet) -> Value {
+    let mut items = Vec::new();
+    for deliverable in &set.deliverables {
+        let (kind, label) = match deliverable {
+            Deliverable::File { description, .. } => ("file", description.clone()),
+            Deliverable::Directory { .. } => ("directory", None),
+            Deliverable::Report { topic, .. } => ("report", Some(topic.clone())),
+        };
+        items.push(json!({
+            "kind": kind,
+            "path": deliverable.path().map(|p| p.display().to_string()),
+            "description": label,
+            "exists": deliverable.exists().await,
+        }));
+    }
+    let missing = set.missing_paths().await;
+    json!({
+        "deliverables": items,
+        "missing": missing,
+        "all_complete": set.all_complete().await,
+        "is_research_task": set.is_research_task,
+        "requires_report": set.requires_report,
+    })
+}
+
+#[async_trait]
+impl Tool for DeliverableStatus {
+    fn name(&self) -> &str {
+        "deliverable_status"
+    }
+
+    fn description(&self) -> &str {
+        "Check which of the mission's required deliverables (files, directories, reports) exist yet. Call this before complete_mission to avoid completing with missing outputs."
+    }
+
+    fn parameters_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {},
+            "required": []
+        })
+    }
+
+    async fn execute(&self, _args: Value, _working_dir: &Path) -> anyhow::Result<String> {
+        let Some(control) = &self.control else {
+            return Ok(
+                "Mission control not available in this context. No deliverables are tracked."
+                    .to_string(),
+            );
+        };
+
+        let set = control.deliverables.read().await.clone();
+        if set.deliverables.is_empty() {
+            return Ok("No explicit deliverables were detected for this mission.".to_string());
+        }
+
+        let report = deliverable_status_report(&set).await;
+        Ok(serde_json::to_string_pretty(&report)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[tokio::test]
+    async fn test_deliverable_status_reports_present_and_missing() {
+        let dir = tempfile::tempdir().unwrap();
+        let present = dir.path().join("report.md");
+        std::fs::write(&present, "# Report").unwrap();
+        let absent: PathBuf = dir.path().join("data.json");
+
+        let (cmd_tx, _cmd_rx) = mpsc::channel(1);
+        let control = MissionControl {
+            current_mission_id: Arc::new(RwLock::new(Some(Uuid::new_v4()))),
+            cmd_tx,
+            deliverables: Arc::new(RwLock::new(DeliverableSet {
+                deliverables: vec![
+                    Deliverable::File {
+                        path: present.clone(),
+                        description: None,
+                    },
+                    Deliverable::File {
+                        path: absent.clone(),
+                        description: None,
+                    },
+                ],
+                ..Default::default()
+            })),
+        };
+
+        let output = DeliverableStatus::with_control(control)
+            .execute(json!({}), dir.path())
+            .await
+            .unwrap();
+        let report: Value = serde_json::from_str(&output).unwrap();
+
+        assert_eq!(report["deliverables"][0]["exists"], json!(true));
+        assert_eq!(report["deliverables"][1]["exists"], json!(false));
+        assert_eq!(report["missing"], json!([absent.display().to_string()]));
+        assert_eq!(report["all_complete"], json!(false));
+    }
+}
@@ -7,31 +7,70 @@
 //!
 //! Tools are designed to work **relative to the workspace** by default:
 //! - Relative paths (e.g., `output/report.md`) resolve from the workspace directory
-//! - Absolute paths (e.g., `/etc/hosts`) work as an escape hatch for system access
+//!   (the mission work dir), or from `OPEN_AGENT_PATH_BASE` when set: a subdirectory
+//!   of the workspace such as `output`, so `./foo.txt` lands in `<workspace>/output/`
+//! - Any path (absolute, `..`-relative or through a symlink) that resolves outside
+//!   the workspace is rejected by [`resolve_within_workspace`], unless
+//!   `OPEN_AGENT_ALLOW_WORKSPACE_ESCAPE=true` turns absolute paths back into an
+//!   escape hatch for host access
 //!
-//! This encourages agents to stay within their assigned workspace while preserving
-//! flexibility for tasks that require broader access.
+//! Every filesystem tool resolves its paths through [`resolve_path`], so an
+//! untrusted agent stays confined to its assigned workspace.
 
+mod browser;
+mod compile_check;
 mod composite;
+mod config_promote;
 mod desktop;
 mod directory;
+mod env_file;
 mod file_ops;
+mod git;
 mod index;
+mod logs;
+mod metrics;
+mod migrations;
 pub mod mission;
+mod normalize;
+pub mod observer;
+pub mod permissions;
+mod rate_limit;
+mod render;
+mod resources;
+pub mod safe_mode;
 mod search;
 mod terminal;
+mod terminal_session;
+mod test_runner;
+pub mod todo;
 mod ui;
 mod web;
+pub mod workspace_ignore;
+mod yaml;
 
+pub use browser::ScreenshotUrl;
+pub use compile_check::CheckCompile;
+pub use composite::GitOverview;
+pub use config_promote::ConfigPromote;
 pub use directory::{ListDirectory, SearchFiles};
-pub use file_ops::{DeleteFile, ReadFile, WriteFile};
+pub use file_ops::{CopyFile, DeleteFile, EditFile, MoveFile, ReadFile, ReadManyFiles, WriteFile};
+pub use git::{GitBranch, GitCheckout, GitCommit, GitRemote, GitStash, GitWorktree};
+pub use logs::Logs;
+pub use metrics::CodeMetrics;
+pub use migrations::RunMigrations;
+pub use normalize::NormalizeFile;
+pub use render::RenderMarkdown;
 pub use search::GrepSearch;
 pub use terminal::RunCommand;
-pub use web::FetchUrl;
+pub use terminal_session::TerminalSession;
+pub use todo::{TodoAdd, TodoComplete, TodoList};
+pub use ui::{awaits_user_input, UiForm};
+pub use web::{FetchUrl, HttpRequest};
+pub use yaml::YamlPath;
 
 use std::collections::HashMap;
-use std::path::{Path, PathBuf};
-use std::sync::Arc;
+use std::path::{Component, Path, PathBuf};
+use std::sync::{Arc, OnceLock};
 
 // ============================================================================
 // Path Resolution Utilities
@@ -66,48 +105,159 @@ impl PathResolution {
     }
 }
 
-/// Resolve a path relative to the workspace.
+/// How agent-supplied paths are resolved against the workspace.
+#[derive(Debug, Clone, Default)]
+pub struct PathPolicy {
+    /// Root for relative paths; relative values are taken from the workspace.
+    /// `None` resolves relative paths from the workspace itself.
+    pub base_dir: Option<PathBuf>,
+    /// Allow paths that resolve outside the workspace (absolute paths become an escape hatch)
+    pub allow_workspace_escape: bool,
+}
+
+impl PathPolicy {
+    /// Load from `OPEN_AGENT_PATH_BASE` and `OPEN_AGENT_ALLOW_WORKSPACE_ESCAPE`.
+    pub fn from_env() -> Self {
+        Self {
+            base_dir: std::env::var("OPEN_AGENT_PATH_BASE")
+                .ok()
+                .filter(|v| !v.trim().is_empty())
+                .map(PathBuf::from),
+            allow_workspace_escape: std::env::var("OPEN_AGENT_ALLOW_WORKSPACE_ESCAPE")
+                .map(|v| {
+                    matches!(
+                        v.trim().to_lowercase().as_str(),
+                        "1" | "true" | "yes" | "on"
+                    )
+                })
+                .unwrap_or(false),
+        }
+    }
+
+    /// Process-wide policy, read from the environment once.
+    pub fn global() -> &'static PathPolicy {
+        static POLICY: OnceLock<PathPolicy> = OnceLock::new();
+        POLICY.get_or_init(Self::from_env)
+    }
+
+    /// Directory relative paths resolve from.
+    pub fn base_for(&self, workspace: &Path) -> PathBuf {
+        match &self.base_dir {
+            Some(base) => workspace.join(base),
+            None => workspace.to_path_buf(),
+        }
+    }
+
+    /// Resolve `path_str` for a tool running in `workspace`.
+    pub fn resolve(&self, path_str: &str, workspace: &Path) -> anyhow::Result<PathResolution> {
+        let path = Path::new(path_str);
+        let was_absolute = path.is_absolute();
+
+        let resolved = if was_absolute {
+            path.to_path_buf()
+        } else {
+            self.base_for(workspace).join(path)
+        };
+
+        let is_outside_workspace = if self.allow_workspace_escape {
+            !is_within(&resolved, workspace)
+        } else {
+            resolve_within_workspace(workspace, &resolved.to_string_lossy())?;
+            false
+        };
+
+        Ok(PathResolution {
+            original: path_str.to_string(),
+            resolved,
+            is_outside_workspace,
+            was_absolute,
+        })
+    }
+}
+
+/// Resolve `user_path` (relative to `workspace`, or absolute) to its real location,
+/// rejecting anything that escapes the workspace through `..` or symlinks.
+///
+/// Paths that don't exist yet are fine as long as their existing ancestors stay
+/// inside; a dangling symlink is judged by where it points.
+pub fn resolve_within_workspace(workspace: &Path, user_path: &str) -> anyhow::Result<PathBuf> {
+    let resolved = canonicalize_existing_prefix(&workspace.join(user_path));
+    if !resolved.starts_with(canonicalize_existing_prefix(workspace)) {
+        return Err(anyhow::anyhow!(
+            "Path '{}' resolves outside the workspace ({}); only paths inside the workspace are allowed",
+            user_path,
+            workspace.display()
+        ));
+    }
+    Ok(resolved)
+}
+
+/// Whether `path` really lives inside `workspace` (handles .., symlinks, not-yet-created files, etc.).
+fn is_within(path: &Path, workspace: &Path) -> bool {
+    canonicalize_existing_prefix(path).starts_with(canonicalize_existing_prefix(workspace))
+}
+
+/// Symlinks followed before giving up on a path (matches Linux's `MAXSYMLINKS`).
+const MAX_SYMLINK_HOPS: usize = 40;
+
+/// Canonicalize the longest existing ancestor of `path` and append the rest,
+/// after removing `.` and `..` lexically.
+fn canonicalize_existing_prefix(path: &Path) -> PathBuf {
+    canonicalize_existing_prefix_inner(path, MAX_SYMLINK_HOPS)
+}
+
+fn canonicalize_existing_prefix_inner(path: &Path, hops: usize) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            other => normalized.push(other),
+        }
+    }
+    let mut rest = Vec::new();
+    let mut existing = normalized.as_path();
+    loop {
+        let canonical = existing.canonicalize().ok().or_else(|| {
+            // Dangling symlink: follow it by hand so a write can't land outside.
+            let target = std::fs::read_link(existing).ok()?;
+            let target = existing.parent().unwrap_or(existing).join(target);
+            (hops > 0).then(|| canonicalize_existing_prefix_inner(&target, hops - 1))
+        });
+        if let Some(canonical) = canonical {
+            return rest
+                .iter()
+                .rev()
+                .fold(canonical, |acc, part| acc.join(part));
+        }
+        match (existing.parent(), existing.file_name()) {
+            (Some(parent), Some(name)) => {
+                rest.push(name.to_os_string());
+                existing = parent;
+            }
+            _ => return normalized,
+        }
+    }
+}
+
+/// Resolve a path relative to the workspace, following [`PathPolicy::global`].
 ///
-/// - Relative paths are joined with `workspace`
-/// - Absolute paths are used as-is (escape hatch)
+/// - Relative paths are joined with the policy's base (the workspace by default)
+/// - Paths outside the workspace are an error, unless the policy allows escaping
+///   it, in which case absolute paths are used as-is
 ///
 /// Returns a `PathResolution` with metadata about the resolution.
-pub fn resolve_path(path_str: &str, workspace: &Path) -> PathResolution {
-    let path = Path::new(path_str);
-    let was_absolute = path.is_absolute();
-
-    let resolved = if was_absolute {
-        path.to_path_buf()
-    } else {
-        workspace.join(path)
-    };
-
-    // Canonicalize for accurate comparison (handles .., symlinks, etc.)
-    let canonical_resolved = resolved.canonicalize().unwrap_or_else(|_| resolved.clone());
-    let canonical_workspace = workspace
-        .canonicalize()
-        .unwrap_or_else(|_| workspace.to_path_buf());
-
-    let is_outside_workspace = !canonical_resolved.starts_with(&canonical_workspace);
-
-    PathResolution {
-        original: path_str.to_string(),
-        resolved,
-        is_outside_workspace,
-        was_absolute,
-    }
+pub fn resolve_path(path_str: &str, workspace: &Path) -> anyhow::Result<PathResolution> {
+    PathPolicy::global().resolve(path_str, workspace)
 }
 
 /// Simple path resolution that just returns the resolved path.
 ///
 /// Use this when you don't need the full `PathResolution` metadata.
-pub fn resolve_path_simple(path_str: &str, workspace: &Path) -> PathBuf {
-    let path = Path::new(path_str);
-    if path.is_absolute() {
-        path.to_path_buf()
-    } else {
-        workspace.join(path)
-    }
+pub fn resolve_path_simple(path_str: &str, workspace: &Path) -> anyhow::Result<PathBuf> {
+    resolve_path(path_str, workspace).map(|resolution| resolution.resolved)
 }
 
 /// Safely truncate a string to a maximum number of bytes at a valid UTF-8 boundary.
@@ -130,6 +280,10 @@ pub fn safe_truncate_index(s: &str, max: usize) -> usize {
 
 use async_trait::async_trait;
 use serde_json::Value;
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+use crate::api::control::{AgentEvent, EventSender};
 
 /// Information about a tool for display purposes.
 #[derive(Debug, Clone)]
@@ -155,11 +309,33 @@ pub trait Tool: Send + Sync {
     /// The `working_dir` is the default directory for relative paths.
     /// Tools can accept absolute paths to operate anywhere on the system.
     async fn execute(&self, args: Value, working_dir: &Path) -> anyhow::Result<String>;
+
+    /// Execute the tool, sending incremental output lines to `output` as they are produced.
+    ///
+    /// The final result is still returned. Tools without incremental output use the
+    /// default, which sends nothing and delegates to [`Tool::execute`].
+    async fn execute_streaming(
+        &self,
+        args: Value,
+        working_dir: &Path,
+        output: mpsc::UnboundedSender<String>,
+    ) -> anyhow::Result<String> {
+        drop(output);
+        self.execute(args, working_dir).await
+    }
 }
 
 /// Registry of available tools.
 pub struct ToolRegistry {
     tools: HashMap<String, Arc<dyn Tool>>,
+    /// Per-tool rate limits (tools without an entry are unlimited)
+    rate_limits: HashMap<String, Arc<rate_limit::RateLimiter>>,
+    /// When set, destructive calls wait for user approval (per-mission safe mode)
+    safe_mode: Option<Arc<dyn safe_mode::ConfirmAction>>,
+    /// Tools that may be listed and executed (by default all but the dangerous ones)
+    allowlist: permissions::ToolAllowlist,
+    /// When set, sees every call with its arguments, duration and outcome
+    observer: Option<Arc<dyn observer::ToolObserver>>,
 }
 
 impl ToolRegistry {
@@ -172,6 +348,10 @@ impl ToolRegistry {
     pub fn empty() -> Self {
         Self {
             tools: HashMap::new(),
+            rate_limits: HashMap::new(),
+            safe_mode: None,
+            allowlist: permissions::ToolAllowlist::default(),
+            observer: None,
         }
     }
 
@@ -183,8 +363,30 @@ impl ToolRegistry {
 
         // File operations
         tools.insert("read_file".to_string(), Arc::new(file_ops::ReadFile));
+        tools.insert(
+            "read_many_files".to_string(),
+            Arc::new(file_ops::ReadManyFiles),
+        );
         tools.insert("write_file".to_string(), Arc::new(file_ops::WriteFile));
+        tools.insert("edit_file".to_string(), Arc::new(file_ops::EditFile));
         tools.insert("delete_file".to_string(), Arc::new(file_ops::DeleteFile));
+        tools.insert("move_file".to_string(), Arc::new(file_ops::MoveFile));
+        tools.insert("copy_file".to_string(), Arc::new(file_ops::CopyFile));
+        tools.insert(
+            "normalize_file".to_string(),
+            Arc::new(normalize::NormalizeFile),
+        );
+
+        // Environment files
+        tools.insert("env_merge".to_string(), Arc::new(env_file::EnvMerge));
+        tools.insert("env_diff".to_string(), Arc::new(env_file::EnvDiff));
+
+        // Structured files
+        tools.insert("yaml_path".to_string(), Arc::new(yaml::YamlPath));
+        tools.insert(
+            "config_promote".to_string(),
+            Arc::new(config_promote::ConfigPromote),
+        );
 
         // Directory operations
         tools.insert(
@@ -202,16 +404,58 @@ impl ToolRegistry {
 
         // Terminal
         tools.insert("run_command".to_string(), Arc::new(terminal::RunCommand));
+        tools.insert(
+            "terminal_session".to_string(),
+            Arc::new(terminal_session::TerminalSession::default()),
+        );
+
+        // System
+        tools.insert(
+            "resource_usage".to_string(),
+            Arc::new(resources::ResourceUsage),
+        );
+        tools.insert("logs".to_string(), Arc::new(logs::Logs));
+
+        // Testing
+        tools.insert("run_tests".to_string(), Arc::new(test_runner::RunTests));
+        tools.insert(
+            "check_compile".to_string(),
+            Arc::new(compile_check::CheckCompile),
+        );
+
+        // Database
+        tools.insert(
+            "run_migrations".to_string(),
+            Arc::new(migrations::RunMigrations),
+        );
 
         // Search
         tools.insert("grep_search".to_string(), Arc::new(search::GrepSearch));
 
-        // Web (fetch only; web search removed in favor of OMO/Exa)
+        // Code metrics
+        tools.insert("code_metrics".to_string(), Arc::new(metrics::CodeMetrics));
+
+        // Web (fetch and HTTP requests; web search removed in favor of OMO/Exa)
         tools.insert("fetch_url".to_string(), Arc::new(web::FetchUrl));
+        tools.insert(
+            "http_request".to_string(),
+            Arc::new(web::HttpRequest::default()),
+        );
+        tools.insert(
+            "screenshot_url".to_string(),
+            Arc::new(browser::ScreenshotUrl),
+        );
+
+        // Documents
+        tools.insert(
+            "render_markdown".to_string(),
+            Arc::new(render::RenderMarkdown),
+        );
 
         // Frontend Tool UI (schemas for rich rendering in the dashboard)
         tools.insert("ui_optionList".to_string(), Arc::new(ui::UiOptionList));
         tools.insert("ui_dataTable".to_string(), Arc::new(ui::UiDataTable));
+        tools.insert("ui_form".to_string(), Arc::new(ui::UiForm));
 
         // Composite tools (higher-level workflow operations)
         tools.insert(
@@ -224,6 +468,15 @@ impl ToolRegistry {
             Arc::new(composite::PrepareProject),
         );
         tools.insert("debug_error".to_string(), Arc::new(composite::DebugError));
+        tools.insert("git_overview".to_string(), Arc::new(composite::GitOverview));
+
+        // Git
+        tools.insert("git_branch".to_string(), Arc::new(git::GitBranch));
+        tools.insert("git_checkout".to_string(), Arc::new(git::GitCheckout));
+        tools.insert("git_commit".to_string(), Arc::new(git::GitCommit));
+        tools.insert("git_remote".to_string(), Arc::new(git::GitRemote));
+        tools.insert("git_stash".to_string(), Arc::new(git::GitStash));
+        tools.insert("git_worktree".to_string(), Arc::new(git::GitWorktree));
 
         // Desktop automation (conditional on DESKTOP_ENABLED)
         if desktop::desktop_enabled() {
@@ -253,32 +506,118 @@ impl ToolRegistry {
             );
         }
 
-        // Mission control (allows agent to complete/fail missions)
-        let mission_tool: Arc<dyn Tool> = match mission_control {
-            Some(ctrl) => Arc::new(mission::CompleteMission::with_control(ctrl)),
-            None => Arc::new(mission::CompleteMission::new()),
+        // Planning
+        tools.insert("todo_add".to_string(), Arc::new(todo::TodoAdd));
+        tools.insert("todo_complete".to_string(), Arc::new(todo::TodoComplete));
+        tools.insert("todo_list".to_string(), Arc::new(todo::TodoList));
+
+        // Mission control (allows agent to check deliverables and complete/fail missions)
+        let (mission_tool, deliverable_tool): (Arc<dyn Tool>, Arc<dyn Tool>) = match mission_control
+        {
+            Some(ctrl) => (
+                Arc::new(mission::CompleteMission::with_control(ctrl.clone())),
+                Arc::new(mission::DeliverableStatus::with_control(ctrl)),
+            ),
+            None => (
+                Arc::new(mission::CompleteMission::new()),
+                Arc::new(mission::DeliverableStatus::new()),
+            ),
         };
         tools.insert("complete_mission".to_string(), mission_tool);
+        tools.insert("deliverable_status".to_string(), deliverable_tool);
 
         tracing::info!(
             "Registry {} complete with {} total tools",
             registry_id,
             tools.len()
         );
-        Self { tools }
+        Self::from_tools(tools)
+    }
+
+    /// Create a registry holding exactly `tools`, with the allowlist, rate
+    /// limits and observer configured in the environment.
+    pub fn from_tools(tools: HashMap<String, Arc<dyn Tool>>) -> Self {
+        let mut registry = Self {
+            tools,
+            allowlist: permissions::allowlist_from_env(),
+            observer: observer::observer_from_env(),
+            ..Self::empty()
+        };
+        for (name, per_minute) in rate_limit::rate_limits_from_env() {
+            registry.set_rate_limit(&name, per_minute);
+        }
+        registry
+    }
+
+    /// Enable safe mode: destructive tools wait for confirmation through `gate`.
+    pub fn with_safe_mode(mut self, gate: impl safe_mode::ConfirmAction + 'static) -> Self {
+        self.safe_mode = Some(Arc::new(gate));
+        self
+    }
+
+    /// Report every tool call to `observer` (replaces one configured from the environment).
+    pub fn with_observer(mut self, observer: Arc<dyn observer::ToolObserver>) -> Self {
+        self.observer = Some(observer);
+        self
+    }
+
+    /// Register a tool under its `name()`, replacing any tool already registered
+    /// under that name.
+    pub fn register(&mut self, tool: Arc<dyn Tool>) {
+        let name = tool.name().to_string();
+        if self.tools.insert(name.clone(), tool).is_some() {
+            tracing::warn!("Tool {} was already registered; replacing it", name);
+        }
+    }
+
+    /// Remove a tool by name, returning it if it was registered.
+    pub fn unregister(&mut self, name: &str) -> Option<Arc<dyn Tool>> {
+        self.rate_limits.remove(name);
+        self.tools.remove(name)
+    }
+
+    /// Restrict the registry to `names` (`"*"` = every tool except
+    /// [`permissions::DANGEROUS_TOOLS`], which must be named explicitly).
+    pub fn with_allowed(mut self, names: &[&str]) -> Self {
+        self.allowlist = permissions::ToolAllowlist::new(names);
+        self
+    }
+
+    /// Whether `name` may be executed under the current allowlist.
+    pub fn is_allowed(&self, name: &str) -> bool {
+        self.allowlist.allows(name)
+    }
+
+    /// Limit `name` to `per_minute` calls per minute. Over-limit calls fail with a throttled error.
+    pub fn set_rate_limit(&mut self, name: &str, per_minute: u32) {
+        self.rate_limits.insert(
+            name.to_string(),
+            Arc::new(rate_limit::RateLimiter::new(per_minute)),
+        );
     }
 
-    /// List all available tools.
+    /// List all allowed tools.
     pub fn list_tools(&self) -> Vec<ToolInfo> {
         self.tools
-            .values()
-            .map(|t| ToolInfo {
+            .iter()
+            .filter(|(name, _)| self.is_allowed(name))
+            .map(|(_, t)| ToolInfo {
                 name: t.name().to_string(),
                 description: t.description().to_string(),
             })
             .collect()
     }
 
+    /// All allowed tools, for callers that need more than names and
+    /// descriptions (e.g. parameter schemas).
+    pub fn allowed_tools(&self) -> Vec<Arc<dyn Tool>> {
+        self.tools
+            .iter()
+            .filter(|(name, _)| self.is_allowed(name))
+            .map(|(_, tool)| Arc::clone(tool))
+            .collect()
+    }
+
     /// Check if a tool exists by name.
     pub fn has_tool(&self, name: &str) -> bool {
         self.tools.contains_key(name)
@@ -293,13 +632,121 @@ impl ToolRegistry {
         name: &str,
         args: Value,
         working_dir: &Path,
+    ) -> anyhow::Result<String> {
+        self.execute_inner(name, args, working_dir, None).await
+    }
+
+    /// Execute a tool by name, sending its incremental output lines to `output`
+    /// (for callers outside the control session, such as the MCP host).
+    pub async fn execute_with_output(
+        &self,
+        name: &str,
+        args: Value,
+        working_dir: &Path,
+        output: mpsc::UnboundedSender<String>,
+    ) -> anyhow::Result<String> {
+        self.execute_inner(name, args, working_dir, Some(output))
+            .await
+    }
+
+    /// Execute a tool by name, forwarding its incremental output as `Thinking` events
+    /// so long-running commands show progress in real time.
+    pub async fn execute_streaming(
+        &self,
+        name: &str,
+        args: Value,
+        working_dir: &Path,
+        events_tx: &EventSender,
+        mission_id: Option<Uuid>,
+    ) -> anyhow::Result<String> {
+        let (tx, mut rx) = mpsc::unbounded_channel::<String>();
+        let events_tx = events_tx.clone();
+        let forwarder = tokio::spawn(async move {
+            while let Some(line) = rx.recv().await {
+                let _ = events_tx.send(AgentEvent::Thinking {
+                    content: line,
+                    done: false,
+                    mission_id,
+                });
+            }
+        });
+        let result = self.execute_inner(name, args, working_dir, Some(tx)).await;
+        let _ = forwarder.await;
+        result
+    }
+
+    async fn execute_inner(
+        &self,
+        name: &str,
+        args: Value,
+        working_dir: &Path,
+        output: Option<mpsc::UnboundedSender<String>>,
+    ) -> anyhow::Result<String> {
+        let Some(observer) = &self.observer else {
+            return self.run_tool(name, args, working_dir, output).await;
+        };
+        observer.on_call(name, &args);
+        let started = std::time::Instant::now();
+        let result = self.run_tool(name, args.clone(), working_dir, output).await;
+        observer.on_result(name, &args, started.elapsed(), result.is_ok());
+        result
+    }
+
+    async fn run_tool(
+        &self,
+        name: &str,
+        args: Value,
+        working_dir: &Path,
+        output: Option<mpsc::UnboundedSender<String>>,
     ) -> anyhow::Result<String> {
         let tool = self
             .tools
             .get(name)
             .ok_or_else(|| anyhow::anyhow!("Unknown tool: {}", name))?;
 
-        tool.execute(args, working_dir).await
+        if !self.is_allowed(name) {
+            tracing::warn!("Tool {} blocked by allowlist", name);
+            return Err(anyhow::anyhow!(
+                "Permission denied: tool '{}' is not enabled in this workspace",
+                name
+            ));
+        }
+
+        if let Some(limiter) = self.rate_limits.get(name) {
+            if let Err(retry_after) = limiter.try_acquire() {
+                tracing::warn!("Tool {} throttled by rate limit", name);
+                return Err(anyhow::anyhow!(
+                    "Rate limit exceeded for tool '{}'. Retry in {}s.",
+                    name,
+                    retry_after.as_secs().max(1)
+                ));
+            }
+        }
+
+        if let Some(gate) = &self.safe_mode {
+            if let Some(reason) = safe_mode::destructive_reason(name, &args) {
+                let approved = gate.confirm(name, &args, &reason).await.map_err(|e| {
+                    anyhow::anyhow!(
+                        "Safe mode could not confirm '{}' ({}), so it was not performed: {}",
+                        name,
+                        reason,
+                        e
+                    )
+                })?;
+                if !approved {
+                    tracing::info!("Safe mode: user declined {}", name);
+                    return Ok(format!(
+                        "The user declined this action ({}). It was not performed.",
+                        reason
+                    ));
+                }
+            }
+        }
+
+        match output {
+            Some(output) => tool.execute_streaming(args, working_dir, output).await,
+            None => tool.execute(args, working_dir).await,
+        }
     }
 }
 
@@ -308,3 +755,347 @@ impl Default for ToolRegistry {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Echo(&'static str);
+
+    #[async_trait]
+    impl Tool for Echo {
+        fn name(&self) -> &str {
+            "echo"
+        }
+
+        fn description(&self) -> &str {
+            self.0
+        }
+
+        fn parameters_schema(&self) -> Value {
+            serde_json::json!({"type": "object", "properties": {}})
+        }
+
+        async fn execute(&self, _args: Value, _working_dir: &Path) -> anyhow::Result<String> {
+            Ok(self.0.to_string())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_register_and_unregister_custom_tool() {
+        let mut registry = ToolRegistry::empty();
+        registry.register(Arc::new(Echo("first")));
+        registry.register(Arc::new(Echo("second")));
+
+        let tools = registry.list_tools();
+        assert_eq!(tools.len(), 1);
+        assert_eq!(tools[0].description, "second");
+        let out = registry
+            .execute("echo", serde_json::json!({}), Path::new("."))
+            .await
+            .unwrap();
+        assert_eq!(out, "second");
+
+        assert!(registry.unregister("echo").is_some());
+        assert!(!registry.has_tool("echo"));
+        assert!(registry.unregister("echo").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_allowlist_gates_listing_and_execution() {
+        let mut registry = ToolRegistry::empty().with_allowed(&["*"]);
+        registry
+            .tools
+            .insert("ui_dataTable".to_string(), Arc::new(ui::UiDataTable));
+        registry
+            .tools
+            .insert("run_command".to_string(), Arc::new(terminal::RunCommand));
+
+        let listed: Vec<String> = registry.list_tools().into_iter().map(|t| t.name).collect();
+        assert_eq!(listed, vec!["ui_dataTable".to_string()]);
+
+        let err = registry
+            .execute(
+                "run_command",
+                serde_json::json!({"command": "true"}),
+                Path::new("."),
+            )
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("Permission denied"));
+
+        let registry = registry.with_allowed(&["*", "run_command"]);
+        assert!(registry.is_allowed("run_command"));
+        assert!(!ToolRegistry::empty().is_allowed("run_command"));
+        assert_eq!(registry.list_tools().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_from_tools_gates_execution() {
+        let mut tools: HashMap<String, Arc<dyn Tool>> = HashMap::new();
+        tools.insert("echo".to_string(), Arc::new(Echo("hi")));
+        tools.insert("delete_file".to_string(), Arc::new(file_ops::DeleteFile));
+        let registry = ToolRegistry::from_tools(tools);
+
+        let listed: Vec<String> = registry
+            .allowed_tools()
+            .iter()
+            .map(|t| t.name().to_string())
+            .collect();
+        assert_eq!(listed, vec!["echo".to_string()]);
+        let err = registry
+            .execute(
+                "delete_file",
+                serde_json::json!({"path": "x"}),
+                Path::new("."),
+            )
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("Permission denied"));
+    }
+
+    #[tokio::test]
+    async fn test_execute_with_output_streams_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut registry = ToolRegistry::empty().with_allowed(&["run_command"]);
+        registry.register(Arc::new(terminal::RunCommand));
+
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        registry
+            .execute_with_output(
+                "run_command",
+                serde_json::json!({"command": "echo one; echo two"}),
+                dir.path(),
+                tx,
+            )
+            .await
+            .unwrap();
+        let mut lines = Vec::new();
+        while let Some(line) = rx.recv().await {
+            lines.push(line);
+        }
+        assert_eq!(lines, vec!["one", "two"]);
+    }
+
+    #[tokio::test]
+    async fn test_observer_records_calls() {
+        let dir = tempfile::tempdir().unwrap();
+        let log = dir.path().join("logs/tools.jsonl");
+        let mut registry = ToolRegistry::empty()
+            .with_observer(Arc::new(observer::JsonlFileObserver::open(&log).unwrap()));
+        registry.register(Arc::new(Echo("hello")));
+
+        registry
+            .execute("echo", serde_json::json!({"text": "hi"}), dir.path())
+            .await
+            .unwrap();
+        assert!(registry
+            .execute("missing", serde_json::json!({}), dir.path())
+            .await
+            .is_err());
+
+        let records: Vec<Value> = std::fs::read_to_string(&log)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        let summary: Vec<(&str, &str, Option<bool>)> = records
+            .iter()
+            .map(|r| {
+                (
+                    r["event"].as_str().unwrap(),
+                    r["tool"].as_str().unwrap(),
+                    r["success"].as_bool(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("call", "echo", None),
+                ("result", "echo", Some(true)),
+                ("call", "missing", None),
+                ("result", "missing", Some(false)),
+            ]
+        );
+        assert_eq!(records[1]["args"], serde_json::json!({"text": "hi"}));
+        assert!(records[1]["duration_ms"].is_u64());
+    }
+
+    #[tokio::test]
+    async fn test_observer_redacts_credential_headers() {
+        let dir = tempfile::tempdir().unwrap();
+        let log = dir.path().join("tools.jsonl");
+        let mut registry = ToolRegistry::empty()
+            .with_observer(Arc::new(observer::JsonlFileObserver::open(&log).unwrap()));
+        registry.register(Arc::new(Echo("hello")));
+
+        let args = serde_json::json!({
+            "url": "https://example.com/?echo=sk-live-1234",
+            "headers": {"Authorization": "Bearer sk-live-1234", "Accept": "text/plain"},
+        });
+        registry.execute("echo", args, dir.path()).await.unwrap();
+
+        let written = std::fs::read_to_string(&log).unwrap();
+        assert!(!written.contains("sk-live-1234"), "{}", written);
+        let record: Value = serde_json::from_str(written.lines().next().unwrap()).unwrap();
+        assert_eq!(record["args"]["headers"]["Authorization"], "[REDACTED]");
+        assert_eq!(record["args"]["headers"]["Accept"], "text/plain");
+    }
+
+    #[tokio::test]
+    async fn test_rate_limited_tool_is_throttled() {
+        let mut registry = ToolRegistry::empty();
+        registry
+            .tools
+            .insert("ui_dataTable".to_string(), Arc::new(ui::UiDataTable));
+        registry.set_rate_limit("ui_dataTable", 2);
+
+        let dir = Path::new(".");
+        let args = serde_json::json!({"id": "t", "columns": [{}], "rows": []});
+        assert!(registry
+            .execute("ui_dataTable", args.clone(), dir)
+            .await
+            .is_ok());
+        assert!(registry
+            .execute("ui_dataTable", args.clone(), dir)
+            .await
+            .is_ok());
+        let err = registry
+            .execute("ui_dataTable", args, dir)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("Rate limit exceeded"));
+    }
+
+    #[tokio::test]
+    async fn test_safe_mode_delete_waits_for_approval() {
+        use crate::api::control::{AgentEvent, FrontendToolHub};
+
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("doomed.txt");
+        std::fs::write(&file, "bye").unwrap();
+
+        let hub = Arc::new(FrontendToolHub::new());
+        let (events_tx, mut events_rx) = EventSender::channel(16);
+        let mut registry = ToolRegistry::empty()
+            .with_allowed(&["*", "delete_file"])
+            .with_safe_mode(safe_mode::SafeModeGate::new(
+                Arc::clone(&hub),
+                events_tx,
+                None,
+            ));
+        registry
+            .tools
+            .insert("delete_file".to_string(), Arc::new(file_ops::DeleteFile));
+
+        let work_dir = dir.path().to_path_buf();
+        let pending = tokio::spawn(async move {
+            registry
+                .execute(
+                    "delete_file",
+                    serde_json::json!({"path": "doomed.txt"}),
+                    &work_dir,
+                )
+                .await
+        });
+
+        let tool_call_id = match events_rx.recv().await.unwrap().event {
+            AgentEvent::ToolCall {
+                tool_call_id, name, ..
+            } => {
+                assert_eq!(name, safe_mode::CONFIRM_TOOL_NAME);
+                tool_call_id
+            }
+            other => panic!("unexpected event: {:?}", other.event_name()),
+        };
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        assert!(file.exists(), "delete must wait for approval");
+
+        hub.resolve(&tool_call_id, serde_json::json!({"approved": true}))
+            .await
+            .unwrap();
+        pending.await.unwrap().unwrap();
+        assert!(!file.exists());
+    }
+
+    #[test]
+    fn test_resolve_within_workspace_rejects_escapes() {
+        let outer = tempfile::tempdir().unwrap();
+        let workspace = outer.path().join("ws");
+        std::fs::create_dir_all(workspace.join("src")).unwrap();
+        std::fs::write(outer.path().join("secret.txt"), "").unwrap();
+
+        let inside = resolve_within_workspace(&workspace, "src/../new/file.txt").unwrap();
+        assert_eq!(
+            inside,
+            workspace.canonicalize().unwrap().join("new/file.txt")
+        );
+        for path in [
+            "../secret.txt",
+            "src/../../secret.txt",
+            "../../../etc/passwd",
+            "/etc/passwd",
+        ] {
+            assert!(
+                resolve_within_workspace(&workspace, path).is_err(),
+                "{}",
+                path
+            );
+        }
+
+        // Symlinks are judged by where they point, dangling or not.
+        std::os::unix::fs::symlink(outer.path(), workspace.join("up")).unwrap();
+        std::os::unix::fs::symlink(outer.path().join("gone.txt"), workspace.join("dangling"))
+            .unwrap();
+        std::os::unix::fs::symlink("src", workspace.join("local")).unwrap();
+        assert!(resolve_within_workspace(&workspace, "up/secret.txt").is_err());
+        assert!(resolve_within_workspace(&workspace, "dangling").is_err());
+        assert!(resolve_within_workspace(&workspace, "local/main.rs").is_ok());
+
+        let policy = PathPolicy::default();
+        assert!(policy.resolve("up/secret.txt", &workspace).is_err());
+        assert!(policy.resolve("src/main.rs", &workspace).is_ok());
+    }
+
+    #[test]
+    fn test_relative_paths_resolve_against_configured_base() {
+        let dir = tempfile::tempdir().unwrap();
+        let workspace = dir.path();
+        let policy = PathPolicy {
+            base_dir: Some(PathBuf::from("output")),
+            allow_workspace_escape: false,
+        };
+
+        let resolution = policy.resolve("./foo.txt", workspace).unwrap();
+        assert_eq!(
+            resolution.resolved,
+            workspace.join("output").join("foo.txt")
+        );
+        assert!(!resolution.is_outside_workspace);
+
+        // Absolute paths are fine inside the workspace, rejected outside it.
+        let inside = workspace.join("notes.md");
+        let resolution = policy.resolve(inside.to_str().unwrap(), workspace).unwrap();
+        assert!(resolution.was_absolute);
+        assert_eq!(resolution.resolved, inside);
+        assert!(policy.resolve("/etc/hosts", workspace).is_err());
+        assert!(policy.resolve("../../escape.txt", workspace).is_err());
+        assert!(policy.resolve("../sibling.txt", workspace).is_ok());
+
+        // Escape allowed: absolute paths reach the host.
+        let escape = PathPolicy {
+            base_dir: None,
+            allow_workspace_escape: true,
+        };
+        let resolution = escape.resolve("/etc/hosts", workspace).unwrap();
+        assert!(resolution.is_outside_workspace);
+        assert_eq!(
+            PathPolicy::default()
+                .resolve("foo.txt", workspace)
+                .unwrap()
+                .resolved,
+            workspace.join("foo.txt")
+        );
+    }
+}
@@ -0,0 +1,335 @@
+//! Normalize a text file's line endings and (optionally) transcode it to UTF-8.
+//!
+//! The encoding is detected heuristically: byte-order marks first, then UTF-8
+//! validity, falling back to Windows-1252 (a superset of Latin-1 for printable
+//! text). Files that look binary are refused.
+
+use std::path::Path;
+
+use async_trait::async_trait;
+use serde_json::{json, Value};
+
+use super::{resolve_path, Tool};
+
+/// Windows-1252 characters for bytes 0x80..=0x9F (`None` = unassigned).
+const CP1252_HIGH: [Option<char>; 32] = [
+    Some('€'),
+    None,
+    Some('‚'),
+    Some('ƒ'),
+    Some('„'),
+    Some('…'),
+    Some('†'),
+    Some('‡'),
+    Some('ˆ'),
+    Some('‰'),
+    Some('Š'),
+    Some('‹'),
+    Some('Œ'),
+    None,
+    Some('Ž'),
+    None,
+    None,
+    Some('\u{2018}'),
+    Some('\u{2019}'),
+    Some('\u{201C}'),
+    Some('\u{201D}'),
+    Some('•'),
+    Some('–'),
+    Some('—'),
+    Some('˜'),
+    Some('™'),
+    Some('š'),
+    Some('›'),
+    Some('œ'),
+    None,
+    Some('ž'),
+    Some('Ÿ'),
+];
+
+/// A detected text encoding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextEncoding {
+    Utf8,
+    Utf8Bom,
+    Utf16Le,
+    Utf16Be,
+    Windows1252,
+}
+
+impl TextEncoding {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Utf8 => "utf-8",
+            Self::Utf8Bom => "utf-8-bom",
+            Self::Utf16Le => "utf-16le",
+            Self::Utf16Be => "utf-16be",
+            Self::Windows1252 => "windows-1252",
+        }
+    }
+
+    /// Encode text, re-adding the byte-order mark where the encoding has one.
+    fn encode(&self, text: &str) -> Vec<u8> {
+        match self {
+            Self::Utf8 => text.as_bytes().to_vec(),
+            Self::Utf8Bom => [&[0xEF, 0xBB, 0xBF][..], text.as_bytes()].concat(),
+            Self::Utf16Le => [0xFF, 0xFE]
+                .into_iter()
+                .chain(text.encode_utf16().flat_map(u16::to_le_bytes))
+                .collect(),
+            Self::Utf16Be => [0xFE, 0xFF]
+                .into_iter()
+                .chain(text.encode_utf16().flat_map(u16::to_be_bytes))
+                .collect(),
+            // Decoded from Windows-1252, so every char maps back to one byte.
+            Self::Windows1252 => text
+                .chars()
+                .map(|c| match CP1252_HIGH.iter().position(|&h| h == Some(c)) {
+                    Some(idx) => 0x80 + idx as u8,
+                    None => c as u32 as u8,
+                })
+                .collect(),
+        }
+    }
+}
+
+fn decode_utf16(bytes: &[u8], from_bytes: fn([u8; 2]) -> u16) -> Option<String> {
+    let pairs = bytes.chunks_exact(2);
+    if !pairs.remainder().is_empty() {
+        return None;
+    }
+    let units: Vec<u16> = pairs.map(|pair| from_bytes([pair[0], pair[1]])).collect();
+    String::from_utf16(&units).ok()
+}
+
+/// Whether decoded text looks like binary data rather than text.
+fn looks_binary(text: &str) -> bool {
+    let control = text
+        .chars()
+        .filter(|c| c.is_control() && !matches!(c, '\n' | '\r' | '\t' | '\u{c}'))
+        .count();
+    text.contains('\0') || control * 10 > text.chars().count().max(1)
+}
+
+/// Detect the encoding of `bytes` and decode them.
+pub fn decode_text(bytes: &[u8]) -> anyhow::Result<(TextEncoding, String)> {
+    let decoded = if let Some(rest) = bytes.strip_prefix(&[0xEF, 0xBB, 0xBF]) {
+        std::str::from_utf8(rest)
+            .ok()
+            .map(|s| (TextEncoding::Utf8Bom, s.to_string()))
+    } else if let Some(rest) = bytes.strip_prefix(&[0xFF, 0xFE]) {
+        decode_utf16(rest, u16::from_le_bytes).map(|s| (TextEncoding::Utf16Le, s))
+    } else if let Some(rest) = bytes.strip_prefix(&[0xFE, 0xFF]) {
+        decode_utf16(rest, u16::from_be_bytes).map(|s| (TextEncoding::Utf16Be, s))
+    } else if let Ok(s) = std::str::from_utf8(bytes) {
+        Some((TextEncoding::Utf8, s.to_string()))
+    } else {
+        bytes
+            .iter()
+            .map(|&b| match b {
+                0x80..=0x9F => CP1252_HIGH[(b - 0x80) as usize],
+                _ => Some(b as char),
+            })
+            .collect::<Option<String>>()
+            .map(|s| (TextEncoding::Windows1252, s))
+    };
+
+    match decoded {
+        Some((encoding, text)) if !looks_binary(&text) => Ok((encoding, text)),
+        _ => Err(anyhow::anyhow!(
+            "File appears to be binary or uses an unsupported encoding; refusing to normalize it"
+        )),
+    }
+}
+
+/// Line ending counts found in a text.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct LineEndings {
+    pub crlf: usize,
+    pub lf: usize,
+    pub cr: usize,
+}
+
+pub fn count_line_endings(text: &str) -> LineEndings {
+    let mut counts = LineEndings::default();
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\r' if chars.peek() == Some(&'\n') => {
+                chars.next();
+                counts.crlf += 1;
+            }
+            '\r' => counts.cr += 1,
+            '\n' => counts.lf += 1,
+            _ => {}
+        }
+    }
+    counts
+}
+
+/// Rewrite every line ending (CRLF, LF or lone CR) as `eol`.
+pub fn normalize_line_endings(text: &str, eol: &str) -> String {
+    text.replace("\r\n", "\n")
+        .replace('\r', "\n")
+        .replace('\n', eol)
+}
+
+/// Convert a file's line endings and optionally transcode it to UTF-8.
+pub struct NormalizeFile;
+
+#[async_trait]
+impl Tool for NormalizeFile {
+    fn name(&self) -> &str {
+        "normalize_file"
+    }
+
+    fn description(&self) -> &str {
+        "Normalize a text file: convert all line endings (CRLF, LF, CR) to LF or CRLF and optionally transcode it to UTF-8. The current encoding is detected automatically (UTF-8, UTF-8 BOM, UTF-16, Windows-1252); binary files are refused. Reports what changed."
+    }
+
+    fn parameters_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "path": {
+                    "type": "string",
+                    "description": "Path to the file. Use relative paths (e.g., 'src/main.rs') for workspace files."
+                },
+                "line_ending": {
+                    "type": "string",
+                    "enum": ["lf", "crlf"],
+                    "description": "Target line ending (default: lf)"
+                },
+                "to_utf8": {
+                    "type": "boolean",
+                    "description": "Transcode the file to UTF-8 without BOM (default: false keeps the detected encoding)"
+                }
+            },
+            "required": ["path"]
+        })
+    }
+
+    async fn execute(&self, args: Value, working_dir: &Path) -> anyhow::Result<String> {
+        let path = args["path"]
+            .as_str()
+            .ok_or_else(|| anyhow::anyhow!("Missing 'path' argument"))?;
+        let (line_ending, eol) = match args["line_ending"].as_str().unwrap_or("lf") {
+            "lf" | "LF" => ("lf", "\n"),
+            "crlf" | "CRLF" => ("crlf", "\r\n"),
+            other => {
+                return Err(anyhow::anyhow!(
+                    "Invalid line_ending '{}': expected 'lf' or 'crlf'",
+                    other
+                ))
+            }
+        };
+        let to_utf8 = args["to_utf8"].as_bool().unwrap_or(false);
+
+        let resolved = resolve_path(path, working_dir)?.resolved;
+        let bytes = tokio::fs::read(&resolved).await.map_err(|e| {
+            anyhow::anyhow!(
+                "Failed to read {} (resolved to: {}): {}",
+                path,
+                resolved.display(),
+                e
+            )
+        })?;
+
+        let (detected, text) = decode_text(&bytes)?;
+        let before = count_line_endings(&text);
+        let target = if to_utf8 {
+            TextEncoding::Utf8
+        } else {
+            detected
+        };
+        let output = target.encode(&normalize_line_endings(&text, eol));
+        let changed = output != bytes;
+        if changed {
+            tokio::fs::write(&resolved, &output).await?;
+        }
+
+        Ok(json!({
+            "path": resolved.display().to_string(),
+            "changed": changed,
+            "encoding_detected": detected.as_str(),
+            "encoding_written": target.as_str(),
+            "line_endings_before": {
+                "crlf": before.crlf,
+                "lf": before.lf,
+                "cr": before.cr,
+            },
+            "line_ending": line_ending,
+        })
+        .to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_crlf_to_lf_preserves_content() {
+        let dir = tempfile::tempdir().unwrap();
+        let original = "line one\r\nline two\r\n\r\nlast\rline";
+        std::fs::write(dir.path().join("notes.txt"), original).unwrap();
+
+        let out = NormalizeFile
+            .execute(json!({ "path": "notes.txt" }), dir.path())
+            .await
+            .unwrap();
+        let report: Value = serde_json::from_str(&out).unwrap();
+        assert_eq!(report["changed"], true);
+        assert_eq!(report["encoding_detected"], "utf-8");
+        assert_eq!(
+            report["line_endings_before"],
+            json!({"crlf": 3, "lf": 0, "cr": 1})
+        );
+
+        let normalized = std::fs::read_to_string(dir.path().join("notes.txt")).unwrap();
+        assert_eq!(normalized, "line one\nline two\n\nlast\nline");
+        assert_eq!(
+            normalized.split('\n').collect::<Vec<_>>(),
+            ["line one", "line two", "", "last", "line"]
+        );
+
+        // Already normalized: nothing to write.
+        let out = NormalizeFile
+            .execute(json!({ "path": "notes.txt" }), dir.path())
+            .await
+            .unwrap();
+        assert!(out.contains("\"changed\":false"));
+    }
+
+    #[tokio::test]
+    async fn test_transcode_windows1252_and_refuse_binary() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("quote.txt"), b"\x93caf\xe9\x94\r\n").unwrap();
+
+        let out = NormalizeFile
+            .execute(
+                json!({ "path": "quote.txt", "to_utf8": true, "line_ending": "crlf" }),
+                dir.path(),
+            )
+            .await
+            .unwrap();
+        let report: Value = serde_json::from_str(&out).unwrap();
+        assert_eq!(report["encoding_detected"], "windows-1252");
+        assert_eq!(report["encoding_written"], "utf-8");
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("quote.txt")).unwrap(),
+            "\u{201C}café\u{201D}\r\n"
+        );
+
+        std::fs::write(
+            dir.path().join("blob.bin"),
+            [0x7f, b'E', b'L', b'F', 0, 0, 1],
+        )
+        .unwrap();
+        let err = NormalizeFile
+            .execute(json!({ "path": "blob.bin" }), dir.path())
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("binary"));
+    }
+}
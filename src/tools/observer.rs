@@ -0,0 +1,96 @@
+//! Structured records of tool calls.
+//!
+//! A [`ToolObserver`] set with [`super::ToolRegistry::with_observer`] sees every
+//! call the registry executes: the arguments before it runs, then how long it
+//! took and whether it succeeded. Unlike tracing logs these are meant to be
+//! machine-readable, so runs can be audited, replayed and diffed.
+//!
+//! [`JsonlFileObserver`] appends one JSON object per line to a file. Set
+//! `OPEN_AGENT_TOOL_LOG` to a path to enable it for every registry.
+
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use serde_json::{json, Value};
+
+use super::web::redact_header_secrets;
+
+/// Hook invoked around every tool call executed by a [`super::ToolRegistry`].
+///
+/// Calls rejected by the registry itself (unknown tool, allowlist, rate limit)
+/// are reported too, as failures.
+pub trait ToolObserver: Send + Sync {
+    /// A tool is about to run.
+    fn on_call(&self, _name: &str, _args: &Value) {}
+
+    /// A tool finished (`success` is false when it returned an error).
+    fn on_result(&self, name: &str, args: &Value, duration: Duration, success: bool);
+}
+
+/// Appends `call` and `result` records as JSON lines to a file.
+///
+/// Credential headers in the arguments (as passed to `fetch_url`) are redacted.
+pub struct JsonlFileObserver {
+    file: Mutex<File>,
+}
+
+impl JsonlFileObserver {
+    /// Open `path` for appending, creating it (and its parent directories) if needed.
+    pub fn open(path: &Path) -> std::io::Result<Self> {
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Self {
+            file: Mutex::new(file),
+        })
+    }
+
+    fn write(&self, record: Value) {
+        let mut line = record.to_string();
+        line.push('\n');
+        let mut file = self.file.lock().unwrap_or_else(|e| e.into_inner());
+        if let Err(e) = file.write_all(line.as_bytes()) {
+            tracing::warn!("Failed to write tool log record: {}", e);
+        }
+    }
+}
+
+impl ToolObserver for JsonlFileObserver {
+    fn on_call(&self, name: &str, args: &Value) {
+        self.write(json!({
+            "ts": chrono::Utc::now().to_rfc3339(),
+            "event": "call",
+            "tool": name,
+            "args": redact_header_secrets(args),
+        }));
+    }
+
+    fn on_result(&self, name: &str, args: &Value, duration: Duration, success: bool) {
+        self.write(json!({
+            "ts": chrono::Utc::now().to_rfc3339(),
+            "event": "result",
+            "tool": name,
+            "args": redact_header_secrets(args),
+            "duration_ms": duration.as_millis() as u64,
+            "success": success,
+        }));
+    }
+}
+
+/// JSONL observer configured by `OPEN_AGENT_TOOL_LOG`, if set and openable.
+pub fn observer_from_env() -> Option<Arc<dyn ToolObserver>> {
+    let path = std::env::var("OPEN_AGENT_TOOL_LOG")
+        .ok()
+        .filter(|v| !v.trim().is_empty())?;
+    match JsonlFileObserver::open(Path::new(path.trim())) {
+        Ok(observer) => Some(Arc::new(observer)),
+        Err(e) => {
+            tracing::warn!("Cannot open tool log {}: {}", path, e);
+            None
+        }
+    }
+}
@@ -0,0 +1,95 @@
+//! Per-tool permission gating.
+//!
+//! Only listed tools can be called; `*` enables every tool except the
+//! [`DANGEROUS_TOOLS`], which must always be named explicitly. Without a
+//! configured allowlist the policy is `*`, so dangerous tools are off by default.
+//!
+//! Configure via `OPEN_AGENT_TOOL_ALLOWLIST`, a comma-separated list of tool
+//! names (e.g. `read_file,grep_search` for a read-only agent, `*,run_command`
+//! to also allow shell commands).
+
+use std::collections::HashSet;
+
+/// Tools that stay disabled under an allowlist unless explicitly named.
+pub const DANGEROUS_TOOLS: &[&str] = &[
+    "run_command",
+    "terminal_session",
+    "delete_file",
+    "git_commit",
+    "git_remote",
+];
+
+/// Wildcard entry enabling every non-dangerous tool.
+const WILDCARD: &str = "*";
+
+/// Set of tools a registry may execute.
+#[derive(Debug, Clone)]
+pub struct ToolAllowlist {
+    names: HashSet<String>,
+    all_safe: bool,
+}
+
+impl ToolAllowlist {
+    pub fn new(names: &[&str]) -> Self {
+        let mut allowlist = Self {
+            names: HashSet::new(),
+            all_safe: false,
+        };
+        for name in names.iter().map(|n| n.trim()).filter(|n| !n.is_empty()) {
+            if name == WILDCARD {
+                allowlist.all_safe = true;
+            } else {
+                allowlist.names.insert(name.to_string());
+            }
+        }
+        allowlist
+    }
+
+    /// Parse a comma-separated list of tool names.
+    pub fn parse(spec: &str) -> Self {
+        Self::new(&spec.split(',').collect::<Vec<_>>())
+    }
+
+    pub fn allows(&self, name: &str) -> bool {
+        self.names.contains(name) || (self.all_safe && !DANGEROUS_TOOLS.contains(&name))
+    }
+}
+
+impl Default for ToolAllowlist {
+    /// Every tool except the [`DANGEROUS_TOOLS`].
+    fn default() -> Self {
+        Self::new(&[WILDCARD])
+    }
+}
+
+/// Load the allowlist from `OPEN_AGENT_TOOL_ALLOWLIST` (the default when unset).
+pub fn allowlist_from_env() -> ToolAllowlist {
+    std::env::var("OPEN_AGENT_TOOL_ALLOWLIST")
+        .ok()
+        .filter(|v| !v.trim().is_empty())
+        .map(|v| ToolAllowlist::parse(&v))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wildcard_excludes_dangerous_tools() {
+        let allowlist = ToolAllowlist::parse("*, delete_file");
+        assert!(allowlist.allows("read_file"));
+        assert!(allowlist.allows("delete_file"));
+        assert!(!allowlist.allows("run_command"));
+
+        let read_only = ToolAllowlist::new(&["read_file", "grep_search"]);
+        assert!(read_only.allows("grep_search"));
+        assert!(!read_only.allows("write_file"));
+
+        let default = ToolAllowlist::default();
+        assert!(default.allows("write_file"));
+        for name in DANGEROUS_TOOLS {
+            assert!(!default.allows(name), "{}", name);
+        }
+    }
+}
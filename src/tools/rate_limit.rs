@@ -0,0 +1,100 @@
+//! Per-tool rate limiting.
+//!
+//! Protects third-party quotas (e.g. `fetch_url`) from a misbehaving agent.
+//! Limits are expressed in requests per minute and enforced with a sliding
+//! one-minute window. Tools without a configured limit are unlimited.
+//!
+//! Configure via `OPEN_AGENT_TOOL_RATE_LIMITS`, a comma-separated list of
+//! `tool_name=requests_per_minute` pairs (e.g. `fetch_url=30,run_command=120`).
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+const WINDOW: Duration = Duration::from_secs(60);
+
+/// Sliding-window limiter for a single tool.
+#[derive(Debug)]
+pub struct RateLimiter {
+    per_minute: u32,
+    calls: Mutex<VecDeque<Instant>>,
+}
+
+impl RateLimiter {
+    pub fn new(per_minute: u32) -> Self {
+        Self {
+            per_minute,
+            calls: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    /// Record a call if allowed. Returns how long to wait before retrying otherwise.
+    pub fn try_acquire(&self) -> Result<(), Duration> {
+        self.try_acquire_at(Instant::now())
+    }
+
+    fn try_acquire_at(&self, now: Instant) -> Result<(), Duration> {
+        let mut calls = self.calls.lock().unwrap_or_else(|e| e.into_inner());
+        while let Some(&oldest) = calls.front() {
+            if now.duration_since(oldest) >= WINDOW {
+                calls.pop_front();
+            } else {
+                break;
+            }
+        }
+        if calls.len() < self.per_minute as usize {
+            calls.push_back(now);
+            return Ok(());
+        }
+        let oldest = calls.front().copied().unwrap_or(now);
+        Err(WINDOW.saturating_sub(now.duration_since(oldest)))
+    }
+}
+
+/// Parse `tool=rpm,tool2=rpm` into a map, skipping malformed entries.
+pub fn parse_rate_limits(spec: &str) -> HashMap<String, u32> {
+    let mut limits = HashMap::new();
+    for entry in spec.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+        match entry.split_once('=') {
+            Some((name, rpm)) => match rpm.trim().parse::<u32>() {
+                Ok(rpm) if rpm > 0 => {
+                    limits.insert(name.trim().to_string(), rpm);
+                }
+                _ => tracing::warn!("Ignoring invalid tool rate limit: {}", entry),
+            },
+            None => tracing::warn!("Ignoring invalid tool rate limit: {}", entry),
+        }
+    }
+    limits
+}
+
+/// Load limits from `OPEN_AGENT_TOOL_RATE_LIMITS` (empty when unset).
+pub fn rate_limits_from_env() -> HashMap<String, u32> {
+    std::env::var("OPEN_AGENT_TOOL_RATE_LIMITS")
+        .map(|v| parse_rate_limits(&v))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_rate_limits() {
+        let limits = parse_rate_limits("fetch_url=30, run_command = 5,bad,zero=0");
+        assert_eq!(limits.len(), 2);
+        assert_eq!(limits["fetch_url"], 30);
+        assert_eq!(limits["run_command"], 5);
+    }
+
+    #[test]
+    fn test_window_expires() {
+        let limiter = RateLimiter::new(1);
+        let start = Instant::now();
+        assert!(limiter.try_acquire_at(start).is_ok());
+        assert!(limiter
+            .try_acquire_at(start + Duration::from_secs(30))
+            .is_err());
+        assert!(limiter.try_acquire_at(start + WINDOW).is_ok());
+    }
+}
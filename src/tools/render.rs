@@ -0,0 +1,289 @@
+//! Render workspace markdown files to standalone HTML (and optionally PDF).
+//!
+//! Relative image and link targets are resolved against the markdown file and
+//! rewritten relative to the output file, so the rendered page still finds its
+//! assets. PDFs are printed with the headless browser used by `screenshot_url`.
+
+use std::path::{Component, Path, PathBuf};
+
+use async_trait::async_trait;
+use pulldown_cmark::{html, CowStr, Event, HeadingLevel, Options, Parser, Tag, TagEnd};
+use serde_json::{json, Value};
+
+use super::{browser, resolve_path_simple, Tool};
+
+/// Built-in stylesheet used when no theme is given.
+const DEFAULT_THEME: &str = r#"
+body { max-width: 46rem; margin: 2.5rem auto; padding: 0 1.25rem; font: 16px/1.6 -apple-system, "Segoe UI", Helvetica, Arial, sans-serif; color: #1f2328; }
+h1, h2, h3 { line-height: 1.25; margin-top: 1.8em; }
+h1 { border-bottom: 1px solid #d0d7de; padding-bottom: .3em; }
+a { color: #0969da; }
+img { max-width: 100%; }
+code { font-family: ui-monospace, Menlo, Consolas, monospace; background: #f6f8fa; padding: .1em .3em; border-radius: 4px; }
+pre { background: #f6f8fa; padding: 1em; overflow-x: auto; border-radius: 6px; }
+pre code { padding: 0; }
+table { border-collapse: collapse; }
+th, td { border: 1px solid #d0d7de; padding: .4em .8em; }
+blockquote { margin: 0; padding: 0 1em; color: #59636e; border-left: .25em solid #d0d7de; }
+@media print { body { margin: 0 auto; } }
+"#;
+
+/// Lexically normalize an absolute path (drop `.`, apply `..`).
+fn normalize(path: &Path) -> PathBuf {
+    let mut out = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                out.pop();
+            }
+            other => out.push(other),
+        }
+    }
+    out
+}
+
+/// Path of `target` relative to the directory `base` (both absolute).
+fn relative_path(target: &Path, base: &Path) -> PathBuf {
+    let target = normalize(target);
+    let base = normalize(base);
+    let common = target
+        .components()
+        .zip(base.components())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let mut out = PathBuf::new();
+    for _ in base.components().skip(common) {
+        out.push("..");
+    }
+    out.extend(target.components().skip(common));
+    out
+}
+
+/// Rewrite a relative URL found in `source_dir` so it works from `output_dir`.
+/// Absolute URLs, anchors and root-relative paths are left untouched.
+fn rebase_url(url: &str, source_dir: &Path, output_dir: &Path) -> Option<String> {
+    if url.is_empty() || url.starts_with(['#', '/']) || url.contains(':') {
+        return None;
+    }
+    let split = url.find(['#', '?']).unwrap_or(url.len());
+    let (path, suffix) = url.split_at(split);
+    let rebased = relative_path(&source_dir.join(path), output_dir);
+    Some(format!("{}{}", rebased.display(), suffix))
+}
+
+/// Convert markdown to an HTML body, returning it with the first heading's text.
+pub fn markdown_to_html(
+    markdown: &str,
+    source_dir: &Path,
+    output_dir: &Path,
+) -> (String, Option<String>) {
+    let options = Options::ENABLE_TABLES
+        | Options::ENABLE_STRIKETHROUGH
+        | Options::ENABLE_TASKLISTS
+        | Options::ENABLE_FOOTNOTES;
+    let rebase = |url: CowStr<'_>| -> CowStr<'static> {
+        match rebase_url(&url, source_dir, output_dir) {
+            Some(rebased) => rebased.into(),
+            None => url.into_string().into(),
+        }
+    };
+
+    let mut title: Option<String> = None;
+    let mut in_title = false;
+    let events: Vec<Event> = Parser::new_ext(markdown, options)
+        .map(|event| match event {
+            Event::Start(Tag::Image {
+                link_type,
+                dest_url,
+                title,
+                id,
+            }) => Event::Start(Tag::Image {
+                link_type,
+                dest_url: rebase(dest_url),
+                title,
+                id,
+            }),
+            Event::Start(Tag::Link {
+                link_type,
+                dest_url,
+                title,
+                id,
+            }) => Event::Start(Tag::Link {
+                link_type,
+                dest_url: rebase(dest_url),
+                title,
+                id,
+            }),
+            other => other,
+        })
+        .inspect(|event| match event {
+            Event::Start(Tag::Heading {
+                level: HeadingLevel::H1,
+                ..
+            }) if title.is_none() => in_title = true,
+            Event::End(TagEnd::Heading(_)) => in_title = false,
+            Event::Text(text) | Event::Code(text) if in_title => {
+                title.get_or_insert_with(String::new).push_str(text);
+            }
+            _ => {}
+        })
+        .collect();
+
+    let mut body = String::new();
+    html::push_html(&mut body, events.into_iter());
+    (body, title)
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Wrap an HTML body into a standalone document.
+fn html_document(title: &str, css: &str, body: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n\
+         <title>{}</title>\n<style>{}</style>\n</head>\n<body>\n{}</body>\n</html>\n",
+        escape_html(title),
+        css,
+        body
+    )
+}
+
+/// Render a markdown file to HTML, optionally printing it to PDF.
+pub struct RenderMarkdown;
+
+#[async_trait]
+impl Tool for RenderMarkdown {
+    fn name(&self) -> &str {
+        "render_markdown"
+    }
+
+    fn description(&self) -> &str {
+        "Render a markdown file into a polished standalone HTML page (tables, task lists, footnotes), optionally also a PDF via a headless browser. Relative images and links keep working from the output location. Writes to output/<name>.html by default."
+    }
+
+    fn parameters_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "path": {
+                    "type": "string",
+                    "description": "Markdown file to render (e.g., 'output/report.md')"
+                },
+                "output": {
+                    "type": "string",
+                    "description": "Where to write the HTML (default: output/<name>.html). The PDF goes next to it."
+                },
+                "theme": {
+                    "type": "string",
+                    "description": "'default' (built-in style), 'none', or a path to a CSS file to inline"
+                },
+                "pdf": {
+                    "type": "boolean",
+                    "description": "Also print the page to PDF (requires chromium). Default: false"
+                }
+            },
+            "required": ["path"]
+        })
+    }
+
+    async fn execute(&self, args: Value, working_dir: &Path) -> anyhow::Result<String> {
+        let path = args["path"]
+            .as_str()
+            .ok_or_else(|| anyhow::anyhow!("Missing 'path' argument"))?;
+        let source = resolve_path_simple(path, working_dir)?;
+        let markdown = tokio::fs::read_to_string(&source).await.map_err(|e| {
+            anyhow::anyhow!(
+                "Failed to read {} (resolved to: {}): {}",
+                path,
+                source.display(),
+                e
+            )
+        })?;
+        let stem = source
+            .file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_else(|| "document".to_string());
+
+        let output = match args["output"].as_str() {
+            Some(out) => resolve_path_simple(out, working_dir)?,
+            None => working_dir.join("output").join(format!("{}.html", stem)),
+        };
+        let css = match args["theme"].as_str().unwrap_or("default") {
+            "default" => DEFAULT_THEME.to_string(),
+            "none" => String::new(),
+            theme => {
+                let theme_path = resolve_path_simple(theme, working_dir)?;
+                tokio::fs::read_to_string(&theme_path).await.map_err(|e| {
+                    anyhow::anyhow!("Failed to read theme {}: {}", theme_path.display(), e)
+                })?
+            }
+        };
+
+        let source_dir = source.parent().unwrap_or(working_dir);
+        let output_dir = output.parent().unwrap_or(working_dir);
+        let (body, heading) = markdown_to_html(&markdown, source_dir, output_dir);
+        let title = heading.unwrap_or(stem);
+        tokio::fs::create_dir_all(output_dir).await?;
+        tokio::fs::write(&output, html_document(&title, &css, &body)).await?;
+
+        let mut result = json!({
+            "success": true,
+            "html": output.display().to_string(),
+            "title": title,
+        });
+        if args["pdf"].as_bool().unwrap_or(false) {
+            let pdf = output.with_extension("pdf");
+            let browser = browser::find_browser().ok_or_else(|| {
+                anyhow::anyhow!(
+                    "HTML written to {}, but no headless browser was found for the PDF. \
+                     Install chromium or set OPEN_AGENT_CHROMIUM_PATH.",
+                    output.display()
+                )
+            })?;
+            browser::print_to_pdf(&browser, &output, &pdf).await?;
+            result["pdf"] = json!(pdf.display().to_string());
+        }
+        Ok(result.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_render_markdown_fixture_to_html() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("docs/images")).unwrap();
+        std::fs::write(
+            dir.path().join("docs/report.md"),
+            "# Sales & `metrics`\n\nSee the [appendix](appendix.md#data) or [site](https://example.com).\n\n\
+             ![chart](images/chart.png)\n\n| Metric | Value |\n|---|---|\n| users | 42 |\n\n- [x] shipped\n",
+        )
+        .unwrap();
+
+        let out = RenderMarkdown
+            .execute(json!({ "path": "docs/report.md" }), dir.path())
+            .await
+            .unwrap();
+        let report: Value = serde_json::from_str(&out).unwrap();
+        assert_eq!(report["title"], "Sales & metrics");
+
+        let html = std::fs::read_to_string(dir.path().join("output/report.html")).unwrap();
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<title>Sales &amp; metrics</title>"));
+        assert!(html.contains("<h1>Sales &amp; <code>metrics</code></h1>"));
+        assert!(html.contains("<img src=\"../docs/images/chart.png\" alt=\"chart\""));
+        assert!(html.contains("href=\"../docs/appendix.md#data\""));
+        assert!(html.contains("href=\"https://example.com\""));
+        assert!(html.contains("<td>42</td>"));
+        assert!(html.contains("type=\"checkbox\""));
+        assert!(html.contains("border-collapse"));
+    }
+}
@@ -0,0 +1,323 @@
+//! Resource usage tool: CPU and memory of the workspace's execution context.
+//!
+//! Reads the cgroup v2 controller files (`/sys/fs/cgroup`) and `/proc` for the
+//! host-wide view. In a container workspace the files are read inside the
+//! container (the same way `run_command` runs there), so the cgroup numbers are
+//! the container's rather than the MCP process's. CPU percentages are computed
+//! from two samples taken `sample_ms` apart.
+
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use super::terminal::{container_root_from_env, RunCommand};
+use super::Tool;
+
+const CGROUP_ROOT: &str = "/sys/fs/cgroup";
+const DEFAULT_SAMPLE_MS: u64 = 250;
+const MAX_SAMPLE_MS: u64 = 5000;
+
+/// Host-wide usage derived from `/proc`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct HostUsage {
+    pub cpu_percent: Option<f64>,
+    pub cpu_count: usize,
+    pub load_average: Option<[f64; 3]>,
+    pub memory_total_bytes: u64,
+    pub memory_used_bytes: u64,
+    pub memory_percent: f64,
+}
+
+/// Usage of the current cgroup (container or service slice).
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CgroupUsage {
+    /// CPU usage as a percentage of one core (may exceed 100 on multi-core).
+    pub cpu_percent: Option<f64>,
+    pub memory_used_bytes: u64,
+    /// `None` when the cgroup has no memory limit.
+    pub memory_limit_bytes: Option<u64>,
+    pub memory_percent: Option<f64>,
+}
+
+/// Parse the aggregate `cpu` line of `/proc/stat` into `(total, idle)` jiffies.
+pub fn parse_proc_stat(content: &str) -> Option<(u64, u64)> {
+    let line = content.lines().find(|l| l.starts_with("cpu "))?;
+    let values: Vec<u64> = line
+        .split_whitespace()
+        .skip(1)
+        .filter_map(|v| v.parse().ok())
+        .collect();
+    if values.len() < 4 {
+        return None;
+    }
+    let idle = values[3] + values.get(4).copied().unwrap_or(0);
+    Some((values.iter().sum(), idle))
+}
+
+/// Count per-core `cpuN` lines in `/proc/stat`.
+pub fn parse_cpu_count(content: &str) -> usize {
+    content
+        .lines()
+        .filter(|l| {
+            l.strip_prefix("cpu")
+                .and_then(|rest| rest.chars().next())
+                .is_some_and(|c| c.is_ascii_digit())
+        })
+        .count()
+}
+
+/// Parse `/proc/meminfo` into `(total_bytes, available_bytes)`.
+pub fn parse_meminfo(content: &str) -> Option<(u64, u64)> {
+    let field = |name: &str| {
+        content
+            .lines()
+            .find(|l| l.starts_with(name))
+            .and_then(|l| l.split_whitespace().nth(1))
+            .and_then(|v| v.parse::<u64>().ok())
+            .map(|kb| kb * 1024)
+    };
+    let total = field("MemTotal:")?;
+    let available = field("MemAvailable:").or_else(|| field("MemFree:"))?;
+    Some((total, available))
+}
+
+/// Parse the three load averages from `/proc/loadavg`.
+pub fn parse_loadavg(content: &str) -> Option<[f64; 3]> {
+    let mut parts = content.split_whitespace().map(|v| v.parse::<f64>().ok());
+    Some([parts.next()??, parts.next()??, parts.next()??])
+}
+
+/// Parse `usage_usec` from a cgroup v2 `cpu.stat` file.
+pub fn parse_cgroup_cpu_usage(content: &str) -> Option<u64> {
+    content
+        .lines()
+        .find_map(|l| l.strip_prefix("usage_usec "))
+        .and_then(|v| v.trim().parse().ok())
+}
+
+/// Parse a cgroup v2 `memory.max` value (`max` means unlimited).
+pub fn parse_cgroup_memory_max(content: &str) -> Option<u64> {
+    match content.trim() {
+        "max" | "" => None,
+        v => v.parse().ok(),
+    }
+}
+
+fn percent(part: f64, whole: f64) -> f64 {
+    if whole <= 0.0 {
+        0.0
+    } else {
+        ((part / whole) * 1000.0).round() / 10.0
+    }
+}
+
+/// Where metric files are read from.
+enum Reader<'a> {
+    /// This process's filesystem.
+    Local,
+    /// Inside the container workspace; holds the tool's working directory.
+    Container(&'a Path),
+}
+
+impl Reader<'_> {
+    async fn read(&self, path: impl AsRef<Path>) -> Option<String> {
+        let path = path.as_ref();
+        match self {
+            Reader::Local => tokio::fs::read_to_string(path).await.ok(),
+            Reader::Container(working_dir) => {
+                let args = json!({
+                    "command": format!("cat '{}'", path.display()),
+                    "timeout_secs": 10,
+                });
+                let run = RunCommand
+                    .run_captured(&args, working_dir, None)
+                    .await
+                    .ok()?;
+                (run.exit_code == 0).then_some(run.stdout)
+            }
+        }
+    }
+}
+
+async fn host_usage(reader: &Reader<'_>, sample: Duration) -> Option<HostUsage> {
+    let stat_before = reader.read("/proc/stat").await?;
+    tokio::time::sleep(sample).await;
+    let stat_after = reader.read("/proc/stat").await?;
+    let (mem_total, mem_available) = parse_meminfo(&reader.read("/proc/meminfo").await?)?;
+
+    let cpu_percent = match (parse_proc_stat(&stat_before), parse_proc_stat(&stat_after)) {
+        (Some((t0, i0)), Some((t1, i1))) if t1 > t0 => {
+            let busy = (t1 - t0).saturating_sub(i1.saturating_sub(i0));
+            Some(percent(busy as f64, (t1 - t0) as f64))
+        }
+        _ => None,
+    };
+    let used = mem_total.saturating_sub(mem_available);
+
+    Some(HostUsage {
+        cpu_percent,
+        cpu_count: parse_cpu_count(&stat_after),
+        load_average: reader
+            .read("/proc/loadavg")
+            .await
+            .as_deref()
+            .and_then(parse_loadavg),
+        memory_total_bytes: mem_total,
+        memory_used_bytes: used,
+        memory_percent: percent(used as f64, mem_total as f64),
+    })
+}
+
+async fn cgroup_usage(reader: &Reader<'_>, root: &Path, sample: Duration) -> Option<CgroupUsage> {
+    let memory_used = reader
+        .read(root.join("memory.current"))
+        .await?
+        .trim()
+        .parse::<u64>()
+        .ok()?;
+    let memory_limit = reader
+        .read(root.join("memory.max"))
+        .await
+        .as_deref()
+        .and_then(parse_cgroup_memory_max);
+
+    let before = reader
+        .read(root.join("cpu.stat"))
+        .await
+        .as_deref()
+        .and_then(parse_cgroup_cpu_usage);
+    let started = Instant::now();
+    tokio::time::sleep(sample).await;
+    let after = reader
+        .read(root.join("cpu.stat"))
+        .await
+        .as_deref()
+        .and_then(parse_cgroup_cpu_usage);
+    let elapsed_usec = started.elapsed().as_micros() as f64;
+    let cpu_percent = match (before, after) {
+        (Some(b), Some(a)) if a >= b => Some(percent((a - b) as f64, elapsed_usec)),
+        _ => None,
+    };
+
+    Some(CgroupUsage {
+        cpu_percent,
+        memory_used_bytes: memory_used,
+        memory_limit_bytes: memory_limit,
+        memory_percent: memory_limit.map(|limit| percent(memory_used as f64, limit as f64)),
+    })
+}
+
+/// Report current CPU and memory usage.
+pub struct ResourceUsage;
+
+#[async_trait]
+impl Tool for ResourceUsage {
+    fn name(&self) -> &str {
+        "resource_usage"
+    }
+
+    fn description(&self) -> &str {
+        "Report current CPU and memory usage as JSON: the workspace's cgroup usage (the container's in container workspaces, when available) and host-wide usage from /proc. Use it to detect thrashing or memory pressure during long-running tasks."
+    }
+
+    fn parameters_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "sample_ms": {
+                    "type": "integer",
+                    "description": "CPU sampling window in milliseconds (default: 250, max: 5000)"
+                }
+            }
+        })
+    }
+
+    async fn execute(&self, args: Value, working_dir: &Path) -> anyhow::Result<String> {
+        let sample = Duration::from_millis(
+            args["sample_ms"]
+                .as_u64()
+                .unwrap_or(DEFAULT_SAMPLE_MS)
+                .min(MAX_SAMPLE_MS),
+        );
+
+        let reader = match container_root_from_env() {
+            Some(_) => Reader::Container(working_dir),
+            None => Reader::Local,
+        };
+        let (host, cgroup) = tokio::join!(
+            host_usage(&reader, sample),
+            cgroup_usage(&reader, Path::new(CGROUP_ROOT), sample)
+        );
+        if host.is_none() && cgroup.is_none() {
+            return Err(anyhow::anyhow!(
+                "Resource usage is unavailable: neither /proc nor cgroup v2 metrics are readable"
+            ));
+        }
+
+        Ok(serde_json::to_string_pretty(&json!({
+            "cgroup": cgroup,
+            "host": host,
+        }))?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_proc_samples() {
+        let stat = "cpu  100 0 50 800 50 0 0 0 0 0\ncpu0 50 0 25 400 25 0 0 0 0 0\ncpu1 50 0 25 400 25 0 0 0 0 0\nintr 1 2 3\n";
+        assert_eq!(parse_proc_stat(stat), Some((1000, 850)));
+        assert_eq!(parse_cpu_count(stat), 2);
+
+        let meminfo =
+            "MemTotal:       16384 kB\nMemFree:         1024 kB\nMemAvailable:    4096 kB\n";
+        assert_eq!(parse_meminfo(meminfo), Some((16384 * 1024, 4096 * 1024)));
+        assert_eq!(
+            parse_loadavg("0.50 1.25 2.00 1/234 5678\n"),
+            Some([0.5, 1.25, 2.0])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_cgroup_usage_from_files() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("memory.current"), "536870912\n").unwrap();
+        std::fs::write(dir.path().join("memory.max"), "1073741824\n").unwrap();
+        std::fs::write(
+            dir.path().join("cpu.stat"),
+            "usage_usec 123456\nuser_usec 100000\nsystem_usec 23456\n",
+        )
+        .unwrap();
+
+        let usage = cgroup_usage(&Reader::Local, dir.path(), Duration::from_millis(1))
+            .await
+            .unwrap();
+        assert_eq!(usage.memory_used_bytes, 536870912);
+        assert_eq!(usage.memory_limit_bytes, Some(1073741824));
+        assert_eq!(usage.memory_percent, Some(50.0));
+        assert_eq!(usage.cpu_percent, Some(0.0));
+        assert_eq!(parse_cgroup_memory_max("max\n"), None);
+    }
+
+    #[tokio::test]
+    async fn test_workspace_reader_reads_through_commands() {
+        // Outside a container workspace the command runs on this host, which is
+        // enough to check the file comes back through `run_command`.
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("memory.current"), "4096\n").unwrap();
+        let reader = Reader::Container(dir.path());
+        assert_eq!(
+            reader
+                .read(dir.path().join("memory.current"))
+                .await
+                .as_deref(),
+            Some("4096\n")
+        );
+        assert_eq!(reader.read(dir.path().join("missing")).await, None);
+    }
+}
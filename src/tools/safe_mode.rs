@@ -0,0 +1,327 @@
+//! Safe mode: human confirmation for destructive tool calls.
+//!
+//! When a mission runs with safe mode enabled, destructive operations (`delete_file`,
+//! `rm`, `git reset --hard`, ...) are not executed immediately. Instead a
+//! `ui_confirmAction` frontend tool call is emitted and the tool waits on the
+//! `FrontendToolHub` until the user approves or rejects it. The control status is
+//! switched to `WaitingForTool` while waiting.
+//!
+//! Missions created with `safe_mode: true` get this behaviour. Their tools run in
+//! the workspace MCP host, which asks the backend through
+//! `POST /api/control/missions/:id/confirm_action`; the backend checks the
+//! mission's flag and runs the [`SafeModeGate`].
+
+use std::sync::{Arc, OnceLock};
+
+use async_trait::async_trait;
+use regex::Regex;
+use serde_json::{json, Value};
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use crate::api::control::{
+    AgentEvent, ControlRunState, ControlStatus, EventSender, FrontendToolHub,
+};
+
+/// Name of the frontend tool used to ask for confirmation.
+pub const CONFIRM_TOOL_NAME: &str = "ui_confirmAction";
+
+/// Shell command patterns considered destructive.
+const DESTRUCTIVE_COMMAND_PATTERNS: &[&str] = &[
+    r"(^|[;&|]\s*|\bsudo\s+)rm\s",
+    r"\bgit\s+reset\s+--hard\b",
+    r"\bgit\s+clean\s+-[a-zA-Z]*f",
+    r"\bgit\s+push\s+.*(--force|-f\b)",
+    r"\bgit\s+branch\s+-D\b",
+    r"\bmkfs(\.\w+)?\b",
+    r"\bdd\s+.*\bof=",
+    r"\btruncate\s",
+    r"\bshred\s",
+];
+
+fn destructive_commands() -> &'static [Regex] {
+    static COMMANDS: OnceLock<Vec<Regex>> = OnceLock::new();
+    COMMANDS.get_or_init(|| {
+        DESTRUCTIVE_COMMAND_PATTERNS
+            .iter()
+            .map(|pattern| Regex::new(pattern).expect("valid destructive pattern"))
+            .collect()
+    })
+}
+
+/// Why a tool call needs confirmation, or `None` if it is safe to run directly.
+pub fn destructive_reason(tool_name: &str, args: &Value) -> Option<String> {
+    let flag = |name: &str| args[name].as_bool().unwrap_or(false);
+    let action = args["action"].as_str().unwrap_or("");
+    match tool_name {
+        "delete_file" if args["dry_run"].as_bool().unwrap_or(false) => None,
+        "delete_file" => Some(format!(
+            "{} {}",
+            if args["recursive"].as_bool().unwrap_or(false) {
+                "Recursively delete"
+            } else {
+                "Delete"
+            },
+            args["path"].as_str().unwrap_or("a file")
+        )),
+        "run_command" | "terminal_session" => {
+            let command = args["command"].as_str()?;
+            destructive_commands()
+                .iter()
+                .any(|re| re.is_match(command))
+                .then(|| format!("Run destructive command: {}", command))
+        }
+        "move_file" if flag("overwrite") => Some(format!(
+            "Move {} over {}, replacing it",
+            args["from"].as_str().unwrap_or("a file"),
+            args["to"].as_str().unwrap_or("the destination")
+        )),
+        "git_branch" if action == "delete" => Some(format!(
+            "Delete branch {}",
+            args["name"].as_str().unwrap_or("")
+        )),
+        "git_stash" if action == "drop" => Some(format!(
+            "Drop stash@{{{}}}",
+            args["index"].as_u64().unwrap_or(0)
+        )),
+        "git_worktree" if action == "remove" => Some(format!(
+            "Remove worktree {}",
+            args["worktree_path"].as_str().unwrap_or("")
+        )),
+        "git_checkout" if flag("force") => Some(format!(
+            "Force checkout of {}, discarding local changes",
+            args["target"].as_str().unwrap_or("HEAD")
+        )),
+        _ => None,
+    }
+}
+
+/// Asks for approval of a destructive tool call.
+#[async_trait]
+pub trait ConfirmAction: Send + Sync {
+    /// Returns true only on explicit approval; `Err` when the user could not be asked.
+    async fn confirm(&self, tool_name: &str, args: &Value, reason: &str) -> anyhow::Result<bool>;
+}
+
+/// Ask the backend at `api_base` to confirm a destructive call of `mission_id`
+/// (`POST /api/control/missions/:id/confirm_action`).
+///
+/// Used by the workspace MCP host, whose tools can't reach the frontend tool hub
+/// directly. An unreachable API or an error reply is an `Err`, not a decline.
+pub async fn confirm_via_api(
+    api_base: &str,
+    auth_token: Option<&str>,
+    mission_id: &str,
+    tool_name: &str,
+    args: &Value,
+) -> anyhow::Result<bool> {
+    // No timeout: the user may take a while to answer.
+    let mut request = reqwest::Client::new()
+        .post(format!(
+            "{}/api/control/missions/{}/confirm_action",
+            api_base, mission_id
+        ))
+        .json(&json!({ "tool": tool_name, "args": args }));
+    if let Some(token) = auth_token {
+        request = request.header("Authorization", format!("Bearer {}", token));
+    }
+    let response = request
+        .send()
+        .await
+        .map_err(|e| anyhow::anyhow!("could not reach the API to confirm: {}", e))?;
+    let status = response.status();
+    if !status.is_success() {
+        let error_text = response.text().await.unwrap_or_default();
+        return Err(anyhow::anyhow!(
+            "the API refused the confirmation request: {} {}",
+            status,
+            error_text.trim()
+        ));
+    }
+    let body: Value = response.json().await?;
+    Ok(body["approved"].as_bool().unwrap_or(false))
+}
+
+/// Routes destructive tool calls through the frontend for user approval.
+#[derive(Clone)]
+pub struct SafeModeGate {
+    hub: Arc<FrontendToolHub>,
+    events_tx: EventSender,
+    status: Option<Arc<RwLock<ControlStatus>>>,
+    mission_id: Option<Uuid>,
+}
+
+impl SafeModeGate {
+    pub fn new(
+        hub: Arc<FrontendToolHub>,
+        events_tx: EventSender,
+        mission_id: Option<Uuid>,
+    ) -> Self {
+        Self {
+            hub,
+            events_tx,
+            status: None,
+            mission_id,
+        }
+    }
+
+    /// Also switch the control session to `WaitingForTool` while awaiting approval.
+    pub fn with_status(mut self, status: Arc<RwLock<ControlStatus>>) -> Self {
+        self.status = Some(status);
+        self
+    }
+
+    async fn set_state(&self, state: ControlRunState) -> Option<ControlRunState> {
+        let status = self.status.as_ref()?;
+        let mut s = status.write().await;
+        let previous = s.state;
+        s.state = state;
+        let _ = self.events_tx.send(AgentEvent::Status {
+            state,
+            queue_len: s.queue_len,
+            mission_id: s.mission_id,
+        });
+        Some(previous)
+    }
+}
+
+#[async_trait]
+impl ConfirmAction for SafeModeGate {
+    /// Ask the user through the frontend; the call waits until they answer.
+    async fn confirm(&self, tool_name: &str, args: &Value, reason: &str) -> anyhow::Result<bool> {
+        let tool_call_id = format!("confirm_{}", Uuid::new_v4());
+        let rx = self.hub.register(tool_call_id.clone()).await;
+
+        let _ = self.events_tx.send(AgentEvent::ToolCall {
+            tool_call_id: tool_call_id.clone(),
+            name: CONFIRM_TOOL_NAME.to_string(),
+            args: json!({
+                "id": tool_call_id,
+                "title": "Confirm destructive action",
+                "description": reason,
+                "tool": tool_name,
+                "arguments": args,
+            }),
+            mission_id: self.mission_id,
+        });
+
+        let previous = self.set_state(ControlRunState::WaitingForTool).await;
+        let result = rx.await.unwrap_or(Value::Null);
+        if let Some(previous) = previous {
+            self.set_state(previous).await;
+        }
+
+        let _ = self.events_tx.send(AgentEvent::ToolResult {
+            tool_call_id,
+            name: CONFIRM_TOOL_NAME.to_string(),
+            result: result.clone(),
+            mission_id: self.mission_id,
+        });
+
+        Ok(is_approval(&result))
+    }
+}
+
+/// Interpret a frontend answer as approval (`{"approved": true}`, `true`, or `"approve"`).
+fn is_approval(result: &Value) -> bool {
+    match result {
+        Value::Bool(b) => *b,
+        Value::String(s) => matches!(s.as_str(), "approve" | "approved" | "yes"),
+        Value::Object(map) => ["approved", "confirmed"]
+            .iter()
+            .any(|k| map.get(*k).and_then(|v| v.as_bool()) == Some(true)),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_destructive_detection() {
+        assert!(destructive_reason("delete_file", &json!({"path": "a.txt"})).is_some());
+        assert!(
+            destructive_reason("delete_file", &json!({"path": "a", "dry_run": true})).is_none()
+        );
+        assert!(destructive_reason("run_command", &json!({"command": "rm -rf build"})).is_some());
+        assert!(destructive_reason(
+            "run_command",
+            &json!({"command": "git reset --hard HEAD~1"})
+        )
+        .is_some());
+        assert!(destructive_reason("run_command", &json!({"command": "ls -la"})).is_none());
+        assert!(destructive_reason("run_command", &json!({"command": "npm run format"})).is_none());
+        assert!(destructive_reason("read_file", &json!({"path": "a.txt"})).is_none());
+
+        for (tool, args) in [
+            (
+                "move_file",
+                json!({"from": "a", "to": "b", "overwrite": true}),
+            ),
+            ("git_branch", json!({"action": "delete", "name": "topic"})),
+            ("git_stash", json!({"action": "drop", "index": 1})),
+            (
+                "git_worktree",
+                json!({"action": "remove", "worktree_path": "../wt"}),
+            ),
+            ("git_checkout", json!({"target": "main", "force": true})),
+        ] {
+            assert!(destructive_reason(tool, &args).is_some(), "{}", tool);
+        }
+        for (tool, args) in [
+            ("move_file", json!({"from": "a", "to": "b"})),
+            ("git_branch", json!({"action": "create", "name": "topic"})),
+            ("git_stash", json!({"action": "pop"})),
+            ("git_worktree", json!({"action": "add", "branch": "x"})),
+            ("git_checkout", json!({"target": "main"})),
+        ] {
+            assert!(destructive_reason(tool, &args).is_none(), "{}", tool);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_api_confirmation_failures_are_errors() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        // Auth enabled, no token: the server answers 401.
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut buf = [0u8; 4096];
+            let _ = socket.read(&mut buf).await;
+            let _ = socket
+                .write_all(
+                    b"HTTP/1.1 401 Unauthorized\r\ncontent-length: 28\r\n\r\nMissing Authorization header",
+                )
+                .await;
+        });
+        let args = json!({"path": "a.txt"});
+        let err = confirm_via_api(
+            &format!("http://{}", addr),
+            None,
+            "mission-1",
+            "delete_file",
+            &args,
+        )
+        .await
+        .unwrap_err();
+        assert!(err.to_string().contains("401"), "{}", err);
+
+        // Nothing listening.
+        let closed = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let closed_addr = closed.local_addr().unwrap();
+        drop(closed);
+        let err = confirm_via_api(
+            &format!("http://{}", closed_addr),
+            None,
+            "mission-1",
+            "delete_file",
+            &args,
+        )
+        .await
+        .unwrap_err();
+        assert!(err.to_string().contains("could not reach the API"));
+    }
+}
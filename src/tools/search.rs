@@ -11,8 +11,10 @@ use std::process::Stdio;
 
 use async_trait::async_trait;
 use serde_json::{json, Value};
+use tokio::io::{AsyncBufReadExt, BufReader};
 use tokio::process::Command;
 
+use super::workspace_ignore::WorkspaceIgnore;
 use super::{resolve_path, Tool};
 
 /// Search file contents with regex/grep.
@@ -25,7 +27,7 @@ impl Tool for GrepSearch {
     }
 
     fn description(&self) -> &str {
-        "Search for a pattern in file contents using regex. Searches workspace by default. Great for finding function definitions, usages, or patterns."
+        "Search for a pattern in file contents using regex. Searches workspace by default, skipping .openagentignore paths and heavy dirs like node_modules and target. Great for finding function definitions, usages, or patterns."
     }
 
     fn parameters_schema(&self) -> Value {
@@ -47,6 +49,26 @@ impl Tool for GrepSearch {
                 "case_sensitive": {
                     "type": "boolean",
                     "description": "Whether search is case-sensitive (default: false)"
+                },
+                "case_insensitive": {
+                    "type": "boolean",
+                    "description": "Optional: force case-insensitive (-i) or case-sensitive matching. Overrides case_sensitive."
+                },
+                "include_glob": {
+                    "type": "string",
+                    "description": "Optional: only search files matching this glob (e.g., '*.rs'). Alias of file_pattern."
+                },
+                "context_lines": {
+                    "type": "integer",
+                    "description": "Lines of context to show before and after each match (default: 0, max: 20)"
+                },
+                "max_results": {
+                    "type": "integer",
+                    "description": "Maximum number of matching lines to return (default: 100, max: 1000)"
+                },
+                "respect_ignore": {
+                    "type": "boolean",
+                    "description": "Skip paths matched by .openagentignore and the built-in heavy dirs (node_modules, target, .git, ...) (default: true)"
                 }
             },
             "required": ["pattern"]
@@ -58,87 +80,312 @@ impl Tool for GrepSearch {
             .as_str()
             .ok_or_else(|| anyhow::anyhow!("Missing 'pattern' argument"))?;
         let path = args["path"].as_str().unwrap_or(".");
-        let file_pattern = args["file_pattern"].as_str();
-        let case_sensitive = args["case_sensitive"].as_bool().unwrap_or(false);
+        let file_pattern = args["include_glob"]
+            .as_str()
+            .or_else(|| args["file_pattern"].as_str());
+        let case_insensitive = args["case_insensitive"]
+            .as_bool()
+            .unwrap_or_else(|| !args["case_sensitive"].as_bool().unwrap_or(false));
+        let context_lines = args["context_lines"]
+            .as_u64()
+            .unwrap_or(0)
+            .min(MAX_CONTEXT_LINES);
+        let max_results = args["max_results"]
+            .as_u64()
+            .map(|n| n.clamp(1, MAX_RESULTS_LIMIT) as usize)
+            .unwrap_or(DEFAULT_MAX_RESULTS);
 
-        let resolution = resolve_path(path, working_dir);
+        let resolution = resolve_path(path, working_dir)?;
         let search_path = resolution.resolved;
+        let ignore = WorkspaceIgnore::for_call(&args, working_dir, &search_path);
+        let excluded_dirs = ignore
+            .as_ref()
+            .map(|i| i.ignored_default_dirs())
+            .unwrap_or_default();
 
-        // Try to use ripgrep (rg) if available, fall back to grep
+        // Try to use ripgrep (rg) if available, fall back to grep.
+        // Both print `file\0line:text` for matches and `file\0line-text` for context.
         let mut cmd = if which_exists("rg") {
             let mut c = Command::new("rg");
             c.arg("--line-number");
             c.arg("--no-heading");
             c.arg("--color=never");
+            c.arg("--null");
 
-            if !case_sensitive {
+            if case_insensitive {
                 c.arg("-i");
             }
-
+            if context_lines > 0 {
+                c.arg("-C").arg(context_lines.to_string());
+            }
             if let Some(fp) = file_pattern {
                 c.arg("-g").arg(fp);
             }
+            for dir in &excluded_dirs {
+                c.arg("-g").arg(format!("!{}/", dir));
+            }
+            if let Some(file) = ignore.as_ref().and_then(|i| i.ignore_file()) {
+                // Patterns in --ignore-file are relative to the current directory.
+                c.current_dir(working_dir).arg("--ignore-file").arg(file);
+            }
 
             c.arg("--").arg(pattern).arg(&search_path);
             c
         } else {
             let mut c = Command::new("grep");
-            c.arg("-rn");
+            c.arg("-rnZ");
 
-            if !case_sensitive {
+            if case_insensitive {
                 c.arg("-i");
             }
-
+            if context_lines > 0 {
+                c.arg("-C").arg(context_lines.to_string());
+            }
             if let Some(fp) = file_pattern {
                 c.arg("--include").arg(fp);
             }
+            for dir in &excluded_dirs {
+                c.arg(format!("--exclude-dir={}", dir));
+            }
 
-            c.arg(pattern).arg(&search_path);
+            c.arg("-e").arg(pattern).arg(&search_path);
             c
         };
 
-        let output = cmd
+        let mut child = cmd
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
-            .output()
-            .await
+            .kill_on_drop(true)
+            .spawn()
             .map_err(|e| anyhow::anyhow!("Failed to execute search: {}", e))?;
 
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        let stderr = String::from_utf8_lossy(&output.stderr);
+        // Read incrementally and stop once enough matches are collected, so huge
+        // trees (node_modules, ...) never get buffered in full.
+        let stdout = child
+            .stdout
+            .take()
+            .ok_or_else(|| anyhow::anyhow!("Failed to capture search output"))?;
+        let mut reader = BufReader::new(stdout);
+        let mut results = SearchResults::new(max_results);
+        let mut buf = Vec::new();
+        let mut truncated = false;
+        loop {
+            buf.clear();
+            if reader.read_until(b'\n', &mut buf).await? == 0 {
+                break;
+            }
+            let line = String::from_utf8_lossy(&buf);
+            // The exclusions above only prune; the ignore file has the final say.
+            let file = line.split_once('\0').map(|(file, _)| Path::new(file));
+            if file.is_some_and(|f| ignore.as_ref().is_some_and(|i| i.is_ignored(f, false))) {
+                continue;
+            }
+            if !results.push_line(line.trim_end_matches(['\n', '\r'])) {
+                truncated = true;
+                break;
+            }
+        }
 
-        // grep returns exit code 1 when no matches found
-        if !output.status.success() && output.status.code() != Some(1) {
-            if !stderr.is_empty() {
+        if truncated {
+            let _ = child.kill().await;
+        } else {
+            let output = child
+                .wait_with_output()
+                .await
+                .map_err(|e| anyhow::anyhow!("Failed to execute search: {}", e))?;
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            // grep returns exit code 1 when no matches found
+            if !output.status.success() && output.status.code() != Some(1) && !stderr.is_empty() {
                 return Err(anyhow::anyhow!("Search error: {}", stderr));
             }
         }
 
-        if stdout.is_empty() {
+        if results.match_count == 0 {
             return Ok(format!("No matches found for pattern: {}", pattern));
         }
 
-        // Show results with full paths for system-wide clarity
-        let result: String = stdout
-            .lines()
-            .take(100) // Limit results
-            .collect::<Vec<_>>()
-            .join("\n");
+        let mut rendered = results.render();
+        if truncated {
+            rendered.push_str(&format!(
+                "\n\n... (showing first {} matches; narrow the search or raise max_results)",
+                max_results
+            ));
+        }
+        Ok(rendered)
+    }
+}
+
+const DEFAULT_MAX_RESULTS: usize = 100;
+const MAX_RESULTS_LIMIT: u64 = 1000;
+const MAX_CONTEXT_LINES: u64 = 20;
 
-        let line_count = result.lines().count();
-        if line_count >= 100 {
-            Ok(format!("{}\n\n... (showing first 100 matches)", result))
-        } else {
-            Ok(result)
+/// A line in the output: the line number, whether it matched, and its text.
+type SearchLine = (u64, bool, String);
+
+/// Matches grouped by file, in the order the search tool reported them.
+struct SearchResults {
+    files: Vec<(String, Vec<SearchLine>)>,
+    match_count: usize,
+    max_results: usize,
+}
+
+impl SearchResults {
+    fn new(max_results: usize) -> Self {
+        Self {
+            files: Vec::new(),
+            match_count: 0,
+            max_results,
         }
     }
+
+    /// Add one line of `file\0NUM:text` / `file\0NUM-text` output.
+    /// Returns false once the match limit has been exceeded.
+    fn push_line(&mut self, line: &str) -> bool {
+        let Some((file, rest)) = line.split_once('\0') else {
+            // `--` group separators and anything unparseable.
+            return true;
+        };
+        let digits = rest.chars().take_while(|c| c.is_ascii_digit()).count();
+        let Ok(number) = rest[..digits].parse::<u64>() else {
+            return true;
+        };
+        let is_match = match rest[digits..].chars().next() {
+            Some(':') => true,
+            Some('-') => false,
+            _ => return true,
+        };
+        if is_match {
+            if self.match_count >= self.max_results {
+                return false;
+            }
+            self.match_count += 1;
+        }
+        let text = rest[digits + 1..].to_string();
+        match self.files.last_mut() {
+            Some((last, lines)) if last == file => lines.push((number, is_match, text)),
+            _ => self
+                .files
+                .push((file.to_string(), vec![(number, is_match, text)])),
+        }
+        true
+    }
+
+    /// Render grouped by file, with `--` between non-adjacent context windows.
+    fn render(&self) -> String {
+        let mut out = String::new();
+        for (file, lines) in &self.files {
+            // Context read ahead of a match past the limit leaves a match-less group.
+            if lines.iter().all(|l| !l.1) {
+                continue;
+            }
+            if !out.is_empty() {
+                out.push('\n');
+            }
+            out.push_str(file);
+            out.push('\n');
+            let mut previous: Option<u64> = None;
+            for (number, is_match, text) in lines {
+                if previous.is_some_and(|p| *number > p + 1) {
+                    out.push_str("  --\n");
+                }
+                let marker = if *is_match { ':' } else { '-' };
+                out.push_str(&format!("{:>6}{} {}\n", number, marker, text));
+                previous = Some(*number);
+            }
+        }
+        out.trim_end().to_string()
+    }
 }
 
 /// Check if a command exists in PATH.
-fn which_exists(cmd: &str) -> bool {
+pub(super) fn which_exists(cmd: &str) -> bool {
     std::process::Command::new("which")
         .arg(cmd)
         .output()
         .map(|o| o.status.success())
         .unwrap_or(false)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_results_grouped_with_context() {
+        let mut results = SearchResults::new(2);
+        for line in [
+            "src/a.rs\x001-use std;",
+            "src/a.rs\x002:fn main() {",
+            "src/a.rs\x003-}",
+            "--",
+            "src/a.rs\x0010:fn main2() {",
+            "src/b.rs\x004:fn main3() {",
+        ] {
+            if !results.push_line(line) {
+                break;
+            }
+        }
+        assert_eq!(results.match_count, 2);
+        assert_eq!(
+            results.render(),
+            "src/a.rs\n     1- use std;\n     2: fn main() {\n     3- }\n  --\n    10: fn main2() {"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_grep_search_caps_results() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("a.rs"), "Foo\nfoo\nbar\nFOO\n").unwrap();
+        std::fs::write(dir.path().join("b.txt"), "foo\n").unwrap();
+
+        let out = GrepSearch
+            .execute(
+                json!({
+                    "pattern": "foo",
+                    "case_insensitive": true,
+                    "include_glob": "*.rs",
+                    "max_results": 2
+                }),
+                dir.path(),
+            )
+            .await
+            .unwrap();
+        assert!(out.contains("a.rs"));
+        assert!(!out.contains("b.txt"));
+        assert!(out.contains("showing first 2 matches"));
+    }
+
+    #[tokio::test]
+    async fn test_grep_search_honours_ignore_rules() {
+        let dir = tempfile::tempdir().unwrap();
+        for file in ["src/lib.rs", "node_modules/pkg/index.js", "gen/out.rs"] {
+            let path = dir.path().join(file);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, "needle\n").unwrap();
+        }
+        std::fs::write(dir.path().join(".openagentignore"), "gen/\n").unwrap();
+
+        let search = |args: Value| GrepSearch.execute(args, dir.path());
+        let out = search(json!({ "pattern": "needle" })).await.unwrap();
+        assert!(out.contains("lib.rs"), "{}", out);
+        assert!(
+            !out.contains("index.js") && !out.contains("out.rs"),
+            "{}",
+            out
+        );
+
+        let out = search(json!({ "pattern": "needle", "respect_ignore": false }))
+            .await
+            .unwrap();
+        assert!(
+            out.contains("index.js") && out.contains("out.rs"),
+            "{}",
+            out
+        );
+
+        let out = search(json!({ "pattern": "needle", "path": "gen" }))
+            .await
+            .unwrap();
+        assert!(out.contains("out.rs"), "{}", out);
+    }
+}
@@ -10,12 +10,14 @@ use std::collections::HashMap;
 use std::env;
 use std::path::{Path, PathBuf};
 use std::process::{Output, Stdio};
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
 use async_trait::async_trait;
 use serde_json::{json, Value};
-use tokio::io::AsyncWriteExt;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWriteExt};
 use tokio::process::Command;
+use tokio::sync::mpsc;
 
 use super::{resolve_path_simple as resolve_path, Tool};
 use crate::nspawn;
@@ -79,7 +81,7 @@ fn read_runtime_context(container_root: &Path) -> RuntimeContext {
 
 /// Sanitize command output to be safe for LLM consumption.
 /// Removes binary garbage while preserving valid text.
-fn sanitize_output(bytes: &[u8]) -> String {
+pub(super) fn sanitize_output(bytes: &[u8]) -> String {
     // Check if output appears to be mostly binary
     let non_printable_count = bytes
         .iter()
@@ -140,7 +142,7 @@ const DANGEROUS_PATTERNS: &[(&str, &str)] = &[
 
 /// Validate a command against dangerous patterns.
 /// Returns Ok(()) if safe, Err with suggestion if blocked.
-fn validate_command(cmd: &str) -> Result<(), String> {
+pub(super) fn validate_command(cmd: &str) -> Result<(), String> {
     let cmd_trimmed = cmd.trim();
     let prefixes = ["sudo ", "time ", "nice ", "nohup "];
 
@@ -224,7 +226,7 @@ fn validate_command(cmd: &str) -> Result<(), String> {
     Ok(())
 }
 
-fn container_root_from_env() -> Option<PathBuf> {
+pub(super) fn container_root_from_env() -> Option<PathBuf> {
     let workspace_type = env::var("OPEN_AGENT_WORKSPACE_TYPE").ok()?;
     if workspace_type != "container" {
         return None;
@@ -250,6 +252,18 @@ struct CommandOptions {
     shell: Option<String>,
     max_output_chars: usize,
     raw_output: bool,
+    /// Receives stdout/stderr lines as they are produced.
+    stream: Option<mpsc::UnboundedSender<String>>,
+}
+
+impl CommandOptions {
+    /// Options for internal helper commands whose output should not be streamed.
+    fn quiet(&self) -> Self {
+        Self {
+            stream: None,
+            ..self.clone()
+        }
+    }
 }
 
 const DEFAULT_MAX_OUTPUT_CHARS: usize = 10_000;
@@ -267,7 +281,7 @@ fn default_timeout_from_env() -> Duration {
     Duration::from_secs_f64(DEFAULT_COMMAND_TIMEOUT_SECS)
 }
 
-fn parse_timeout(args: &Value) -> Duration {
+pub(super) fn parse_timeout(args: &Value) -> Duration {
     if let Some(ms) = args.get("timeout_ms").and_then(|v| v.as_u64()) {
         return Duration::from_millis(ms.max(1));
     }
@@ -282,7 +296,7 @@ fn parse_timeout(args: &Value) -> Duration {
     default_timeout_from_env()
 }
 
-fn parse_env(args: &Value) -> HashMap<String, String> {
+pub(super) fn parse_env(args: &Value) -> HashMap<String, String> {
     let mut envs = HashMap::new();
     let Some(obj) = args.get("env").and_then(|v| v.as_object()) else {
         return envs;
@@ -343,7 +357,7 @@ fn workspace_env_vars() -> HashMap<String, String> {
     envs
 }
 
-fn parse_max_output_chars(args: &Value) -> usize {
+pub(super) fn parse_max_output_chars(args: &Value) -> usize {
     let max = args
         .get("max_output_chars")
         .and_then(|v| v.as_u64())
@@ -370,6 +384,7 @@ fn parse_command_options(args: &Value) -> CommandOptions {
             .map(|s| s.to_string()),
         max_output_chars: parse_max_output_chars(args),
         raw_output: args.get("raw").and_then(|v| v.as_bool()).unwrap_or(false),
+        stream: None,
     }
 }
 
@@ -381,7 +396,7 @@ fn shell_exists(shell: &str, container_root: Option<&Path>) -> bool {
     Path::new(shell).exists()
 }
 
-fn resolve_shell(shell: Option<&str>, container_root: Option<&Path>) -> String {
+pub(super) fn resolve_shell(shell: Option<&str>, container_root: Option<&Path>) -> String {
     if let Some(shell) = shell {
         if shell_exists(shell, container_root) {
             return shell.to_string();
@@ -429,6 +444,10 @@ async fn run_shell_command(
         .stdout(Stdio::piped())
         .stderr(Stdio::piped());
 
+    // Run in a dedicated process group so a timeout can kill the whole tree.
+    #[cfg(unix)]
+    cmd.process_group(0);
+
     let mut child = cmd
         .spawn()
         .map_err(|e| anyhow::anyhow!("Failed to execute command: {}", e))?;
@@ -441,17 +460,117 @@ async fn run_shell_command(
                 .map_err(|e| anyhow::anyhow!("Failed to write to stdin: {}", e))?;
         }
     }
+    // Close stdin so commands waiting on input see EOF instead of hanging.
+    drop(child.stdin.take());
+
+    let stdout_buf = Arc::new(Mutex::new(Vec::new()));
+    let stderr_buf = Arc::new(Mutex::new(Vec::new()));
+    let readers = [
+        child.stdout.take().map(|pipe| {
+            tokio::spawn(collect_pipe(
+                pipe,
+                stdout_buf.clone(),
+                options.stream.clone(),
+            ))
+        }),
+        child.stderr.take().map(|pipe| {
+            tokio::spawn(collect_pipe(
+                pipe,
+                stderr_buf.clone(),
+                options.stream.clone(),
+            ))
+        }),
+    ];
+
+    let status = match tokio::time::timeout(options.timeout, child.wait()).await {
+        Ok(Ok(status)) => status,
+        Ok(Err(e)) => return Err(anyhow::anyhow!("Failed to execute command: {}", e)),
+        Err(_) => {
+            kill_process_tree(&mut child).await;
+            finish_readers(readers, Duration::from_secs(1)).await;
+            let stdout = sanitize_output(&take_buf(&stdout_buf));
+            let stderr = sanitize_output(&take_buf(&stderr_buf));
+            let mut message = format!(
+                "Command timed out after {} seconds and was killed",
+                options.timeout.as_secs_f64()
+            );
+            if !stdout.is_empty() {
+                message.push_str("\n\n--- partial stdout ---\n");
+                message.push_str(&stdout);
+            }
+            if !stderr.is_empty() {
+                message.push_str("\n\n--- partial stderr ---\n");
+                message.push_str(&stderr);
+            }
+            return Err(anyhow::anyhow!(message));
+        }
+    };
+
+    // Background processes that inherited the pipes may keep them open; don't wait forever.
+    finish_readers(readers, Duration::from_secs(5)).await;
 
-    let output = tokio::time::timeout(options.timeout, child.wait_with_output()).await;
+    Ok(Output {
+        status,
+        stdout: take_buf(&stdout_buf),
+        stderr: take_buf(&stderr_buf),
+    })
+}
 
-    match output {
-        Ok(Ok(output)) => Ok(output),
-        Ok(Err(e)) => Err(anyhow::anyhow!("Failed to execute command: {}", e)),
-        Err(_) => Err(anyhow::anyhow!(
-            "Command timed out after {} seconds",
-            options.timeout.as_secs_f64()
-        )),
+async fn collect_pipe<R: AsyncRead + Unpin>(
+    mut pipe: R,
+    buf: Arc<Mutex<Vec<u8>>>,
+    stream: Option<mpsc::UnboundedSender<String>>,
+) {
+    let mut chunk = [0u8; 8192];
+    let mut line = Vec::new();
+    while let Ok(n) = pipe.read(&mut chunk).await {
+        if n == 0 {
+            break;
+        }
+        buf.lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .extend_from_slice(&chunk[..n]);
+        if let Some(tx) = &stream {
+            for &byte in &chunk[..n] {
+                if byte == b'\n' {
+                    let _ = tx.send(sanitize_output(&std::mem::take(&mut line)));
+                } else {
+                    line.push(byte);
+                }
+            }
+        }
+    }
+    if let Some(tx) = &stream {
+        if !line.is_empty() {
+            let _ = tx.send(sanitize_output(&line));
+        }
+    }
+}
+
+async fn finish_readers(readers: [Option<tokio::task::JoinHandle<()>>; 2], grace: Duration) {
+    for reader in readers.into_iter().flatten() {
+        let abort = reader.abort_handle();
+        if tokio::time::timeout(grace, reader).await.is_err() {
+            abort.abort();
+        }
+    }
+}
+
+fn take_buf(buf: &Arc<Mutex<Vec<u8>>>) -> Vec<u8> {
+    std::mem::take(&mut *buf.lock().unwrap_or_else(|e| e.into_inner()))
+}
+
+/// Kill the child's process group (or just the child off unix) and reap it.
+async fn kill_process_tree(child: &mut tokio::process::Child) {
+    #[cfg(unix)]
+    if let Some(pid) = child.id() {
+        // SAFETY: killpg only sends a signal; the group was created for this child.
+        unsafe {
+            libc::killpg(pid as libc::pid_t, libc::SIGKILL);
+        }
     }
+    let _ = child.kill().await;
+    let _ = child.wait().await;
 }
 
 async fn run_host_command(
@@ -640,7 +759,8 @@ async fn run_container_command(
                     } else {
                         "machinectl"
                     };
-                    let _ = run_shell_command(machinectl, &terminate_args, None, options).await;
+                    let _ = run_shell_command(machinectl, &terminate_args, None, &options.quiet())
+                        .await;
                 }
             }
         }
@@ -672,7 +792,7 @@ async fn running_container_leader(machine_name: &str, options: &CommandOptions)
         "Leader".to_string(),
         "--value".to_string(),
     ];
-    let output = run_shell_command(machinectl, &args, None, options)
+    let output = run_shell_command(machinectl, &args, None, &options.quiet())
         .await
         .ok()?;
     if !output.status.success() {
@@ -764,7 +884,7 @@ impl Tool for RunCommand {
                 },
                 "cwd": {
                     "type": "string",
-                    "description": "Optional: working directory. Defaults to workspace. Use relative paths (e.g., 'subdir/') or absolute for system access."
+                    "description": "Optional: working directory. Defaults to workspace. Use relative paths (e.g., 'subdir/') inside the workspace."
                 },
                 "timeout_secs": {
                     "type": "integer",
@@ -809,44 +929,39 @@ impl Tool for RunCommand {
     }
 
     async fn execute(&self, args: Value, working_dir: &Path) -> anyhow::Result<String> {
-        let command = args["command"]
-            .as_str()
-            .ok_or_else(|| anyhow::anyhow!("Missing 'command' argument"))?;
-
-        let container_root = container_root_from_env();
-        if container_root.is_none() {
-            // Validate command against dangerous patterns on host only.
-            if let Err(msg) = validate_command(command) {
-                tracing::warn!("Blocked dangerous command: {}", command);
-                return Err(anyhow::anyhow!("{}", msg));
-            }
-        }
-
-        let cwd = args["cwd"]
-            .as_str()
-            .map(|p| resolve_path(p, working_dir))
-            .unwrap_or_else(|| working_dir.to_path_buf());
-        let options = parse_command_options(&args);
-
-        tracing::info!("Executing command in {:?}: {}", cwd, command);
+        self.run(args, working_dir, None).await
+    }
 
-        let output = match container_root {
-            Some(container_root) => {
-                run_container_command(&container_root, &cwd, command, &options).await?
-            }
-            None => run_host_command(&cwd, command, &options).await?,
-        };
+    async fn execute_streaming(
+        &self,
+        args: Value,
+        working_dir: &Path,
+        output: mpsc::UnboundedSender<String>,
+    ) -> anyhow::Result<String> {
+        self.run(args, working_dir, Some(output)).await
+    }
+}
 
-        let stdout = sanitize_output(&output.stdout);
-        let stderr = sanitize_output(&output.stderr);
-        let exit_code = output.status.code().unwrap_or(-1);
+/// A finished command's exit code and full (untruncated) output.
+pub(super) struct CommandResult {
+    pub exit_code: i32,
+    pub stdout: String,
+    pub stderr: String,
+}
 
-        tracing::debug!(
-            "Command completed: exit={}, stdout_len={}, stderr_len={}",
+impl RunCommand {
+    async fn run(
+        &self,
+        args: Value,
+        working_dir: &Path,
+        stream: Option<mpsc::UnboundedSender<String>>,
+    ) -> anyhow::Result<String> {
+        let options = parse_command_options(&args);
+        let CommandResult {
             exit_code,
-            stdout.len(),
-            stderr.len()
-        );
+            stdout,
+            stderr,
+        } = self.run_captured(&args, working_dir, stream).await?;
 
         let result = if options.raw_output {
             let mut raw = String::new();
@@ -891,4 +1006,104 @@ impl Tool for RunCommand {
 
         Ok(result)
     }
+
+    /// Run `args["command"]` like the tool does, but return the exit code and
+    /// the full output, for tools that read the output themselves.
+    pub(super) async fn run_captured(
+        &self,
+        args: &Value,
+        working_dir: &Path,
+        stream: Option<mpsc::UnboundedSender<String>>,
+    ) -> anyhow::Result<CommandResult> {
+        let command = args["command"]
+            .as_str()
+            .ok_or_else(|| anyhow::anyhow!("Missing 'command' argument"))?;
+
+        let container_root = container_root_from_env();
+        if container_root.is_none() {
+            // Validate command against dangerous patterns on host only.
+            if let Err(msg) = validate_command(command) {
+                tracing::warn!("Blocked dangerous command: {}", command);
+                return Err(anyhow::anyhow!("{}", msg));
+            }
+        }
+
+        let cwd = args["cwd"]
+            .as_str()
+            .map(|p| resolve_path(p, working_dir))
+            .transpose()?
+            .unwrap_or_else(|| working_dir.to_path_buf());
+        let mut options = parse_command_options(args);
+        options.stream = stream;
+
+        tracing::info!("Executing command in {:?}: {}", cwd, command);
+
+        let output = match container_root {
+            Some(container_root) => {
+                run_container_command(&container_root, &cwd, command, &options).await?
+            }
+            None => run_host_command(&cwd, command, &options).await?,
+        };
+
+        let stdout = sanitize_output(&output.stdout);
+        let stderr = sanitize_output(&output.stderr);
+        let exit_code = output.status.code().unwrap_or(-1);
+
+        tracing::debug!(
+            "Command completed: exit={}, stdout_len={}, stderr_len={}",
+            exit_code,
+            stdout.len(),
+            stderr.len()
+        );
+
+        Ok(CommandResult {
+            exit_code,
+            stdout,
+            stderr,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_timeout_kills_command_and_keeps_partial_output() {
+        let dir = tempfile::tempdir().unwrap();
+        let options = parse_command_options(&json!({ "timeout_ms": 500 }));
+        let started = std::time::Instant::now();
+
+        let err = run_host_command(dir.path(), "echo partial; sleep 30", &options)
+            .await
+            .unwrap_err()
+            .to_string();
+
+        assert!(err.contains("timed out after 0.5 seconds"));
+        assert!(err.contains("partial"));
+        assert!(started.elapsed() < Duration::from_secs(10));
+    }
+
+    #[tokio::test]
+    async fn test_streaming_sends_output_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        let (tx, mut rx) = mpsc::unbounded_channel();
+
+        let result = RunCommand
+            .execute_streaming(
+                json!({ "command": "echo first; echo second >&2; printf third" }),
+                dir.path(),
+                tx,
+            )
+            .await
+            .unwrap();
+        assert!(result.contains("Exit code: 0"));
+
+        let mut lines = Vec::new();
+        while let Ok(line) = rx.try_recv() {
+            lines.push(line);
+        }
+        lines.sort();
+        assert_eq!(lines, vec!["first", "second", "third"]);
+    }
 }
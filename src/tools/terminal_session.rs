@@ -0,0 +1,554 @@
+//! Persistent shell sessions.
+//!
+//! `run_command` spawns a fresh shell per call, so `cd`, `export` and activated
+//! virtualenvs are lost between calls. `terminal_session` keeps one long-lived
+//! shell per session instead: commands run in it one after another and share
+//! its working directory and environment.
+//!
+//! Each command is written to the shell's stdin followed by a marker line
+//! carrying a per-session token, the exit code and `$PWD`; output is read up to
+//! that marker. Sessions idle for longer than the idle timeout are reaped, and
+//! a command that times out takes its session down with it.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, Weak};
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use serde_json::{json, Value};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::process::{Child, ChildStdin, ChildStdout, Command};
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+use super::terminal::{
+    container_root_from_env, parse_env, parse_max_output_chars, parse_timeout, resolve_shell,
+    sanitize_output, validate_command,
+};
+use super::{resolve_path_simple as resolve_path, Tool};
+
+/// Sessions unused for this long are closed.
+const DEFAULT_IDLE_TIMEOUT: Duration = Duration::from_secs(30 * 60);
+
+/// Open sessions allowed at once.
+const MAX_SESSIONS: usize = 8;
+
+/// Output kept per command before the rest is dropped (the shell is still drained).
+const MAX_BUFFERED_OUTPUT: usize = 1024 * 1024;
+
+/// A long-lived shell with stderr merged into stdout.
+struct Shell {
+    child: Child,
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+    marker: String,
+    cwd: PathBuf,
+}
+
+/// Outcome of one command.
+struct CommandOutput {
+    /// `None` when the shell exited instead of finishing the command
+    exit_code: Option<i32>,
+    output: String,
+}
+
+impl Shell {
+    async fn spawn(shell: &str, cwd: &Path, env: &HashMap<String, String>) -> anyhow::Result<Self> {
+        let mut cmd = Command::new(shell);
+        cmd.current_dir(cwd)
+            .envs(env)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .kill_on_drop(true);
+        // Own process group, so closing the session also kills what it started.
+        #[cfg(unix)]
+        cmd.process_group(0);
+        let mut child = cmd
+            .spawn()
+            .map_err(|e| anyhow::anyhow!("Failed to start shell {}: {}", shell, e))?;
+        let stdin = child.stdin.take().expect("piped stdin");
+        let stdout = BufReader::new(child.stdout.take().expect("piped stdout"));
+        let mut shell = Self {
+            child,
+            stdin,
+            stdout,
+            marker: format!("__OPEN_AGENT_DONE_{}__", Uuid::new_v4().simple()),
+            cwd: cwd.to_path_buf(),
+        };
+        shell.stdin.write_all(b"exec 2>&1\n").await?;
+        Ok(shell)
+    }
+
+    /// Run `command`, returning its output once the marker line comes back.
+    async fn run(
+        &mut self,
+        command: &str,
+        stream: Option<&mpsc::UnboundedSender<String>>,
+    ) -> anyhow::Result<CommandOutput> {
+        // `eval` keeps syntax errors from ending the shell; stdin stays ours.
+        let script = format!(
+            "eval '{}' < /dev/null\n__oa_status=$?\nprintf '\\n%s %s %s\\n' '{}' \"$__oa_status\" \"$PWD\"\n",
+            command.replace('\'', r"'\''"),
+            self.marker
+        );
+        self.stdin.write_all(script.as_bytes()).await?;
+        self.stdin.flush().await?;
+
+        let mut output = Vec::new();
+        let mut line = Vec::new();
+        loop {
+            line.clear();
+            if self.stdout.read_until(b'\n', &mut line).await? == 0 {
+                let status = self.child.wait().await?;
+                output.extend_from_slice(&line);
+                return Ok(CommandOutput {
+                    exit_code: None,
+                    output: format!(
+                        "{}\n[shell exited with code {}]",
+                        sanitize_output(&output).trim_end(),
+                        status.code().unwrap_or(-1)
+                    ),
+                });
+            }
+            let text = String::from_utf8_lossy(&line);
+            if let Some(rest) = text.strip_prefix(self.marker.as_str()) {
+                let rest = rest.trim_end_matches('\n');
+                let (code, cwd) = rest.trim_start().split_once(' ').unwrap_or((rest, ""));
+                if !cwd.is_empty() {
+                    self.cwd = PathBuf::from(cwd);
+                }
+                // Includes the newline printed before the marker.
+                return Ok(CommandOutput {
+                    exit_code: Some(code.trim().parse().unwrap_or(-1)),
+                    output: sanitize_output(&output).trim_end_matches('\n').to_string(),
+                });
+            }
+            if let Some(tx) = stream {
+                let _ = tx.send(sanitize_output(line.strip_suffix(b"\n").unwrap_or(&line)));
+            }
+            if output.len() < MAX_BUFFERED_OUTPUT {
+                output.extend_from_slice(&line);
+            }
+        }
+    }
+}
+
+impl Drop for Shell {
+    fn drop(&mut self) {
+        #[cfg(unix)]
+        if let Some(pid) = self.child.id() {
+            // SAFETY: killpg only sends a signal; the group was created for this shell.
+            unsafe {
+                libc::killpg(pid as libc::pid_t, libc::SIGKILL);
+            }
+        }
+    }
+}
+
+struct SessionEntry {
+    shell: Arc<tokio::sync::Mutex<Shell>>,
+    last_used: Instant,
+}
+
+type SessionMap = Mutex<HashMap<String, SessionEntry>>;
+
+/// Close sessions idle for longer than `idle_timeout` (busy ones are kept).
+fn reap_idle(sessions: &SessionMap, idle_timeout: Duration) {
+    let mut sessions = sessions.lock().unwrap_or_else(|e| e.into_inner());
+    sessions.retain(|id, entry| {
+        let keep = entry.last_used.elapsed() < idle_timeout || entry.shell.try_lock().is_err();
+        if !keep {
+            tracing::info!("Closing idle terminal session {}", id);
+        }
+        keep
+    });
+}
+
+/// Open, use and close persistent shell sessions.
+pub struct TerminalSession {
+    sessions: Arc<SessionMap>,
+    idle_timeout: Duration,
+    reaper_started: AtomicBool,
+}
+
+impl Default for TerminalSession {
+    fn default() -> Self {
+        Self::with_idle_timeout(DEFAULT_IDLE_TIMEOUT)
+    }
+}
+
+impl TerminalSession {
+    pub fn with_idle_timeout(idle_timeout: Duration) -> Self {
+        Self {
+            sessions: Arc::new(Mutex::new(HashMap::new())),
+            idle_timeout,
+            reaper_started: AtomicBool::new(false),
+        }
+    }
+
+    /// Reap idle sessions in the background for as long as the tool is alive.
+    fn start_reaper(&self) {
+        if self.reaper_started.swap(true, Ordering::SeqCst) {
+            return;
+        }
+        let sessions: Weak<SessionMap> = Arc::downgrade(&self.sessions);
+        let idle_timeout = self.idle_timeout;
+        let period = (idle_timeout / 4).max(Duration::from_millis(50));
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(period).await;
+                let Some(sessions) = sessions.upgrade() else {
+                    break;
+                };
+                reap_idle(&sessions, idle_timeout);
+            }
+        });
+    }
+
+    fn session(&self, args: &Value) -> anyhow::Result<(String, Arc<tokio::sync::Mutex<Shell>>)> {
+        let id = args["session_id"]
+            .as_str()
+            .ok_or_else(|| anyhow::anyhow!("Missing 'session_id' argument"))?;
+        let mut sessions = self.sessions.lock().unwrap_or_else(|e| e.into_inner());
+        let entry = sessions.get_mut(id).ok_or_else(|| {
+            anyhow::anyhow!(
+                "Unknown terminal session '{}' (it may have been closed after being idle); open a new one",
+                id
+            )
+        })?;
+        entry.last_used = Instant::now();
+        Ok((id.to_string(), entry.shell.clone()))
+    }
+
+    fn remove(&self, id: &str) -> Option<SessionEntry> {
+        self.sessions
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(id)
+    }
+
+    async fn open(&self, args: &Value, working_dir: &Path) -> anyhow::Result<String> {
+        if container_root_from_env().is_some() {
+            anyhow::bail!(
+                "terminal_session is not available in container workspaces; use run_command"
+            );
+        }
+        if self
+            .sessions
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .len()
+            >= MAX_SESSIONS
+        {
+            anyhow::bail!(
+                "Too many open terminal sessions (max {}); close one first",
+                MAX_SESSIONS
+            );
+        }
+        let cwd = args["cwd"]
+            .as_str()
+            .map(|p| resolve_path(p, working_dir))
+            .transpose()?
+            .unwrap_or_else(|| working_dir.to_path_buf());
+        let shell = resolve_shell(args["shell"].as_str(), None);
+        let shell = Shell::spawn(&shell, &cwd, &parse_env(args)).await?;
+        let id = Uuid::new_v4().simple().to_string()[..8].to_string();
+        let cwd = shell.cwd.display().to_string();
+        self.sessions
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(
+                id.clone(),
+                SessionEntry {
+                    shell: Arc::new(tokio::sync::Mutex::new(shell)),
+                    last_used: Instant::now(),
+                },
+            );
+        self.start_reaper();
+        Ok(format!("Opened terminal session {}\ncwd: {}", id, cwd))
+    }
+
+    async fn run(
+        &self,
+        args: &Value,
+        stream: Option<mpsc::UnboundedSender<String>>,
+    ) -> anyhow::Result<String> {
+        let command = args["command"]
+            .as_str()
+            .ok_or_else(|| anyhow::anyhow!("Missing 'command' argument"))?;
+        if let Err(msg) = validate_command(command) {
+            tracing::warn!("Blocked dangerous command: {}", command);
+            return Err(anyhow::anyhow!("{}", msg));
+        }
+        let (id, shell) = self.session(args)?;
+        let mut shell = shell.lock().await;
+        let timeout = parse_timeout(args);
+
+        let result = match tokio::time::timeout(timeout, shell.run(command, stream.as_ref())).await
+        {
+            Ok(Ok(result)) => result,
+            Ok(Err(e)) => {
+                drop(shell);
+                self.remove(&id);
+                return Err(anyhow::anyhow!(
+                    "Terminal session {} failed and was closed: {}",
+                    id,
+                    e
+                ));
+            }
+            Err(_) => {
+                drop(shell);
+                self.remove(&id);
+                return Err(anyhow::anyhow!(
+                    "Command timed out after {} seconds; terminal session {} was closed",
+                    timeout.as_secs_f64(),
+                    id
+                ));
+            }
+        };
+        if let Some(entry) = self
+            .sessions
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get_mut(&id)
+        {
+            entry.last_used = Instant::now();
+        }
+
+        let mut text = match result.exit_code {
+            Some(code) => format!("Exit code: {}\ncwd: {}\n", code, shell.cwd.display()),
+            None => format!("Terminal session {} ended\n", id),
+        };
+        if result.exit_code.is_none() {
+            drop(shell);
+            self.remove(&id);
+        }
+        if !result.output.is_empty() {
+            text.push('\n');
+            text.push_str(&result.output);
+        }
+        let max_output_chars = parse_max_output_chars(args);
+        if text.len() > max_output_chars {
+            let end = super::safe_truncate_index(&text, max_output_chars);
+            text.truncate(end);
+            text.push_str("\n... [output truncated]");
+        }
+        Ok(text)
+    }
+
+    fn list(&self) -> String {
+        let sessions = self.sessions.lock().unwrap_or_else(|e| e.into_inner());
+        if sessions.is_empty() {
+            return "No open terminal sessions".to_string();
+        }
+        let mut lines: Vec<String> = sessions
+            .iter()
+            .map(|(id, entry)| {
+                let cwd = match entry.shell.try_lock() {
+                    Ok(shell) => shell.cwd.display().to_string(),
+                    Err(_) => "(running a command)".to_string(),
+                };
+                format!(
+                    "{}  cwd: {}  idle: {}s",
+                    id,
+                    cwd,
+                    entry.last_used.elapsed().as_secs()
+                )
+            })
+            .collect();
+        lines.sort();
+        lines.join("\n")
+    }
+
+    async fn dispatch(
+        &self,
+        args: Value,
+        working_dir: &Path,
+        stream: Option<mpsc::UnboundedSender<String>>,
+    ) -> anyhow::Result<String> {
+        reap_idle(&self.sessions, self.idle_timeout);
+        match args["action"].as_str().unwrap_or("run") {
+            "open" => self.open(&args, working_dir).await,
+            "run" => self.run(&args, stream).await,
+            "close" => {
+                let (id, _) = self.session(&args)?;
+                self.remove(&id);
+                Ok(format!("Closed terminal session {}", id))
+            }
+            "list" => Ok(self.list()),
+            other => Err(anyhow::anyhow!(
+                "Unknown action '{}': expected 'open', 'run', 'close' or 'list'",
+                other
+            )),
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for TerminalSession {
+    fn name(&self) -> &str {
+        "terminal_session"
+    }
+
+    fn description(&self) -> &str {
+        "Run commands in a persistent shell where `cd`, exported variables and activated virtualenvs carry over between calls. Actions: 'open' (returns a session_id), 'run' (a command in the session; output includes the exit code and the working directory afterwards), 'close', 'list'. Idle sessions are closed automatically."
+    }
+
+    fn parameters_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["open", "run", "close", "list"],
+                    "description": "Operation to perform (default: 'run')"
+                },
+                "session_id": {
+                    "type": "string",
+                    "description": "Session to run in or close (from 'open')"
+                },
+                "command": {
+                    "type": "string",
+                    "description": "Shell command to run (for 'run')"
+                },
+                "cwd": {
+                    "type": "string",
+                    "description": "Starting directory (for 'open'; default: workspace)"
+                },
+                "env": {
+                    "type": "object",
+                    "description": "Environment variables for the session (for 'open')",
+                    "additionalProperties": { "type": "string" }
+                },
+                "shell": {
+                    "type": "string",
+                    "description": "Shell executable (for 'open'; default: /bin/bash, else /bin/sh)"
+                },
+                "timeout_secs": {
+                    "type": "integer",
+                    "description": "Timeout for 'run' in seconds; on timeout the session is closed"
+                },
+                "max_output_chars": {
+                    "type": "integer",
+                    "description": "Maximum output characters to return (default: 10000)."
+                }
+            },
+            "required": ["action"]
+        })
+    }
+
+    async fn execute(&self, args: Value, working_dir: &Path) -> anyhow::Result<String> {
+        self.dispatch(args, working_dir, None).await
+    }
+
+    async fn execute_streaming(
+        &self,
+        args: Value,
+        working_dir: &Path,
+        output: mpsc::UnboundedSender<String>,
+    ) -> anyhow::Result<String> {
+        self.dispatch(args, working_dir, Some(output)).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session_id(opened: &str) -> String {
+        opened
+            .lines()
+            .next()
+            .and_then(|line| line.strip_prefix("Opened terminal session "))
+            .unwrap()
+            .to_string()
+    }
+
+    #[tokio::test]
+    async fn test_session_keeps_cwd_and_env() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("sub")).unwrap();
+        let tool = TerminalSession::default();
+        let id = session_id(
+            &tool
+                .execute(json!({"action": "open"}), dir.path())
+                .await
+                .unwrap(),
+        );
+
+        let out = tool
+            .execute(
+                json!({"session_id": id, "command": "cd sub && export GREETING='it''s me'"}),
+                dir.path(),
+            )
+            .await
+            .unwrap();
+        let sub = dir.path().join("sub").canonicalize().unwrap();
+        assert_eq!(out, format!("Exit code: 0\ncwd: {}\n", sub.display()));
+
+        let out = tool
+            .execute(
+                json!({"session_id": id, "command": "echo \"$GREETING\"; ls ..; false"}),
+                dir.path(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            out,
+            format!("Exit code: 1\ncwd: {}\n\nits me\nsub", sub.display())
+        );
+
+        // A syntax error doesn't take the session down.
+        let out = tool
+            .execute(json!({"session_id": id, "command": "if then"}), dir.path())
+            .await
+            .unwrap();
+        assert!(out.starts_with("Exit code: 2"), "{}", out);
+        assert!(tool
+            .execute(json!({"session_id": id, "command": "pwd"}), dir.path())
+            .await
+            .unwrap()
+            .ends_with(&sub.display().to_string()));
+
+        let out = tool
+            .execute(json!({"session_id": id, "command": "exit 3"}), dir.path())
+            .await
+            .unwrap();
+        assert!(out.contains("[shell exited with code 3]"), "{}", out);
+        assert!(tool
+            .execute(json!({"session_id": id, "command": "pwd"}), dir.path())
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_idle_sessions_are_reaped_and_timeouts_close() {
+        let dir = tempfile::tempdir().unwrap();
+        let tool = TerminalSession::with_idle_timeout(Duration::from_millis(200));
+        let id = session_id(
+            &tool
+                .execute(json!({"action": "open"}), dir.path())
+                .await
+                .unwrap(),
+        );
+        let err = tool
+            .execute(
+                json!({"session_id": id, "command": "sleep 30", "timeout_ms": 100}),
+                dir.path(),
+            )
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("was closed"), "{}", err);
+
+        tool.execute(json!({"action": "open"}), dir.path())
+            .await
+            .unwrap();
+        assert_ne!(tool.list(), "No open terminal sessions");
+        tokio::time::sleep(Duration::from_millis(600)).await;
+        assert_eq!(tool.list(), "No open terminal sessions");
+    }
+}
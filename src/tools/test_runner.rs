@@ -0,0 +1,333 @@
+//! Test runner tool: run a project's test suite, optionally filtered by test name.
+//!
+//! ## Workspace-First Design
+//!
+//! The framework is detected from the project directory (workspace by default):
+//! - `Cargo.toml` → `cargo test <filter>`
+//! - `go.mod` → `go test ./... -run <filter>`
+//! - `package.json` with vitest/jest → `npx vitest run -t <filter>` / `npx jest -t <filter>`
+//! - Python project files → `python -m pytest -k <filter>`
+//!
+//! Execution is delegated to `run_command` so container workspaces behave the same way.
+
+use std::path::Path;
+
+use async_trait::async_trait;
+use regex::Regex;
+use serde_json::{json, Value};
+
+use super::terminal::{CommandResult, RunCommand};
+use super::{resolve_path_simple as resolve_path, Tool};
+
+/// A supported test framework.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TestFramework {
+    Cargo,
+    GoTest,
+    Jest,
+    Vitest,
+    Pytest,
+}
+
+impl TestFramework {
+    fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "cargo" | "rust" => Some(Self::Cargo),
+            "go" | "gotest" | "go_test" => Some(Self::GoTest),
+            "jest" => Some(Self::Jest),
+            "vitest" => Some(Self::Vitest),
+            "pytest" | "python" => Some(Self::Pytest),
+            _ => None,
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Cargo => "cargo",
+            Self::GoTest => "go",
+            Self::Jest => "jest",
+            Self::Vitest => "vitest",
+            Self::Pytest => "pytest",
+        }
+    }
+}
+
+/// Detect the test framework used by the project at `dir`.
+pub fn detect_test_framework(dir: &Path) -> Option<TestFramework> {
+    if dir.join("Cargo.toml").exists() {
+        return Some(TestFramework::Cargo);
+    }
+    if dir.join("go.mod").exists() {
+        return Some(TestFramework::GoTest);
+    }
+    if let Ok(package_json) = std::fs::read_to_string(dir.join("package.json")) {
+        if package_json.contains("vitest") {
+            return Some(TestFramework::Vitest);
+        }
+        if package_json.contains("jest") {
+            return Some(TestFramework::Jest);
+        }
+    }
+    let python_markers = [
+        "pytest.ini",
+        "conftest.py",
+        "pyproject.toml",
+        "setup.cfg",
+        "setup.py",
+        "requirements.txt",
+    ];
+    if python_markers.iter().any(|m| dir.join(m).exists()) {
+        return Some(TestFramework::Pytest);
+    }
+    None
+}
+
+/// Quote a value for safe interpolation into a POSIX shell command.
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+/// Build the shell command that runs the tests, selecting only `filter` when given.
+pub fn build_test_command(framework: TestFramework, filter: Option<&str>) -> String {
+    let filter = filter.map(str::trim).filter(|f| !f.is_empty());
+    match (framework, filter) {
+        (TestFramework::Cargo, Some(f)) => format!("cargo test {}", shell_quote(f)),
+        (TestFramework::Cargo, None) => "cargo test".to_string(),
+        (TestFramework::GoTest, Some(f)) => format!("go test ./... -run {}", shell_quote(f)),
+        (TestFramework::GoTest, None) => "go test ./...".to_string(),
+        (TestFramework::Jest, Some(f)) => format!("npx jest -t {}", shell_quote(f)),
+        (TestFramework::Jest, None) => "npx jest".to_string(),
+        (TestFramework::Vitest, Some(f)) => format!("npx vitest run -t {}", shell_quote(f)),
+        (TestFramework::Vitest, None) => "npx vitest run".to_string(),
+        (TestFramework::Pytest, Some(f)) => format!("python -m pytest -k {}", shell_quote(f)),
+        (TestFramework::Pytest, None) => "python -m pytest".to_string(),
+    }
+}
+
+/// Extract the framework's pass/fail summary lines from test output.
+fn summarize_test_output(framework: TestFramework, output: &str) -> Vec<String> {
+    let pattern = match framework {
+        TestFramework::Cargo => r"^test result: .*$",
+        TestFramework::GoTest => r"^(ok|FAIL|---\s+FAIL:)\s+.*$",
+        TestFramework::Jest | TestFramework::Vitest => r"^\s*Tests?:?\s+.*(passed|failed).*$",
+        TestFramework::Pytest => r"^=+ .*(passed|failed|error|no tests ran).* =+$",
+    };
+    let re = Regex::new(&format!("(?m){}", pattern)).expect("valid summary pattern");
+    re.find_iter(output)
+        .map(|m| m.as_str().trim().to_string())
+        .collect()
+}
+
+/// Bytes of test output returned to the agent (the end is kept).
+const MAX_OUTPUT_CHARS: usize = 20_000;
+
+/// The last `max` bytes of `output`, starting on a line (or char) boundary.
+fn output_tail(output: &str, max: usize) -> &str {
+    if output.len() <= max {
+        return output;
+    }
+    let mut start = output.len() - max;
+    while !output.is_char_boundary(start) {
+        start += 1;
+    }
+    match output[start..].find('\n') {
+        Some(idx) if idx + 1 < output.len() - start => &output[start + idx + 1..],
+        _ => &output[start..],
+    }
+}
+
+/// Format a test run for the agent. The summary is taken from the full output;
+/// only the output shown after it is capped.
+fn test_report(framework: TestFramework, command: &str, run: CommandResult) -> String {
+    let mut output = run.stdout;
+    if !run.stderr.is_empty() {
+        if !output.is_empty() {
+            output.push('\n');
+        }
+        output.push_str(&run.stderr);
+    }
+    let passed = run.exit_code == 0;
+    let summary = summarize_test_output(framework, &output);
+
+    let mut result = format!(
+        "Tests {} ({}: `{}`)\nExit code: {}\n",
+        if passed { "PASSED" } else { "FAILED" },
+        framework.as_str(),
+        command,
+        run.exit_code
+    );
+    if !summary.is_empty() {
+        result.push_str("\n--- summary ---\n");
+        result.push_str(&summary.join("\n"));
+        result.push('\n');
+    }
+    result.push_str("\n--- output ---\n");
+    let tail = output_tail(&output, MAX_OUTPUT_CHARS);
+    if tail.len() < output.len() {
+        result.push_str("... [earlier output truncated]\n");
+    }
+    result.push_str(tail);
+    result
+}
+
+/// Run the project's tests, optionally only those matching a name filter.
+pub struct RunTests;
+
+#[async_trait]
+impl Tool for RunTests {
+    fn name(&self) -> &str {
+        "run_tests"
+    }
+
+    fn description(&self) -> &str {
+        "Run the project's test suite (cargo, go, jest, vitest, pytest — auto-detected). Pass 'filter' to run only tests whose name matches, for a fast edit-test loop. Returns the pass/fail summary and output."
+    }
+
+    fn parameters_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "filter": {
+                    "type": "string",
+                    "description": "Optional: test name or pattern. Maps to the framework's selection flag (cargo test <name>, pytest -k, jest -t, go test -run)."
+                },
+                "path": {
+                    "type": "string",
+                    "description": "Project directory. Defaults to workspace ('.')."
+                },
+                "framework": {
+                    "type": "string",
+                    "enum": ["cargo", "go", "jest", "vitest", "pytest"],
+                    "description": "Optional: override framework auto-detection."
+                },
+                "timeout_secs": {
+                    "type": "integer",
+                    "description": "Timeout in seconds (default: command default)."
+                }
+            },
+            "required": []
+        })
+    }
+
+    async fn execute(&self, args: Value, working_dir: &Path) -> anyhow::Result<String> {
+        let path = args["path"].as_str().unwrap_or(".");
+        let project_dir = resolve_path(path, working_dir)?;
+
+        let framework = match args["framework"].as_str() {
+            Some(name) => TestFramework::parse(name)
+                .ok_or_else(|| anyhow::anyhow!("Unsupported test framework: {}", name))?,
+            None => detect_test_framework(&project_dir).ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Could not detect a test framework in {}. Pass 'framework' explicitly or use run_command.",
+                    project_dir.display()
+                )
+            })?,
+        };
+
+        let command = build_test_command(framework, args["filter"].as_str());
+        tracing::info!("Running {} tests: {}", framework.as_str(), command);
+
+        let mut run_args = json!({
+            "command": command,
+            "cwd": project_dir.to_string_lossy(),
+        });
+        if let Some(timeout) = args["timeout_secs"].as_u64() {
+            run_args["timeout_secs"] = json!(timeout);
+        }
+
+        let run = RunCommand
+            .run_captured(&run_args, working_dir, None)
+            .await?;
+        let result = test_report(framework, &command, run);
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_selection_flag_per_framework() {
+        let f = Some("parses_config");
+        assert_eq!(
+            build_test_command(TestFramework::Cargo, f),
+            "cargo test 'parses_config'"
+        );
+        assert_eq!(
+            build_test_command(TestFramework::Pytest, f),
+            "python -m pytest -k 'parses_config'"
+        );
+        assert_eq!(
+            build_test_command(TestFramework::Jest, f),
+            "npx jest -t 'parses_config'"
+        );
+        assert_eq!(
+            build_test_command(TestFramework::Vitest, f),
+            "npx vitest run -t 'parses_config'"
+        );
+        assert_eq!(
+            build_test_command(TestFramework::GoTest, f),
+            "go test ./... -run 'parses_config'"
+        );
+        assert_eq!(build_test_command(TestFramework::Cargo, None), "cargo test");
+        assert_eq!(
+            build_test_command(TestFramework::Jest, Some("it's fine")),
+            r"npx jest -t 'it'\''s fine'"
+        );
+    }
+
+    #[test]
+    fn test_detect_framework() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(detect_test_framework(dir.path()), None);
+
+        std::fs::write(
+            dir.path().join("package.json"),
+            r#"{"devDependencies":{"jest":"^29"}}"#,
+        )
+        .unwrap();
+        assert_eq!(detect_test_framework(dir.path()), Some(TestFramework::Jest));
+
+        std::fs::write(dir.path().join("Cargo.toml"), "[package]").unwrap();
+        assert_eq!(
+            detect_test_framework(dir.path()),
+            Some(TestFramework::Cargo)
+        );
+    }
+
+    #[test]
+    fn test_summarize_output() {
+        let out =
+            "running 1 test\ntest foo ... ok\n\ntest result: ok. 1 passed; 0 failed; 0 ignored\n";
+        assert_eq!(
+            summarize_test_output(TestFramework::Cargo, out),
+            vec!["test result: ok. 1 passed; 0 failed; 0 ignored".to_string()]
+        );
+        let out = "collected 3 items\n\n===== 2 passed, 1 failed in 0.12s =====\n";
+        assert_eq!(summarize_test_output(TestFramework::Pytest, out).len(), 1);
+    }
+
+    #[test]
+    fn test_report_keeps_summary_of_long_output() {
+        let mut stdout: String = (0..3000)
+            .map(|i| format!("test case_{} ... ok\n", i))
+            .collect();
+        stdout.push_str("test result: FAILED. 2999 passed; 1 failed; 0 ignored\n");
+        let report = test_report(
+            TestFramework::Cargo,
+            "cargo test",
+            CommandResult {
+                exit_code: 101,
+                stdout,
+                stderr: "error: test failed, to rerun pass `--lib`".to_string(),
+            },
+        );
+        assert!(report.starts_with("Tests FAILED (cargo: `cargo test`)\nExit code: 101\n"));
+        assert!(report
+            .contains("--- summary ---\ntest result: FAILED. 2999 passed; 1 failed; 0 ignored\n"));
+        assert!(report.contains("... [earlier output truncated]\ntest case_"));
+        assert!(report.ends_with("error: test failed, to rerun pass `--lib`"));
+        assert!(report.len() < MAX_OUTPUT_CHARS + 500);
+    }
+}
@@ -0,0 +1,298 @@
+//! Todo tools: an explicit plan the agent keeps in the workspace.
+//!
+//! Todos are stored in `.openagent/todos.json` under the working directory, so
+//! the plan survives across turns (and restarts). `todo_add` and
+//! `todo_complete` return the full list; the control session turns those
+//! results into `todos_updated` events so the UI can render a checklist.
+
+use std::path::Path;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use super::Tool;
+
+/// Todo file, relative to the working directory.
+pub const TODO_FILE: &str = ".openagent/todos.json";
+
+/// One entry of the agent's plan.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TodoItem {
+    pub id: u32,
+    pub text: String,
+    pub done: bool,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct TodoFile {
+    next_id: u32,
+    todos: Vec<TodoItem>,
+}
+
+fn load(working_dir: &Path) -> anyhow::Result<TodoFile> {
+    let path = working_dir.join(TODO_FILE);
+    match std::fs::read(&path) {
+        Ok(bytes) => serde_json::from_slice(&bytes)
+            .map_err(|e| anyhow::anyhow!("Corrupt todo file {}: {}", path.display(), e)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(TodoFile::default()),
+        Err(e) => Err(anyhow::anyhow!("Failed to read {}: {}", path.display(), e)),
+    }
+}
+
+fn save(working_dir: &Path, file: &TodoFile) -> anyhow::Result<()> {
+    let path = working_dir.join(TODO_FILE);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let tmp = path.with_extension("json.tmp");
+    std::fs::write(&tmp, serde_json::to_vec_pretty(file)?)?;
+    std::fs::rename(&tmp, &path)?;
+    Ok(())
+}
+
+fn render(todos: &[TodoItem]) -> anyhow::Result<String> {
+    let done = todos.iter().filter(|t| t.done).count();
+    Ok(serde_json::to_string_pretty(&json!({
+        "todos": todos,
+        "open": todos.len() - done,
+        "done": done,
+    }))?)
+}
+
+/// The full todo list carried by a `todo_add` / `todo_complete` result, if `name` is one of them.
+///
+/// Accepts MCP-prefixed names (`mcp__workspace__todo_add`) and results given
+/// as an object, a JSON string or MCP text content blocks.
+pub fn todos_from_tool_result(name: &str, result: &Value) -> Option<Vec<TodoItem>> {
+    let base = name.rsplit("__").next().unwrap_or(name);
+    if !matches!(base, "todo_add" | "todo_complete") {
+        return None;
+    }
+    let text = match result {
+        Value::String(text) => Some(text.as_str()),
+        Value::Array(blocks) => blocks
+            .iter()
+            .find_map(|block| block.get("text").and_then(|t| t.as_str())),
+        _ => None,
+    };
+    let object = match text {
+        Some(text) => serde_json::from_str::<Value>(text).ok()?,
+        None => result.clone(),
+    };
+    serde_json::from_value(object.get("todos")?.clone()).ok()
+}
+
+/// Add one or more items to the plan.
+pub struct TodoAdd;
+
+#[async_trait]
+impl Tool for TodoAdd {
+    fn name(&self) -> &str {
+        "todo_add"
+    }
+
+    fn description(&self) -> &str {
+        "Add items to your mission plan (a todo list kept in the workspace across turns). Use it to write down the steps of a multi-step task, then mark them with todo_complete as you go. Returns the full list."
+    }
+
+    fn parameters_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "items": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "Todo items to add, in order."
+                }
+            },
+            "required": ["items"]
+        })
+    }
+
+    async fn execute(&self, args: Value, working_dir: &Path) -> anyhow::Result<String> {
+        let items: Vec<String> = args["items"]
+            .as_array()
+            .ok_or_else(|| anyhow::anyhow!("Missing 'items' array"))?
+            .iter()
+            .filter_map(|item| item.as_str())
+            .map(|item| item.trim().to_string())
+            .filter(|item| !item.is_empty())
+            .collect();
+        if items.is_empty() {
+            anyhow::bail!("No todo items given");
+        }
+
+        let mut file = load(working_dir)?;
+        for text in items {
+            file.next_id += 1;
+            file.todos.push(TodoItem {
+                id: file.next_id,
+                text,
+                done: false,
+            });
+        }
+        save(working_dir, &file)?;
+        render(&file.todos)
+    }
+}
+
+/// Mark plan items as done.
+pub struct TodoComplete;
+
+#[async_trait]
+impl Tool for TodoComplete {
+    fn name(&self) -> &str {
+        "todo_complete"
+    }
+
+    fn description(&self) -> &str {
+        "Mark items of your mission plan as done, by id (see todo_list). Returns the full list."
+    }
+
+    fn parameters_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "ids": {
+                    "type": "array",
+                    "items": { "type": "integer" },
+                    "description": "Ids of the todo items that are done."
+                }
+            },
+            "required": ["ids"]
+        })
+    }
+
+    async fn execute(&self, args: Value, working_dir: &Path) -> anyhow::Result<String> {
+        let ids: Vec<u64> = args["ids"]
+            .as_array()
+            .ok_or_else(|| anyhow::anyhow!("Missing 'ids' array"))?
+            .iter()
+            .filter_map(|id| id.as_u64())
+            .collect();
+
+        let mut file = load(working_dir)?;
+        let unknown: Vec<String> = ids
+            .iter()
+            .filter(|id| !file.todos.iter().any(|t| u64::from(t.id) == **id))
+            .map(|id| id.to_string())
+            .collect();
+        if !unknown.is_empty() {
+            anyhow::bail!("Unknown todo id(s): {}", unknown.join(", "));
+        }
+        for todo in &mut file.todos {
+            if ids.contains(&u64::from(todo.id)) {
+                todo.done = true;
+            }
+        }
+        save(working_dir, &file)?;
+        render(&file.todos)
+    }
+}
+
+/// Show the plan.
+pub struct TodoList;
+
+#[async_trait]
+impl Tool for TodoList {
+    fn name(&self) -> &str {
+        "todo_list"
+    }
+
+    fn description(&self) -> &str {
+        "Show your mission plan: every todo item with its id and whether it is done."
+    }
+
+    fn parameters_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "include_done": {
+                    "type": "boolean",
+                    "description": "Include completed items (default: true)."
+                }
+            },
+            "required": []
+        })
+    }
+
+    async fn execute(&self, args: Value, working_dir: &Path) -> anyhow::Result<String> {
+        let include_done = args["include_done"].as_bool().unwrap_or(true);
+        let file = load(working_dir)?;
+        let todos: Vec<TodoItem> = file
+            .todos
+            .into_iter()
+            .filter(|t| include_done || !t.done)
+            .collect();
+        render(&todos)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_todos_persist_across_calls() {
+        let dir = tempfile::tempdir().unwrap();
+        let added = TodoAdd
+            .execute(
+                json!({ "items": ["Write migration", "Add endpoint", "  "] }),
+                dir.path(),
+            )
+            .await
+            .unwrap();
+        let todos = todos_from_tool_result("todo_add", &json!(added)).unwrap();
+        assert_eq!(
+            todos.iter().map(|t| t.id).collect::<Vec<_>>(),
+            vec![1, 2],
+            "blank items are skipped"
+        );
+
+        TodoAdd
+            .execute(json!({ "items": ["Write tests"] }), dir.path())
+            .await
+            .unwrap();
+        let completed = TodoComplete
+            .execute(json!({ "ids": [1, 3] }), dir.path())
+            .await
+            .unwrap();
+        let completed: Value = serde_json::from_str(&completed).unwrap();
+        assert_eq!(
+            (completed["open"].clone(), completed["done"].clone()),
+            (json!(1), json!(2))
+        );
+        assert!(TodoComplete
+            .execute(json!({ "ids": [9] }), dir.path())
+            .await
+            .is_err());
+
+        let open = TodoList
+            .execute(json!({ "include_done": false }), dir.path())
+            .await
+            .unwrap();
+        let open: Value = serde_json::from_str(&open).unwrap();
+        assert_eq!(
+            open["todos"],
+            json!([{ "id": 2, "text": "Add endpoint", "done": false }])
+        );
+        assert!(dir.path().join(TODO_FILE).exists());
+    }
+
+    #[test]
+    fn test_todos_from_tool_result() {
+        let result = json!([{ "type": "text", "text": "{\"todos\":[{\"id\":1,\"text\":\"a\",\"done\":true}]}" }]);
+        let todos = todos_from_tool_result("mcp__workspace__todo_complete", &result).unwrap();
+        assert_eq!(
+            todos,
+            vec![TodoItem {
+                id: 1,
+                text: "a".to_string(),
+                done: true
+            }]
+        );
+        assert!(todos_from_tool_result("todo_list", &result).is_none());
+        assert!(todos_from_tool_result("read_file", &result).is_none());
+    }
+}
@@ -91,3 +91,98 @@ impl Tool for UiDataTable {
         Ok(serde_json::to_string(&args).unwrap_or_else(|_| args.to_string()))
     }
 }
+
+/// Field types supported by `ui_form`.
+pub const FORM_FIELD_TYPES: &[&str] = &["text", "number", "select", "checkbox", "date"];
+
+/// Frontend tools that block until the user answers in the dashboard.
+pub fn awaits_user_input(name: &str) -> bool {
+    matches!(
+        name,
+        "question" | "ui_optionList" | "ui_form" | super::safe_mode::CONFIRM_TOOL_NAME
+    )
+}
+
+/// Ask the user to fill in a multi-field form (interactive).
+pub struct UiForm;
+
+#[async_trait]
+impl Tool for UiForm {
+    fn name(&self) -> &str {
+        "ui_form"
+    }
+
+    fn description(&self) -> &str {
+        "Render a form for the user to fill in (text, number, select, checkbox, date fields) and wait for the submitted values, returned as a JSON object keyed by field name (frontend Tool UI). Use it to collect missing parameters instead of guessing."
+    }
+
+    fn parameters_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "required": ["id", "title", "fields"],
+            "properties": {
+                "id": { "type": "string", "description": "Stable identifier for this UI element." },
+                "title": { "type": "string" },
+                "description": { "type": "string" },
+                "submitLabel": { "type": "string" },
+                "fields": {
+                    "type": "array",
+                    "minItems": 1,
+                    "items": {
+                        "type": "object",
+                        "required": ["name", "label", "type"],
+                        "properties": {
+                            "name": { "type": "string", "description": "Key of this field in the submitted values." },
+                            "label": { "type": "string" },
+                            "type": { "type": "string", "enum": FORM_FIELD_TYPES },
+                            "description": { "type": "string" },
+                            "required": { "type": "boolean", "default": false },
+                            "placeholder": { "type": "string" },
+                            "default": { "description": "Initial value (string, number or boolean depending on type)." },
+                            "min": { "type": "number", "description": "Minimum for number fields." },
+                            "max": { "type": "number", "description": "Maximum for number fields." },
+                            "options": {
+                                "type": "array",
+                                "description": "Choices for select fields.",
+                                "items": {
+                                    "type": "object",
+                                    "required": ["value", "label"],
+                                    "properties": {
+                                        "value": { "type": "string" },
+                                        "label": { "type": "string" }
+                                    }
+                                }
+                            }
+                        },
+                        "additionalProperties": true
+                    }
+                }
+            },
+            "additionalProperties": true
+        })
+    }
+
+    async fn execute(&self, args: Value, _workspace: &Path) -> anyhow::Result<String> {
+        // Like ui_optionList, the runtime routes this to the frontend and waits for
+        // the submitted values; this fallback only echoes the request.
+        Ok(serde_json::to_string(&args).unwrap_or_else(|_| args.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_form_schema_and_blocking_tools() {
+        let schema = UiForm.parameters_schema();
+        let field_types = &schema["properties"]["fields"]["items"]["properties"]["type"]["enum"];
+        assert_eq!(
+            field_types,
+            &json!(["text", "number", "select", "checkbox", "date"])
+        );
+        assert!(awaits_user_input("ui_form"));
+        assert!(awaits_user_input("question"));
+        assert!(!awaits_user_input("ui_dataTable"));
+    }
+}
@@ -0,0 +1,142 @@
+//! `.openagentignore`: paths the traversal tools skip.
+//!
+//! `list_directory`, `search_files` and `grep_search` skip heavy directories
+//! (`node_modules`, `target`, `.git`, ...) plus whatever the workspace's
+//! `.openagentignore` lists, in gitignore syntax. The file is read after the
+//! built-in defaults, so `!target/` brings a default back. A call passes
+//! `respect_ignore: false` to see everything.
+
+use std::path::{Path, PathBuf};
+
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use serde_json::Value;
+
+/// Ignore file at the workspace root.
+pub const IGNORE_FILE: &str = ".openagentignore";
+
+/// Directories skipped even without an ignore file.
+pub const DEFAULT_IGNORES: &[&str] = &[
+    ".git/",
+    "node_modules/",
+    "target/",
+    "__pycache__/",
+    ".venv/",
+    "venv/",
+    ".next/",
+];
+
+/// Whether a call asked to honour the ignore rules (default: yes).
+pub fn respect_ignore(args: &Value) -> bool {
+    args["respect_ignore"].as_bool().unwrap_or(true)
+}
+
+/// Compiled ignore rules for one workspace.
+#[derive(Clone)]
+pub struct WorkspaceIgnore {
+    root: PathBuf,
+    rules: Gitignore,
+}
+
+impl WorkspaceIgnore {
+    /// Defaults plus `<workspace>/.openagentignore`, if present.
+    pub fn load(workspace: &Path) -> Self {
+        let mut builder = GitignoreBuilder::new(workspace);
+        for pattern in DEFAULT_IGNORES {
+            builder
+                .add_line(None, pattern)
+                .expect("valid default ignore pattern");
+        }
+        let file = workspace.join(IGNORE_FILE);
+        if file.is_file() {
+            if let Some(e) = builder.add(&file) {
+                tracing::warn!("Invalid lines in {}: {}", file.display(), e);
+            }
+        }
+        let rules = builder.build().unwrap_or_else(|e| {
+            tracing::warn!("Failed to build ignore rules: {}", e);
+            Gitignore::empty()
+        });
+        Self {
+            root: workspace.to_path_buf(),
+            rules,
+        }
+    }
+
+    /// Rules for a tool call on `target`: [`Self::load`], or nothing when
+    /// `respect_ignore` is false or `target` is itself ignored (asking for it
+    /// explicitly wins).
+    pub fn for_call(args: &Value, workspace: &Path, target: &Path) -> Option<Self> {
+        if !respect_ignore(args) {
+            return None;
+        }
+        let ignore = Self::load(workspace);
+        (!ignore.is_ignored(target, target.is_dir())).then_some(ignore)
+    }
+
+    /// Whether `path` (or a directory above it) is ignored. Paths outside the workspace never are.
+    pub fn is_ignored(&self, path: &Path, is_dir: bool) -> bool {
+        let Ok(relative) = path.strip_prefix(&self.root) else {
+            return false;
+        };
+        if relative.as_os_str().is_empty() {
+            return false;
+        }
+        self.rules
+            .matched_path_or_any_parents(relative, is_dir)
+            .is_ignore()
+    }
+
+    /// Default directory names still ignored after the ignore file, for
+    /// search tools that take plain exclusions.
+    pub fn ignored_default_dirs(&self) -> Vec<&'static str> {
+        DEFAULT_IGNORES
+            .iter()
+            .map(|p| p.trim_end_matches('/'))
+            .filter(|name| self.is_ignored(&self.root.join(name), true))
+            .collect()
+    }
+
+    /// The workspace's ignore file, if it exists.
+    pub fn ignore_file(&self) -> Option<PathBuf> {
+        Some(self.root.join(IGNORE_FILE)).filter(|f| f.is_file())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_defaults_and_ignore_file() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join(IGNORE_FILE),
+            "*.log\n!target/\nfixtures/big/\n",
+        )
+        .unwrap();
+        let ignore = WorkspaceIgnore::load(dir.path());
+        let ignored = |p: &str, is_dir| ignore.is_ignored(&dir.path().join(p), is_dir);
+
+        assert!(ignored("node_modules", true));
+        assert!(ignored("web/node_modules/react/index.js", false));
+        assert!(ignored("debug.log", false));
+        assert!(ignored("fixtures/big/data.json", false));
+        assert!(!ignored("target/debug/app", false), "re-included");
+        assert!(!ignored("src/main.rs", false));
+        assert!(!ignore.is_ignored(Path::new("/elsewhere/node_modules"), true));
+
+        assert!(!ignore.ignored_default_dirs().contains(&"target"));
+        assert!(ignore.ignored_default_dirs().contains(&"node_modules"));
+
+        let args = serde_json::json!({});
+        std::fs::create_dir(dir.path().join("node_modules")).unwrap();
+        assert!(WorkspaceIgnore::for_call(&args, dir.path(), dir.path()).is_some());
+        assert!(
+            WorkspaceIgnore::for_call(&args, dir.path(), &dir.path().join("node_modules"))
+                .is_none(),
+            "explicitly requested"
+        );
+        let args = serde_json::json!({"respect_ignore": false});
+        assert!(WorkspaceIgnore::for_call(&args, dir.path(), dir.path()).is_none());
+    }
+}
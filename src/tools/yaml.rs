@@ -0,0 +1,719 @@
+//! YAML path tool: get, set and delete values at a path (like `yq`).
+//!
+//! Paths look like `spec.template.spec.containers[0].image` (a leading `.` is
+//! optional, `containers.0` works too, and `["a.b"]` quotes keys with dots).
+//!
+//! Edits are applied to the lines of the file so comments, key order and
+//! indentation survive. Every edit is checked by parsing the result and
+//! comparing it with the same change applied to the parsed document; when the
+//! line edit cannot express the change (flow collections, new intermediate
+//! keys, ...) the document is re-serialized instead, which drops comments.
+
+use std::path::Path;
+
+use async_trait::async_trait;
+use serde_json::{json, Value};
+use serde_yaml::Value as Yaml;
+
+use super::{resolve_path_simple, Tool};
+
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum Segment {
+    Key(String),
+    Index(usize),
+}
+
+impl Segment {
+    /// Index meaning of the segment (`[0]`, or `.0` when applied to a sequence).
+    fn as_index(&self) -> Option<usize> {
+        match self {
+            Segment::Index(i) => Some(*i),
+            Segment::Key(k) => k.parse().ok(),
+        }
+    }
+}
+
+pub(super) fn parse_path(path: &str) -> anyhow::Result<Vec<Segment>> {
+    let invalid = || anyhow::anyhow!("Invalid YAML path: '{}'", path);
+    let mut segments = Vec::new();
+    let mut rest = path.trim().strip_prefix('.').unwrap_or(path.trim());
+    while !rest.is_empty() {
+        if let Some(inner) = rest.strip_prefix('[') {
+            let close = inner.find(']').ok_or_else(invalid)?;
+            let token = &inner[..close];
+            let quoted = token
+                .strip_prefix('"')
+                .and_then(|t| t.strip_suffix('"'))
+                .or_else(|| token.strip_prefix('\'').and_then(|t| t.strip_suffix('\'')));
+            segments.push(match quoted {
+                Some(key) => Segment::Key(key.to_string()),
+                None => Segment::Index(token.trim().parse().map_err(|_| invalid())?),
+            });
+            rest = &inner[close + 1..];
+        } else {
+            let end = rest.find(['.', '[']).unwrap_or(rest.len());
+            if end == 0 {
+                return Err(invalid());
+            }
+            segments.push(Segment::Key(rest[..end].to_string()));
+            rest = &rest[end..];
+        }
+        if let Some(next) = rest.strip_prefix('.') {
+            if next.is_empty() || next.starts_with(['.', '[']) {
+                return Err(invalid());
+            }
+            rest = next;
+        }
+    }
+    Ok(segments)
+}
+
+// === Operations on the parsed document ===
+
+fn child<'a>(node: &'a Yaml, segment: &Segment) -> Option<&'a Yaml> {
+    match (node, segment) {
+        (Yaml::Mapping(map), Segment::Key(key)) => map.get(key.as_str()),
+        (Yaml::Sequence(seq), segment) => seq.get(segment.as_index()?),
+        _ => None,
+    }
+}
+
+fn get_value<'a>(root: &'a Yaml, path: &[Segment]) -> Option<&'a Yaml> {
+    path.iter().try_fold(root, child)
+}
+
+/// Set `path` to `value`, creating missing mapping keys along the way.
+fn set_value(root: &mut Yaml, path: &[Segment], value: Yaml) -> anyhow::Result<()> {
+    let Some((segment, rest)) = path.split_first() else {
+        *root = value;
+        return Ok(());
+    };
+    if root.is_null() {
+        *root = Yaml::Mapping(Default::default());
+    }
+    let next = match (root, segment) {
+        (Yaml::Mapping(map), Segment::Key(key)) => {
+            map.entry(Yaml::String(key.clone())).or_insert(Yaml::Null)
+        }
+        (Yaml::Sequence(seq), segment) => {
+            let index = segment
+                .as_index()
+                .ok_or_else(|| anyhow::anyhow!("'{:?}' is not a sequence index", segment))?;
+            let len = seq.len();
+            if index == len {
+                seq.push(Yaml::Null);
+            }
+            seq.get_mut(index)
+                .ok_or_else(|| anyhow::anyhow!("Index {} out of range (length {})", index, len))?
+        }
+        (_, segment) => {
+            return Err(anyhow::anyhow!(
+                "Cannot descend into a scalar with {:?}",
+                segment
+            ))
+        }
+    };
+    set_value(next, rest, value)
+}
+
+/// Remove `path`, returning whether it existed.
+fn delete_value(root: &mut Yaml, path: &[Segment]) -> bool {
+    let Some((last, parents)) = path.split_last() else {
+        return false;
+    };
+    let mut node = root;
+    for segment in parents {
+        let next = match (node, segment) {
+            (Yaml::Mapping(map), Segment::Key(key)) => map.get_mut(key.as_str()),
+            (Yaml::Sequence(seq), segment) => segment.as_index().and_then(|i| seq.get_mut(i)),
+            _ => None,
+        };
+        match next {
+            Some(next) => node = next,
+            None => return false,
+        }
+    }
+    match (node, last) {
+        (Yaml::Mapping(map), Segment::Key(key)) => map.remove(key.as_str()).is_some(),
+        (Yaml::Sequence(seq), segment) => match segment.as_index() {
+            Some(i) if i < seq.len() => {
+                seq.remove(i);
+                true
+            }
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+// === Line-level editing of block-style YAML ===
+
+fn indent_of(line: &str) -> usize {
+    line.len() - line.trim_start_matches(' ').len()
+}
+
+/// Lines carrying data (not blank, comments or document markers).
+fn is_content(line: &str) -> bool {
+    let trimmed = line.trim();
+    !trimmed.is_empty() && !trimmed.starts_with('#') && trimmed != "---" && trimmed != "..."
+}
+
+fn is_dash(text: &str) -> bool {
+    text == "-" || text.starts_with("- ")
+}
+
+/// A key or sequence item located in the file.
+#[derive(Debug)]
+struct Node {
+    line: usize,
+    /// Column of the key or of the dash.
+    col: usize,
+    /// End of the `key:` / `-` marker; the inline value follows.
+    head_end: usize,
+    /// Exclusive end of the node's block (last content line + 1).
+    end: usize,
+    is_item: bool,
+}
+
+/// Lines `start..end`; the first line starts at `first_col` inside a `- ` item.
+#[derive(Debug, Clone, Copy)]
+struct Scope {
+    start: usize,
+    end: usize,
+    first_col: Option<usize>,
+}
+
+impl Scope {
+    fn col(&self, lines: &[String], i: usize) -> usize {
+        match self.first_col {
+            Some(col) if i == self.start => col,
+            _ => indent_of(&lines[i]),
+        }
+    }
+
+    /// Column of the scope's first content line.
+    fn base_col(&self, lines: &[String]) -> Option<usize> {
+        (self.start..self.end)
+            .find(|&i| is_content(&lines[i]))
+            .map(|i| self.col(lines, i))
+    }
+}
+
+/// End of a block whose children are indented past `col`.
+fn block_end(lines: &[String], from: usize, col: usize, dash_at_col: bool) -> usize {
+    let mut end = from;
+    for (i, line) in lines.iter().enumerate().skip(from) {
+        if !is_content(line) {
+            continue;
+        }
+        let indent = indent_of(line);
+        if indent > col || (dash_at_col && indent == col && is_dash(line.trim_start())) {
+            end = i + 1;
+        } else {
+            break;
+        }
+    }
+    end
+}
+
+/// Byte offset just past `key:` when `text` starts with that key.
+fn match_key(text: &str, key: &str) -> Option<usize> {
+    let candidates = [
+        key.to_string(),
+        format!("\"{}\"", key),
+        format!("'{}'", key),
+    ];
+    candidates.iter().find_map(|candidate| {
+        let rest = text.strip_prefix(candidate.as_str())?;
+        let spaces = rest.len() - rest.trim_start_matches(' ').len();
+        let after = rest[spaces..].strip_prefix(':')?;
+        (after.is_empty() || after.starts_with([' ', '\t'])).then_some(candidate.len() + spaces + 1)
+    })
+}
+
+/// Split an inline value into `(value, trailing comment with its spacing)`.
+fn split_comment(inline: &str) -> (&str, &str) {
+    let skip = match inline.trim_start().chars().next() {
+        Some(q @ ('"' | '\'')) => {
+            let start = inline.len() - inline.trim_start().len() + 1;
+            inline[start..].find(q).map(|i| start + i + 1).unwrap_or(0)
+        }
+        _ => 0,
+    };
+    let comment = if inline.trim_start().starts_with('#') {
+        Some(0)
+    } else {
+        inline[skip..].find(" #").map(|i| skip + i)
+    };
+    match comment {
+        Some(i) => {
+            let value = inline[..i].trim_end();
+            (value, &inline[value.len()..])
+        }
+        None => (inline.trim_end(), ""),
+    }
+}
+
+fn inline_value<'a>(lines: &'a [String], node: &Node) -> &'a str {
+    split_comment(&lines[node.line][node.head_end..]).0.trim()
+}
+
+/// Scope holding a node's children, if they are written in block style.
+fn child_scope(lines: &[String], node: &Node) -> Option<Scope> {
+    let inline = inline_value(lines, node);
+    if inline.is_empty() {
+        Some(Scope {
+            start: node.line + 1,
+            end: node.end,
+            first_col: None,
+        })
+    } else if node.is_item && !inline.starts_with(['[', '{', '"', '\'', '|', '>', '&', '*']) {
+        let text = &lines[node.line];
+        let value_col = text.len() - text[node.head_end..].trim_start().len();
+        Some(Scope {
+            start: node.line,
+            end: node.end,
+            first_col: Some(value_col),
+        })
+    } else {
+        None
+    }
+}
+
+fn find_key(lines: &[String], scope: Scope, key: &str) -> Option<Node> {
+    let base = scope.base_col(lines)?;
+    for i in scope.start..scope.end {
+        if !is_content(&lines[i]) || scope.col(lines, i) != base {
+            continue;
+        }
+        let text = &lines[i][base..];
+        if let Some(offset) = match_key(text, key) {
+            return Some(Node {
+                line: i,
+                col: base,
+                head_end: base + offset,
+                end: block_end(lines, i + 1, base, true),
+                is_item: false,
+            });
+        }
+    }
+    None
+}
+
+fn find_item(lines: &[String], scope: Scope, index: usize) -> Option<Node> {
+    let base = scope.base_col(lines)?;
+    (scope.start..scope.end)
+        .filter(|&i| {
+            is_content(&lines[i]) && scope.col(lines, i) == base && is_dash(&lines[i][base..])
+        })
+        .nth(index)
+        .map(|i| Node {
+            line: i,
+            col: base,
+            head_end: base + 1,
+            end: block_end(lines, i + 1, base, false),
+            is_item: true,
+        })
+}
+
+fn is_sequence_scope(lines: &[String], scope: Scope) -> bool {
+    (scope.start..scope.end)
+        .find(|&i| is_content(&lines[i]))
+        .is_some_and(|i| is_dash(&lines[i][scope.col(lines, i)..]))
+}
+
+fn find_in_scope(lines: &[String], scope: Scope, segment: &Segment) -> Option<Node> {
+    if is_sequence_scope(lines, scope) {
+        find_item(lines, scope, segment.as_index()?)
+    } else {
+        match segment {
+            Segment::Key(key) => find_key(lines, scope, key),
+            Segment::Index(_) => None,
+        }
+    }
+}
+
+fn root_scope(lines: &[String]) -> Scope {
+    Scope {
+        start: 0,
+        end: lines.len(),
+        first_col: None,
+    }
+}
+
+/// Locate the node at `path`.
+fn locate(lines: &[String], path: &[Segment]) -> Option<Node> {
+    let (first, rest) = path.split_first()?;
+    let mut node = find_in_scope(lines, root_scope(lines), first)?;
+    for segment in rest {
+        let scope = child_scope(lines, &node)?;
+        node = find_in_scope(lines, scope, segment)?;
+    }
+    Some(node)
+}
+
+/// Serialize `value` as YAML lines (a single line for scalars and empty collections).
+fn render(value: &Yaml) -> anyhow::Result<Vec<String>> {
+    let text = serde_yaml::to_string(value)?;
+    Ok(text.lines().map(str::to_string).collect())
+}
+
+/// Lines for `head` (e.g. `  image:` or `  -`) followed by `value`.
+fn render_entry(
+    head: &str,
+    value: &Yaml,
+    comment: &str,
+    is_item: bool,
+) -> anyhow::Result<Vec<String>> {
+    let rendered = render(value)?;
+    let child_indent = " ".repeat(indent_of(head) + 2);
+    let multiline = rendered.len() > 1;
+    let first_inline =
+        !multiline || is_item || rendered[0].starts_with('|') || rendered[0].starts_with('>');
+    let mut out = Vec::with_capacity(rendered.len() + 1);
+    let mut rest = rendered.iter();
+    if first_inline {
+        let first = rest.next().map(String::as_str).unwrap_or_default();
+        out.push(format!("{} {}{}", head, first, comment));
+    } else {
+        out.push(format!("{}{}", head, comment));
+    }
+    out.extend(rest.map(|line| format!("{}{}", child_indent, line)));
+    Ok(out)
+}
+
+/// Apply a set on the lines; `None` when the line editor cannot express it.
+fn edit_set(lines: &[String], path: &[Segment], value: &Yaml) -> Option<Vec<String>> {
+    let mut out = lines.to_vec();
+    if let Some(node) = locate(lines, path) {
+        let text = &lines[node.line];
+        let (old, comment) = split_comment(&text[node.head_end..]);
+        // Keep the inline comment only when it annotated an inline value.
+        let comment = if old.trim().is_empty() && node.end > node.line + 1 {
+            ""
+        } else {
+            comment
+        };
+        let new_lines = render_entry(&text[..node.head_end], value, comment, node.is_item).ok()?;
+        out.splice(node.line..node.end, new_lines);
+        return Some(out);
+    }
+
+    // Add a missing key to an existing mapping.
+    let (Segment::Key(key), parents) = path.split_last()? else {
+        return None;
+    };
+    let (scope, default_col) = if parents.is_empty() {
+        (root_scope(lines), 0)
+    } else {
+        let parent = locate(lines, parents)?;
+        (child_scope(lines, &parent)?, parent.col + 2)
+    };
+    if is_sequence_scope(lines, scope) {
+        return None;
+    }
+    let col = scope.base_col(lines).unwrap_or(default_col);
+    let insert_at = (scope.start..scope.end)
+        .rev()
+        .find(|&i| is_content(&lines[i]))
+        .map_or(scope.start, |i| i + 1);
+    let head = format!("{}{}:", " ".repeat(col), key);
+    let new_lines = render_entry(&head, value, "", false).ok()?;
+    out.splice(insert_at..insert_at, new_lines);
+    Some(out)
+}
+
+/// Apply a delete on the lines; `None` when the line editor cannot express it.
+fn edit_delete(lines: &[String], path: &[Segment]) -> Option<Vec<String>> {
+    let node = locate(lines, path)?;
+    // A key sharing its line with a `- ` marker would take the dash with it.
+    if !node.is_item && node.col != indent_of(&lines[node.line]) {
+        return None;
+    }
+    let mut out = lines.to_vec();
+    out.drain(node.line..node.end);
+    Some(out)
+}
+
+/// Outcome of an edit: the new file contents and whether formatting survived.
+pub(super) struct Edited {
+    pub content: String,
+    pub preserved: bool,
+}
+
+/// Apply a line edit if it yields `expected`, else re-serialize `expected`.
+fn finish_edit(
+    content: &str,
+    edited: Option<Vec<String>>,
+    expected: &Yaml,
+) -> anyhow::Result<Edited> {
+    let newline = if content.contains("\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    };
+    if let Some(lines) = edited {
+        let mut text = lines.join(newline);
+        if content.ends_with('\n') || !content.contains('\n') {
+            text.push_str(newline);
+        }
+        if serde_yaml::from_str::<Yaml>(&text).ok().as_ref() == Some(expected) {
+            return Ok(Edited {
+                content: text,
+                preserved: true,
+            });
+        }
+    }
+    let text = serde_yaml::to_string(expected)?;
+    // Guard against serializer bugs before touching the file.
+    serde_yaml::from_str::<Yaml>(&text)
+        .map_err(|e| anyhow::anyhow!("Edited YAML does not parse: {}", e))?;
+    Ok(Edited {
+        content: text,
+        preserved: false,
+    })
+}
+
+pub(super) fn set_in_document(
+    content: &str,
+    path: &[Segment],
+    value: Yaml,
+) -> anyhow::Result<Edited> {
+    let mut expected: Yaml = serde_yaml::from_str(content)?;
+    set_value(&mut expected, path, value.clone())?;
+    let lines: Vec<String> = content.lines().map(str::to_string).collect();
+    finish_edit(content, edit_set(&lines, path, &value), &expected)
+}
+
+fn delete_in_document(content: &str, path: &[Segment]) -> anyhow::Result<Option<Edited>> {
+    let mut expected: Yaml = serde_yaml::from_str(content)?;
+    if !delete_value(&mut expected, path) {
+        return Ok(None);
+    }
+    let lines: Vec<String> = content.lines().map(str::to_string).collect();
+    finish_edit(content, edit_delete(&lines, path), &expected).map(Some)
+}
+
+/// Get, set or delete values in a YAML file by path.
+pub struct YamlPath;
+
+#[async_trait]
+impl Tool for YamlPath {
+    fn name(&self) -> &str {
+        "yaml_path"
+    }
+
+    fn description(&self) -> &str {
+        "Query or edit a YAML file at a path expression, like yq (e.g. 'spec.template.spec.containers[0].image'). action='get' returns the value as YAML; 'set' and 'delete' edit the file in place, keeping comments and indentation where possible, and verify the result still parses. Prefer this over hand-editing YAML indentation."
+    }
+
+    fn parameters_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "path": {
+                    "type": "string",
+                    "description": "YAML file to read or edit"
+                },
+                "action": {
+                    "type": "string",
+                    "enum": ["get", "set", "delete"],
+                    "description": "Operation (default: get)"
+                },
+                "query": {
+                    "type": "string",
+                    "description": "Path expression: dot-separated keys with [n] for sequence items, e.g. 'jobs.build.steps[2].run'. Use '.' for the whole document with 'get'."
+                },
+                "value": {
+                    "description": "New value for 'set' (string, number, boolean, null, object or array)"
+                }
+            },
+            "required": ["path", "query"]
+        })
+    }
+
+    async fn execute(&self, args: Value, working_dir: &Path) -> anyhow::Result<String> {
+        let path = args["path"]
+            .as_str()
+            .ok_or_else(|| anyhow::anyhow!("Missing 'path' argument"))?;
+        let query = args["query"]
+            .as_str()
+            .ok_or_else(|| anyhow::anyhow!("Missing 'query' argument"))?;
+        let action = args["action"].as_str().unwrap_or("get");
+        let segments = parse_path(query)?;
+
+        let resolved = resolve_path_simple(path, working_dir)?;
+        let content = tokio::fs::read_to_string(&resolved).await.map_err(|e| {
+            anyhow::anyhow!(
+                "Failed to read {} (resolved to: {}): {}",
+                path,
+                resolved.display(),
+                e
+            )
+        })?;
+        let document: Yaml = serde_yaml::from_str(&content)
+            .map_err(|e| anyhow::anyhow!("{} is not valid YAML: {}", path, e))?;
+
+        let edited = match action {
+            "get" => {
+                let value = get_value(&document, &segments)
+                    .ok_or_else(|| anyhow::anyhow!("Path '{}' not found in {}", query, path))?;
+                return Ok(serde_yaml::to_string(value)?.trim_end().to_string());
+            }
+            "set" => {
+                if segments.is_empty() {
+                    return Err(anyhow::anyhow!("'set' needs a non-empty path"));
+                }
+                let value = args
+                    .get("value")
+                    .ok_or_else(|| anyhow::anyhow!("Missing 'value' argument for set"))?;
+                set_in_document(&content, &segments, serde_yaml::to_value(value)?)?
+            }
+            "delete" => delete_in_document(&content, &segments)?
+                .ok_or_else(|| anyhow::anyhow!("Path '{}' not found in {}", query, path))?,
+            other => {
+                return Err(anyhow::anyhow!(
+                    "Invalid action '{}': expected 'get', 'set' or 'delete'",
+                    other
+                ))
+            }
+        };
+
+        tokio::fs::write(&resolved, &edited.content).await?;
+        Ok(json!({
+            "success": true,
+            "action": action,
+            "query": query,
+            "formatting": if edited.preserved { "preserved" } else { "reserialized (comments removed)" },
+        })
+        .to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DEPLOYMENT: &str = "\
+# Web deployment
+apiVersion: apps/v1
+kind: Deployment
+metadata:
+  name: web # public name
+  labels:
+    app: web
+spec:
+  replicas: 2
+  template:
+    spec:
+      containers:
+      - name: web
+        image: nginx:1.25   # pinned
+        ports:
+          - containerPort: 80
+      - name: sidecar
+        image: envoy:1.29
+";
+
+    #[tokio::test]
+    async fn test_get_nested_values() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("deploy.yaml"), DEPLOYMENT).unwrap();
+        let get = |query: &str| {
+            YamlPath.execute(json!({ "path": "deploy.yaml", "query": query }), dir.path())
+        };
+
+        assert_eq!(
+            get("spec.template.spec.containers[1].image").await.unwrap(),
+            "envoy:1.29"
+        );
+        assert_eq!(
+            get(".spec.template.spec.containers.0.ports[0].containerPort")
+                .await
+                .unwrap(),
+            "80"
+        );
+        assert_eq!(get("metadata.labels").await.unwrap(), "app: web");
+        assert!(get("spec.missing").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_set_and_delete_preserve_document() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("deploy.yaml");
+        std::fs::write(&file, DEPLOYMENT).unwrap();
+        let run = |args: Value| YamlPath.execute(args, dir.path());
+
+        let out = run(json!({
+            "path": "deploy.yaml",
+            "action": "set",
+            "query": "spec.template.spec.containers[0].image",
+            "value": "nginx:1.27"
+        }))
+        .await
+        .unwrap();
+        assert!(out.contains("\"formatting\":\"preserved\""));
+        let text = std::fs::read_to_string(&file).unwrap();
+        assert_eq!(
+            text,
+            DEPLOYMENT.replace(
+                "image: nginx:1.25   # pinned",
+                "image: nginx:1.27   # pinned"
+            )
+        );
+
+        // New keys and structured values are written as block YAML.
+        run(json!({
+            "path": "deploy.yaml",
+            "action": "set",
+            "query": "metadata.labels.tier",
+            "value": "frontend"
+        }))
+        .await
+        .unwrap();
+        run(json!({
+            "path": "deploy.yaml",
+            "action": "set",
+            "query": "spec.template.spec.containers[1].env",
+            "value": [{ "name": "MODE", "value": "edge" }]
+        }))
+        .await
+        .unwrap();
+        run(json!({ "path": "deploy.yaml", "action": "delete", "query": "spec.replicas" }))
+            .await
+            .unwrap();
+
+        let text = std::fs::read_to_string(&file).unwrap();
+        assert!(text.starts_with("# Web deployment\n"));
+        assert!(text.contains("  name: web # public name\n"));
+        assert!(text.contains("    app: web\n    tier: frontend\n"));
+        assert!(text.contains(
+            "        image: envoy:1.29\n        env:\n          - name: MODE\n            value: edge\n"
+        ));
+        assert!(!text.contains("replicas"));
+
+        let doc: Yaml = serde_yaml::from_str(&text).unwrap();
+        let path = parse_path("spec.template.spec.containers[1].env[0].value").unwrap();
+        assert_eq!(
+            get_value(&doc, &path),
+            Some(&serde_yaml::to_value("edge").unwrap())
+        );
+        let path = parse_path("spec.template.spec.containers[0].ports[0].containerPort").unwrap();
+        assert_eq!(
+            get_value(&doc, &path),
+            Some(&serde_yaml::to_value(80).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_flow_style_falls_back_to_reserializing() {
+        let edited = set_in_document(
+            "# comment\nspec: {replicas: 1}\n",
+            &parse_path("spec.replicas").unwrap(),
+            serde_yaml::to_value(3).unwrap(),
+        )
+        .unwrap();
+        assert!(!edited.preserved);
+        assert_eq!(edited.content, "spec:\n  replicas: 3\n");
+    }
+}
@@ -2473,7 +2473,7 @@ async fn prepare_workspace_dir(path: &Path) -> anyhow::Result<PathBuf> {
 /// - Non-empty `workspace_mcps` → include only MCPs whose name is in the list
 ///
 /// In both cases, globally disabled MCPs are excluded.
-fn filter_mcp_configs_for_workspace(
+pub fn filter_mcp_configs_for_workspace(
     configs: Vec<McpServerConfig>,
     workspace_mcps: &[String],
 ) -> Vec<McpServerConfig> {
@@ -2564,7 +2564,13 @@ pub async fn prepare_mission_workspace_with_skills(
     mission_id: Uuid,
 ) -> anyhow::Result<PathBuf> {
     prepare_mission_workspace_with_skills_backend(
-        workspace, mcp, library, mission_id, "opencode", None,
+        workspace,
+        mcp,
+        library,
+        mission_id,
+        "opencode",
+        None,
+        &HashMap::new(),
     )
     .await
 }
@@ -2602,6 +2608,9 @@ fn read_custom_providers_from_file(workspace_root: &Path) -> Vec<AIProvider> {
 }
 
 /// Prepare a workspace directory for a mission with skill and tool syncing for a specific backend.
+///
+/// `mcp_env` is added to the environment of the workspace MCP (the mission's API
+/// token and safe-mode flag); other MCP servers don't see it.
 pub async fn prepare_mission_workspace_with_skills_backend(
     workspace: &Workspace,
     mcp: &McpRegistry,
@@ -2609,6 +2618,7 @@ pub async fn prepare_mission_workspace_with_skills_backend(
     mission_id: Uuid,
     backend_id: &str,
     custom_providers: Option<&[AIProvider]>,
+    mcp_env: &HashMap<String, String>,
 ) -> anyhow::Result<PathBuf> {
     let dir = mission_workspace_dir_for_root(&workspace.path, mission_id);
     prepare_workspace_dir(&dir).await?;
@@ -2625,7 +2635,13 @@ pub async fn prepare_mission_workspace_with_skills_backend(
             Some(providers_from_file.as_slice())
         }
     };
-    let mcp_configs = filter_mcp_configs_for_workspace(mcp.list_configs().await, &workspace.mcps);
+    let mut mcp_configs =
+        filter_mcp_configs_for_workspace(mcp.list_configs().await, &workspace.mcps);
+    for config in mcp_configs.iter_mut().filter(|c| c.name == "workspace") {
+        if let McpTransport::Stdio { env, .. } = &mut config.transport {
+            env.extend(mcp_env.iter().map(|(k, v)| (k.clone(), v.clone())));
+        }
+    }
     let skill_allowlist = if workspace.skills.is_empty() {
         None
     } else {
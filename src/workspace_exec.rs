@@ -16,6 +16,60 @@ use tokio::process::{Child, Command};
 use crate::nspawn;
 use crate::workspace::{use_nspawn_for_workspace, Workspace, WorkspaceType};
 
+/// Bin directories of common per-user toolchains (opencode, cargo, bun, npm, pip).
+/// Relative entries are resolved against the command's working directory and
+/// searched after the system PATH.
+const DEFAULT_EXTRA_PATH_DIRS: &[&str] = &[
+    "node_modules/.bin",
+    "$HOME/.opencode/bin",
+    "$HOME/.cargo/bin",
+    "$HOME/.bun/bin",
+    "$HOME/.npm-global/bin",
+    "$HOME/.local/bin",
+];
+
+/// PATH used inside containers when none is provided.
+const DEFAULT_CONTAINER_PATH: &str = "/usr/local/sbin:/usr/local/bin:/usr/sbin:/usr/bin:/sbin:/bin";
+
+/// Add `extra_dirs` to `base`, skipping directories already present.
+///
+/// `~`, `~/...` and `$HOME/...` entries are expanded and searched before `base`,
+/// like other absolute entries. Relative (project-local) entries are resolved
+/// against `cwd` and searched after `base`, so a repository can't shadow system
+/// binaries. Other `~user` or `$VAR` entries are kept as written.
+pub fn augment_path(base: &str, extra_dirs: &[String], home: &str, cwd: &str) -> String {
+    let mut front: Vec<String> = Vec::new();
+    let mut back: Vec<String> = Vec::new();
+    for dir in extra_dirs {
+        let dir = dir.trim();
+        if dir.is_empty() {
+            continue;
+        }
+        let home_rest = dir
+            .strip_prefix("$HOME")
+            .or_else(|| dir.strip_prefix('~'))
+            .filter(|rest| rest.is_empty() || rest.starts_with('/'));
+        if let Some(rest) = home_rest {
+            front.push(format!("{}{}", home, rest));
+        } else if dir.starts_with(['/', '~', '$']) {
+            front.push(dir.to_string());
+        } else {
+            back.push(format!("{}/{}", cwd.trim_end_matches('/'), dir));
+        }
+    }
+    let base = base
+        .split(':')
+        .filter(|d| !d.is_empty())
+        .map(str::to_string);
+    let mut entries: Vec<String> = Vec::new();
+    for dir in front.into_iter().chain(base).chain(back) {
+        if !entries.contains(&dir) {
+            entries.push(dir);
+        }
+    }
+    entries.join(":")
+}
+
 #[derive(Debug, Clone)]
 pub struct WorkspaceExec {
     pub workspace: Workspace,
@@ -56,7 +110,57 @@ impl WorkspaceExec {
         }
     }
 
-    fn build_env(&self, extra_env: HashMap<String, String>) -> HashMap<String, String> {
+    /// Extra PATH directories for this workspace, highest priority first.
+    ///
+    /// Combines the workspace `config.extra_path` list, the global
+    /// `OPEN_AGENT_EXTRA_PATH` (colon-separated) and the default toolchain bin dirs.
+    pub fn extra_path_dirs(&self) -> Vec<String> {
+        let mut dirs: Vec<String> = self
+            .workspace
+            .config
+            .get("extra_path")
+            .and_then(|v| v.as_array())
+            .map(|arr| {
+                arr.iter()
+                    .filter_map(|v| v.as_str().map(|s| s.to_string()))
+                    .collect()
+            })
+            .unwrap_or_default();
+        if let Ok(global) = std::env::var("OPEN_AGENT_EXTRA_PATH") {
+            dirs.extend(global.split(':').map(|s| s.to_string()));
+        }
+        dirs.extend(DEFAULT_EXTRA_PATH_DIRS.iter().map(|s| s.to_string()));
+        dirs
+    }
+
+    /// The PATH processes spawned in `cwd` will see.
+    pub fn search_path(&self, cwd: &Path, env: &HashMap<String, String>) -> String {
+        let in_container = use_nspawn_for_workspace(&self.workspace);
+        let base = env.get("PATH").cloned().unwrap_or_else(|| {
+            if in_container {
+                DEFAULT_CONTAINER_PATH.to_string()
+            } else {
+                std::env::var("PATH").unwrap_or_default()
+            }
+        });
+        let home = env
+            .get("HOME")
+            .cloned()
+            .or_else(|| {
+                (!in_container)
+                    .then(|| std::env::var("HOME").ok())
+                    .flatten()
+            })
+            .unwrap_or_else(|| "/root".to_string());
+        let cwd = if in_container {
+            self.rel_path_in_container(cwd)
+        } else {
+            cwd.to_string_lossy().to_string()
+        };
+        augment_path(&base, &self.extra_path_dirs(), &home, &cwd)
+    }
+
+    fn build_env(&self, cwd: &Path, extra_env: HashMap<String, String>) -> HashMap<String, String> {
         let mut merged = self.workspace.env_vars.clone();
         merged.extend(extra_env);
         merged
@@ -94,6 +198,8 @@ impl WorkspaceExec {
                 .entry("OPEN_AGENT_CONTAINER_FALLBACK".to_string())
                 .or_insert_with(|| "1".to_string());
         }
+        let path = self.search_path(cwd, &merged);
+        merged.insert("PATH".to_string(), path);
         merged
     }
 
@@ -495,7 +601,7 @@ impl WorkspaceExec {
         args: &[String],
         env: HashMap<String, String>,
     ) -> anyhow::Result<std::process::Output> {
-        let env = self.build_env(env);
+        let env = self.build_env(cwd, env);
         let mut cmd = self
             .build_command(
                 cwd,
@@ -522,7 +628,7 @@ impl WorkspaceExec {
         args: &[String],
         env: HashMap<String, String>,
     ) -> anyhow::Result<Child> {
-        let env = self.build_env(env);
+        let env = self.build_env(cwd, env);
         let mut cmd = self
             .build_command(
                 cwd,
@@ -540,3 +646,69 @@ impl WorkspaceExec {
         Ok(child)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_augment_path_prepends_and_dedups() {
+        let path = augment_path(
+            "/usr/bin:/home/u/.cargo/bin",
+            &[
+                "~/.opencode/bin".to_string(),
+                "$HOME/.cargo/bin".to_string(),
+                "node_modules/.bin".to_string(),
+            ],
+            "/home/u",
+            "/work/",
+        );
+        assert_eq!(
+            path,
+            "/home/u/.opencode/bin:/home/u/.cargo/bin:/usr/bin:/work/node_modules/.bin"
+        );
+    }
+
+    #[test]
+    fn test_augment_path_expands_only_own_home() {
+        let path = augment_path(
+            "/usr/bin",
+            &[
+                "~".to_string(),
+                "~other/bin".to_string(),
+                "$HOMEuser/bin".to_string(),
+            ],
+            "/home/u",
+            "/work",
+        );
+        assert_eq!(path, "/home/u:~other/bin:$HOMEuser/bin:/usr/bin");
+    }
+
+    #[tokio::test]
+    async fn test_spawn_receives_augmented_path() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut workspace = Workspace::default_host(dir.path().to_path_buf());
+        workspace.config = serde_json::json!({ "extra_path": ["/opt/custom/bin"] });
+        let exec = WorkspaceExec::new(workspace);
+
+        let mut env = HashMap::new();
+        env.insert("PATH".to_string(), "/usr/bin:/bin".to_string());
+        env.insert("HOME".to_string(), "/home/tester".to_string());
+        let output = exec
+            .output(
+                dir.path(),
+                "/bin/sh",
+                &["-c".to_string(), "printf %s \"$PATH\"".to_string()],
+                env,
+            )
+            .await
+            .unwrap();
+        let path = String::from_utf8_lossy(&output.stdout);
+        let entries: Vec<&str> = path.split(':').collect();
+        assert_eq!(entries[0], "/opt/custom/bin");
+        assert!(entries.contains(&"/home/tester/.opencode/bin"));
+        let local_bin = format!("{}/node_modules/.bin", dir.path().display());
+        assert_eq!(entries.last(), Some(&local_bin.as_str()));
+        assert!(path.contains("/usr/bin:/bin:"));
+    }
+}